@@ -1,13 +1,188 @@
 // This source code follows Bristol Fashion's specification https://nigelsmart.github.io/MPC-Circuits/
 use std::fs::File;
-use std::io::{BufRead, BufReader};
-use std::path::Path;
-use crate::bristol_fashion_adaptor::{GateInfo, GateType};
+use std::io::{BufRead, BufReader, Lines};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use crate::bristol_fashion_adaptor::{BristolFashionGateType, CircuitParseError, GateInfo, GateType, GroupLayoutError};
+use crate::functionalities_and_protocols::crypto_backend::Hash;
+
+// Yields the non-empty lines of a circuit split into whitespace-separated parts, with 1-based line numbers
+struct LineReader<R: BufRead> {
+    lines: Lines<R>,
+    line_number: usize,
+}
+
+impl<R: BufRead> LineReader<R> {
+    fn new(reader: R) -> Self {
+        Self {
+            lines: reader.lines(),
+            line_number: 0,
+        }
+    }
+
+    fn next_non_empty_line(&mut self, expected: &str) -> Result<(usize, Vec<String>), CircuitParseError> {
+        for line in self.lines.by_ref() {
+            self.line_number += 1;
+            let line = line.map_err(
+                |error| CircuitParseError::Syntax { line: self.line_number, message: error.to_string() }
+            )?;
+            let parts = line.split_whitespace().map(String::from).collect::<Vec<String>>();
+            if !parts.is_empty() {
+                return Ok((self.line_number, parts));
+            }
+        }
+        Err(CircuitParseError::Syntax {
+            line: self.line_number + 1,
+            message: format!("unexpected end of circuit, expected {}", expected),
+        })
+    }
+
+    fn expect_end(&mut self, num_gates: usize) -> Result<(), CircuitParseError> {
+        for line in self.lines.by_ref() {
+            self.line_number += 1;
+            let line = line.map_err(
+                |error| CircuitParseError::Syntax { line: self.line_number, message: error.to_string() }
+            )?;
+            if !line.trim().is_empty() {
+                return Err(CircuitParseError::Syntax {
+                    line: self.line_number,
+                    message: format!("unexpected content after the {} gates declared in the header", num_gates),
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+// Lowers the Bristol Fashion gates into the AND/XOR/NOT core:
+// EQW makes its output wire an alias of its input wire,
+// EQ makes its output wire an alias of a constant wire, which is derived once from input wire 0 as 0 = w0 XOR w0 and 1 = NOT 0,
+// MAND with n outputs becomes n AND gates
+struct Lowering {
+    num_wires: usize,
+    wire_alias_vec: Vec<usize>,
+    constant_wire_vec: [Option<usize>; 2],
+    gate_vec: Vec<GateInfo>,
+}
+
+impl Lowering {
+    fn new(num_wires: usize) -> Self {
+        Self {
+            num_wires,
+            wire_alias_vec: (0..num_wires).collect(),
+            constant_wire_vec: [None, None],
+            gate_vec: Vec::new(),
+        }
+    }
+
+    fn resolve(&self, wire: usize) -> usize {
+        self.wire_alias_vec[wire]
+    }
+
+    fn alias(&mut self, wire: usize, target_wire: usize) {
+        self.wire_alias_vec[wire] = target_wire;
+    }
+
+    fn new_wire(&mut self) -> usize {
+        self.num_wires += 1;
+        self.num_wires - 1
+    }
+
+    fn constant_wire(&mut self, constant: u8) -> usize {
+        if let Some(constant_wire) = self.constant_wire_vec[constant as usize] {
+            return constant_wire;
+        }
+        let zero_wire = match self.constant_wire_vec[0] {
+            Some(zero_wire) => zero_wire,
+            None => {
+                let zero_wire = self.new_wire();
+                self.gate_vec.push(GateInfo::new(0, 0, zero_wire, GateType::XOR));
+                self.constant_wire_vec[0] = Some(zero_wire);
+                zero_wire
+            }
+        };
+        if constant == 0 {
+            return zero_wire;
+        }
+        let one_wire = self.new_wire();
+        self.gate_vec.push(GateInfo::new(zero_wire, 0, one_wire, GateType::NOT));
+        self.constant_wire_vec[1] = Some(one_wire);
+        one_wire
+    }
+
+    fn lower(&mut self, bristol_fashion_gate_type: &BristolFashionGateType, input_wire_vec: &[usize], output_wire_vec: &[usize]) {
+        let input_wire_vec = input_wire_vec.iter().map(|input_wire| self.resolve(*input_wire)).collect::<Vec<usize>>();
+        match bristol_fashion_gate_type {
+            BristolFashionGateType::AND => self.gate_vec.push(
+                GateInfo::new(input_wire_vec[0], input_wire_vec[1], output_wire_vec[0], GateType::AND)
+            ),
+            BristolFashionGateType::XOR => self.gate_vec.push(
+                GateInfo::new(input_wire_vec[0], input_wire_vec[1], output_wire_vec[0], GateType::XOR)
+            ),
+            BristolFashionGateType::INV => self.gate_vec.push(
+                GateInfo::new(input_wire_vec[0], 0, output_wire_vec[0], GateType::NOT)
+            ),
+            BristolFashionGateType::EQW => self.alias(output_wire_vec[0], input_wire_vec[0]),
+            BristolFashionGateType::MAND => {
+                let num_and_gates = output_wire_vec.len();
+                for i in 0..num_and_gates {
+                    self.gate_vec.push(
+                        GateInfo::new(input_wire_vec[i], input_wire_vec[num_and_gates + i], output_wire_vec[i], GateType::AND)
+                    );
+                }
+            }
+            BristolFashionGateType::EQ => unreachable!("EQ takes a constant rather than an input wire"),
+        }
+    }
+}
+
+fn check_num_groups(expected: usize, actual: usize) -> Result<(), GroupLayoutError> {
+    if expected != actual {
+        return Err(GroupLayoutError::NumGroupsMismatch { expected, actual });
+    }
+    Ok(())
+}
+
+fn check_num_bits(expected: usize, actual: usize) -> Result<(), GroupLayoutError> {
+    if expected != actual {
+        return Err(GroupLayoutError::NumBitsMismatch { expected, actual });
+    }
+    Ok(())
+}
+
+fn parse_usize(line_number: usize, part: &str, what: &str) -> Result<usize, CircuitParseError> {
+    part.parse::<usize>().map_err(
+        |_| CircuitParseError::Syntax { line: line_number, message: format!("invalid {}: {:?}", what, part) }
+    )
+}
+
+// sums header values, which may be as large as usize::MAX, without overflowing
+fn checked_sum(line_number: usize, value_vec: &[usize], what: &str) -> Result<usize, CircuitParseError> {
+    value_vec.iter().try_fold(0usize, |sum, value| sum.checked_add(*value)).ok_or(
+        CircuitParseError::Syntax { line: line_number, message: format!("{} overflow", what) }
+    )
+}
+
+fn expect_num_parts(line_number: usize, parts: &[String], expected: usize) -> Result<(), CircuitParseError> {
+    if parts.len() != expected {
+        return Err(CircuitParseError::Syntax {
+            line: line_number,
+            message: format!("expected {} entries, found {}", expected, parts.len()),
+        });
+    }
+    Ok(())
+}
+
+const CIRCUIT_DIGEST_CONTEXT: &str = "pa-gc-rs BristolFashionAdaptor circuit digest v1";
 
 pub struct BristolFashionAdaptor {
     num_wires: usize,
     num_input_bits: usize,
     num_output_bits: usize,
+    // the number of bits of each input and output group, as listed in the header
+    input_group_size_vec: Vec<usize>,
+    output_group_size_vec: Vec<usize>,
     gate_vec: Vec<GateInfo>,
     and_gate_id_vec: Vec<usize>,
     and_gate_output_wire_vec: Vec<usize>,
@@ -23,92 +198,141 @@ impl BristolFashionAdaptor {
         (0..gate_vec.len()).filter(|&i| gate_vec[i].gate_type == GateType::AND).collect()
     }
 
+    fn bundled_circuit_path(circuit_file_name: &String) -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("src/bristol_fashion_adaptor/circuit_data")
+            .join(circuit_file_name)
+    }
+
     fn read_circuit_file(circuit_file_name: &String) -> Self {
-        let full_circuit_file_name = Path::new(file!())
-            .parent().unwrap().display().to_string()
-            + "/circuit_data/"
-            + circuit_file_name;
-        let input_file = File::open(full_circuit_file_name).unwrap();
-        let mut input_file = BufReader::new(&input_file);
-        let mut line = String::new();
+        let full_circuit_file_name = Self::bundled_circuit_path(circuit_file_name);
+        Self::from_path(&full_circuit_file_name).unwrap_or_else(
+            |error| panic!("{}: {}", full_circuit_file_name.display(), error)
+        )
+    }
+
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self, CircuitParseError> {
+        let input_file = File::open(path.as_ref()).map_err(
+            |error| CircuitParseError::Io(format!("{}: {}", path.as_ref().display(), error))
+        )?;
+        Self::from_reader(BufReader::new(input_file))
+    }
+
+    pub fn from_reader<R: BufRead>(reader: R) -> Result<Self, CircuitParseError> {
+        let mut line_reader = LineReader::new(reader);
 
         // read num_gates and num_wires
-        input_file.read_line(&mut line).unwrap();
-        let mut parts = line.split_whitespace();
-        let num_gates = parts.next().unwrap().parse::<usize>().unwrap();
-        let num_wires = parts.next().unwrap().parse::<usize>().unwrap();
+        let (line_number, parts) = line_reader.next_non_empty_line("the number of gates and wires")?;
+        expect_num_parts(line_number, &parts, 2)?;
+        let num_gates = parse_usize(line_number, &parts[0], "number of gates")?;
+        let num_wires = parse_usize(line_number, &parts[1], "number of wires")?;
 
         // read num_inputs
-        line.clear();
-        input_file.read_line(&mut line).unwrap();
-        parts = line.split_whitespace();
-        let num_inputs = parts.next().unwrap().parse::<usize>().unwrap();
-        let mut num_input_bits: usize = 0;
-        for _ in 0..num_inputs {
-            let partial_num_input_bits = parts.next().unwrap().parse::<usize>().unwrap();
-            num_input_bits += partial_num_input_bits;
-        }
+        let (line_number, parts) = line_reader.next_non_empty_line("the input header")?;
+        let num_inputs = parse_usize(line_number, &parts[0], "number of inputs")?;
+        expect_num_parts(line_number, &parts, checked_sum(line_number, &[1, num_inputs], "number of inputs")?)?;
+        let input_group_size_vec = parts[1..].iter().map(
+            |part| parse_usize(line_number, part, "number of input bits")
+        ).collect::<Result<Vec<usize>, CircuitParseError>>()?;
+        let num_input_bits = checked_sum(line_number, &input_group_size_vec, "number of input bits")?;
 
         // read num_outputs
-        line.clear();
-        input_file.read_line(&mut line).unwrap();
-        parts = line.split_whitespace();
-        let num_outputs = parts.next().unwrap().parse::<usize>().unwrap();
-        let mut num_output_bits: usize = 0;
-        for _ in 0..num_outputs {
-            let partial_num_output_bits = parts.next().unwrap().parse::<usize>().unwrap();
-            num_output_bits += partial_num_output_bits;
+        let (line_number, parts) = line_reader.next_non_empty_line("the output header")?;
+        let num_outputs = parse_usize(line_number, &parts[0], "number of outputs")?;
+        expect_num_parts(line_number, &parts, checked_sum(line_number, &[1, num_outputs], "number of outputs")?)?;
+        let output_group_size_vec = parts[1..].iter().map(
+            |part| parse_usize(line_number, part, "number of output bits")
+        ).collect::<Result<Vec<usize>, CircuitParseError>>()?;
+        let num_output_bits = checked_sum(line_number, &output_group_size_vec, "number of output bits")?;
+        if checked_sum(line_number, &[num_input_bits, num_output_bits], "number of input and output bits")? > num_wires {
+            return Err(CircuitParseError::Syntax {
+                line: line_number,
+                message: format!("{} input bits and {} output bits do not fit in {} wires", num_input_bits, num_output_bits, num_wires),
+            });
         }
 
-        // read empty line
-        line.clear();
-        input_file.read_line(&mut line).unwrap();
-
-        // start reading the gates
-        let mut gate_vec: Vec<GateInfo> = Vec::new();
-        for _ in 0..num_gates {
-            line.clear();
-            input_file.read_line(&mut line).unwrap();
-            parts = line.split_whitespace();
-            let num_gate_input_bits = parts.next().unwrap().parse::<usize>().unwrap();
-            let num_gate_output_bits = parts.next().unwrap().parse::<usize>().unwrap();
-            assert!(num_gate_input_bits == 1 || num_gate_input_bits == 2);
-            assert_eq!(num_gate_output_bits, 1);
-            let left_gate_input_wire = parts.next().unwrap().parse::<usize>().unwrap();
-            let mut right_gate_input_wire = 0;
-            if num_gate_input_bits == 2 {
-                right_gate_input_wire = parts.next().unwrap().parse::<usize>().unwrap();
+        // start reading the gates, the empty line after the header is skipped by next_non_empty_line
+        // EQ, EQW and MAND are lowered into AND, XOR and NOT while reading
+        let mut lowering = Lowering::new(num_wires);
+        for gate_id in 0..num_gates {
+            let (line_number, parts) = line_reader.next_non_empty_line(&format!("gate {}", gate_id))?;
+            let num_gate_input_bits = parse_usize(line_number, &parts[0], "number of gate inputs")?;
+            let num_gate_output_bits = parts.get(1).map_or(
+                Err(CircuitParseError::Syntax { line: line_number, message: "missing number of gate outputs".to_string() }),
+                |part| parse_usize(line_number, part, "number of gate outputs")
+            )?;
+            expect_num_parts(line_number, &parts, 2 + num_gate_input_bits + num_gate_output_bits + 1)?;
+            let gate_name = parts[parts.len() - 1].as_str();
+            let bristol_fashion_gate_type = BristolFashionGateType::from_name(gate_name).ok_or(
+                CircuitParseError::Syntax { line: line_number, message: format!("unsupported gate {}", gate_name) }
+            )?;
+            if !bristol_fashion_gate_type.accepts_arity(num_gate_input_bits, num_gate_output_bits) {
+                return Err(CircuitParseError::Syntax {
+                    line: line_number,
+                    message: format!("{} gate with {} inputs and {} outputs", gate_name, num_gate_input_bits, num_gate_output_bits),
+                });
             }
-            let gate_output_wire = parts.next().unwrap().parse::<usize>().unwrap();
-            let gate_name = parts.next().unwrap();
-            let gate_type: GateType = match gate_name {
-                "AND" => GateType::AND,
-                "XOR" => GateType::XOR,
-                "INV" => GateType::NOT,
-                _ => panic!("Unknown gate vec_type: {}", gate_name),
+            let parse_wire = |part: &String| {
+                let wire = parse_usize(line_number, part, "wire")?;
+                if wire >= num_wires {
+                    return Err(CircuitParseError::Syntax {
+                        line: line_number,
+                        message: format!("wire {} is out of range, the circuit has {} wires", wire, num_wires),
+                    });
+                }
+                Ok(wire)
             };
-            gate_vec.push(
-                GateInfo::new(
-                    left_gate_input_wire, 
-                    right_gate_input_wire, 
-                    gate_output_wire, 
-                    gate_type
-                )
-            );
+            let input_parts = &parts[2..2 + num_gate_input_bits];
+            let output_wire_vec = parts[2 + num_gate_input_bits..2 + num_gate_input_bits + num_gate_output_bits].iter().map(
+                parse_wire
+            ).collect::<Result<Vec<usize>, CircuitParseError>>()?;
+
+            match bristol_fashion_gate_type {
+                BristolFashionGateType::EQ => {
+                    // the input of EQ is a constant, not a wire
+                    let constant = match input_parts[0].as_str() {
+                        "0" => 0u8,
+                        "1" => 1u8,
+                        part => return Err(CircuitParseError::Syntax {
+                            line: line_number,
+                            message: format!("invalid EQ constant: {:?}", part),
+                        }),
+                    };
+                    if num_input_bits == 0 {
+                        return Err(CircuitParseError::Syntax {
+                            line: line_number,
+                            message: "EQ requires at least one input wire to derive constants from".to_string(),
+                        });
+                    }
+                    let constant_wire = lowering.constant_wire(constant);
+                    lowering.alias(output_wire_vec[0], constant_wire);
+                }
+                _ => {
+                    let input_wire_vec = input_parts.iter().map(parse_wire).collect::<Result<Vec<usize>, CircuitParseError>>()?;
+                    lowering.lower(&bristol_fashion_gate_type, &input_wire_vec, &output_wire_vec);
+                }
+            }
         }
+        line_reader.expect_end(num_gates)?;
 
+        let output_wire_vec = (num_wires - num_output_bits..num_wires).map(
+            |output_wire| lowering.resolve(output_wire)
+        ).collect();
+        let gate_vec = lowering.gate_vec;
         let and_gate_id_vec = Self::determine_and_gate_id_vec(&gate_vec);
         let and_gate_output_wire_vec = Self::determine_and_gate_output_wires(&gate_vec, &and_gate_id_vec);
 
-        Self {
-            num_wires,
+        Ok(Self {
+            num_wires: lowering.num_wires,
             num_input_bits,
             num_output_bits,
+            input_group_size_vec,
+            output_group_size_vec,
             gate_vec,
             and_gate_id_vec,
             and_gate_output_wire_vec,
-            output_wire_vec: (num_wires - num_output_bits..num_wires).collect(),
-        }
+            output_wire_vec,
+        })
     }
 
     pub fn compute_output_bits(&self, input_bit_vec: &Vec<u8>) -> Vec<u8> {
@@ -119,9 +343,7 @@ impl BristolFashionAdaptor {
             assert!(*val == 0 || *val == 1);
         }
         let mut wire_values: Vec<u8> = vec![255; self.num_wires]; // 255 means not assigned
-        for i in 0..self.num_input_bits {
-            wire_values[i] = input_bit_vec[i];
-        }
+        wire_values[..self.num_input_bits].copy_from_slice(input_bit_vec);
         for gate in &self.gate_vec {
             let gate_output_bit = match gate.gate_type {
                 GateType::AND => {
@@ -145,9 +367,98 @@ impl BristolFashionAdaptor {
             };
             wire_values[gate.output_wire] = gate_output_bit;
         }
-        wire_values[wire_values.len().saturating_sub(self.num_output_bits)..].to_vec()
+        // output wires are not necessarily the last wires once EQ and EQW are lowered into aliases
+        self.output_wire_vec.iter().map(|output_wire| wire_values[*output_wire]).collect()
     }
     
+    // the input wires of group group_id, the groups are laid out one after another from wire 0
+    pub fn get_input_group_wire_range(&self, group_id: usize) -> Range<usize> {
+        let start = self.input_group_size_vec[..group_id].iter().sum::<usize>();
+        start..start + self.input_group_size_vec[group_id]
+    }
+
+    // the positions of group group_id in the output of compute_output_bits
+    pub fn get_output_group_bit_range(&self, group_id: usize) -> Range<usize> {
+        let start = self.output_group_size_vec[..group_id].iter().sum::<usize>();
+        start..start + self.output_group_size_vec[group_id]
+    }
+
+    // the input groups in pa_input_group_id_vec are given to PA, the others to PB,
+    // and big_ia, big_ib list their wires in group order
+    pub fn determine_big_ia_and_big_ib(&self, pa_input_group_id_vec: &[usize]) -> Result<(Vec<usize>, Vec<usize>), GroupLayoutError> {
+        let mut is_pa_group_vec = vec![false; self.input_group_size_vec.len()];
+        for group_id in pa_input_group_id_vec {
+            if *group_id >= is_pa_group_vec.len() || is_pa_group_vec[*group_id] {
+                return Err(GroupLayoutError::InvalidInputGroup(*group_id));
+            }
+            is_pa_group_vec[*group_id] = true;
+        }
+        let mut big_ia = Vec::new();
+        let mut big_ib = Vec::new();
+        for (group_id, is_pa) in is_pa_group_vec.iter().enumerate() {
+            let input_wire_range = self.get_input_group_wire_range(group_id);
+            if *is_pa {
+                big_ia.extend(input_wire_range);
+            } else {
+                big_ib.extend(input_wire_range);
+            }
+        }
+        Ok((big_ia, big_ib))
+    }
+
+    // the input bits of the whole circuit from one integer per input group, least significant bit first
+    pub fn determine_input_bit_vec_from_u64_vec(&self, value_vec: &[u64]) -> Result<Vec<u8>, GroupLayoutError> {
+        check_num_groups(self.input_group_size_vec.len(), value_vec.len())?;
+        let mut input_bit_vec = Vec::with_capacity(self.num_input_bits);
+        for (group_id, (value, num_group_bits)) in value_vec.iter().zip(self.input_group_size_vec.iter()).enumerate() {
+            if *num_group_bits < 64 && (*value >> *num_group_bits) != 0 {
+                return Err(GroupLayoutError::GroupSizeMismatch { group_id, num_group_bits: *num_group_bits });
+            }
+            input_bit_vec.extend((0..*num_group_bits).map(|i| if i < 64 { ((*value >> i) & 1) as u8 } else { 0u8 }));
+        }
+        Ok(input_bit_vec)
+    }
+
+    // the input bits of the whole circuit from one little-endian byte array of ceil(group size / 8) bytes per input group
+    pub fn determine_input_bit_vec_from_byte_vec_vec(&self, byte_vec_vec: &[Vec<u8>]) -> Result<Vec<u8>, GroupLayoutError> {
+        check_num_groups(self.input_group_size_vec.len(), byte_vec_vec.len())?;
+        let mut input_bit_vec = Vec::with_capacity(self.num_input_bits);
+        for (group_id, (byte_vec, num_group_bits)) in byte_vec_vec.iter().zip(self.input_group_size_vec.iter()).enumerate() {
+            let group_bit_vec = byte_vec.iter().flat_map(|byte| (0..8).map(move |i| (byte >> i) & 1)).collect::<Vec<u8>>();
+            if byte_vec.len() != num_group_bits.div_ceil(8) || group_bit_vec[*num_group_bits..].iter().any(|bit| *bit != 0) {
+                return Err(GroupLayoutError::GroupSizeMismatch { group_id, num_group_bits: *num_group_bits });
+            }
+            input_bit_vec.extend_from_slice(&group_bit_vec[..*num_group_bits]);
+        }
+        Ok(input_bit_vec)
+    }
+
+    // splits the output of compute_output_bits, or a published output, into one integer per output group
+    pub fn split_output_bit_vec_into_u64_vec(&self, output_bit_vec: &[u8]) -> Result<Vec<u64>, GroupLayoutError> {
+        check_num_bits(self.num_output_bits, output_bit_vec.len())?;
+        (0..self.output_group_size_vec.len()).map(
+            |group_id| {
+                let output_bit_range = self.get_output_group_bit_range(group_id);
+                if output_bit_range.len() > 64 {
+                    return Err(GroupLayoutError::GroupTooWide { group_id, num_group_bits: output_bit_range.len() });
+                }
+                Ok(output_bit_vec[output_bit_range].iter().enumerate().fold(
+                    0u64, |value, (i, bit)| value | ((*bit as u64) << i)
+                ))
+            }
+        ).collect()
+    }
+
+    // splits the output of compute_output_bits, or a published output, into one little-endian byte array per output group
+    pub fn split_output_bit_vec_into_byte_vec_vec(&self, output_bit_vec: &[u8]) -> Result<Vec<Vec<u8>>, GroupLayoutError> {
+        check_num_bits(self.num_output_bits, output_bit_vec.len())?;
+        Ok((0..self.output_group_size_vec.len()).map(
+            |group_id| output_bit_vec[self.get_output_group_bit_range(group_id)].chunks(8).map(
+                |bit_chunk| bit_chunk.iter().enumerate().fold(0u8, |byte, (i, bit)| byte | (bit << i))
+            ).collect()
+        ).collect())
+    }
+
     // pub fn compute_num_and_gates(&self) -> usize {
     //     let mut num_and_gates: usize = 0;
     //     for gate in &self.gate_vec {
@@ -193,9 +504,44 @@ impl BristolFashionAdaptor {
         self.num_output_bits
     }
     
+    pub fn get_input_group_size_vec(&self) -> &Vec<usize> {
+        &self.input_group_size_vec
+    }
+
+    pub fn get_output_group_size_vec(&self) -> &Vec<usize> {
+        &self.output_group_size_vec
+    }
+
     pub fn get_output_wire_vec(&self) -> &Vec<usize> {
         self.output_wire_vec.as_ref()
     }
+
+    // Hashes the lowered circuit, i.e., the wire count, the input and output groups, every AND/XOR/NOT gate and
+    // the output wires, each as a length-prefixed list of u64s. Two adaptors share a digest exactly when the
+    // protocols would evaluate the same circuit on them, whatever the file they were read from looked like.
+    pub fn determine_digest(&self, hash: &dyn Hash) -> [u8; 32] {
+        let mut byte_vec = Vec::<u8>::new();
+        let mut append_usize_vec = |usize_vec: &[usize]| {
+            byte_vec.extend_from_slice(&(usize_vec.len() as u64).to_le_bytes());
+            for value in usize_vec {
+                byte_vec.extend_from_slice(&(*value as u64).to_le_bytes());
+            }
+        };
+        append_usize_vec(&[self.num_wires, self.num_input_bits, self.num_output_bits]);
+        append_usize_vec(&self.input_group_size_vec);
+        append_usize_vec(&self.output_group_size_vec);
+        append_usize_vec(&[self.gate_vec.len()]);
+        for gate in &self.gate_vec {
+            let gate_type_tag = match gate.gate_type {
+                GateType::AND => 0,
+                GateType::XOR => 1,
+                GateType::NOT => 2,
+            };
+            append_usize_vec(&[gate_type_tag, gate.left_input_wire, gate.right_input_wire, gate.output_wire]);
+        }
+        append_usize_vec(&self.output_wire_vec);
+        hash.digest(CIRCUIT_DIGEST_CONTEXT, &byte_vec)
+    }
 }
 
 // #[test]
@@ -213,10 +559,22 @@ impl BristolFashionAdaptor {
 //     assert_eq!(output_hex_string_1, expected_output_hex_string_1);
 // }
 
+impl FromStr for BristolFashionAdaptor {
+    type Err = CircuitParseError;
+
+    fn from_str(circuit: &str) -> Result<Self, Self::Err> {
+        Self::from_reader(circuit.as_bytes())
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use std::io::BufReader;
+    use std::str::FromStr;
     use rand::Rng;
     use crate::bristol_fashion_adaptor::bristol_fashion_adaptor::BristolFashionAdaptor;
+    use crate::bristol_fashion_adaptor::{CircuitParseError, GroupLayoutError};
+    use crate::functionalities_and_protocols::crypto_backend::HashBackend;
 
     // pub fn compute_output_hex_string_from_input_hex_string(input_hex_string: String)
     //                                                        -> String {
@@ -226,30 +584,15 @@ mod tests {
     //     output_hex_string
     // }
 
-    pub fn u64_to_bit_vec(u64_value: u64) -> Vec<u8> {
-        let mut bit_vec: Vec<u8> = Vec::new();
-        for i in 0..64 {
-            bit_vec.push(((u64_value >> i) & 1) as u8);
-        }
-        bit_vec   
-    }
-
     #[test]
     pub fn adder64_test_compute_output_hex_string_from_input_hex_string() {
         let mut rng = rand::rng();
         let a: u64 = rng.random::<u64>();
         let b: u64 = rng.random::<u64>();
-        let sum =  a.wrapping_add(b);
-        // println!("a: {:?}, b: {:?}, sum: {:?}", a, b, sum);
-        let expected_output_bit_vec = u64_to_bit_vec(sum);
-        let mut input_bit_vec = u64_to_bit_vec(a);
-        input_bit_vec.append(&mut u64_to_bit_vec(b));
         let bristol_fashion_adaptor = BristolFashionAdaptor::new(&"adder64.txt".to_string());
+        let input_bit_vec = bristol_fashion_adaptor.determine_input_bit_vec_from_u64_vec(&[a, b]).unwrap();
         let output_bit_vec = bristol_fashion_adaptor.compute_output_bits(&input_bit_vec);
-        // println!("         output_bit_vec: {:?}", output_bit_vec);
-        // println!("expected_output_bit_vec: {:?}", expected_output_bit_vec);
-        assert_eq!(output_bit_vec, expected_output_bit_vec);
-        // println!("test passed");
+        assert_eq!(bristol_fashion_adaptor.split_output_bit_vec_into_u64_vec(&output_bit_vec).unwrap(), vec![a.wrapping_add(b)]);
     }
 
     #[test]
@@ -257,16 +600,194 @@ mod tests {
         let mut rng = rand::rng();
         let a: u64 = rng.random::<u64>();
         let b: u64 = rng.random::<u64>();
-        let sum =  a.wrapping_sub(b);
-        // println!("a: {:?}, b: {:?}, sum: {:?}", a, b, sum);
-        let expected_output_bit_vec = u64_to_bit_vec(sum);
-        let mut input_bit_vec = u64_to_bit_vec(a);
-        input_bit_vec.append(&mut u64_to_bit_vec(b));
         let bristol_fashion_adaptor = BristolFashionAdaptor::new(&"sub64.txt".to_string());
+        let input_bit_vec = bristol_fashion_adaptor.determine_input_bit_vec_from_u64_vec(&[a, b]).unwrap();
         let output_bit_vec = bristol_fashion_adaptor.compute_output_bits(&input_bit_vec);
-        // println!("         output_bit_vec: {:?}", output_bit_vec);
-        // println!("expected_output_bit_vec: {:?}", expected_output_bit_vec);
-        assert_eq!(output_bit_vec, expected_output_bit_vec);
-        // println!("test passed");
+        assert_eq!(bristol_fashion_adaptor.split_output_bit_vec_into_u64_vec(&output_bit_vec).unwrap(), vec![a.wrapping_sub(b)]);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_input_and_output_groups() {
+        let bristol_fashion_adaptor = BristolFashionAdaptor::new(&"sub64.txt".to_string());
+        assert_eq!(bristol_fashion_adaptor.get_input_group_size_vec(), &vec![64, 64]);
+        assert_eq!(bristol_fashion_adaptor.get_output_group_size_vec(), &vec![64]);
+        assert_eq!(bristol_fashion_adaptor.get_input_group_wire_range(1), 64..128);
+
+        let (big_ia, big_ib) = bristol_fashion_adaptor.determine_big_ia_and_big_ib(&[1]).unwrap();
+        assert_eq!(big_ia, (64..128).collect::<Vec<usize>>());
+        assert_eq!(big_ib, (0..64).collect::<Vec<usize>>());
+        assert_eq!(bristol_fashion_adaptor.determine_big_ia_and_big_ib(&[0, 0]), Err(GroupLayoutError::InvalidInputGroup(0)));
+        assert_eq!(bristol_fashion_adaptor.determine_big_ia_and_big_ib(&[2]), Err(GroupLayoutError::InvalidInputGroup(2)));
+
+        // byte arrays are little-endian, so they agree with the integers
+        let a = 0x0123456789abcdefu64;
+        let b = 0x0000000000000fedu64;
+        let input_bit_vec = bristol_fashion_adaptor.determine_input_bit_vec_from_byte_vec_vec(
+            &[a.to_le_bytes().to_vec(), b.to_le_bytes().to_vec()]
+        ).unwrap();
+        assert_eq!(input_bit_vec, bristol_fashion_adaptor.determine_input_bit_vec_from_u64_vec(&[a, b]).unwrap());
+        let output_bit_vec = bristol_fashion_adaptor.compute_output_bits(&input_bit_vec);
+        assert_eq!(
+            bristol_fashion_adaptor.split_output_bit_vec_into_byte_vec_vec(&output_bit_vec).unwrap(),
+            vec![(a - b).to_le_bytes().to_vec()]
+        );
+
+        assert_eq!(
+            bristol_fashion_adaptor.determine_input_bit_vec_from_u64_vec(&[a]),
+            Err(GroupLayoutError::NumGroupsMismatch { expected: 2, actual: 1 })
+        );
+        assert_eq!(
+            bristol_fashion_adaptor.split_output_bit_vec_into_u64_vec(&output_bit_vec[1..]),
+            Err(GroupLayoutError::NumBitsMismatch { expected: 64, actual: 63 })
+        );
+
+        // groups that are not a multiple of 8 bits, SMALL_CIRCUIT has input groups of 2 and 1 bits
+        let bristol_fashion_adaptor = BristolFashionAdaptor::from_str(SMALL_CIRCUIT).unwrap();
+        assert_eq!(bristol_fashion_adaptor.determine_input_bit_vec_from_u64_vec(&[0b11, 0]).unwrap(), vec![1, 1, 0]);
+        assert_eq!(
+            bristol_fashion_adaptor.determine_input_bit_vec_from_u64_vec(&[0b100, 0]),
+            Err(GroupLayoutError::GroupSizeMismatch { group_id: 0, num_group_bits: 2 })
+        );
+        assert_eq!(
+            bristol_fashion_adaptor.determine_input_bit_vec_from_byte_vec_vec(&[vec![0b01], vec![0b10]]),
+            Err(GroupLayoutError::GroupSizeMismatch { group_id: 1, num_group_bits: 1 })
+        );
+    }
+
+    // (a AND b) XOR (NOT c) with a, b from the first input and c from the second one
+    const SMALL_CIRCUIT: &str = "3 6\n2 2 1\n1 1\n\n2 1 0 1 3 AND\n1 1 2 4 INV\n2 1 3 4 5 XOR\n";
+
+    #[test]
+    fn test_from_str_and_from_reader() {
+        let bristol_fashion_adaptor = BristolFashionAdaptor::from_str(SMALL_CIRCUIT).unwrap();
+        assert_eq!(bristol_fashion_adaptor.get_num_wires(), 6);
+        assert_eq!(bristol_fashion_adaptor.get_num_input_bits(), 3);
+        assert_eq!(bristol_fashion_adaptor.get_num_output_bits(), 1);
+        assert_eq!(bristol_fashion_adaptor.get_and_gate_id_vec(), &vec![0]);
+        assert_eq!(bristol_fashion_adaptor.get_and_gate_output_wire_vec(), &vec![3]);
+        for input in 0..8u8 {
+            let input_bit_vec = vec![input & 1, (input >> 1) & 1, (input >> 2) & 1];
+            assert_eq!(
+                bristol_fashion_adaptor.compute_output_bits(&input_bit_vec),
+                vec![(input_bit_vec[0] & input_bit_vec[1]) ^ input_bit_vec[2] ^ 1]
+            );
+        }
+
+        let bristol_fashion_adaptor = BristolFashionAdaptor::from_reader(BufReader::new(SMALL_CIRCUIT.as_bytes())).unwrap();
+        assert_eq!(bristol_fashion_adaptor.get_gate_vec().len(), 3);
+    }
+
+    #[test]
+    fn test_digest() {
+        let hash = HashBackend::Blake3.hash();
+        let digest = |circuit: &str| BristolFashionAdaptor::from_str(circuit).unwrap().determine_digest(hash);
+        assert_eq!(digest(SMALL_CIRCUIT), digest(SMALL_CIRCUIT));
+        // the layout of the file does not matter, only the circuit does
+        assert_eq!(digest(SMALL_CIRCUIT), digest(&SMALL_CIRCUIT.replace(' ', "  ").replace('\n', "\n\n")));
+        // swapping AND for XOR, or moving the second input into the first group, yields another circuit
+        assert_ne!(digest(SMALL_CIRCUIT), digest(&SMALL_CIRCUIT.replace("AND", "XOR")));
+        assert_ne!(digest(SMALL_CIRCUIT), digest(&SMALL_CIRCUIT.replace("2 2 1\n", "2 1 2\n")));
+        assert_ne!(
+            BristolFashionAdaptor::new(&"adder64.txt".to_string()).determine_digest(hash),
+            BristolFashionAdaptor::new(&"sub64.txt".to_string()).determine_digest(hash)
+        );
+        // and so does the hash backend
+        assert_ne!(digest(SMALL_CIRCUIT), BristolFashionAdaptor::from_str(SMALL_CIRCUIT).unwrap().determine_digest(HashBackend::Shake256.hash()));
+    }
+
+    // MAND, EQ and EQW with outputs a0 AND b0, NOT (a1 AND b1), the constant 0 and a copy of a0
+    const LOWERED_CIRCUIT: &str = "6 11\n2 2 2\n1 4\n\n\
+        4 2 0 1 2 3 4 5 MAND\n\
+        1 1 1 6 EQ\n\
+        1 1 4 7 EQW\n\
+        2 1 6 5 8 XOR\n\
+        1 1 0 9 EQ\n\
+        1 1 0 10 EQW\n";
+
+    #[test]
+    fn test_lowering_of_eq_eqw_and_mand() {
+        let bristol_fashion_adaptor = BristolFashionAdaptor::from_str(LOWERED_CIRCUIT).unwrap();
+        // two AND gates from MAND, then 0 = w0 XOR w0, 1 = NOT 0 and the XOR
+        assert_eq!(bristol_fashion_adaptor.get_gate_vec().len(), 5);
+        assert_eq!(bristol_fashion_adaptor.get_num_wires(), 13);
+        assert_eq!(bristol_fashion_adaptor.get_and_gate_id_vec(), &vec![0, 1]);
+        assert_eq!(bristol_fashion_adaptor.get_and_gate_output_wire_vec(), &vec![4, 5]);
+        assert_eq!(bristol_fashion_adaptor.get_output_wire_vec(), &vec![4, 8, 11, 0]);
+        for input in 0..16u8 {
+            let input_bit_vec = (0..4).map(|i| (input >> i) & 1).collect::<Vec<u8>>();
+            assert_eq!(
+                bristol_fashion_adaptor.compute_output_bits(&input_bit_vec),
+                vec![
+                    input_bit_vec[0] & input_bit_vec[2],
+                    (input_bit_vec[1] & input_bit_vec[3]) ^ 1,
+                    0,
+                    input_bit_vec[0],
+                ]
+            );
+        }
+
+        let error_line = |circuit: &str| match BristolFashionAdaptor::from_str(circuit) {
+            Err(CircuitParseError::Syntax { line, .. }) => line,
+            _ => panic!("expected a syntax error"),
+        };
+        // EQ takes 0 or 1
+        assert_eq!(error_line("1 3\n1 2\n1 1\n\n1 1 2 2 EQ\n"), 5);
+        // MAND needs twice as many inputs as outputs
+        assert_eq!(error_line("1 4\n1 2\n1 2\n\n3 2 0 1 0 2 3 MAND\n"), 5);
+        // EQW has a single input
+        assert_eq!(error_line("1 3\n1 2\n1 1\n\n2 1 0 1 2 EQW\n"), 5);
+    }
+
+    #[test]
+    fn neg64_test_compute_output_bits() {
+        let mut rng = rand::rng();
+        let a: u64 = rng.random::<u64>();
+        let bristol_fashion_adaptor = BristolFashionAdaptor::new(&"neg64.txt".to_string());
+        let input_bit_vec = bristol_fashion_adaptor.determine_input_bit_vec_from_u64_vec(&[a]).unwrap();
+        let output_bit_vec = bristol_fashion_adaptor.compute_output_bits(&input_bit_vec);
+        assert_eq!(bristol_fashion_adaptor.split_output_bit_vec_into_u64_vec(&output_bit_vec).unwrap(), vec![a.wrapping_neg()]);
+    }
+
+    #[test]
+    fn test_from_path() {
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/src/bristol_fashion_adaptor/circuit_data/sub64.txt");
+        let bristol_fashion_adaptor = BristolFashionAdaptor::from_path(path).unwrap();
+        let bundled_bristol_fashion_adaptor = BristolFashionAdaptor::new(&"sub64.txt".to_string());
+        assert_eq!(bristol_fashion_adaptor.get_gate_vec().len(), 439);
+        assert_eq!(bristol_fashion_adaptor.get_num_wires(), bundled_bristol_fashion_adaptor.get_num_wires());
+        assert_eq!(bristol_fashion_adaptor.get_and_gate_id_vec(), bundled_bristol_fashion_adaptor.get_and_gate_id_vec());
+
+        assert!(matches!(
+            BristolFashionAdaptor::from_path("no_such_circuit.txt"),
+            Err(CircuitParseError::Io(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_errors_carry_line_numbers() {
+        let error_line = |circuit: &str| match BristolFashionAdaptor::from_str(circuit) {
+            Err(CircuitParseError::Syntax { line, .. }) => line,
+            _ => panic!("expected a syntax error"),
+        };
+        // unknown gate
+        assert_eq!(error_line("1 3\n1 2\n1 1\n\n2 1 0 1 2 NAND\n"), 5);
+        // wire out of range
+        assert_eq!(error_line("1 3\n1 2\n1 1\n\n2 1 0 7 2 AND\n"), 5);
+        // wrong number of entries in a gate
+        assert_eq!(error_line("1 3\n1 2\n1 1\n\n2 1 0 2 AND\n"), 5);
+        // not a number in the header
+        assert_eq!(error_line("1 x\n1 2\n1 1\n"), 1);
+        // input header declares more groups than it lists
+        assert_eq!(error_line("1 3\n2 2\n1 1\n"), 2);
+        // fewer gates than declared
+        assert_eq!(error_line("2 3\n1 2\n1 1\n\n2 1 0 1 2 AND\n"), 6);
+        // more gates than declared
+        assert_eq!(error_line("1 3\n1 2\n1 1\n\n2 1 0 1 2 AND\n2 1 0 1 2 XOR\n"), 6);
+        // header values that overflow when added up
+        let max = usize::MAX;
+        assert_eq!(error_line(&format!("1 3\n{} 2\n1 1\n", max)), 2);
+        assert_eq!(error_line(&format!("1 3\n1 2\n{} 1\n", max)), 3);
+        assert_eq!(error_line(&format!("1 3\n2 {} 1\n1 1\n", max)), 2);
+        assert_eq!(error_line(&format!("1 3\n1 {}\n1 1\n", max)), 3);
+    }
+}
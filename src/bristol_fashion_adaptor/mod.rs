@@ -1,3 +1,5 @@
+use std::fmt::{Display, Formatter};
+
 pub mod bristol_fashion_adaptor;
 
 #[derive(PartialEq, Debug, Clone)]
@@ -7,6 +9,43 @@ pub enum GateType {
     NOT,
 }
 
+// The gates allowed by the Bristol Fashion specification. The adaptor lowers them into GateType,
+// so that the protocols only ever see AND, XOR and NOT
+#[derive(PartialEq, Debug, Clone)]
+pub enum BristolFashionGateType {
+    AND,
+    XOR,
+    INV,
+    // assigns a constant 0 or 1 to its output wire
+    EQ,
+    // copies its input wire to its output wire
+    EQW,
+    // n AND gates at once, with inputs a_1..a_n, b_1..b_n and outputs c_1..c_n
+    MAND,
+}
+
+impl BristolFashionGateType {
+    pub fn from_name(gate_name: &str) -> Option<Self> {
+        match gate_name {
+            "AND" => Some(Self::AND),
+            "XOR" => Some(Self::XOR),
+            "INV" => Some(Self::INV),
+            "EQ" => Some(Self::EQ),
+            "EQW" => Some(Self::EQW),
+            "MAND" => Some(Self::MAND),
+            _ => None,
+        }
+    }
+
+    pub fn accepts_arity(&self, num_input_bits: usize, num_output_bits: usize) -> bool {
+        match self {
+            Self::AND | Self::XOR => num_input_bits == 2 && num_output_bits == 1,
+            Self::INV | Self::EQ | Self::EQW => num_input_bits == 1 && num_output_bits == 1,
+            Self::MAND => num_output_bits > 0 && num_input_bits == 2 * num_output_bits,
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct GateInfo {
     pub left_input_wire: usize,
@@ -24,4 +63,70 @@ impl GateInfo {
             gate_type,
         }
     }
-}
\ No newline at end of file
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub enum CircuitParseError {
+    // the circuit could not be read at all
+    Io(String),
+    // the circuit does not follow the Bristol Fashion format, line is 1-based
+    Syntax {
+        line: usize,
+        message: String,
+    },
+}
+
+impl Display for CircuitParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CircuitParseError::Io(message) => write!(f, "cannot read circuit: {}", message),
+            CircuitParseError::Syntax { line, message } => write!(f, "line {}: {}", line, message),
+        }
+    }
+}
+
+impl std::error::Error for CircuitParseError {}
+
+#[derive(PartialEq, Debug, Clone)]
+pub enum GroupLayoutError {
+    // an input group id does not exist or is assigned to PA twice
+    InvalidInputGroup(usize),
+    // the number of values does not match the number of groups
+    NumGroupsMismatch {
+        expected: usize,
+        actual: usize,
+    },
+    // a value does not fit in its group, or a byte array has the wrong length
+    GroupSizeMismatch {
+        group_id: usize,
+        num_group_bits: usize,
+    },
+    // a group is wider than 64 bits and cannot be returned as an integer
+    GroupTooWide {
+        group_id: usize,
+        num_group_bits: usize,
+    },
+    // the bit vector does not cover all groups
+    NumBitsMismatch {
+        expected: usize,
+        actual: usize,
+    },
+}
+
+impl Display for GroupLayoutError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GroupLayoutError::InvalidInputGroup(group_id) => write!(f, "invalid input group {}", group_id),
+            GroupLayoutError::NumGroupsMismatch { expected, actual } => write!(f, "expected {} groups, got {}", expected, actual),
+            GroupLayoutError::GroupSizeMismatch { group_id, num_group_bits } => write!(
+                f, "value of group {} does not fit in {} bits", group_id, num_group_bits
+            ),
+            GroupLayoutError::GroupTooWide { group_id, num_group_bits } => write!(
+                f, "group {} has {} bits, which do not fit in an integer", group_id, num_group_bits
+            ),
+            GroupLayoutError::NumBitsMismatch { expected, actual } => write!(f, "expected {} bits, got {}", expected, actual),
+        }
+    }
+}
+
+impl std::error::Error for GroupLayoutError {}
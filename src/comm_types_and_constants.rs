@@ -1,3 +1,6 @@
 pub const SEED_BYTE_LEN: usize = 16;
 pub const NUM_BITS_PER_HEX: usize = 4;
-pub const BLAKE3_HASH_DIGEST_NUM_BYTES: usize = 32;
\ No newline at end of file
+pub const BLAKE3_HASH_DIGEST_NUM_BYTES: usize = 32;
+// Decoding a proof bundle, a public parameter or a channel message claims at most this many bytes, so that a crafted
+// length is rejected before its buffer is allocated. A proof bundle of sha256 takes about 16 MB
+pub const DECODING_BYTE_LIMIT: usize = 1 << 30;
@@ -0,0 +1,135 @@
+use crate::functionalities_and_protocols::all_in_one_vc::one_to_two_prg::{GGMTweak, OneToTwoPRG};
+use crate::value_type::seed_u8x16::SeedU8x16;
+use crate::value_type::Zero;
+
+// A single GGM tree whose leaves are the leaves of all repetitions. Leaf i of every repetition comes before leaf
+// i + 1 of any repetition, so that the excluded leaves are spread over the whole tree and their paths to the root
+// meet near the root, which is why the co-path is shorter than kappa separate seed traces. The tree is padded with
+// unused leaves up to a power of two, and the nodes above unused leaves only are never expanded nor revealed.
+pub struct BatchedGGMTree {
+    depth: u8,
+    num_leaves: usize,
+    // the position of leaf i of each repetition among the leaves of the batched tree
+    leaf_position_rep: Vec<Vec<usize>>,
+    // the repetition id of the tweak is the number of repetitions, which no tree of a single repetition has
+    tweak: GGMTweak,
+}
+
+impl BatchedGGMTree {
+    pub fn new(tau_rep: &[u8], is_pa: bool) -> Self {
+        let max_tau = *tau_rep.iter().max().unwrap();
+        let mut leaf_position_rep: Vec<Vec<usize>> = tau_rep.iter().map(|tau| Vec::with_capacity(1 << tau)).collect();
+        let mut num_leaves = 0usize;
+        for leaf_index in 0..1usize << max_tau {
+            for (repetition_id, tau) in tau_rep.iter().enumerate() {
+                if leaf_index < 1 << tau {
+                    leaf_position_rep[repetition_id].push(num_leaves);
+                    num_leaves += 1;
+                }
+            }
+        }
+        Self {
+            depth: num_leaves.next_power_of_two().trailing_zeros() as u8,
+            num_leaves,
+            leaf_position_rep,
+            tweak: GGMTweak::new(is_pa, tau_rep.len()),
+        }
+    }
+
+    fn tree_len(&self) -> usize {
+        (2 << self.depth) - 1
+    }
+
+    fn first_leaf_index(&self) -> usize {
+        (1 << self.depth) - 1
+    }
+
+    // whether some leaf in use lies below the node, leaves in use being a prefix of all leaves
+    fn is_in_use(&self, node_index: usize) -> bool {
+        let level = (node_index + 1).ilog2();
+        let leftmost_leaf_position = (node_index + 1 - (1 << level)) << (self.depth as u32 - level);
+        leftmost_leaf_position < self.num_leaves
+    }
+
+    // expands every known node in use, parents precede their children so that a single pass suffices
+    fn expand(&self, one_to_two_prg: &OneToTwoPRG, tree: &mut [SeedU8x16], is_known: &mut [bool]) {
+        for node_index in 0..self.first_leaf_index() {
+            if is_known[node_index] && self.is_in_use(node_index) {
+                let (seed_0, seed_1) = one_to_two_prg.generate_double_tweaked(&tree[node_index], &self.tweak, node_index);
+                tree[(node_index << 1) + 1] = seed_0;
+                tree[(node_index << 1) + 2] = seed_1;
+                is_known[(node_index << 1) + 1] = true;
+                is_known[(node_index << 1) + 2] = true;
+            }
+        }
+    }
+
+    pub fn generate(&self, one_to_two_prg: &OneToTwoPRG, seed: &SeedU8x16) -> Vec<SeedU8x16> {
+        let mut tree = vec![SeedU8x16::zero(); self.tree_len()];
+        let mut is_known = vec![false; self.tree_len()];
+        tree[0] = *seed;
+        is_known[0] = true;
+        self.expand(one_to_two_prg, &mut tree, &mut is_known);
+        tree
+    }
+
+    pub fn leaf_seed_vec(&self, tree: &[SeedU8x16], repetition_id: usize) -> Vec<SeedU8x16> {
+        self.leaf_position_rep[repetition_id].iter().map(
+            |leaf_position| tree[self.first_leaf_index() + leaf_position]
+        ).collect()
+    }
+
+    // the nodes in use off the paths from the excluded leaves to the root whose parents are on such a path, i.e.,
+    // the roots of the largest subtrees that together cover exactly the leaves to be revealed
+    fn determine_co_path(&self, excluded_index_rep: &[usize]) -> Vec<usize> {
+        assert_eq!(excluded_index_rep.len(), self.leaf_position_rep.len());
+        let mut is_on_path = vec![false; self.tree_len()];
+        for (leaf_position_vec, excluded_index) in self.leaf_position_rep.iter().zip(excluded_index_rep.iter()) {
+            assert!(*excluded_index < leaf_position_vec.len());
+            let mut node_index = self.first_leaf_index() + leaf_position_vec[*excluded_index];
+            while !is_on_path[node_index] {
+                is_on_path[node_index] = true;
+                if node_index == 0 {
+                    break;
+                }
+                node_index = (node_index - 1) >> 1;
+            }
+        }
+        (1..self.tree_len()).filter(
+            |node_index| !is_on_path[*node_index] && is_on_path[(node_index - 1) >> 1] && self.is_in_use(*node_index)
+        ).collect()
+    }
+
+    pub fn open(&self, tree: &[SeedU8x16], excluded_index_rep: &[usize]) -> Vec<SeedU8x16> {
+        self.determine_co_path(excluded_index_rep).into_iter().map(
+            |node_index| tree[node_index]
+        ).collect()
+    }
+
+    // the leaf seeds of each repetition, with zero at the excluded leaf, or None if the number of seeds does not
+    // match the co-path of the excluded leaves
+    pub fn reconstruct_leaf_seed_vec_rep(
+        &self, one_to_two_prg: &OneToTwoPRG, excluded_index_rep: &[usize], co_path_seed_vec: &[SeedU8x16]
+    ) -> Option<Vec<Vec<SeedU8x16>>> {
+        let co_path = self.determine_co_path(excluded_index_rep);
+        if co_path.len() != co_path_seed_vec.len() {
+            return None;
+        }
+        let mut tree = vec![SeedU8x16::zero(); self.tree_len()];
+        let mut is_known = vec![false; self.tree_len()];
+        for (node_index, seed) in co_path.into_iter().zip(co_path_seed_vec.iter()) {
+            tree[node_index] = *seed;
+            is_known[node_index] = true;
+        }
+        self.expand(one_to_two_prg, &mut tree, &mut is_known);
+        Some(
+            (0..self.leaf_position_rep.len()).map(
+                |repetition_id| {
+                    let mut leaf_seed_vec = self.leaf_seed_vec(&tree, repetition_id);
+                    leaf_seed_vec[excluded_index_rep[repetition_id]] = SeedU8x16::zero();
+                    leaf_seed_vec
+                }
+            ).collect()
+        )
+    }
+}
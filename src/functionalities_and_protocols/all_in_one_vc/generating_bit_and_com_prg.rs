@@ -1,24 +1,41 @@
 use crate::comm_types_and_constants::{SEED_BYTE_LEN};
 use crate::functionalities_and_protocols::all_in_one_vc::one_to_two_prg::OneToTwoPRG;
+use crate::functionalities_and_protocols::all_in_one_vc::LeafExpansionVersion;
 use crate::value_type::seed_u8x16::SeedU8x16;
 use crate::vec_type::bit_vec::BitVec;
 
 pub struct GeneratingBitAndComPRG<'a> {
     one_to_two_prg: &'a OneToTwoPRG,
+    leaf_expansion_version: LeafExpansionVersion,
 }
 
 impl<'a> GeneratingBitAndComPRG<'a> {
 
-    pub fn new(one_to_two_prg: &'a OneToTwoPRG) -> GeneratingBitAndComPRG<'a> {
+    pub fn new(one_to_two_prg: &'a OneToTwoPRG, leaf_expansion_version: LeafExpansionVersion) -> GeneratingBitAndComPRG<'a> {
         Self {
-            one_to_two_prg
+            one_to_two_prg,
+            leaf_expansion_version,
         }
     }
 
+    // the com of the excluded leaf is published, so in V2 it is fed forward and does not reveal the leaf seed
     pub fn generate(&self, seed: &SeedU8x16, bit_vec_len: usize) -> (BitVec, SeedU8x16) {
-        let bit_vec_byte_len =  (bit_vec_len - 1) / SEED_BYTE_LEN + 1;
+        let (bit_vec, com) = match self.leaf_expansion_version {
+            LeafExpansionVersion::V1 => {
+                let (seed_for_bit_vec, com) = self.one_to_two_prg.generate_double(seed);
+                (self.generate_bit_vec_by_chaining(seed_for_bit_vec, bit_vec_len), com)
+            }
+            LeafExpansionVersion::V2 => {
+                let (seed_for_bit_vec, com) = self.one_to_two_prg.generate_double_fed_forward(seed);
+                (self.generate_bit_vec_in_counter_mode(&seed_for_bit_vec, bit_vec_len), com)
+            }
+        };
+        assert_eq!(bit_vec.len(), bit_vec_len);
+        (bit_vec, com)
+    }
 
-        let (mut seed_for_bit_vec, com) = self.one_to_two_prg.generate_double(seed);
+    fn generate_bit_vec_by_chaining(&self, mut seed_for_bit_vec: SeedU8x16, bit_vec_len: usize) -> BitVec {
+        let bit_vec_byte_len =  (bit_vec_len - 1) / SEED_BYTE_LEN + 1;
         let mut bit_vec: BitVec = BitVec::new();
         for _ in 0..bit_vec_byte_len {
             let byte_vec;
@@ -30,7 +47,15 @@ impl<'a> GeneratingBitAndComPRG<'a> {
                 }
             }
         }
-        assert_eq!(bit_vec.len(), bit_vec_len);
-        (bit_vec, com)
+        bit_vec
     }
-}
\ No newline at end of file
+
+    // every block yields 128 bits, i.e., two little-endian words of the BitVec
+    fn generate_bit_vec_in_counter_mode(&self, seed_for_bit_vec: &SeedU8x16, bit_vec_len: usize) -> BitVec {
+        let block_vec = self.one_to_two_prg.generate_counter_mode(seed_for_bit_vec, bit_vec_len.div_ceil(SEED_BYTE_LEN * 8));
+        let word_vec = block_vec.iter().flat_map(
+            |block| block.chunks(8).map(|chunk| u64::from_le_bytes(chunk.try_into().unwrap()))
+        ).collect::<Vec<u64>>();
+        BitVec::from_word_vec(bit_vec_len, word_vec)
+    }
+}
@@ -1,6 +1,44 @@
+use bincode::{Decode, Encode};
+use crate::value_type::seed_u8x16::SeedU8x16;
+
 pub mod prover_in_all_in_one_vc;
 pub(crate) mod one_to_two_prg;
 mod generating_bit_and_com_prg;
 pub mod verifier_in_all_in_one_vc;
+pub mod batched_ggm_tree;
+
+mod test;
+
+// Whether every repetition has a GGM tree of its own, or the leaves of all repetitions hang off a single tree
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Encode, Decode)]
+pub enum VectorCommitmentMode {
+    PerRepetition,
+    Batched,
+}
+
+// How a leaf seed is expanded into its bits and commitment. Both versions commit to the leaf with the second half
+// of a single expansion of the seed, and differ in how the bits are drawn from the first half.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Encode, Decode)]
+pub enum LeafExpansionVersion {
+    // a chain of expansions, keeping the lowest bit of every byte. The commitment is G_1(seed) as in the original
+    // implementation, which anyone holding the public key inverts to the seed of the excluded leaf, so V1 is only
+    // kept for comparison
+    V1,
+    // AES in counter mode, keeping every bit, and the commitment G_1(seed) ^ seed, which is one-way
+    V2,
+}
 
-mod test;
\ No newline at end of file
+// What a prover reveals so that the verifier learns every leaf but the excluded one of each repetition
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode)]
+pub enum Decom {
+    // per repetition, the commitment at the excluded leaf and the seeds of the siblings on its path to the root
+    PerRepetition {
+        decom_rep: Vec<(SeedU8x16, Vec<SeedU8x16>)>,
+    },
+    // the commitment at the excluded leaf of each repetition, and the seeds of the fewest subtrees of the batched
+    // tree that cover all other leaves
+    Batched {
+        com_at_excluded_index_rep: Vec<SeedU8x16>,
+        co_path_seed_vec: Vec<SeedU8x16>,
+    },
+}
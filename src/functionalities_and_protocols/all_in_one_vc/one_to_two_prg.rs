@@ -1,53 +1,95 @@
-use aes::Aes128;
-use aes::cipher::{BlockEncrypt, KeyInit, generic_array::GenericArray};
-use crate::comm_types_and_constants::{SEED_BYTE_LEN};
-use crate::value_type::seed_u8x16::SeedU8x16;
+use crate::functionalities_and_protocols::crypto_backend::{LengthDoublingPrg, PrgBackend};
+use crate::value_type::seed_u8x16::{xor_block, SeedU8x16};
 use crate::value_type::Zero;
 // We imitate the below link for implementing OneToTwoPRG
 // https://github.com/GaloisInc/swanky/blob/dev/schmivitz/src/all_but_one_vc.rs
 
-pub struct OneToTwoPRG {
-    aes_cipher_0: Aes128,
-    aes_cipher_1: Aes128,
+// The tweak of a GGM tree, which together with the index of a node in the tree is xored into the seed of the
+// node before expanding it, so that no two nodes of any tree of either party are expanded on the same input
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GGMTweak {
+    pub is_pa: bool,
+    pub repetition_id: usize,
 }
 
-impl OneToTwoPRG {
-    pub fn new(key: &SeedU8x16) -> OneToTwoPRG {
-        let cipher = Aes128::new(&GenericArray::from(*key));
+impl GGMTweak {
+    pub fn new(is_pa: bool, repetition_id: usize) -> Self {
+        Self {
+            is_pa,
+            repetition_id,
+        }
+    }
 
-        let mut block = GenericArray::from([255u8; SEED_BYTE_LEN]);
-        cipher.encrypt_block(&mut block);
-        let aes_cipher_0 = Aes128::new(&block);
+    // byte 0 is the party, bytes 1 to 4 the repetition and bytes 8 to 15 the node index
+    fn to_block(self, node_index: usize) -> SeedU8x16 {
+        let mut block = SeedU8x16::zero();
+        block[0] = if self.is_pa { 0 } else { 1 };
+        block[1..5].copy_from_slice(&(self.repetition_id as u32).to_le_bytes());
+        block[8..16].copy_from_slice(&(node_index as u64).to_le_bytes());
+        block
+    }
+}
 
-        let mut block = GenericArray::from([254u8; SEED_BYTE_LEN]);
-        cipher.encrypt_block(&mut block);
-        let aes_cipher_1 = Aes128::new(&block);
+pub struct OneToTwoPRG {
+    prg: Box<dyn LengthDoublingPrg>,
+}
 
+impl OneToTwoPRG {
+    // the iv is only set in the salted mode, see the backends for how it enters the keys
+    pub fn new(prg_backend: PrgBackend, key: &SeedU8x16, iv: Option<&SeedU8x16>) -> OneToTwoPRG {
         Self {
-            aes_cipher_0,
-            aes_cipher_1
+            prg: prg_backend.instantiate(key, iv),
         }
     }
 
     pub fn generate_double(&self, seed: &SeedU8x16) -> (SeedU8x16, SeedU8x16) {
-        let mut cloned_key_0 = GenericArray::from(*seed);
-        self.aes_cipher_0.encrypt_block(&mut cloned_key_0);
-        let mut cloned_key_1 = GenericArray::from(*seed);
-        self.aes_cipher_1.encrypt_block(&mut cloned_key_1);
+        self.prg.generate_double(seed)
+    }
+
+    // The seed is fed forward, i.e., child b is G_b(seed) ^ seed, so that the expansion is not a permutation that
+    // anyone holding the public key could invert from a revealed child
+    pub fn generate_double_fed_forward(&self, seed: &SeedU8x16) -> (SeedU8x16, SeedU8x16) {
+        let (seed_0, seed_1) = self.generate_double(seed);
         (
-            cloned_key_0.into(),
-            cloned_key_1.into()
+            xor_block(&seed_0, seed),
+            xor_block(&seed_1, seed)
         )
     }
 
-    pub fn generate_ggm_tree(&self, seed: &SeedU8x16, depth: u8) -> Vec<SeedU8x16> {
+    // the seed is masked with the tweak and fed forward, i.e., child b is G_b(seed ^ tweak) ^ seed ^ tweak
+    pub fn generate_double_tweaked(&self, seed: &SeedU8x16, tweak: &GGMTweak, node_index: usize) -> (SeedU8x16, SeedU8x16) {
+        self.generate_double_fed_forward(&xor_block(seed, &tweak.to_block(node_index)))
+    }
+
+    // the blocks G_0(seed ^ i) ^ seed ^ i for the counters i = 0, 1, ..., which do not depend on each other and
+    // are generated in one batch
+    pub fn generate_counter_mode(&self, seed: &SeedU8x16, num_blocks: usize) -> Vec<SeedU8x16> {
+        let masked_seed_vec = (0..num_blocks).map(
+            |counter| {
+                let mut counter_block = SeedU8x16::zero();
+                counter_block[..8].copy_from_slice(&(counter as u64).to_le_bytes());
+                xor_block(seed, &counter_block)
+            }
+        ).collect::<Vec<SeedU8x16>>();
+        let block_vec = self.prg.generate_first_vec(&masked_seed_vec);
+        block_vec.iter().zip(masked_seed_vec.iter()).map(
+            |(block, masked_seed)| xor_block(block, masked_seed)
+        ).collect()
+    }
+
+    // root_index is the index of the root within the whole tree of the tweak, which is not 0 for the subtrees
+    // below the siblings revealed by a decommitment
+    pub fn generate_ggm_tree(&self, seed: &SeedU8x16, depth: u8, tweak: &GGMTweak, root_index: usize) -> Vec<SeedU8x16> {
         // Here we define depth to be the distance from the root to the leaf
         // If depth is 0, the tree is a single node
         // If depth is 1, the tree is a root and a leaf
         let mut tree: Vec<SeedU8x16> = vec![SeedU8x16::zero(); (1usize << (depth + 1)) - 1];
-        tree[0] = seed.clone();
+        tree[0] = *seed;
         for i in 0..(1usize << depth) - 1 {
-            let (key_0, key_1) = self.generate_double(&tree[i]);
+            // node i of level l in the subtree is node i - 2^l + 1 of level l below the root in the whole tree
+            let level = (i + 1).ilog2();
+            let node_index = ((root_index + 1) << level) + (i + 1 - (1 << level)) - 1;
+            let (key_0, key_1) = self.generate_double_tweaked(&tree[i], tweak, node_index);
             tree[(i << 1) + 1] = key_0;
             tree[(i << 1) + 2] = key_1;       
         }
@@ -58,13 +100,14 @@ impl OneToTwoPRG {
 #[cfg(test)]
 mod tests {
     use crate::comm_types_and_constants::SEED_BYTE_LEN;
-    use crate::functionalities_and_protocols::all_in_one_vc::one_to_two_prg::OneToTwoPRG;
+    use crate::functionalities_and_protocols::all_in_one_vc::one_to_two_prg::{GGMTweak, OneToTwoPRG};
+    use crate::functionalities_and_protocols::crypto_backend::PrgBackend;
     use crate::value_type::seed_u8x16::SeedU8x16;
 
     #[test]
     fn test_one_to_two_prg() {
         let seed: SeedU8x16 = [10u8; SEED_BYTE_LEN];
-        let prg: OneToTwoPRG = OneToTwoPRG::new(&seed);
+        let prg: OneToTwoPRG = OneToTwoPRG::new(PrgBackend::Aes128, &seed, None);
         let res = prg.generate_double(&[255u8; 16]);
         assert_eq!(res.0.len(), SEED_BYTE_LEN);
         println!("{:?} {:?}", seed, res);
@@ -73,4 +116,29 @@ mod tests {
         let res = prg.generate_double(&[255u8; 16]);
         println!("{:?} {:?}", seed, res);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_tweaks_and_iv_separate_expansions() {
+        let key: SeedU8x16 = [10u8; SEED_BYTE_LEN];
+        let prg = OneToTwoPRG::new(PrgBackend::Aes128, &key, None);
+        let seed = [7u8; SEED_BYTE_LEN];
+        let expansion = prg.generate_double_tweaked(&seed, &GGMTweak::new(true, 3), 5);
+        assert_eq!(expansion, prg.generate_double_tweaked(&seed, &GGMTweak::new(true, 3), 5));
+        assert_ne!(expansion, prg.generate_double_tweaked(&seed, &GGMTweak::new(false, 3), 5));
+        assert_ne!(expansion, prg.generate_double_tweaked(&seed, &GGMTweak::new(true, 4), 5));
+        assert_ne!(expansion, prg.generate_double_tweaked(&seed, &GGMTweak::new(true, 3), 6));
+        let salted_prg = OneToTwoPRG::new(PrgBackend::Aes128, &key, Some(&[1u8; SEED_BYTE_LEN]));
+        assert_ne!(expansion, salted_prg.generate_double_tweaked(&seed, &GGMTweak::new(true, 3), 5));
+    }
+
+    // a subtree expanded from a node of the whole tree must agree with the whole tree below that node
+    #[test]
+    fn test_subtree_matches_whole_tree() {
+        let prg = OneToTwoPRG::new(PrgBackend::Aes128, &[10u8; SEED_BYTE_LEN], Some(&[1u8; SEED_BYTE_LEN]));
+        let tweak = GGMTweak::new(false, 2);
+        let tree = prg.generate_ggm_tree(&[7u8; SEED_BYTE_LEN], 4, &tweak, 0);
+        // node 4 is the second node of level 2, so its leaves are leaves 4 to 7 of the whole tree
+        let subtree = prg.generate_ggm_tree(&tree[4], 2, &tweak, 4);
+        assert_eq!(subtree[3..], tree[15 + 4..15 + 8]);
+    }
+}
@@ -1,89 +1,97 @@
 use blake3::Hash;
 use crate::functionalities_and_protocols::all_in_one_vc::generating_bit_and_com_prg::GeneratingBitAndComPRG;
+use crate::functionalities_and_protocols::all_in_one_vc::one_to_two_prg::GGMTweak;
 use crate::functionalities_and_protocols::hasher;
 use crate::functionalities_and_protocols::states_and_parameters::public_parameter::PublicParameter;
-use crate::value_type::{CustomAddition, U8ForGF, Zero};
+use crate::value_type::{CustomAddition, LeafIndexForGF, Zero};
 use crate::value_type::seed_u8x16::SeedU8x16;
 use crate::vec_type::{
     bit_vec::BitVec, gf_vec::GFVec
 };
 
 pub struct ProverInAllInOneVC {
+    tau: u8, // public, the depth of the tree in this repetition
     tree_len: usize, // public
     first_leaf_index: usize, // public
+    tweak: GGMTweak, // public
     tree: Option<Vec<SeedU8x16>>,
     com_vec: Option<Vec<SeedU8x16>>, // private, can be public but better need verifier to reconstruct
 }
 
 impl ProverInAllInOneVC {
     pub fn new(
-        public_parameter: &PublicParameter
+        public_parameter: &PublicParameter, is_pa: bool, repetition_id: usize
     ) -> Self {
-        let big_n: usize = 1 << public_parameter.tau;
+        let tau = public_parameter.tau_rep[repetition_id];
+        let big_n: usize = 1 << tau;
         let tree_len: usize = (big_n << 1) - 1;
         Self {
+            tau,
             tree_len,
-            first_leaf_index: (1 << public_parameter.tau) - 1,
+            first_leaf_index: (1 << tau) - 1,
+            tweak: GGMTweak::new(is_pa, repetition_id),
             tree: None,
             com_vec: None,
         }
     }
 
-    pub fn commit<GFVOLEitH: Clone + Zero + U8ForGF + CustomAddition>(
+    pub fn commit<GFVOLEitH: Clone + Zero + LeafIndexForGF + CustomAddition>(
         &mut self, public_parameter: &PublicParameter, 
         prover_secret_seed_for_generating_ggm_tree: &SeedU8x16,
         output_secret_bit_vec: &mut BitVec, output_secret_voleith_mac_vec: &mut GFVec<GFVOLEitH>
     ) -> Hash {
         let tree: Vec<SeedU8x16> = public_parameter.one_to_two_prg.generate_ggm_tree(
-            prover_secret_seed_for_generating_ggm_tree, public_parameter.tau
+            prover_secret_seed_for_generating_ggm_tree, self.tau, &self.tweak, 0
+        );
+        assert_eq!(tree.len(), self.tree_len);
+        let com_hash = self.commit_to_leaves(
+            public_parameter, &tree[self.first_leaf_index..], output_secret_bit_vec, output_secret_voleith_mac_vec
         );
         self.tree = Some(tree);
-        assert_eq!(self.tree.as_ref().unwrap().len(), self.tree_len);
+        com_hash
+    }
+
+    // the same as commit, but with leaves taken from elsewhere, e.g., from the batched tree, so that open is not
+    // available afterwards and com_at_leaf is the only thing left to reveal
+    pub fn commit_to_leaves<GFVOLEitH: Clone + Zero + LeafIndexForGF + CustomAddition>(
+        &mut self, public_parameter: &PublicParameter, leaf_seed_vec: &[SeedU8x16],
+        output_secret_bit_vec: &mut BitVec, output_secret_voleith_mac_vec: &mut GFVec<GFVOLEitH>
+    ) -> Hash {
+        assert_eq!(leaf_seed_vec.len(), 1 << self.tau);
 
-        // now generating bits and commitments
+        // now generating bits and commitments, each leaf is accumulated into the bits and MACs as soon as it is
+        // expanded, so that a single expansion of big_n bits is alive at a time
         let generating_bit_and_com_prg = GeneratingBitAndComPRG::new(
-            &public_parameter.one_to_two_prg
+            &public_parameter.one_to_two_prg, public_parameter.leaf_expansion_version
         );
-        let mut bit_vec_vec: Vec<BitVec> = Vec::new();
-        let mut com_vec: Vec<SeedU8x16> = Vec::new();
-        for i in self.first_leaf_index..self.tree_len {
-            let (bit_vec, com) = generating_bit_and_com_prg.generate(
-                &self.tree.as_ref().unwrap()[i],
+        let mut com_vec: Vec<SeedU8x16> = Vec::with_capacity(leaf_seed_vec.len());
+        for (i, leaf_seed) in leaf_seed_vec.iter().enumerate() {
+            let (bit_vec_i, com) = generating_bit_and_com_prg.generate(
+                leaf_seed,
                 public_parameter.big_n
             );
-            bit_vec_vec.push(bit_vec);
+            output_secret_bit_vec.add_assign(&bit_vec_i);
+            output_secret_voleith_mac_vec.add_assign_masked(&bit_vec_i, &GFVOLEitH::from_leaf_index(i));
             com_vec.push(com);
         }
-        assert_eq!(bit_vec_vec.len(), 1 << public_parameter.tau);
+        let com_hash = hasher::hash_all_coms(public_parameter, &com_vec);
         self.com_vec = Some(com_vec);
-        
-        let com_hash = Some(hasher::hash_all_coms(&self.com_vec.as_ref().unwrap()));
-        
-        // compute bit_vec and mac tag
-        // let mut bit_vec = BitVec::zero_vec(public_parameter.big_n);
-        // let mut voleith_mac_vec = GFVec::<GFVOLEitH>::zero_vec(public_parameter.big_n);
-        for i in 0..1 << public_parameter.tau {
-            let i_gf = GFVOLEitH::from_u8(i as u8);
-            let bit_vec_i = &bit_vec_vec[i];
-            for j in 0..public_parameter.big_n {
-                output_secret_bit_vec[j] ^= bit_vec_i[j];
-                if bit_vec_i[j] == 1 {
-                    output_secret_voleith_mac_vec[j] = output_secret_voleith_mac_vec[j].custom_add(&i_gf);
-                }        
-            }
-        }
-        com_hash.unwrap()
+        com_hash
+    }
+
+    pub fn com_at_leaf(&self, leaf_index: usize) -> SeedU8x16 {
+        self.com_vec.as_ref().unwrap()[leaf_index]
     }
 
-    pub fn open<GFVOLEitH: U8ForGF>(&self, public_parameter: &PublicParameter, nabla: &GFVOLEitH) -> (SeedU8x16, Vec<SeedU8x16>) {
-        // the excluded index must be in [0, 2^8]
+    pub fn open<GFVOLEitH: LeafIndexForGF>(&self, nabla: &GFVOLEitH) -> (SeedU8x16, Vec<SeedU8x16>) {
+        // the excluded index must be in [0, 2^tau)
         // this can be understood the index among the leaves, i.e., the excluded_index-th leaf
-        let excluded_index = nabla.get_u8() as usize;
-        assert!(excluded_index < 1 << 8);
+        let excluded_index = nabla.get_leaf_index();
+        assert!(excluded_index < 1 << self.tau);
         let mut index_in_tree = self.first_leaf_index + excluded_index;
         let com_at_excluded_index = self.com_vec.as_ref().unwrap()[excluded_index];
         let mut seed_trace: Vec<SeedU8x16> = Vec::new();
-        for i in 0..public_parameter.tau {
+        for i in 0..self.tau {
             if (excluded_index >> i) & 1 == 1 {
                 seed_trace.push(self.tree.as_ref().unwrap()[index_in_tree - 1]);
             } else {
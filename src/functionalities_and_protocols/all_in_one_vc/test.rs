@@ -1,53 +1,123 @@
 #[cfg(test)]
 mod tests {
     use crate::bristol_fashion_adaptor::bristol_fashion_adaptor::BristolFashionAdaptor;
+    use crate::functionalities_and_protocols::all_in_one_vc::{LeafExpansionVersion, VectorCommitmentMode};
+    use crate::functionalities_and_protocols::all_in_one_vc::batched_ggm_tree::BatchedGGMTree;
+    use crate::functionalities_and_protocols::all_in_one_vc::generating_bit_and_com_prg::GeneratingBitAndComPRG;
+    use crate::functionalities_and_protocols::all_in_one_vc::one_to_two_prg::OneToTwoPRG;
     use crate::functionalities_and_protocols::all_in_one_vc::prover_in_all_in_one_vc::ProverInAllInOneVC;
     use crate::functionalities_and_protocols::all_in_one_vc::verifier_in_all_in_one_vc::VerifierInAllInOneVC;
+    use crate::functionalities_and_protocols::crypto_backend::{CryptoBackend, PrgBackend};
     use crate::functionalities_and_protocols::states_and_parameters::public_parameter::PublicParameter;
     use crate::value_type::gf2p8::GF2p8;
-    use crate::value_type::seed_u8x16::SeedU8x16;
+    use crate::value_type::seed_u8x16::{xor_block, SeedU8x16};
     use crate::vec_type::bit_vec::BitVec;
     use crate::vec_type::gf_vec::GFVec;
-    use crate::value_type::{CustomAddition, InsecureRandom};
+    use std::fmt::Debug;
+    use rand::Rng;
+    use crate::value_type::{ByteManipulation, CustomAddition, InsecureRandom, LeafIndexForGF};
+    use crate::value_type::gf2p128::GF2p128;
+    use crate::value_type::gf2p16::GF2p16;
     use crate::value_type::gf2p256::GF2p256;
     use crate::value_type::Zero;
     use crate::vec_type::ZeroVec;
+    use aes::Aes128;
+    use aes::cipher::{BlockDecrypt, BlockEncrypt, KeyInit, generic_array::GenericArray};
 
     #[test]
     fn test_committing_and_reconstructing() {
+        check_committing_and_reconstructing::<GF2p8>(8, LeafExpansionVersion::V1);
+        check_committing_and_reconstructing::<GF2p8>(8, LeafExpansionVersion::V2);
+    }
+
+    // a GGM tree deeper than 8 has more leaves than GF2p8 has elements
+    #[test]
+    fn test_committing_and_reconstructing_with_tau_12() {
+        check_committing_and_reconstructing::<GF2p16>(12, LeafExpansionVersion::V2);
+    }
+
+    // V2 draws its bits from every bit of the AES output, so that about half of them are set, and its bits for a
+    // shorter length are a prefix of those for a longer one
+    #[test]
+    fn test_leaf_expansion_versions() {
+        let one_to_two_prg = OneToTwoPRG::new(PrgBackend::Aes128, &SeedU8x16::insecurely_random(&mut rand::rng()), None);
+        let seed = SeedU8x16::insecurely_random(&mut rand::rng());
+        let (bit_vec_v1, com_v1) = GeneratingBitAndComPRG::new(&one_to_two_prg, LeafExpansionVersion::V1).generate(&seed, 1000);
+        let generating_bit_and_com_prg = GeneratingBitAndComPRG::new(&one_to_two_prg, LeafExpansionVersion::V2);
+        let (bit_vec_v2, com_v2) = generating_bit_and_com_prg.generate(&seed, 1000);
+        assert_ne!(com_v1, com_v2);
+        assert_ne!(bit_vec_v1, bit_vec_v2);
+        assert_eq!(bit_vec_v2.len(), 1000);
+        let num_set_bits = bit_vec_v2.iter().filter(|bit| *bit == 1).count();
+        assert!((400..600).contains(&num_set_bits), "{} of 1000 bits are set", num_set_bits);
+        let (short_bit_vec_v2, _) = generating_bit_and_com_prg.generate(&seed, 200);
+        assert_eq!(short_bit_vec_v2.iter().collect::<Vec<u8>>(), bit_vec_v2.iter().take(200).collect::<Vec<u8>>());
+    }
+
+    // The com of the excluded leaf is public, and so is the key of the PRG. Decrypting com under the key k_1 of
+    // G_1, derived as in Aes128Prg, yields the leaf seed in V1 but not in V2, which feeds the seed forward.
+    #[test]
+    fn test_leaf_com_does_not_reveal_the_leaf_seed() {
+        let key = SeedU8x16::insecurely_random(&mut rand::rng());
+        let one_to_two_prg = OneToTwoPRG::new(PrgBackend::Aes128, &key, None);
+        let mut key_1 = GenericArray::from([254u8; 16]);
+        Aes128::new(&GenericArray::from(key)).encrypt_block(&mut key_1);
+        let invert_com = |com: &SeedU8x16| {
+            let mut block = GenericArray::from(*com);
+            Aes128::new(&key_1).decrypt_block(&mut block);
+            SeedU8x16::from(block)
+        };
+        for _ in 0..10 {
+            let leaf_seed = SeedU8x16::insecurely_random(&mut rand::rng());
+            let (_, com_v1) = GeneratingBitAndComPRG::new(&one_to_two_prg, LeafExpansionVersion::V1).generate(&leaf_seed, 100);
+            assert_eq!(invert_com(&com_v1), leaf_seed);
+            let (_, com_v2) = GeneratingBitAndComPRG::new(&one_to_two_prg, LeafExpansionVersion::V2).generate(&leaf_seed, 100);
+            assert_ne!(invert_com(&com_v2), leaf_seed);
+            // what the inversion yields is G_1(seed) ^ seed decrypted, which depends on the unknown seed twice
+            assert_eq!(xor_block(&com_v2, &leaf_seed), one_to_two_prg.generate_double(&leaf_seed).1);
+        }
+    }
+
+    fn check_committing_and_reconstructing<GFVOLEitH>(tau: u8, leaf_expansion_version: LeafExpansionVersion)
+    where GFVOLEitH: LeafIndexForGF + CustomAddition + ByteManipulation + Zero + Clone + PartialEq + Debug {
         println!("testing committing and reconstructing...");
-        let nabla = GF2p8::insecurely_random();
+        let nabla = GFVOLEitH::from_leaf_index(rand::rng().random_range(0..1 << tau));
         let bristol_fashion_adaptor = BristolFashionAdaptor::new(
             &"adder64.txt".to_string()
         );
-        let public_parameter = PublicParameter::new::<GF2p256, GF2p8>(
+        let public_parameter = PublicParameter::new::<GF2p256, GFVOLEitH>(
             &bristol_fashion_adaptor,
-            8,
-            20,
-            SeedU8x16::insecurely_random(),
+            tau,
+            PublicParameter::determine_kappa(256, tau),
+            VectorCommitmentMode::PerRepetition,
+            leaf_expansion_version,
+            CryptoBackend::default(),
+            SeedU8x16::insecurely_random(&mut rand::rng()),
+            None,
             (0..100).collect(),
             (100..200).collect(),
             10,
             4,
+            None,
         );
         // let prover_secret_input = ProverSecretInput::new(
-        //     SeedU8x16::insecurely_random(),
+        //     SeedU8x16::insecurely_random(&mut rand::rng()),
         // );
 
         // first generate in the prover side
-        let mut prover_in_all_in_one_vc = ProverInAllInOneVC::new(&public_parameter);
-        let prover_secret_seed_for_generating_ggm_tree = SeedU8x16::insecurely_random();
+        let mut prover_in_all_in_one_vc = ProverInAllInOneVC::new(&public_parameter, true, 0);
+        let prover_secret_seed_for_generating_ggm_tree = SeedU8x16::insecurely_random(&mut rand::rng());
         let mut prover_secret_bit_vec = BitVec::zero_vec(public_parameter.big_n);
-        let mut prover_secret_voleith_mac_vec = GFVec::<GF2p8>::zero_vec(public_parameter.big_n);
+        let mut prover_secret_voleith_mac_vec = GFVec::<GFVOLEitH>::zero_vec(public_parameter.big_n);
         let com_hash = prover_in_all_in_one_vc.commit(
             &public_parameter, &prover_secret_seed_for_generating_ggm_tree,
             &mut prover_secret_bit_vec, &mut prover_secret_voleith_mac_vec
         );
-        let decom = prover_in_all_in_one_vc.open(&public_parameter, &nabla);
+        let decom = prover_in_all_in_one_vc.open(&nabla);
 
         // then generate in the verifier side
         let (reconstructed_com_hash, voleith_key_vec) = VerifierInAllInOneVC::reconstruct(
-            &public_parameter, &nabla, &decom
+            &public_parameter, true, 0, &nabla, &decom
         );
         println!("com_hash_from_prover: {:?}", com_hash);
         println!("reconstructed hash: {:?}", reconstructed_com_hash);
@@ -61,7 +131,7 @@ mod tests {
         //     galois_2p8::IrreducablePolynomial::Poly84310
         // );
         for j in 0..public_parameter.big_n {
-            let mut shifted_nabla = GF2p8::zero();
+            let mut shifted_nabla = GFVOLEitH::zero();
             if prover_secret_bit_vec[j] == 1 {
                 shifted_nabla = nabla.clone();
             }
@@ -79,4 +149,79 @@ mod tests {
         );
         println!("voleith correlation checking passed!");
     }
+
+    // 128 bits at tau 11 yield trees of depth 11 and 10, so that the leaves of the batched tree are interleaved
+    // unevenly and the tree is padded
+    #[test]
+    fn test_batched_committing_and_reconstructing() {
+        let bristol_fashion_adaptor = BristolFashionAdaptor::new(
+            &"adder64.txt".to_string()
+        );
+        let public_parameter = PublicParameter::new::<GF2p128, GF2p16>(
+            &bristol_fashion_adaptor,
+            11,
+            12,
+            VectorCommitmentMode::Batched,
+            LeafExpansionVersion::V2,
+            CryptoBackend::default(),
+            SeedU8x16::insecurely_random(&mut rand::rng()),
+            Some(SeedU8x16::insecurely_random(&mut rand::rng())),
+            (0..64).collect(),
+            (64..128).collect(),
+            2,
+            4,
+            None,
+        );
+        let batched_ggm_tree = BatchedGGMTree::new(&public_parameter.tau_rep, false);
+        let tree = batched_ggm_tree.generate(
+            &public_parameter.one_to_two_prg, &SeedU8x16::insecurely_random(&mut rand::rng())
+        );
+        let nabla_rep = public_parameter.tau_rep.iter().map(
+            |tau| GF2p16::from_leaf_index(rand::rng().random_range(0..1 << tau))
+        ).collect::<Vec<GF2p16>>();
+
+        // first commit to the leaves of every repetition in the prover side
+        let mut com_hash_rep = Vec::new();
+        let mut bit_vec_rep = Vec::new();
+        let mut voleith_mac_vec_rep = Vec::new();
+        let mut com_at_excluded_index_rep = Vec::new();
+        for (repetition_id, nabla) in nabla_rep.iter().enumerate() {
+            let mut prover_in_all_in_one_vc = ProverInAllInOneVC::new(&public_parameter, false, repetition_id);
+            let mut bit_vec = BitVec::zero_vec(public_parameter.big_n);
+            let mut voleith_mac_vec = GFVec::<GF2p16>::zero_vec(public_parameter.big_n);
+            com_hash_rep.push(prover_in_all_in_one_vc.commit_to_leaves(
+                &public_parameter, &batched_ggm_tree.leaf_seed_vec(&tree, repetition_id),
+                &mut bit_vec, &mut voleith_mac_vec
+            ));
+            bit_vec_rep.push(bit_vec);
+            voleith_mac_vec_rep.push(voleith_mac_vec);
+            com_at_excluded_index_rep.push(prover_in_all_in_one_vc.com_at_leaf(nabla.get_leaf_index()));
+        }
+        let excluded_index_rep = nabla_rep.iter().map(|nabla| nabla.get_leaf_index()).collect::<Vec<usize>>();
+        let co_path_seed_vec = batched_ggm_tree.open(&tree, &excluded_index_rep);
+
+        // then reconstruct in the verifier side
+        let leaf_seed_vec_rep = VerifierInAllInOneVC::reconstruct_batched_leaf_seed_vec_rep(
+            &public_parameter, false, &nabla_rep, &co_path_seed_vec
+        ).unwrap();
+        for (repetition_id, nabla) in nabla_rep.iter().enumerate() {
+            let (reconstructed_com_hash, voleith_key_vec) = VerifierInAllInOneVC::reconstruct_from_leaf_seeds(
+                &public_parameter, repetition_id, nabla,
+                &com_at_excluded_index_rep[repetition_id], &leaf_seed_vec_rep[repetition_id]
+            );
+            assert_eq!(com_hash_rep[repetition_id], reconstructed_com_hash);
+            for j in 0..public_parameter.big_n {
+                let mut shifted_nabla = GF2p16::zero();
+                if bit_vec_rep[repetition_id][j] == 1 {
+                    shifted_nabla = *nabla;
+                }
+                assert_eq!(voleith_key_vec[j], voleith_mac_vec_rep[repetition_id][j].custom_add(&shifted_nabla));
+            }
+        }
+
+        // a co-path with a seed missing does not match the excluded leaves
+        assert!(VerifierInAllInOneVC::reconstruct_batched_leaf_seed_vec_rep(
+            &public_parameter, false, &nabla_rep, &co_path_seed_vec[1..]
+        ).is_none());
+    }
 }
@@ -1,13 +1,12 @@
 use blake3::Hash;
+use crate::functionalities_and_protocols::all_in_one_vc::batched_ggm_tree::BatchedGGMTree;
 use crate::functionalities_and_protocols::all_in_one_vc::generating_bit_and_com_prg::GeneratingBitAndComPRG;
+use crate::functionalities_and_protocols::all_in_one_vc::one_to_two_prg::GGMTweak;
 use crate::functionalities_and_protocols::hasher;
 use crate::functionalities_and_protocols::states_and_parameters::public_parameter::PublicParameter;
-use crate::value_type::{CustomAddition, U8ForGF, Zero};
+use crate::value_type::{CustomAddition, LeafIndexForGF, Zero};
 use crate::value_type::seed_u8x16::SeedU8x16;
-use crate::vec_type::{
-    bit_vec::BitVec,
-    ZeroVec
-};
+use crate::vec_type::ZeroVec;
 use crate::vec_type::gf_vec::GFVec;
 
 pub struct VerifierInAllInOneVC {
@@ -15,18 +14,17 @@ pub struct VerifierInAllInOneVC {
 
 impl VerifierInAllInOneVC {
     
-    pub fn reconstruct<GFVOLEitH: U8ForGF + Clone + Zero + CustomAddition>(
-        public_parameter: &PublicParameter, 
+    pub fn reconstruct<GFVOLEitH: LeafIndexForGF + Clone + Zero + CustomAddition>(
+        public_parameter: &PublicParameter, is_pa: bool, repetition_id: usize,
         nabla: &GFVOLEitH, decom: &(SeedU8x16, Vec<SeedU8x16>)
     ) -> (Hash, GFVec<GFVOLEitH>) {
-        let com_at_excluded_index = &decom.0;
+        let tau = public_parameter.tau_rep[repetition_id];
         let seed_trace = &decom.1;
-        let generating_bit_and_com_prg = GeneratingBitAndComPRG::new(&public_parameter.one_to_two_prg);
-        let mut coms_at_leaves: Vec<SeedU8x16> = vec![SeedU8x16::zero(); 1 << public_parameter.tau];
-        let mut reconstructed_bit_vec_vec: Vec<BitVec> = vec![BitVec::zero_vec(public_parameter.big_n); 1 << public_parameter.tau];
-        let excluded_index = nabla.get_u8() as usize;
-        coms_at_leaves[excluded_index] = com_at_excluded_index.clone();
-        for i in 0..public_parameter.tau {
+        let mut leaf_seed_vec: Vec<SeedU8x16> = vec![SeedU8x16::zero(); 1 << tau];
+        let excluded_index = nabla.get_leaf_index();
+        assert!(excluded_index < 1 << tau);
+        let tweak = GGMTweak::new(is_pa, repetition_id);
+        for i in 0..tau {
             let sibling = {
                 if (excluded_index >> i) & 1 == 1 {
                     (excluded_index >> i) - 1
@@ -35,33 +33,52 @@ impl VerifierInAllInOneVC {
                 }
             };
             let from_index = sibling << i;
-            let subtree = public_parameter.one_to_two_prg.generate_ggm_tree(&seed_trace[i as usize], i);
-            let mut index_in_subtree_leaves = (1 << i) - 1;
-            for j in from_index..from_index + (1 << i) {
-                let (bit_vec, com) = generating_bit_and_com_prg.generate(
-                    &subtree[index_in_subtree_leaves], 
-                    public_parameter.big_n
-                );
-                reconstructed_bit_vec_vec[j] = bit_vec;
-                coms_at_leaves[j] = com;
-                index_in_subtree_leaves += 1;
-            }
+            // the sibling is node sibling of level tau - i
+            let subtree = public_parameter.one_to_two_prg.generate_ggm_tree(
+                &seed_trace[i as usize], i, &tweak, (1 << (tau - i)) - 1 + sibling
+            );
+            let first_leaf_index_in_subtree = (1 << i) - 1;
+            leaf_seed_vec[from_index..from_index + (1 << i)].copy_from_slice(&subtree[first_leaf_index_in_subtree..]);
         }
-        let reconstructed_com_hash = hasher::hash_all_coms(&coms_at_leaves);
+        Self::reconstruct_from_leaf_seeds(public_parameter, repetition_id, nabla, &decom.0, &leaf_seed_vec)
+    }
+
+    // the leaf seeds of every repetition from the co-path of the batched tree, or None if the co-path has the wrong
+    // number of seeds for the excluded leaves determined by nabla_rep
+    pub fn reconstruct_batched_leaf_seed_vec_rep<GFVOLEitH: LeafIndexForGF>(
+        public_parameter: &PublicParameter, is_pa: bool, nabla_rep: &[GFVOLEitH], co_path_seed_vec: &[SeedU8x16]
+    ) -> Option<Vec<Vec<SeedU8x16>>> {
+        let excluded_index_rep = nabla_rep.iter().map(|nabla| nabla.get_leaf_index()).collect::<Vec<usize>>();
+        BatchedGGMTree::new(&public_parameter.tau_rep, is_pa).reconstruct_leaf_seed_vec_rep(
+            &public_parameter.one_to_two_prg, &excluded_index_rep, co_path_seed_vec
+        )
+    }
+
+    // the seed at the excluded leaf is ignored, the commitment at that leaf is taken from the decommitment instead
+    pub fn reconstruct_from_leaf_seeds<GFVOLEitH: LeafIndexForGF + Clone + Zero + CustomAddition>(
+        public_parameter: &PublicParameter, repetition_id: usize,
+        nabla: &GFVOLEitH, com_at_excluded_index: &SeedU8x16, leaf_seed_vec: &[SeedU8x16]
+    ) -> (Hash, GFVec<GFVOLEitH>) {
+        let tau = public_parameter.tau_rep[repetition_id];
+        assert_eq!(leaf_seed_vec.len(), 1 << tau);
+        let excluded_index = nabla.get_leaf_index();
+        assert!(excluded_index < 1 << tau);
+        let generating_bit_and_com_prg = GeneratingBitAndComPRG::new(
+            &public_parameter.one_to_two_prg, public_parameter.leaf_expansion_version
+        );
+        let mut coms_at_leaves: Vec<SeedU8x16> = vec![SeedU8x16::zero(); 1 << tau];
+        coms_at_leaves[excluded_index] = *com_at_excluded_index;
 
         // now recover the key
         let mut voleith_key_vec = GFVec::<GFVOLEitH>::zero_vec(public_parameter.big_n);
-        for i in 0..1 << public_parameter.tau {
+        for (i, leaf_seed) in leaf_seed_vec.iter().enumerate() {
             if i != excluded_index {
-                let i_shifted = nabla.custom_add(&GFVOLEitH::from_u8(i as u8));
-                let bit_vec_i = &reconstructed_bit_vec_vec[i];
-                for j in 0..public_parameter.big_n {
-                    if bit_vec_i[j] == 1 {
-                        voleith_key_vec[j] = voleith_key_vec[j].custom_add(&i_shifted);
-                    }
-                }
+                let (bit_vec_i, com) = generating_bit_and_com_prg.generate(leaf_seed, public_parameter.big_n);
+                coms_at_leaves[i] = com;
+                voleith_key_vec.add_assign_masked(&bit_vec_i, &nabla.custom_add(&GFVOLEitH::from_leaf_index(i)));
             }
         }
+        let reconstructed_com_hash = hasher::hash_all_coms(public_parameter, &coms_at_leaves);
         (reconstructed_com_hash, voleith_key_vec)
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,39 @@
+use std::sync::mpsc::{channel, Receiver, Sender};
+use crate::functionalities_and_protocols::channel::{Channel, ChannelError, ChannelStatistics};
+
+// One end of a pair of in-process queues, for running PA and PB on separate threads
+pub struct InMemoryChannel {
+    sender: Sender<Vec<u8>>,
+    receiver: Receiver<Vec<u8>>,
+    statistics: ChannelStatistics,
+}
+
+impl InMemoryChannel {
+    pub fn new_pair() -> (Self, Self) {
+        let (sender_a, receiver_b) = channel();
+        let (sender_b, receiver_a) = channel();
+        (
+            Self { sender: sender_a, receiver: receiver_a, statistics: ChannelStatistics::default() },
+            Self { sender: sender_b, receiver: receiver_b, statistics: ChannelStatistics::default() },
+        )
+    }
+}
+
+impl Channel for InMemoryChannel {
+    fn send_byte_vec(&mut self, byte_vec: Vec<u8>) -> Result<(), ChannelError> {
+        let num_bytes = byte_vec.len();
+        self.sender.send(byte_vec).map_err(|_| ChannelError::Disconnected)?;
+        self.statistics.record_send(num_bytes);
+        Ok(())
+    }
+
+    fn recv_byte_vec(&mut self) -> Result<Vec<u8>, ChannelError> {
+        let byte_vec = self.receiver.recv().map_err(|_| ChannelError::Disconnected)?;
+        self.statistics.record_receive(byte_vec.len());
+        Ok(byte_vec)
+    }
+
+    fn statistics(&self) -> ChannelStatistics {
+        self.statistics
+    }
+}
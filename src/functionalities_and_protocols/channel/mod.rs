@@ -0,0 +1,85 @@
+use std::fmt::{Display, Formatter};
+use bincode::{config, decode_from_slice, encode_to_vec, Decode, Encode};
+use crate::comm_types_and_constants::DECODING_BYTE_LIMIT;
+
+mod test;
+pub mod in_memory_channel;
+pub mod tcp_channel;
+
+#[derive(PartialEq, Debug, Clone)]
+pub enum ChannelError {
+    // the other party has hung up
+    Disconnected,
+    Io(String),
+    // a received message does not decode to the expected type
+    Malformed(String),
+}
+
+impl Display for ChannelError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ChannelError::Disconnected => write!(f, "the other party has disconnected"),
+            ChannelError::Io(message) => write!(f, "channel I/O error: {}", message),
+            ChannelError::Malformed(message) => write!(f, "malformed message: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for ChannelError {}
+
+// What went through a channel from the point of view of one party,
+// a round starts whenever the party sends after having received, or sends first
+#[derive(PartialEq, Debug, Clone, Copy, Default)]
+pub struct ChannelStatistics {
+    pub num_sent_bytes: usize,
+    pub num_received_bytes: usize,
+    pub num_sent_messages: usize,
+    pub num_received_messages: usize,
+    pub num_rounds: usize,
+    last_received: bool,
+}
+
+impl ChannelStatistics {
+    pub fn record_send(&mut self, num_bytes: usize) {
+        if self.num_sent_messages == 0 || self.last_received {
+            self.num_rounds += 1;
+        }
+        self.num_sent_bytes += num_bytes;
+        self.num_sent_messages += 1;
+        self.last_received = false;
+    }
+
+    pub fn record_receive(&mut self, num_bytes: usize) {
+        self.num_received_bytes += num_bytes;
+        self.num_received_messages += 1;
+        self.last_received = true;
+    }
+}
+
+// A reliable, ordered, bidirectional channel between PA and PB.
+// Implementations only move byte frames, typed messages are encoded with bincode on top of them
+pub trait Channel {
+    fn send_byte_vec(&mut self, byte_vec: Vec<u8>) -> Result<(), ChannelError>;
+
+    fn recv_byte_vec(&mut self) -> Result<Vec<u8>, ChannelError>;
+
+    fn statistics(&self) -> ChannelStatistics;
+
+    fn send<T: Encode>(&mut self, message: &T) -> Result<(), ChannelError> {
+        let byte_vec = encode_to_vec(message, config::standard()).map_err(
+            |error| ChannelError::Malformed(error.to_string())
+        )?;
+        self.send_byte_vec(byte_vec)
+    }
+
+    fn recv<T: Decode<()>>(&mut self) -> Result<T, ChannelError> {
+        let byte_vec = self.recv_byte_vec()?;
+        let (message, num_bytes) = decode_from_slice(&byte_vec, config::standard().with_limit::<DECODING_BYTE_LIMIT>()).map_err(
+            |error| ChannelError::Malformed(error.to_string())
+        )?;
+        if num_bytes != byte_vec.len() {
+            return Err(ChannelError::Malformed(format!("{} trailing bytes", byte_vec.len() - num_bytes)));
+        }
+        Ok(message)
+    }
+}
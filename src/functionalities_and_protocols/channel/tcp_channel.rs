@@ -0,0 +1,71 @@
+use std::io::{BufReader, BufWriter, ErrorKind, Read, Write};
+use std::net::{TcpListener, TcpStream, ToSocketAddrs};
+use crate::functionalities_and_protocols::channel::{Channel, ChannelError, ChannelStatistics};
+
+// Frames are a little-endian u64 length followed by the message bytes
+const FRAME_HEADER_BYTE_LEN: usize = 8;
+
+pub struct TcpChannel {
+    reader: BufReader<TcpStream>,
+    writer: BufWriter<TcpStream>,
+    statistics: ChannelStatistics,
+}
+
+fn to_channel_error(error: std::io::Error) -> ChannelError {
+    match error.kind() {
+        ErrorKind::UnexpectedEof | ErrorKind::ConnectionReset | ErrorKind::ConnectionAborted | ErrorKind::BrokenPipe => ChannelError::Disconnected,
+        _ => ChannelError::Io(error.to_string()),
+    }
+}
+
+impl TcpChannel {
+    pub fn new(stream: TcpStream) -> Result<Self, ChannelError> {
+        // messages are flushed one by one, so Nagle's algorithm only adds latency
+        stream.set_nodelay(true).map_err(to_channel_error)?;
+        Ok(Self {
+            reader: BufReader::new(stream.try_clone().map_err(to_channel_error)?),
+            writer: BufWriter::new(stream),
+            statistics: ChannelStatistics::default(),
+        })
+    }
+
+    pub fn connect<A: ToSocketAddrs>(address: A) -> Result<Self, ChannelError> {
+        Self::new(TcpStream::connect(address).map_err(to_channel_error)?)
+    }
+
+    // waits for the other party on a listener, e.g. one bound to 127.0.0.1:0
+    pub fn accept(listener: &TcpListener) -> Result<Self, ChannelError> {
+        let (stream, _) = listener.accept().map_err(to_channel_error)?;
+        Self::new(stream)
+    }
+}
+
+impl Channel for TcpChannel {
+    fn send_byte_vec(&mut self, byte_vec: Vec<u8>) -> Result<(), ChannelError> {
+        self.writer.write_all(&(byte_vec.len() as u64).to_le_bytes()).map_err(to_channel_error)?;
+        self.writer.write_all(&byte_vec).map_err(to_channel_error)?;
+        self.writer.flush().map_err(to_channel_error)?;
+        self.statistics.record_send(FRAME_HEADER_BYTE_LEN + byte_vec.len());
+        Ok(())
+    }
+
+    fn recv_byte_vec(&mut self) -> Result<Vec<u8>, ChannelError> {
+        let mut header = [0u8; FRAME_HEADER_BYTE_LEN];
+        self.reader.read_exact(&mut header).map_err(to_channel_error)?;
+        let num_bytes = usize::try_from(u64::from_le_bytes(header)).map_err(
+            |_| ChannelError::Malformed("frame too long".to_string())
+        )?;
+        // read through take rather than into a buffer of the announced length, so that a corrupted header cannot exhaust memory
+        let mut byte_vec = Vec::new();
+        (&mut self.reader).take(num_bytes as u64).read_to_end(&mut byte_vec).map_err(to_channel_error)?;
+        if byte_vec.len() != num_bytes {
+            return Err(ChannelError::Disconnected);
+        }
+        self.statistics.record_receive(FRAME_HEADER_BYTE_LEN + num_bytes);
+        Ok(byte_vec)
+    }
+
+    fn statistics(&self) -> ChannelStatistics {
+        self.statistics
+    }
+}
@@ -0,0 +1,73 @@
+#[cfg(test)]
+mod tests {
+    use std::net::TcpListener;
+    use std::thread;
+    use crate::functionalities_and_protocols::channel::{Channel, ChannelError};
+    use crate::functionalities_and_protocols::channel::in_memory_channel::InMemoryChannel;
+    use crate::functionalities_and_protocols::channel::tcp_channel::TcpChannel;
+    use crate::value_type::gf2p256::GF2p256;
+    use crate::value_type::InsecureRandom;
+    use crate::vec_type::bit_vec::BitVec;
+    use crate::vec_type::BasicVecFunctions;
+
+    // PA sends a field element and a bit vector, PB answers with their sum of bits, then PA hangs up
+    fn exchange<C: Channel + Send + 'static>(mut pa_channel: C, mut pb_channel: C) {
+        let field_element = GF2p256::insecurely_random(&mut rand::rng());
+        let bit_vec = BitVec::from_vec(vec![1, 0, 1, 1]);
+        let pb_thread = thread::spawn(move || {
+            let received_field_element = pb_channel.recv::<GF2p256>().unwrap();
+            let received_bit_vec = pb_channel.recv::<BitVec>().unwrap();
+            pb_channel.send(&(received_bit_vec.iter().map(|bit| bit as u64).sum::<u64>())).unwrap();
+            assert_eq!(pb_channel.recv::<u8>(), Err(ChannelError::Disconnected));
+            (received_field_element, received_bit_vec, pb_channel.statistics())
+        });
+        pa_channel.send(&field_element).unwrap();
+        pa_channel.send(&bit_vec).unwrap();
+        assert_eq!(pa_channel.recv::<u64>().unwrap(), 3);
+        let pa_statistics = pa_channel.statistics();
+        drop(pa_channel);
+
+        let (received_field_element, received_bit_vec, pb_statistics) = pb_thread.join().unwrap();
+        assert_eq!(received_field_element, field_element);
+        assert_eq!(received_bit_vec, bit_vec);
+        assert_eq!(pa_statistics.num_sent_messages, 2);
+        assert_eq!(pa_statistics.num_received_messages, 1);
+        assert_eq!(pa_statistics.num_rounds, 1);
+        assert_eq!(pb_statistics.num_rounds, 1);
+        assert_eq!(pa_statistics.num_sent_bytes, pb_statistics.num_received_bytes);
+        assert_eq!(pa_statistics.num_received_bytes, pb_statistics.num_sent_bytes);
+    }
+
+    #[test]
+    fn test_in_memory_channel() {
+        let (pa_channel, pb_channel) = InMemoryChannel::new_pair();
+        exchange(pa_channel, pb_channel);
+    }
+
+    #[test]
+    fn test_tcp_channel() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let pb_connecting_thread = thread::spawn(move || TcpChannel::connect(address).unwrap());
+        let pa_channel = TcpChannel::accept(&listener).unwrap();
+        exchange(pa_channel, pb_connecting_thread.join().unwrap());
+    }
+
+    #[test]
+    fn test_receiving_the_wrong_type() {
+        let (mut pa_channel, mut pb_channel) = InMemoryChannel::new_pair();
+        pa_channel.send(&0u8).unwrap();
+        assert!(matches!(pb_channel.recv::<GF2p256>(), Err(ChannelError::Malformed(_))));
+    }
+
+    // the length of a bit vector comes from the other party, a huge one is rejected before its buffer is allocated
+    #[test]
+    fn test_receiving_a_huge_length() {
+        let (mut pa_channel, mut pb_channel) = InMemoryChannel::new_pair();
+        pa_channel.send(&(1usize << 40)).unwrap();
+        assert!(matches!(
+            pb_channel.recv::<BitVec>(),
+            Err(ChannelError::Malformed(error)) if error.contains("LimitExceeded")
+        ));
+    }
+}
@@ -0,0 +1,26 @@
+use blake3::Hash;
+use crate::functionalities_and_protocols::crypto_backend;
+use crate::value_type::seed_u8x16::SeedU8x16;
+
+mod test;
+
+// the context of every commitment, so that a commitment never collides with another use of the random oracle
+const COMMITMENT_CONTEXT: &str = "pa-gc-rs commitment v1";
+
+// A hash commitment com = H(len(message) || message || randomness). It is hiding as long as the randomness is a
+// fresh 128-bit seed, and binding by the collision resistance of the hash, and the length prefix makes the split
+// between the message and the randomness unambiguous. The digest is carried in a blake3::Hash whatever the hash
+// backend, since it compares in constant time.
+pub type Com = Hash;
+
+pub fn commit(hash: &dyn crypto_backend::Hash, message: &[u8], randomness: &SeedU8x16) -> Com {
+    let mut byte_vec = Vec::<u8>::with_capacity(8 + message.len() + randomness.len());
+    byte_vec.extend_from_slice(&(message.len() as u64).to_le_bytes());
+    byte_vec.extend_from_slice(message);
+    byte_vec.extend_from_slice(randomness);
+    Com::from_bytes(hash.digest(COMMITMENT_CONTEXT, &byte_vec))
+}
+
+pub fn verify_opening(hash: &dyn crypto_backend::Hash, com: &Com, message: &[u8], randomness: &SeedU8x16) -> bool {
+    *com == commit(hash, message, randomness)
+}
@@ -0,0 +1,43 @@
+#[cfg(test)]
+mod tests {
+    use crate::functionalities_and_protocols::commitment::{commit, verify_opening};
+    use crate::functionalities_and_protocols::crypto_backend::HashBackend;
+    use crate::value_type::InsecureRandom;
+    use crate::value_type::seed_u8x16::SeedU8x16;
+
+    #[test]
+    fn test_commit_and_verify_opening() {
+        for hash_backend in [HashBackend::Blake3, HashBackend::Shake256] {
+            let hash = hash_backend.hash();
+            let message = b"first bit and VOLEitH MACs".to_vec();
+            let randomness = SeedU8x16::insecurely_random(&mut rand::rng());
+            let com = commit(hash, &message, &randomness);
+            assert!(verify_opening(hash, &com, &message, &randomness));
+
+            // hiding: the same message under fresh randomness yields another commitment
+            let other_randomness = SeedU8x16::insecurely_random(&mut rand::rng());
+            assert_ne!(com, commit(hash, &message, &other_randomness));
+            assert!(!verify_opening(hash, &com, &message, &other_randomness));
+
+            // binding: altering any bit of the message, or dropping or appending a byte, fails the opening
+            for byte_id in 0..message.len() {
+                for bit_id in 0..8 {
+                    let mut altered_message = message.clone();
+                    altered_message[byte_id] ^= 1 << bit_id;
+                    assert!(!verify_opening(hash, &com, &altered_message, &randomness));
+                }
+            }
+            assert!(!verify_opening(hash, &com, &message[..message.len() - 1], &randomness));
+            assert!(!verify_opening(hash, &com, &[message.as_slice(), &[0u8]].concat(), &randomness));
+
+            // bytes cannot be moved between the message and the randomness
+            let mut shifted_randomness = SeedU8x16::default();
+            shifted_randomness[1..].copy_from_slice(&randomness[..15]);
+            shifted_randomness[0] = message[message.len() - 1];
+            assert_ne!(commit(hash, &message[..message.len() - 1], &shifted_randomness), commit(hash, &message, &randomness));
+        }
+        // the backends are separate random oracles
+        let randomness = SeedU8x16::default();
+        assert_ne!(commit(HashBackend::Blake3.hash(), b"", &randomness), commit(HashBackend::Shake256.hash(), b"", &randomness));
+    }
+}
@@ -0,0 +1,61 @@
+use sha3::{CShake256, CShake256Core};
+use sha3::digest::{ExtendableOutput, Update, XofReader as Sha3XofReader};
+use crate::functionalities_and_protocols::crypto_backend::{Hash, Xof, XofReader};
+
+pub struct Blake3;
+
+// blake3's derive_key mode, with the context as the key derivation context
+impl Hash for Blake3 {
+    fn new_xof(&self, context: &str) -> Box<dyn Xof> {
+        Box::new(blake3::Hasher::new_derive_key(context))
+    }
+}
+
+impl Xof for blake3::Hasher {
+    fn update(&mut self, message: &[u8]) {
+        blake3::Hasher::update(self, message);
+    }
+
+    fn finalize_xof(&self) -> Box<dyn XofReader> {
+        Box::new(blake3::Hasher::finalize_xof(self))
+    }
+
+    fn box_clone(&self) -> Box<dyn Xof> {
+        Box::new(self.clone())
+    }
+}
+
+impl XofReader for blake3::OutputReader {
+    fn read(&mut self, output: &mut [u8]) {
+        self.fill(output);
+    }
+}
+
+pub struct Shake256;
+
+impl Hash for Shake256 {
+    fn new_xof(&self, context: &str) -> Box<dyn Xof> {
+        Box::new(CShake256::from_core(CShake256Core::new(context.as_bytes())))
+    }
+}
+
+impl Xof for CShake256 {
+    fn update(&mut self, message: &[u8]) {
+        Update::update(self, message);
+    }
+
+    // sha3 consumes the hasher when finalizing, so a copy of the state is finalized instead
+    fn finalize_xof(&self) -> Box<dyn XofReader> {
+        Box::new(ExtendableOutput::finalize_xof(self.clone()))
+    }
+
+    fn box_clone(&self) -> Box<dyn Xof> {
+        Box::new(self.clone())
+    }
+}
+
+impl XofReader for sha3::CShake256Reader {
+    fn read(&mut self, output: &mut [u8]) {
+        Sha3XofReader::read(self, output);
+    }
+}
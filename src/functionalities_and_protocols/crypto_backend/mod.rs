@@ -0,0 +1,107 @@
+use bincode::{Decode, Encode};
+use crate::comm_types_and_constants::BLAKE3_HASH_DIGEST_NUM_BYTES;
+use crate::functionalities_and_protocols::crypto_backend::hash_backends::{Blake3, Shake256};
+use crate::functionalities_and_protocols::crypto_backend::prg_backends::{Aes128Prg, Aes256Prg};
+use crate::value_type::seed_u8x16::SeedU8x16;
+
+pub mod hash_backends;
+pub mod prg_backends;
+
+mod test;
+
+// Reads the output of an XOF, successive reads continue where the previous one stopped
+pub trait XofReader {
+    fn read(&mut self, output: &mut [u8]);
+}
+
+// An incremental hash with an extendable output. Finalizing does not consume it, so that a Fiat-Shamir
+// transcript can keep absorbing after a challenge has been squeezed.
+pub trait Xof: Send + Sync {
+    fn update(&mut self, message: &[u8]);
+
+    fn finalize_xof(&self) -> Box<dyn XofReader>;
+
+    fn box_clone(&self) -> Box<dyn Xof>;
+}
+
+// The random oracle of the protocols, i.e., the CRHF of the commitments and the hash of the garbling and of
+// Fiat-Shamir. Every use passes its own hardcoded context, which separates the uses from each other.
+pub trait Hash: Send + Sync {
+    fn new_xof(&self, context: &str) -> Box<dyn Xof>;
+
+    fn digest(&self, context: &str, message: &[u8]) -> [u8; BLAKE3_HASH_DIGEST_NUM_BYTES] {
+        let mut xof = self.new_xof(context);
+        xof.update(message);
+        let mut digest = [0u8; BLAKE3_HASH_DIGEST_NUM_BYTES];
+        xof.finalize_xof().read(&mut digest);
+        digest
+    }
+}
+
+// A PRG from one seed to two, G(seed) = (G_0(seed), G_1(seed)), which expands the GGM trees and the leaves
+pub trait LengthDoublingPrg: Send + Sync {
+    fn generate_double(&self, seed: &SeedU8x16) -> (SeedU8x16, SeedU8x16);
+
+    // G_0 of every seed, a backend that can pipeline its calls should override it
+    fn generate_first_vec(&self, seed_vec: &[SeedU8x16]) -> Vec<SeedU8x16> {
+        seed_vec.iter().map(|seed| self.generate_double(seed).0).collect()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Encode, Decode)]
+pub enum HashBackend {
+    Blake3,
+    // cSHAKE256 with the context as its customization string
+    Shake256,
+}
+
+impl HashBackend {
+    pub fn hash(self) -> &'static dyn Hash {
+        match self {
+            HashBackend::Blake3 => &Blake3,
+            HashBackend::Shake256 => &Shake256,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Encode, Decode)]
+pub enum PrgBackend {
+    Aes128,
+    Aes256,
+}
+
+impl PrgBackend {
+    pub fn instantiate(self, key: &SeedU8x16, iv: Option<&SeedU8x16>) -> Box<dyn LengthDoublingPrg> {
+        match self {
+            PrgBackend::Aes128 => Box::new(Aes128Prg::new(key, iv)),
+            PrgBackend::Aes256 => Box::new(Aes256Prg::new(key, iv)),
+        }
+    }
+}
+
+// Which primitives a public parameter, and thereby every proof under it, is instantiated with. The choice is part
+// of the public parameter, so that it is hashed into the Fiat-Shamir challenges and a proof bundle describes it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Encode, Decode)]
+pub struct CryptoBackend {
+    pub hash_backend: HashBackend,
+    pub prg_backend: PrgBackend,
+}
+
+impl CryptoBackend {
+    pub const fn new(hash_backend: HashBackend, prg_backend: PrgBackend) -> Self {
+        Self {
+            hash_backend,
+            prg_backend,
+        }
+    }
+
+    pub fn hash(&self) -> &'static dyn Hash {
+        self.hash_backend.hash()
+    }
+}
+
+impl Default for CryptoBackend {
+    fn default() -> Self {
+        Self::new(HashBackend::Blake3, PrgBackend::Aes128)
+    }
+}
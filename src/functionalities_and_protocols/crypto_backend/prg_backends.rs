@@ -0,0 +1,109 @@
+use aes::{Aes128, Aes256};
+use aes::cipher::{BlockEncrypt, KeyInit, generic_array::GenericArray};
+use crate::comm_types_and_constants::SEED_BYTE_LEN;
+use crate::functionalities_and_protocols::crypto_backend::LengthDoublingPrg;
+use crate::value_type::seed_u8x16::{xor_block, SeedU8x16};
+use crate::value_type::Zero;
+
+const AES256_UNSALTED_DOMAIN_TAG: u8 = 0;
+const AES256_SALTED_DOMAIN_TAG: u8 = 1;
+
+// G_b(seed) = AES_{k_b}(seed) under two fixed keys k_0 and k_1 derived from the public key, so that every
+// party expands the same trees
+pub struct Aes128Prg {
+    aes_cipher_0: Aes128,
+    aes_cipher_1: Aes128,
+}
+
+impl Aes128Prg {
+    // In the salted mode, the public iv is xored into the blocks from which the two AES keys are derived, so that
+    // the keys, and thereby all GGM trees, are fresh for every proof and a multi-target attack has to start over
+    pub fn new(key: &SeedU8x16, iv: Option<&SeedU8x16>) -> Self {
+        let cipher = Aes128::new(&GenericArray::from(*key));
+        let iv = iv.copied().unwrap_or(SeedU8x16::zero());
+        let derive_key = |constant: u8| {
+            let mut block = GenericArray::from(xor_block(&[constant; SEED_BYTE_LEN], &iv));
+            cipher.encrypt_block(&mut block);
+            block
+        };
+        Self {
+            aes_cipher_0: Aes128::new(&derive_key(255)),
+            aes_cipher_1: Aes128::new(&derive_key(254)),
+        }
+    }
+}
+
+impl LengthDoublingPrg for Aes128Prg {
+    fn generate_double(&self, seed: &SeedU8x16) -> (SeedU8x16, SeedU8x16) {
+        encrypt_double(&self.aes_cipher_0, &self.aes_cipher_1, seed)
+    }
+
+    // the blocks do not depend on each other and are encrypted in one batch, so that the AES instructions are
+    // pipelined
+    fn generate_first_vec(&self, seed_vec: &[SeedU8x16]) -> Vec<SeedU8x16> {
+        encrypt_vec(&self.aes_cipher_0, seed_vec)
+    }
+}
+
+// Aes128Prg with 256-bit keys. The seeds stay the 128-bit blocks of Aes128Prg, so that a GGM tree or a leaf
+// expanded by it is no harder to guess than a 128-bit seed, i.e., it does not reach the 256-bit security level and
+// only lengthens the PRG keys. The two keys are derived by AES-256 under the key followed by the iv, or by zeros
+// without an iv, one pair of blocks each.
+pub struct Aes256Prg {
+    aes_cipher_0: Aes256,
+    aes_cipher_1: Aes256,
+}
+
+impl Aes256Prg {
+    pub fn new(key: &SeedU8x16, iv: Option<&SeedU8x16>) -> Self {
+        let mut derivation_key = [0u8; 2 * SEED_BYTE_LEN];
+        derivation_key[..SEED_BYTE_LEN].copy_from_slice(key);
+        if let Some(iv) = iv {
+            derivation_key[SEED_BYTE_LEN..].copy_from_slice(iv);
+        }
+        // the last byte of every derivation block tells whether there is an iv, so that None and the zero iv differ
+        let domain_tag = if iv.is_some() { AES256_SALTED_DOMAIN_TAG } else { AES256_UNSALTED_DOMAIN_TAG };
+        let cipher = Aes256::new(&GenericArray::from(derivation_key));
+        let derive_key = |constant: u8| {
+            let mut block_vec = [[constant; SEED_BYTE_LEN], [constant - 1; SEED_BYTE_LEN]].map(|mut block| {
+                block[SEED_BYTE_LEN - 1] = domain_tag;
+                GenericArray::from(block)
+            });
+            cipher.encrypt_blocks(&mut block_vec);
+            let mut derived_key = [0u8; 2 * SEED_BYTE_LEN];
+            derived_key[..SEED_BYTE_LEN].copy_from_slice(&block_vec[0]);
+            derived_key[SEED_BYTE_LEN..].copy_from_slice(&block_vec[1]);
+            GenericArray::from(derived_key)
+        };
+        Self {
+            aes_cipher_0: Aes256::new(&derive_key(255)),
+            aes_cipher_1: Aes256::new(&derive_key(253)),
+        }
+    }
+}
+
+impl LengthDoublingPrg for Aes256Prg {
+    fn generate_double(&self, seed: &SeedU8x16) -> (SeedU8x16, SeedU8x16) {
+        encrypt_double(&self.aes_cipher_0, &self.aes_cipher_1, seed)
+    }
+
+    fn generate_first_vec(&self, seed_vec: &[SeedU8x16]) -> Vec<SeedU8x16> {
+        encrypt_vec(&self.aes_cipher_0, seed_vec)
+    }
+}
+
+fn encrypt_double<C>(aes_cipher_0: &C, aes_cipher_1: &C, seed: &SeedU8x16) -> (SeedU8x16, SeedU8x16)
+where C: BlockEncrypt + aes::cipher::BlockSizeUser<BlockSize = aes::cipher::consts::U16> {
+    let mut block_0 = GenericArray::from(*seed);
+    aes_cipher_0.encrypt_block(&mut block_0);
+    let mut block_1 = GenericArray::from(*seed);
+    aes_cipher_1.encrypt_block(&mut block_1);
+    (block_0.into(), block_1.into())
+}
+
+fn encrypt_vec<C>(aes_cipher: &C, seed_vec: &[SeedU8x16]) -> Vec<SeedU8x16>
+where C: BlockEncrypt + aes::cipher::BlockSizeUser<BlockSize = aes::cipher::consts::U16> {
+    let mut block_vec = seed_vec.iter().map(|seed| GenericArray::from(*seed)).collect::<Vec<_>>();
+    aes_cipher.encrypt_blocks(&mut block_vec);
+    block_vec.into_iter().map(|block| block.into()).collect()
+}
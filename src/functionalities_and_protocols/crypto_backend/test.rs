@@ -0,0 +1,83 @@
+#[cfg(test)]
+mod tests {
+    use crate::functionalities_and_protocols::crypto_backend::{CryptoBackend, HashBackend, PrgBackend};
+    use crate::value_type::{InsecureRandom, Zero};
+    use crate::value_type::seed_u8x16::SeedU8x16;
+
+    #[test]
+    fn test_hash_backends() {
+        for hash_backend in [HashBackend::Blake3, HashBackend::Shake256] {
+            let hash = hash_backend.hash();
+            assert_eq!(hash.digest("context", b"message"), hash.digest("context", b"message"));
+            assert_ne!(hash.digest("context", b"message"), hash.digest("other context", b"message"));
+            assert_ne!(hash.digest("context", b"message"), hash.digest("context", b"other message"));
+
+            // absorbing in pieces equals absorbing at once
+            let mut xof = hash.new_xof("context");
+            xof.update(b"mess");
+            xof.update(b"age");
+            let mut digest = [0u8; 32];
+            xof.finalize_xof().read(&mut digest);
+            assert_eq!(digest, hash.digest("context", b"message"));
+
+            // finalizing does not consume the xof, and reads continue where the previous one stopped
+            let mut reader = xof.finalize_xof();
+            let mut first_half = [0u8; 16];
+            let mut second_half = [0u8; 16];
+            reader.read(&mut first_half);
+            reader.read(&mut second_half);
+            assert_eq!([first_half, second_half].concat(), digest);
+            xof.update(b"!");
+            xof.finalize_xof().read(&mut digest);
+            assert_eq!(digest, hash.digest("context", b"message!"));
+
+            // a clone absorbs independently of the original
+            let mut cloned_xof = xof.box_clone();
+            cloned_xof.update(b"?");
+            let mut cloned_digest = [0u8; 32];
+            cloned_xof.finalize_xof().read(&mut cloned_digest);
+            xof.finalize_xof().read(&mut digest);
+            assert_eq!(digest, hash.digest("context", b"message!"));
+            assert_eq!(cloned_digest, hash.digest("context", b"message!?"));
+        }
+        assert_ne!(HashBackend::Blake3.hash().digest("context", b""), HashBackend::Shake256.hash().digest("context", b""));
+    }
+
+    #[test]
+    fn test_prg_backends() {
+        let key = SeedU8x16::insecurely_random(&mut rand::rng());
+        let iv = SeedU8x16::insecurely_random(&mut rand::rng());
+        let seed_vec: Vec<SeedU8x16> = (0..10).map(|_| SeedU8x16::insecurely_random(&mut rand::rng())).collect();
+        for prg_backend in [PrgBackend::Aes128, PrgBackend::Aes256] {
+            let prg = prg_backend.instantiate(&key, Some(&iv));
+            let (seed_0, seed_1) = prg.generate_double(&seed_vec[0]);
+            assert_ne!(seed_0, seed_1);
+            assert_eq!((seed_0, seed_1), prg_backend.instantiate(&key, Some(&iv)).generate_double(&seed_vec[0]));
+
+            // the batched G_0 agrees with the one-by-one G_0
+            assert_eq!(
+                prg.generate_first_vec(&seed_vec),
+                seed_vec.iter().map(|seed| prg.generate_double(seed).0).collect::<Vec<SeedU8x16>>()
+            );
+
+            // the iv refreshes the keys
+            assert_ne!((seed_0, seed_1), prg_backend.instantiate(&key, None).generate_double(&seed_vec[0]));
+        }
+        // AES-256 derives its keys under the key and the iv together, so that it tells no iv from the zero iv
+        assert_ne!(
+            PrgBackend::Aes256.instantiate(&key, None).generate_double(&seed_vec[0]),
+            PrgBackend::Aes256.instantiate(&key, Some(&SeedU8x16::zero())).generate_double(&seed_vec[0])
+        );
+        assert_ne!(
+            PrgBackend::Aes128.instantiate(&key, Some(&iv)).generate_double(&seed_vec[0]),
+            PrgBackend::Aes256.instantiate(&key, Some(&iv)).generate_double(&seed_vec[0])
+        );
+    }
+
+    #[test]
+    fn test_default_crypto_backend() {
+        let crypto_backend = CryptoBackend::default();
+        assert_eq!(crypto_backend.hash_backend, HashBackend::Blake3);
+        assert_eq!(crypto_backend.prg_backend, PrgBackend::Aes128);
+    }
+}
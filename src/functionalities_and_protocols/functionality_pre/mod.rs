@@ -0,0 +1,56 @@
+use crate::vec_type::bit_vec::BitVec;
+use crate::vec_type::gf_vec::GFVec;
+
+// One party's endpoint of the pre-processing functionality FPre that ProverInPA2PC::deal obtains the correlations of
+// that party from. Every call only takes and returns what the calling party holds, PA and PB make the same calls in the
+// same order on their own endpoints, and the outputs of matching calls are correlated. An implementation either deals
+// the correlations of both parties in one process, as InsecureFunctionalityPre does, or runs a two-party protocol
+// behind these calls
+pub trait PreprocessingFunctionality<GFVOLE> {
+    // samples the global key delta of the calling party
+    fn generate_delta(&mut self, delta: &mut Option<GFVOLE>);
+
+    // samples len random bits of the calling party together with their VOLE MACs, authenticated with the delta of
+    // the other party, who calls generate_random_tuple_keys at the same time
+    fn generate_random_tuples(
+        &mut self,
+        len: usize,
+        rand_bit_vec: &mut BitVec,
+        vole_mac_rand_vec: &mut GFVec<GFVOLE>,
+    );
+
+    // the VOLE keys of the calling party for the len random bits of the other party, who calls generate_random_tuples
+    // at the same time, i.e., other_vole_key_rand_vec[i] = vole_mac_rand_vec[i] + rand_bit_vec[i] * delta
+    fn generate_random_tuple_keys(
+        &mut self,
+        len: usize,
+        delta: &GFVOLE,
+        other_vole_key_rand_vec: &mut GFVec<GFVOLE>,
+    );
+
+    // samples the shares of the calling party of kappa repetitions of len secret-shared multiplication triples,
+    // i.e., (pa_c ^ pb_c) = (pa_a ^ pb_a) & (pa_b ^ pb_b)
+    fn generate_random_and_tuples(
+        &mut self,
+        kappa: usize, len: usize,
+        rand_a_bit_vec_rep: &mut Vec<BitVec>,
+        rand_b_bit_vec_rep: &mut Vec<BitVec>,
+        rand_c_bit_vec_rep: &mut Vec<BitVec>,
+    );
+
+    // samples the shares of the calling party of the AND of the given shared input bits, one per entry, the output bit
+    // is authenticated with the delta of the other party, and the calling party obtains the VOLE keys for the output
+    // bits of the other party under her own delta
+    fn generate_random_authenticated_and_tuples(
+        &mut self,
+        delta: &GFVOLE,
+        left_input_bit_vec: &BitVec,
+        right_input_bit_vec: &BitVec,
+        output_bit_vec: &mut BitVec,
+        vole_mac_output_vec: &mut GFVec<GFVOLE>,
+        other_vole_key_output_vec: &mut GFVec<GFVOLE>,
+    );
+
+    // the number of bytes the calling party has exchanged so far to realize the functionality
+    fn num_communicated_bytes(&self) -> usize;
+}
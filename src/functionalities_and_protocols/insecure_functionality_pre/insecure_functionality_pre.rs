@@ -1,116 +1,343 @@
-use rand::Rng;
+use std::sync::{Arc, Condvar, Mutex};
+use rand::{CryptoRng, Rng, RngCore};
+use crate::functionalities_and_protocols::functionality_pre::PreprocessingFunctionality;
 use crate::value_type::{CustomAddition, CustomMultiplyingBit, InsecureRandom, Zero};
-use crate::vec_type::{bit_vec::BitVec, gf_vec::GFVec};
+use crate::vec_type::{bit_vec::BitVec, gf_vec::GFVec, ZeroVec};
 
-pub struct InsecureFunctionalityPre;
+// What one party asks FPre for, see PreprocessingFunctionality
+enum Request<GFVOLE> {
+    Delta,
+    RandomTuples { len: usize },
+    RandomTupleKeys { len: usize, delta: GFVOLE },
+    RandomAndTuples { kappa: usize, len: usize },
+    RandomAuthenticatedAndTuples { delta: GFVOLE, left_input_bit_vec: BitVec, right_input_bit_vec: BitVec },
+}
 
-impl InsecureFunctionalityPre {
-    pub fn generate_delta<GFVOLE: InsecureRandom>(delta: &mut Option<GFVOLE>) {
-        *delta = Some(GFVOLE::insecurely_random());
-    }
+// What one party obtains from FPre for her request
+enum Response<GFVOLE> {
+    Delta(GFVOLE),
+    RandomTuples { rand_bit_vec: BitVec, vole_mac_rand_vec: GFVec<GFVOLE> },
+    RandomTupleKeys { other_vole_key_rand_vec: GFVec<GFVOLE> },
+    RandomAndTuples { rand_a_bit_vec_rep: Vec<BitVec>, rand_b_bit_vec_rep: Vec<BitVec>, rand_c_bit_vec_rep: Vec<BitVec> },
+    RandomAuthenticatedAndTuples { output_bit_vec: BitVec, vole_mac_output_vec: GFVec<GFVOLE>, other_vole_key_output_vec: GFVec<GFVOLE> },
+}
 
-    fn generate_random_vole_macs_and_keys<GFVOLE: InsecureRandom + CustomAddition + CustomMultiplyingBit + Clone + Zero>(
-        delta: &GFVOLE,
-        rand_bit_vec: &BitVec,
-        vole_mac_rand_vec: &mut GFVec<GFVOLE>,
-        vole_key_rand_vec: &mut GFVec<GFVOLE>,
-    ) {
-        for (i, bit) in (0..rand_bit_vec.len()).zip(rand_bit_vec.iter()) {
-            let mac = GFVOLE::insecurely_random();
-            let key = mac.custom_add(&delta.custom_multiply_bit(*bit));
+fn party_index(is_pa: bool) -> usize {
+    if is_pa { 0 } else { 1 }
+}
+
+// The state both endpoints share, the request of a party waits here until the other party makes the matching request,
+// and the response of the party that came first waits here until she picks it up
+struct Dealer<R, GFVOLE> {
+    rng: R,
+    pending_request_pair: [Option<Request<GFVOLE>>; 2],
+    pending_response_pair: [Option<Response<GFVOLE>>; 2],
+    is_dropped_pair: [bool; 2],
+}
+
+impl<R, GFVOLE> Dealer<R, GFVOLE>
+where
+    R: RngCore + CryptoRng,
+    GFVOLE: InsecureRandom + CustomAddition + CustomMultiplyingBit + Clone + Zero
+{
+    // len random bits of one party with their VOLE MACs, and the VOLE keys of the other party under her delta
+    fn deal_random_tuples(&mut self, len: usize, delta: &GFVOLE) -> (Response<GFVOLE>, Response<GFVOLE>) {
+        let mut rand_bit_vec = BitVec::zero_vec(len);
+        (0..len).for_each(
+            |i| {
+                rand_bit_vec.set(i, self.rng.random::<u8>() & 1u8);
+            }
+        );
+        let mut vole_mac_rand_vec = GFVec::<GFVOLE>::zero_vec(len);
+        let mut other_vole_key_rand_vec = GFVec::<GFVOLE>::zero_vec(len);
+        for (i, bit) in (0..len).zip(rand_bit_vec.iter()) {
+            let mac = GFVOLE::insecurely_random(&mut self.rng);
+            other_vole_key_rand_vec[i] = mac.custom_add(&delta.custom_multiply_bit(bit));
             vole_mac_rand_vec[i] = mac;
-            vole_key_rand_vec[i] = key;
+        }
+        (
+            Response::RandomTuples { rand_bit_vec, vole_mac_rand_vec },
+            Response::RandomTupleKeys { other_vole_key_rand_vec },
+        )
+    }
+
+    // the randomness is always drawn for PA first, so that the correlations only depend on the rng and on the order of
+    // the calls, not on which party happens to make her call first
+    fn serve(&mut self, pa_request: Request<GFVOLE>, pb_request: Request<GFVOLE>) -> (Response<GFVOLE>, Response<GFVOLE>) {
+        match (pa_request, pb_request) {
+            (Request::Delta, Request::Delta) => {
+                let delta_a = GFVOLE::insecurely_random(&mut self.rng);
+                let delta_b = GFVOLE::insecurely_random(&mut self.rng);
+                (Response::Delta(delta_a), Response::Delta(delta_b))
+            },
+            (Request::RandomTuples { len }, Request::RandomTupleKeys { len: keys_len, delta: delta_b }) if len == keys_len => {
+                self.deal_random_tuples(len, &delta_b)
+            },
+            (Request::RandomTupleKeys { len: keys_len, delta: delta_a }, Request::RandomTuples { len }) if len == keys_len => {
+                let (pb_response, pa_response) = self.deal_random_tuples(len, &delta_a);
+                (pa_response, pb_response)
+            },
+            (Request::RandomAndTuples { kappa, len }, Request::RandomAndTuples { kappa: pb_kappa, len: pb_len }) if (kappa, len) == (pb_kappa, pb_len) => {
+                let mut pa_rand_a_bit_vec_rep = vec![BitVec::zero_vec(len); kappa];
+                let mut pa_rand_b_bit_vec_rep = vec![BitVec::zero_vec(len); kappa];
+                let mut pa_rand_c_bit_vec_rep = vec![BitVec::zero_vec(len); kappa];
+                let mut pb_rand_a_bit_vec_rep = vec![BitVec::zero_vec(len); kappa];
+                let mut pb_rand_b_bit_vec_rep = vec![BitVec::zero_vec(len); kappa];
+                let mut pb_rand_c_bit_vec_rep = vec![BitVec::zero_vec(len); kappa];
+                for repetition_id in 0..kappa {
+                    for i in 0..len {
+                        pa_rand_a_bit_vec_rep[repetition_id].set(i, self.rng.random::<u8>() & 1);
+                        pa_rand_b_bit_vec_rep[repetition_id].set(i, self.rng.random::<u8>() & 1);
+                        pa_rand_c_bit_vec_rep[repetition_id].set(i, self.rng.random::<u8>() & 1);
+                        pb_rand_a_bit_vec_rep[repetition_id].set(i, self.rng.random::<u8>() & 1);
+                        pb_rand_b_bit_vec_rep[repetition_id].set(i, self.rng.random::<u8>() & 1);
+                        pb_rand_c_bit_vec_rep[repetition_id].set(i,
+                            (pa_rand_a_bit_vec_rep[repetition_id][i] ^ pb_rand_a_bit_vec_rep[repetition_id][i])
+                                & (pa_rand_b_bit_vec_rep[repetition_id][i] ^ pb_rand_b_bit_vec_rep[repetition_id][i])
+                                ^ pa_rand_c_bit_vec_rep[repetition_id][i]
+                        );
+                    }
+                }
+                (
+                    Response::RandomAndTuples {
+                        rand_a_bit_vec_rep: pa_rand_a_bit_vec_rep,
+                        rand_b_bit_vec_rep: pa_rand_b_bit_vec_rep,
+                        rand_c_bit_vec_rep: pa_rand_c_bit_vec_rep,
+                    },
+                    Response::RandomAndTuples {
+                        rand_a_bit_vec_rep: pb_rand_a_bit_vec_rep,
+                        rand_b_bit_vec_rep: pb_rand_b_bit_vec_rep,
+                        rand_c_bit_vec_rep: pb_rand_c_bit_vec_rep,
+                    },
+                )
+            },
+            (
+                Request::RandomAuthenticatedAndTuples { delta: delta_a, left_input_bit_vec: pa_left_input_bit_vec, right_input_bit_vec: pa_right_input_bit_vec },
+                Request::RandomAuthenticatedAndTuples { delta: delta_b, left_input_bit_vec: pb_left_input_bit_vec, right_input_bit_vec: pb_right_input_bit_vec },
+            ) if [pa_right_input_bit_vec.len(), pb_left_input_bit_vec.len(), pb_right_input_bit_vec.len()].iter().all(|len| *len == pa_left_input_bit_vec.len()) => {
+                let len = pa_left_input_bit_vec.len();
+                let mut pa_output_bit_vec = BitVec::zero_vec(len);
+                let mut pa_vole_mac_output_vec = GFVec::<GFVOLE>::zero_vec(len);
+                let mut pa_vole_key_output_vec = GFVec::<GFVOLE>::zero_vec(len);
+                let mut pb_output_bit_vec = BitVec::zero_vec(len);
+                let mut pb_vole_mac_output_vec = GFVec::<GFVOLE>::zero_vec(len);
+                let mut pb_vole_key_output_vec = GFVec::<GFVOLE>::zero_vec(len);
+                for i in 0..len {
+                    let pa_output_bit = self.rng.random::<u8>() & 1;
+                    let pb_output_bit = (pa_left_input_bit_vec[i] ^ pb_left_input_bit_vec[i])
+                        & (pa_right_input_bit_vec[i] ^ pb_right_input_bit_vec[i]) ^ pa_output_bit;
+                    pa_output_bit_vec.set(i, pa_output_bit);
+                    pb_output_bit_vec.set(i, pb_output_bit);
+                    pa_vole_mac_output_vec[i] = GFVOLE::insecurely_random(&mut self.rng);
+                    pa_vole_key_output_vec[i] = pa_vole_mac_output_vec[i].custom_add(&delta_b.custom_multiply_bit(pa_output_bit));
+                    pb_vole_mac_output_vec[i] = GFVOLE::insecurely_random(&mut self.rng);
+                    pb_vole_key_output_vec[i] = pb_vole_mac_output_vec[i].custom_add(&delta_a.custom_multiply_bit(pb_output_bit));
+                }
+                (
+                    Response::RandomAuthenticatedAndTuples {
+                        output_bit_vec: pa_output_bit_vec,
+                        vole_mac_output_vec: pa_vole_mac_output_vec,
+                        other_vole_key_output_vec: pb_vole_key_output_vec,
+                    },
+                    Response::RandomAuthenticatedAndTuples {
+                        output_bit_vec: pb_output_bit_vec,
+                        vole_mac_output_vec: pb_vole_mac_output_vec,
+                        other_vole_key_output_vec: pa_vole_key_output_vec,
+                    },
+                )
+            },
+            _ => panic!("PA and PB made mismatching calls to FPre"),
+        }
+    }
+}
+
+// A trusted dealer that samples the correlations of both parties with the given rng, which is only meant for
+// simulating the pre-processing and needs no communication. Each party holds one of the two endpoints of new_pair,
+// a call on one endpoint waits until the other party makes the matching call on hers, so the parties either run on
+// their own threads or take turns call by call. Seeding the rng makes the dealt correlations, and thus the whole proof,
+// reproducible
+pub struct InsecureFunctionalityPre<R, GFVOLE> {
+    is_pa: bool,
+    dealer: Arc<(Mutex<Dealer<R, GFVOLE>>, Condvar)>,
+}
+
+impl<R, GFVOLE> InsecureFunctionalityPre<R, GFVOLE>
+where
+    R: RngCore + CryptoRng,
+    GFVOLE: InsecureRandom + CustomAddition + CustomMultiplyingBit + Clone + Zero
+{
+    // the endpoints of PA and PB
+    pub fn new_pair(rng: R) -> (Self, Self) {
+        let dealer = Arc::new((
+            Mutex::new(Dealer {
+                rng,
+                pending_request_pair: [None, None],
+                pending_response_pair: [None, None],
+                is_dropped_pair: [false, false],
+            }),
+            Condvar::new(),
+        ));
+        (
+            Self { is_pa: true, dealer: dealer.clone() },
+            Self { is_pa: false, dealer },
+        )
+    }
+
+    fn call(&mut self, request: Request<GFVOLE>) -> Response<GFVOLE> {
+        let (mutex, condvar) = &*self.dealer;
+        let mut dealer = mutex.lock().unwrap();
+        let (own_index, other_index) = (party_index(self.is_pa), party_index(!self.is_pa));
+        if let Some(other_request) = dealer.pending_request_pair[other_index].take() {
+            let (pa_response, pb_response) = if self.is_pa {
+                dealer.serve(request, other_request)
+            } else {
+                dealer.serve(other_request, request)
+            };
+            let (own_response, other_response) = if self.is_pa { (pa_response, pb_response) } else { (pb_response, pa_response) };
+            dealer.pending_response_pair[other_index] = Some(other_response);
+            condvar.notify_all();
+            return own_response;
+        }
+        dealer.pending_request_pair[own_index] = Some(request);
+        loop {
+            if let Some(own_response) = dealer.pending_response_pair[own_index].take() {
+                return own_response;
+            }
+            if dealer.is_dropped_pair[other_index] {
+                panic!("the other party left FPre");
+            }
+            // the other party may have panicked while holding the lock, her endpoint has then been dropped
+            dealer = condvar.wait(dealer).unwrap_or_else(|error| error.into_inner());
         }
     }
+}
 
-    pub fn generate_random_tuples<GFVOLE, GFVOLEitH>(
+// The lock is taken even if a panic in serve poisoned it, so that the other party is woken up and panics as well
+// instead of waiting forever
+impl<R, GFVOLE> Drop for InsecureFunctionalityPre<R, GFVOLE> {
+    fn drop(&mut self) {
+        let (mutex, condvar) = &*self.dealer;
+        let mut dealer = mutex.lock().unwrap_or_else(|error| error.into_inner());
+        dealer.is_dropped_pair[party_index(self.is_pa)] = true;
+        condvar.notify_all();
+    }
+}
+
+impl<R, GFVOLE> PreprocessingFunctionality<GFVOLE> for InsecureFunctionalityPre<R, GFVOLE>
+where
+    R: RngCore + CryptoRng,
+    GFVOLE: InsecureRandom + CustomAddition + CustomMultiplyingBit + Clone + Zero
+{
+    fn generate_delta(&mut self, delta: &mut Option<GFVOLE>) {
+        match self.call(Request::Delta) {
+            Response::Delta(own_delta) => *delta = Some(own_delta),
+            _ => unreachable!(),
+        }
+    }
+
+    fn generate_random_tuples(
+        &mut self,
         len: usize,
-        delta: &GFVOLE,
         rand_bit_vec: &mut BitVec,
         vole_mac_rand_vec: &mut GFVec<GFVOLE>,
-        vole_key_rand_vec: &mut GFVec<GFVOLE>,
-    )
-    where
-        GFVOLE: InsecureRandom + CustomAddition + Clone + CustomMultiplyingBit + Zero
-    {
-        let mut rng = rand::rng();
-        (0..len).for_each(
-            |i| {
-                rand_bit_vec[i] = rng.random::<u8>() & 1u8;
-            }
-        );
+    ) {
+        match self.call(Request::RandomTuples { len }) {
+            Response::RandomTuples { rand_bit_vec: own_rand_bit_vec, vole_mac_rand_vec: own_vole_mac_rand_vec } => {
+                *rand_bit_vec = own_rand_bit_vec;
+                *vole_mac_rand_vec = own_vole_mac_rand_vec;
+            },
+            _ => unreachable!(),
+        }
+    }
 
-        Self::generate_random_vole_macs_and_keys(
-            delta, rand_bit_vec, vole_mac_rand_vec, vole_key_rand_vec
-        );
+    fn generate_random_tuple_keys(
+        &mut self,
+        len: usize,
+        delta: &GFVOLE,
+        other_vole_key_rand_vec: &mut GFVec<GFVOLE>,
+    ) {
+        match self.call(Request::RandomTupleKeys { len, delta: delta.clone() }) {
+            Response::RandomTupleKeys { other_vole_key_rand_vec: own_other_vole_key_rand_vec } => {
+                *other_vole_key_rand_vec = own_other_vole_key_rand_vec;
+            },
+            _ => unreachable!(),
+        }
     }
 
-    pub fn generate_random_and_tuples(
+    fn generate_random_and_tuples(
+        &mut self,
         kappa: usize, len: usize,
-        pa_rand_a_bit_vec_rep: &mut Vec<BitVec>,
-        pa_rand_b_bit_vec_rep: &mut Vec<BitVec>,
-        pa_rand_c_bit_vec_rep: &mut Vec<BitVec>,
-        pb_rand_a_bit_vec_rep: &mut Vec<BitVec>,
-        pb_rand_b_bit_vec_rep: &mut Vec<BitVec>,
-        pb_rand_c_bit_vec_rep: &mut Vec<BitVec>
+        rand_a_bit_vec_rep: &mut Vec<BitVec>,
+        rand_b_bit_vec_rep: &mut Vec<BitVec>,
+        rand_c_bit_vec_rep: &mut Vec<BitVec>,
     ) {
-        let mut rng = rand::rng();
-        for repetition_id in 0..kappa {
-            for i in 0..len {
-                pa_rand_a_bit_vec_rep[repetition_id][i] = rng.random::<u8>() & 1;
-                pa_rand_b_bit_vec_rep[repetition_id][i] = rng.random::<u8>() & 1;
-                pa_rand_c_bit_vec_rep[repetition_id][i] = rng.random::<u8>() & 1;
-                pb_rand_a_bit_vec_rep[repetition_id][i] = rng.random::<u8>() & 1;
-                pb_rand_b_bit_vec_rep[repetition_id][i] = rng.random::<u8>() & 1;
-                pb_rand_c_bit_vec_rep[repetition_id][i] =
-                    (pa_rand_a_bit_vec_rep[repetition_id][i] ^ pb_rand_a_bit_vec_rep[repetition_id][i])
-                        & (pa_rand_b_bit_vec_rep[repetition_id][i] ^ pb_rand_b_bit_vec_rep[repetition_id][i])
-                        ^ pa_rand_c_bit_vec_rep[repetition_id][i];
-            }
+        match self.call(Request::RandomAndTuples { kappa, len }) {
+            Response::RandomAndTuples {
+                rand_a_bit_vec_rep: own_rand_a_bit_vec_rep,
+                rand_b_bit_vec_rep: own_rand_b_bit_vec_rep,
+                rand_c_bit_vec_rep: own_rand_c_bit_vec_rep,
+            } => {
+                *rand_a_bit_vec_rep = own_rand_a_bit_vec_rep;
+                *rand_b_bit_vec_rep = own_rand_b_bit_vec_rep;
+                *rand_c_bit_vec_rep = own_rand_c_bit_vec_rep;
+            },
+            _ => unreachable!(),
         }
     }
 
-    pub fn generate_random_authenticated_and_tuples<GFVOLE>(
-        delta_a: &GFVOLE,
-        pa_left_input_bit: u8,
-        pa_right_input_bit: u8,
-        pa_output_bit: &mut u8, pa_vole_mac_output: &mut GFVOLE, pa_vole_key_output: &mut GFVOLE,
-        delta_b: &GFVOLE,
-        pb_left_input_bit: u8,
-        pb_right_input_bit: u8,
-        pb_output_bit: &mut u8, pb_vole_mac_output: &mut GFVOLE, pb_vole_key_output: &mut GFVOLE,
-    )
-    where
-        GFVOLE: InsecureRandom + CustomAddition + CustomMultiplyingBit
-    {
-        let mut rng = rand::rng();
-        *pa_output_bit = rng.random::<u8>() & 1;
-        *pb_output_bit = (pa_left_input_bit ^ pb_left_input_bit) & (pa_right_input_bit ^ pb_right_input_bit) ^ *pa_output_bit;
-        *pa_vole_mac_output = GFVOLE::insecurely_random();
-        *pa_vole_key_output = pa_vole_mac_output.custom_add(&delta_b.custom_multiply_bit(*pa_output_bit));
-        *pb_vole_mac_output = GFVOLE::insecurely_random();
-        *pb_vole_key_output = pb_vole_mac_output.custom_add(&delta_a.custom_multiply_bit(*pb_output_bit));
+    fn generate_random_authenticated_and_tuples(
+        &mut self,
+        delta: &GFVOLE,
+        left_input_bit_vec: &BitVec,
+        right_input_bit_vec: &BitVec,
+        output_bit_vec: &mut BitVec,
+        vole_mac_output_vec: &mut GFVec<GFVOLE>,
+        other_vole_key_output_vec: &mut GFVec<GFVOLE>,
+    ) {
+        match self.call(Request::RandomAuthenticatedAndTuples {
+            delta: delta.clone(),
+            left_input_bit_vec: left_input_bit_vec.clone(),
+            right_input_bit_vec: right_input_bit_vec.clone(),
+        }) {
+            Response::RandomAuthenticatedAndTuples {
+                output_bit_vec: own_output_bit_vec,
+                vole_mac_output_vec: own_vole_mac_output_vec,
+                other_vole_key_output_vec: own_other_vole_key_output_vec,
+            } => {
+                *output_bit_vec = own_output_bit_vec;
+                *vole_mac_output_vec = own_vole_mac_output_vec;
+                *other_vole_key_output_vec = own_other_vole_key_output_vec;
+            },
+            _ => unreachable!(),
+        }
+    }
+
+    fn num_communicated_bytes(&self) -> usize {
+        0
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::value_type::Zero;
+    use std::panic::{self, AssertUnwindSafe};
+    use std::sync::mpsc;
+    use std::thread;
+    use std::time::Duration;
     use itertools::izip;
     use rand::Rng;
+    use rand_chacha::ChaCha20Rng;
+    use rand::SeedableRng;
+    use crate::functionalities_and_protocols::functionality_pre::PreprocessingFunctionality;
     use crate::functionalities_and_protocols::insecure_functionality_pre::insecure_functionality_pre::InsecureFunctionalityPre;
     use crate::value_type::gf2p256::GF2p256;
     use crate::vec_type::bit_vec::BitVec;
     use crate::vec_type::gf_vec::GFVec;
     use crate::value_type::{CustomAddition, CustomMultiplyingBit, InsecureRandom};
-    use crate::value_type::gf2p8::GF2p8;
     use crate::vec_type::ZeroVec;
 
     #[test]
     fn test_functionality_pre_generating_random_tuples() {
-        let delta_a = GF2p256::insecurely_random();
-        let delta_b = GF2p256::insecurely_random();
-        println!("delta_a: {:?}", delta_a);
+        let delta_b = GF2p256::insecurely_random(&mut rand::rng());
         println!("delta_b: {:?}", delta_b);
 
         let num_random_tuples = 100;
@@ -119,10 +346,14 @@ mod tests {
         let mut vole_mac_rand_vec = GFVec::<GF2p256>::zero_vec(num_random_tuples);
         let mut vole_key_rand_vec = GFVec::<GF2p256>::zero_vec(num_random_tuples);
 
-        InsecureFunctionalityPre::generate_random_tuples::<GF2p256, GF2p8>(
-            num_random_tuples, &delta_a,
-            &mut rand_bit_vec, &mut vole_mac_rand_vec, &mut vole_key_rand_vec
-        );
+        // PA and PB take turns on one thread, PA's call waits for PB's, so PA runs on her own thread
+        let (mut pa_functionality_pre, mut pb_functionality_pre) = InsecureFunctionalityPre::new_pair(ChaCha20Rng::from_rng(&mut rand::rng()));
+        thread::scope(|scope| {
+            scope.spawn(|| pa_functionality_pre.generate_random_tuples(
+                num_random_tuples, &mut rand_bit_vec, &mut vole_mac_rand_vec
+            ));
+            pb_functionality_pre.generate_random_tuple_keys(num_random_tuples, &delta_b, &mut vole_key_rand_vec);
+        });
 
         // check the lengths
         assert_eq!(rand_bit_vec.len(), num_random_tuples);
@@ -130,13 +361,13 @@ mod tests {
         assert_eq!(vole_key_rand_vec.len(), num_random_tuples);
 
         for (rand_bit, vole_mac_rand, vole_key_rand) in izip!(
-            rand_bit_vec.iter(), 
-            vole_mac_rand_vec.iter(), 
+            rand_bit_vec.iter(),
+            vole_mac_rand_vec.iter(),
             vole_key_rand_vec.iter()
         ) {
             println!("rand_bit, vole_mac_rand, vole_key_rand: {:?} {:?} {:?}",
                      rand_bit, vole_mac_rand, vole_key_rand);
-            assert_eq!(*vole_key_rand, vole_mac_rand.custom_add(&delta_a.custom_multiply_bit(*rand_bit)));
+            assert_eq!(*vole_key_rand, vole_mac_rand.custom_add(&delta_b.custom_multiply_bit(rand_bit)));
         }
         println!("test_functionality_pre_generating_random_tuples passed");
     }
@@ -145,18 +376,25 @@ mod tests {
     fn test_functionality_pre_generating_random_and_tuples () {
         let kappa = 10;
         let num_random_and_tuples = 100;
-        let mut pa_rand_a_bit_vec_rep: Vec<BitVec> = vec![BitVec::zero_vec(num_random_and_tuples); kappa];
-        let mut pa_rand_b_bit_vec_rep: Vec<BitVec> = vec![BitVec::zero_vec(num_random_and_tuples); kappa];
-        let mut pa_rand_c_bit_vec_rep: Vec<BitVec> = vec![BitVec::zero_vec(num_random_and_tuples); kappa];
-        let mut pb_rand_a_bit_vec_rep: Vec<BitVec> = vec![BitVec::zero_vec(num_random_and_tuples); kappa];
-        let mut pb_rand_b_bit_vec_rep: Vec<BitVec> = vec![BitVec::zero_vec(num_random_and_tuples); kappa];
-        let mut pb_rand_c_bit_vec_rep: Vec<BitVec> = vec![BitVec::zero_vec(num_random_and_tuples); kappa];
-        InsecureFunctionalityPre::generate_random_and_tuples(
-            kappa, num_random_and_tuples,
-            &mut pa_rand_a_bit_vec_rep, &mut pa_rand_b_bit_vec_rep, &mut pa_rand_c_bit_vec_rep,
-            &mut pb_rand_a_bit_vec_rep, &mut pb_rand_b_bit_vec_rep, &mut pb_rand_c_bit_vec_rep
-        );
-        
+        let mut pa_rand_a_bit_vec_rep: Vec<BitVec> = Vec::new();
+        let mut pa_rand_b_bit_vec_rep: Vec<BitVec> = Vec::new();
+        let mut pa_rand_c_bit_vec_rep: Vec<BitVec> = Vec::new();
+        let mut pb_rand_a_bit_vec_rep: Vec<BitVec> = Vec::new();
+        let mut pb_rand_b_bit_vec_rep: Vec<BitVec> = Vec::new();
+        let mut pb_rand_c_bit_vec_rep: Vec<BitVec> = Vec::new();
+        let (mut pa_functionality_pre, mut pb_functionality_pre) = InsecureFunctionalityPre::<_, GF2p256>::new_pair(ChaCha20Rng::from_rng(&mut rand::rng()));
+        thread::scope(|scope| {
+            scope.spawn(|| pb_functionality_pre.generate_random_and_tuples(
+                kappa, num_random_and_tuples,
+                &mut pb_rand_a_bit_vec_rep, &mut pb_rand_b_bit_vec_rep, &mut pb_rand_c_bit_vec_rep
+            ));
+            pa_functionality_pre.generate_random_and_tuples(
+                kappa, num_random_and_tuples,
+                &mut pa_rand_a_bit_vec_rep, &mut pa_rand_b_bit_vec_rep, &mut pa_rand_c_bit_vec_rep
+            );
+        });
+        assert_eq!(pa_rand_a_bit_vec_rep.len(), kappa);
+
         for (
             pa_rand_a_bit_vec, pa_rand_b_bit_vec, pa_rand_c_bit_vec,
             pb_rand_a_bit_vec, pb_rand_b_bit_vec, pb_rand_c_bit_vec
@@ -164,15 +402,16 @@ mod tests {
             pa_rand_a_bit_vec_rep.iter(), pa_rand_b_bit_vec_rep.iter(), pa_rand_c_bit_vec_rep.iter(),
             pb_rand_a_bit_vec_rep.iter(), pb_rand_b_bit_vec_rep.iter(), pb_rand_c_bit_vec_rep.iter()
         ) {
+            assert_eq!(pa_rand_a_bit_vec.len(), num_random_and_tuples);
             for (
-                pa_rand_a_bit, pa_rand_b_bit, pa_rand_c_bit, 
+                pa_rand_a_bit, pa_rand_b_bit, pa_rand_c_bit,
                 pb_rand_a_bit, pb_rand_b_bit, pb_rand_c_bit
             ) in izip!(
                 pa_rand_a_bit_vec.iter(), pa_rand_b_bit_vec.iter(), pa_rand_c_bit_vec.iter(),
                 pb_rand_a_bit_vec.iter(), pb_rand_b_bit_vec.iter(), pb_rand_c_bit_vec.iter()
             ) {
                 println!(
-                    "pa_rand_a, pa_rand_b, pa_rand_c, pb_rand_a, pb_rand_b, pb_rand_c: {:?} {:?} {:?} {:?} {:?} {:?}", 
+                    "pa_rand_a, pa_rand_b, pa_rand_c, pb_rand_a, pb_rand_b, pb_rand_c: {:?} {:?} {:?} {:?} {:?} {:?}",
                     pa_rand_a_bit, pa_rand_b_bit, pa_rand_c_bit,
                     pb_rand_a_bit, pb_rand_b_bit, pb_rand_c_bit
                 );
@@ -181,37 +420,94 @@ mod tests {
         }
         println!("test_functionality_pre_generating_random_and_tuples passed!");
     }
-    
+
     #[test]
     fn test_functionality_pre_generating_random_authenticated_and_tuples () {
-        let delta_a = GF2p256::insecurely_random();
-        let delta_b = GF2p256::insecurely_random();
+        let delta_a = GF2p256::insecurely_random(&mut rand::rng());
+        let delta_b = GF2p256::insecurely_random(&mut rand::rng());
         println!("delta_a: {:?}", delta_a);
         println!("delta_b: {:?}", delta_b);
 
+        let len = 10;
         let mut rng = rand::rng();
-        let pa_a_bit: u8 = rng.random::<u8>() & 1;
-        let pa_b_bit: u8 = rng.random::<u8>() & 1;
-        let pb_a_bit: u8 = rng.random::<u8>() & 1;
-        let pb_b_bit: u8 = rng.random::<u8>() & 1;
-        
-        let mut pa_c_bit: u8 = 0;
-        let mut pb_c_bit: u8 = 0;
-        let mut pa_vole_mac_c = GF2p256::zero();
-        let mut pa_vole_key_c = GF2p256::zero();
-        let mut pb_vole_mac_c = GF2p256::zero();
-        let mut pb_vole_key_c = GF2p256::zero();
-        InsecureFunctionalityPre::generate_random_authenticated_and_tuples::<GF2p256>(
-            &delta_a, pa_a_bit, pa_b_bit, &mut pa_c_bit, &mut pa_vole_mac_c, &mut pa_vole_key_c,
-            &delta_b, pb_a_bit, pb_b_bit, &mut pb_c_bit, &mut pb_vole_mac_c, &mut pb_vole_key_c
+        let [pa_a_bit_vec, pa_b_bit_vec, pb_a_bit_vec, pb_b_bit_vec] = [(); 4].map(
+            |_| (0..len).map(|_| rng.random::<u8>() & 1).collect::<BitVec>()
         );
-        println!("pa_a_bit, pa_b_bit, pa_c_bit: {:?}, {:?}, {:?}", pa_a_bit, pa_b_bit, pa_c_bit);
-        println!("pb_a_bit, pb_b_bit, pb_c_bit: {:?}, {:?}, {:?}", pb_a_bit, pb_b_bit, pb_c_bit);
-        println!("pa_vole_mac_c, pa_vole_key_c: {:?}, {:?}", pa_vole_mac_c, pa_vole_key_c);
-        println!("pb_vole_mac_c, pb_vole_key_c: {:?}, {:?}", pb_vole_mac_c, pb_vole_key_c);
-        assert_eq!(pa_c_bit ^ pb_c_bit, (pa_a_bit ^ pb_a_bit) & (pa_b_bit ^ pb_b_bit));
-        assert_eq!(pa_vole_key_c, pa_vole_mac_c.custom_add(&delta_b.custom_multiply_bit(pa_c_bit)));
-        assert_eq!(pb_vole_key_c, pb_vole_mac_c.custom_add(&delta_a.custom_multiply_bit(pb_c_bit)));
+
+        let (mut pa_c_bit_vec, mut pa_vole_mac_c_vec, mut pa_vole_key_c_vec) = (BitVec::new(), GFVec::<GF2p256>::new(), GFVec::<GF2p256>::new());
+        let (mut pb_c_bit_vec, mut pb_vole_mac_c_vec, mut pb_vole_key_c_vec) = (BitVec::new(), GFVec::<GF2p256>::new(), GFVec::<GF2p256>::new());
+        let (mut pa_functionality_pre, mut pb_functionality_pre) = InsecureFunctionalityPre::new_pair(ChaCha20Rng::from_rng(&mut rand::rng()));
+        thread::scope(|scope| {
+            scope.spawn(|| pa_functionality_pre.generate_random_authenticated_and_tuples(
+                &delta_a, &pa_a_bit_vec, &pa_b_bit_vec, &mut pa_c_bit_vec, &mut pa_vole_mac_c_vec, &mut pa_vole_key_c_vec
+            ));
+            pb_functionality_pre.generate_random_authenticated_and_tuples(
+                &delta_b, &pb_a_bit_vec, &pb_b_bit_vec, &mut pb_c_bit_vec, &mut pb_vole_mac_c_vec, &mut pb_vole_key_c_vec
+            );
+        });
+        assert_eq!(pa_c_bit_vec.len(), len);
+        for i in 0..len {
+            assert_eq!(pa_c_bit_vec[i] ^ pb_c_bit_vec[i], (pa_a_bit_vec[i] ^ pb_a_bit_vec[i]) & (pa_b_bit_vec[i] ^ pb_b_bit_vec[i]));
+            // each party holds the key for the output bit of the other party
+            assert_eq!(pb_vole_key_c_vec[i], pa_vole_mac_c_vec[i].custom_add(&delta_b.custom_multiply_bit(pa_c_bit_vec[i])));
+            assert_eq!(pa_vole_key_c_vec[i], pb_vole_mac_c_vec[i].custom_add(&delta_a.custom_multiply_bit(pb_c_bit_vec[i])));
+        }
         println!("test_functionality_pre_generating_random_authenticated_and_tuples passed!");
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_functionality_pre_is_independent_of_the_order_of_calls() {
+        let deal = |is_pa_first: bool| {
+            let (mut pa_functionality_pre, mut pb_functionality_pre) = InsecureFunctionalityPre::<_, GF2p256>::new_pair(ChaCha20Rng::seed_from_u64(0));
+            let (mut delta_a, mut delta_b) = (None, None);
+            thread::scope(|scope| {
+                if is_pa_first {
+                    scope.spawn(|| pa_functionality_pre.generate_delta(&mut delta_a));
+                    thread::sleep(std::time::Duration::from_millis(10));
+                    pb_functionality_pre.generate_delta(&mut delta_b);
+                } else {
+                    scope.spawn(|| pb_functionality_pre.generate_delta(&mut delta_b));
+                    thread::sleep(std::time::Duration::from_millis(10));
+                    pa_functionality_pre.generate_delta(&mut delta_a);
+                }
+            });
+            (delta_a.unwrap(), delta_b.unwrap())
+        };
+        assert_eq!(deal(true), deal(false));
+    }
+
+    // the party that makes the second of two mismatching calls panics inside the lock, and the party waiting for
+    // her response has to panic too instead of blocking
+    #[test]
+    fn test_functionality_pre_panics_on_both_sides_of_mismatching_calls() {
+        let (pa_functionality_pre, pb_functionality_pre) = InsecureFunctionalityPre::<_, GF2p256>::new_pair(ChaCha20Rng::from_rng(&mut rand::rng()));
+        let (sender, receiver) = mpsc::channel();
+        let pa_sender = sender.clone();
+        thread::spawn(move || {
+            let mut pa_functionality_pre = pa_functionality_pre;
+            let result = panic::catch_unwind(AssertUnwindSafe(|| pa_functionality_pre.generate_delta(&mut None)));
+            // the endpoint is dropped before the result is reported
+            drop(pa_functionality_pre);
+            pa_sender.send(result.is_err()).unwrap();
+        });
+        thread::spawn(move || {
+            let mut pb_functionality_pre = pb_functionality_pre;
+            let result = panic::catch_unwind(AssertUnwindSafe(
+                || pb_functionality_pre.generate_random_tuples(10, &mut BitVec::new(), &mut GFVec::new())
+            ));
+            drop(pb_functionality_pre);
+            sender.send(result.is_err()).unwrap();
+        });
+        for _ in 0..2 {
+            assert_eq!(receiver.recv_timeout(Duration::from_secs(10)), Ok(true));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "the other party left FPre")]
+    fn test_functionality_pre_panics_when_the_other_party_left() {
+        let (mut pa_functionality_pre, pb_functionality_pre) = InsecureFunctionalityPre::<_, GF2p256>::new_pair(ChaCha20Rng::from_rng(&mut rand::rng()));
+        drop(pb_functionality_pre);
+        pa_functionality_pre.generate_delta(&mut None);
+    }
+}
@@ -1 +1 @@
-pub(crate) mod insecure_functionality_pre;
\ No newline at end of file
+pub mod insecure_functionality_pre;
\ No newline at end of file
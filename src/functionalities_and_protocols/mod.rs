@@ -1,3 +1,6 @@
+pub mod channel;
+pub mod crypto_backend;
+pub mod functionality_pre;
 pub mod insecure_functionality_pre;
 mod protocol_svole_2pc;
 mod protocol_check_and;
@@ -6,4 +9,8 @@ pub mod states_and_parameters;
 pub mod all_in_one_vc;
 mod util;
 pub mod protocol_pa_2pc;
-mod hasher;
\ No newline at end of file
+mod hasher;
+mod commitment;
+mod permutation;
+mod transcript;
+pub mod verification_result;
\ No newline at end of file
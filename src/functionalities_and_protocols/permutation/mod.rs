@@ -0,0 +1,29 @@
+mod test;
+
+// A uniform integer in 0..bound from a stream of uniform u64s. The u64s below the threshold 2^64 mod bound, i.e.,
+// bound.wrapping_neg() % bound, are rejected, which leaves a multiple of bound values, and the first u64 at or above
+// it is reduced modulo bound. Fewer than one in two u64s is rejected for any bound.
+pub fn sample_below(next_u64: &mut impl FnMut() -> u64, bound: usize) -> usize {
+    assert!(bound > 0);
+    let bound = bound as u64;
+    let threshold = bound.wrapping_neg() % bound;
+    loop {
+        let value = next_u64();
+        if value >= threshold {
+            return (value % bound) as usize;
+        }
+    }
+}
+
+// A uniform permutation of 0..len by Fisher-Yates, which is what the soundness of bucketing assumes: for i from
+// len - 1 down to 0, entry i is swapped with entry j for j = sample_below(next_u64, i + 1). Every place that derives a
+// permutation goes through it, the Fiat-Shamir challenge and the tests alike. The last step, at i = 0, always draws
+// j = 0 but still consumes a u64 from the stream.
+pub fn sample_permutation(next_u64: &mut impl FnMut() -> u64, len: usize) -> Vec<usize> {
+    let mut permutation = (0..len).collect::<Vec<usize>>();
+    for i in (0..len).rev() {
+        let j = sample_below(next_u64, i + 1);
+        permutation.swap(i, j);
+    }
+    permutation
+}
@@ -0,0 +1,83 @@
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use rand::{Rng, SeedableRng};
+    use rand_chacha::ChaCha20Rng;
+    use crate::functionalities_and_protocols::permutation::{sample_below, sample_permutation};
+
+    fn replay(value_vec: &[u64]) -> impl FnMut() -> u64 + '_ {
+        let mut value_iter = value_vec.iter();
+        move || *value_iter.next().unwrap()
+    }
+
+    #[test]
+    fn test_sample_below_rejects_the_biased_values() {
+        // 2^64 mod 3 = 1, so 0 is rejected
+        assert_eq!(sample_below(&mut replay(&[0, 5]), 3), 2);
+        assert_eq!(sample_below(&mut replay(&[1]), 3), 1);
+        // 2^64 mod (2^63 + 1) = 2^63 - 1, so almost half of the u64s are rejected
+        let bound = (1usize << 63) + 1;
+        assert_eq!(sample_below(&mut replay(&[0, (1 << 63) - 2, (1 << 63) - 1]), bound), (1 << 63) - 1);
+        assert_eq!(sample_below(&mut replay(&[u64::MAX]), bound), (1 << 63) - 2);
+        // powers of two never reject, and bounds beyond u32 are not truncated
+        assert_eq!(sample_below(&mut replay(&[0]), 1 << 40), 0);
+        assert_eq!(sample_below(&mut replay(&[u64::MAX]), 1 << 40), (1 << 40) - 1);
+        assert_eq!(sample_below(&mut replay(&[u64::MAX]), 1), 0);
+    }
+
+    #[test]
+    fn test_sample_below_is_uniform() {
+        let mut rng = ChaCha20Rng::seed_from_u64(0);
+        let num_samples = 60000;
+        for bound in [2usize, 3, 5, 6, 7] {
+            let mut count_vec = vec![0usize; bound];
+            for _ in 0..num_samples {
+                count_vec[sample_below(&mut || rng.random::<u64>(), bound)] += 1;
+            }
+            assert!(chi_square(&count_vec, num_samples) < chi_square_bound(bound - 1));
+        }
+    }
+
+    #[test]
+    fn test_sample_permutation_is_uniform() {
+        let mut rng = ChaCha20Rng::seed_from_u64(0);
+        for len in [1usize, 2, 3, 4] {
+            let num_permutations = (1..=len).product::<usize>();
+            let num_samples = 2000 * num_permutations;
+            let mut count_map = HashMap::<Vec<usize>, usize>::new();
+            for _ in 0..num_samples {
+                let permutation = sample_permutation(&mut || rng.random::<u64>(), len);
+                let mut sorted_permutation = permutation.clone();
+                sorted_permutation.sort();
+                assert_eq!(sorted_permutation, (0..len).collect::<Vec<usize>>());
+                *count_map.entry(permutation).or_insert(0) += 1;
+            }
+            // every permutation shows up about equally often
+            assert_eq!(count_map.len(), num_permutations);
+            let count_vec = count_map.into_values().collect::<Vec<usize>>();
+            assert!(chi_square(&count_vec, num_samples) < chi_square_bound(num_permutations - 1));
+        }
+    }
+
+    #[test]
+    fn test_sample_permutation_consumes_one_u64_per_position() {
+        let mut num_draws = 0;
+        // u64::MAX is never rejected, and reduces to 0, 3, 0, 1 and 0 modulo 5, 4, 3, 2 and 1
+        let permutation = sample_permutation(&mut || { num_draws += 1; u64::MAX }, 5);
+        assert_eq!(num_draws, 5);
+        assert_eq!(permutation, vec![2, 1, 4, 3, 0]);
+        assert!(sample_permutation(&mut || u64::MAX, 0).is_empty());
+    }
+
+    fn chi_square(count_vec: &[usize], num_samples: usize) -> f64 {
+        let expected = num_samples as f64 / count_vec.len() as f64;
+        count_vec.iter().map(|count| (*count as f64 - expected).powi(2) / expected).sum()
+    }
+
+    // a loose bound on the chi-square statistic, the mean plus ten standard deviations, which a fixed seed passes
+    // and a sampler that is off by a few percent fails
+    fn chi_square_bound(degrees_of_freedom: usize) -> f64 {
+        let degrees_of_freedom = degrees_of_freedom as f64;
+        degrees_of_freedom + 10f64 * (2f64 * degrees_of_freedom).sqrt() + 1f64
+    }
+}
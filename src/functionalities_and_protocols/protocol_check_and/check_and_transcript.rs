@@ -1,8 +1,8 @@
-use bincode::Encode;
+use bincode::{Decode, Encode};
 use crate::vec_type::bit_vec::BitVec;
 use crate::vec_type::gf_vec::GFVec;
 
-#[derive(Encode)]
+#[derive(Encode, Decode)]
 pub struct CheckAndTranscript<GFVOLEitH>
 where GFVOLEitH: Encode {
 
@@ -2,6 +2,8 @@
 mod tests {
     use itertools::izip;
     use crate::bristol_fashion_adaptor::bristol_fashion_adaptor::BristolFashionAdaptor;
+    use crate::functionalities_and_protocols::all_in_one_vc::{LeafExpansionVersion, VectorCommitmentMode};
+    use crate::functionalities_and_protocols::crypto_backend::CryptoBackend;
     use crate::functionalities_and_protocols::protocol_check_and::check_and_transcript::CheckAndTranscript;
     use crate::functionalities_and_protocols::states_and_parameters::public_parameter::PublicParameter;
     use crate::functionalities_and_protocols::protocol_check_and::prover_in_protocol_check_and::ProverInProtocolCheckAND;
@@ -37,7 +39,7 @@ mod tests {
         GFVec::<GF2p8>::from_vec(
             bit_vec.iter().zip(voleith_mac_vec.iter()).map(
                 |(bit, voleith_mac)|
-                    if *bit == 1 {
+                    if bit == 1 {
                         nabla.clone().custom_add(voleith_mac)
                     } else {
                         voleith_mac.clone()
@@ -51,7 +53,7 @@ mod tests {
         for _ in 0..kappa {
             gf2p8_vec_rep.push(GFVec::new());
             for _ in 0..len {
-                gf2p8_vec_rep.last_mut().unwrap().push(GF2p8::insecurely_random());
+                gf2p8_vec_rep.last_mut().unwrap().push(GF2p8::insecurely_random(&mut rand::rng()));
             }
         }
         gf2p8_vec_rep
@@ -68,18 +70,23 @@ mod tests {
         let public_parameter = PublicParameter::new::<GF2p256, GF2p8>(
             &bristol_fashion_adaptor,
             8,
-            10,
-            SeedU8x16::insecurely_random(),
+            PublicParameter::determine_kappa(256, 8),
+            VectorCommitmentMode::PerRepetition,
+            LeafExpansionVersion::V2,
+            CryptoBackend::default(),
+            SeedU8x16::insecurely_random(&mut rand::rng()),
+            None,
             big_ia,
             big_ib,
             10,
             4,
+            None,
         );
         let nabla_a_rep = (0..public_parameter.kappa).map(
-            |_| GF2p8::insecurely_random()
+            |_| GF2p8::insecurely_random(&mut rand::rng())
         ).collect::<Vec<GF2p8>>();
         let nabla_b_rep = (0..public_parameter.kappa).map(
-            |_|GF2p8::insecurely_random()
+            |_|GF2p8::insecurely_random(&mut rand::rng())
         ).collect::<Vec<GF2p8>>();
 
         // prepare random vectors for pa
@@ -93,7 +100,7 @@ mod tests {
             Verifier::verify_vole_correlations(
                 &pa_x_bit_vec, &pa_voleith_mac_x_vec_rep[repetition_id], &nabla_b_rep[repetition_id],
                 &pa_voleith_key_x_vec_rep[repetition_id],
-            );
+            ).unwrap();
         }
         let pa_y_bit_vec = generate_random_bit_vec(public_parameter.big_iw_size);
         let pa_voleith_mac_y_vec_rep = generate_random_gf2p8_vec_rep(public_parameter.kappa, public_parameter.big_iw_size);
@@ -105,7 +112,7 @@ mod tests {
             Verifier::verify_vole_correlations(
                 &pa_y_bit_vec, &pa_voleith_mac_y_vec_rep[repetition_id], &nabla_b_rep[repetition_id],
                 &pa_voleith_key_y_vec_rep[repetition_id],
-            );
+            ).unwrap();
         }
         let pa_z_bit_vec = generate_random_bit_vec(public_parameter.big_iw_size);
         let pa_voleith_mac_z_vec_rep = generate_random_gf2p8_vec_rep(public_parameter.kappa, public_parameter.big_iw_size);
@@ -117,7 +124,7 @@ mod tests {
             Verifier::verify_vole_correlations(
                 &pa_z_bit_vec, &pa_voleith_mac_z_vec_rep[repetition_id], &nabla_b_rep[repetition_id],
                 &pa_voleith_key_z_vec_rep[repetition_id],
-            );
+            ).unwrap();
         }
         let pa_a_bit_vec_rep = generate_random_bit_vec_rep(public_parameter.kappa, public_parameter.big_iw_size);
         let pa_voleith_mac_a_vec_rep = generate_random_gf2p8_vec_rep(public_parameter.kappa, public_parameter.big_iw_size);
@@ -129,7 +136,7 @@ mod tests {
             Verifier::verify_vole_correlations(
                 &pa_a_bit_vec_rep[repetition_id], &pa_voleith_mac_a_vec_rep[repetition_id], &nabla_b_rep[repetition_id],
                 &pa_voleith_key_a_vec_rep[repetition_id],
-            );
+            ).unwrap();
         }
         let pa_b_bit_vec_rep = generate_random_bit_vec_rep(public_parameter.kappa, public_parameter.big_iw_size);
         let pa_voleith_mac_b_vec_rep = generate_random_gf2p8_vec_rep(public_parameter.kappa, public_parameter.big_iw_size);
@@ -141,7 +148,7 @@ mod tests {
             Verifier::verify_vole_correlations(
                 &pa_b_bit_vec_rep[repetition_id], &pa_voleith_mac_b_vec_rep[repetition_id], &nabla_b_rep[repetition_id],
                 &pa_voleith_key_b_vec_rep[repetition_id],
-            );
+            ).unwrap();
         }
         let pa_c_bit_vec_rep = generate_random_bit_vec_rep(public_parameter.kappa, public_parameter.big_iw_size);
         let pa_voleith_mac_c_vec_rep = generate_random_gf2p8_vec_rep(public_parameter.kappa, public_parameter.big_iw_size);
@@ -153,7 +160,7 @@ mod tests {
             Verifier::verify_vole_correlations(
                 &pa_c_bit_vec_rep[repitition_id], &pa_voleith_mac_c_vec_rep[repitition_id], &nabla_b_rep[repitition_id],
                 &pa_voleith_key_c_vec_rep[repitition_id],
-            );
+            ).unwrap();
         }
 
         // prepare random vectors for pb
@@ -167,7 +174,7 @@ mod tests {
             Verifier::verify_vole_correlations(
                 &pb_x_bit_vec, &pb_voleith_mac_x_vec_rep[repetition_id], &nabla_a_rep[repetition_id],
                 &pb_voleith_key_x_vec_rep[repetition_id],
-            );
+            ).unwrap();
         }
         let pb_y_bit_vec = generate_random_bit_vec(public_parameter.big_iw_size);
         let pb_voleith_mac_y_vec_rep = generate_random_gf2p8_vec_rep(public_parameter.kappa, public_parameter.big_iw_size);
@@ -179,7 +186,7 @@ mod tests {
             Verifier::verify_vole_correlations(
                 &pb_y_bit_vec, &pb_voleith_mac_y_vec_rep[repetition_id], &nabla_a_rep[repetition_id],
                 &pb_voleith_key_y_vec_rep[repetition_id],
-            );
+            ).unwrap();
         }
         let pb_z_bit_vec = BitVec::from_vec(izip!(
             pa_x_bit_vec.iter(), pa_y_bit_vec.iter(), pa_z_bit_vec.iter(),
@@ -197,7 +204,7 @@ mod tests {
             Verifier::verify_vole_correlations(
                 &pb_z_bit_vec, &pb_voleith_mac_z_vec_rep[repetition_id], &nabla_a_rep[repetition_id],
                 &pb_voleith_key_z_vec_rep[repetition_id],
-            );
+            ).unwrap();
         }
         let pb_a_bit_vec_rep = generate_random_bit_vec_rep(public_parameter.kappa, public_parameter.big_iw_size);
         let pb_voleith_mac_a_vec_rep = generate_random_gf2p8_vec_rep(public_parameter.kappa, public_parameter.big_iw_size);
@@ -209,7 +216,7 @@ mod tests {
             Verifier::verify_vole_correlations(
                 &pb_a_bit_vec_rep[repetition_id], &pb_voleith_mac_a_vec_rep[repetition_id], &nabla_a_rep[repetition_id],
                 &pb_voleith_key_a_vec_rep[repetition_id],
-            );
+            ).unwrap();
         }
         let pb_b_bit_vec_rep = generate_random_bit_vec_rep(public_parameter.kappa, public_parameter.big_iw_size);
         let pb_voleith_mac_b_vec_rep = generate_random_gf2p8_vec_rep(public_parameter.kappa, public_parameter.big_iw_size);
@@ -221,7 +228,7 @@ mod tests {
             Verifier::verify_vole_correlations(
                 &pb_b_bit_vec_rep[repetition_id], &pb_voleith_mac_b_vec_rep[repetition_id], &nabla_a_rep[repetition_id],
                 &pb_voleith_key_b_vec_rep[repetition_id],
-            );
+            ).unwrap();
         }
         let pb_c_bit_vec_rep = izip!(
             pa_a_bit_vec_rep.iter(), pa_b_bit_vec_rep.iter(), pa_c_bit_vec_rep.iter(),
@@ -246,7 +253,7 @@ mod tests {
             Verifier::verify_vole_correlations(
                 &pb_c_bit_vec_rep[repetition_id], &pb_voleith_mac_c_vec_rep[repetition_id], &nabla_a_rep[repetition_id],
                 &pb_voleith_key_c_vec_rep[repetition_id],
-            );
+            ).unwrap();
         }
 
         // start testing correct multiplications
@@ -334,6 +341,7 @@ mod tests {
 
         VerifierInProtocolCheckAND::verify(
             &public_parameter,
+            0,
             &check_and_transcript,
             &nabla_a_rep, &nabla_b_rep,
             (
@@ -344,6 +352,6 @@ mod tests {
                 (&pb_voleith_key_x_vec_rep, &pb_voleith_key_y_vec_rep, &pb_voleith_key_z_vec_rep),
                 (&pb_voleith_key_a_vec_rep, &pb_voleith_key_b_vec_rep, &pb_voleith_key_c_vec_rep)
             )
-        );
+        ).unwrap();
     }
 }
\ No newline at end of file
@@ -3,6 +3,7 @@ use bincode::Encode;
 use crate::functionalities_and_protocols::protocol_check_and::check_and_transcript::CheckAndTranscript;
 use crate::functionalities_and_protocols::states_and_parameters::public_parameter::PublicParameter;
 use crate::functionalities_and_protocols::util::verifier::Verifier;
+use crate::functionalities_and_protocols::verification_result::VerificationError;
 use crate::value_type::{CustomAddition, CustomMultiplyingBit, Zero};
 use crate::vec_type::bit_vec::BitVec;
 use crate::vec_type::gf_vec::GFVec;
@@ -14,6 +15,7 @@ impl VerifierInProtocolCheckAND {
 
     pub fn verify<GFVOLEitH>(
         public_parameter: &PublicParameter,
+        block_id: usize,
         // pa_published_bit_and_voleith_mac_tuple_rep: &(
         //     (Vec<BitVec>, Vec<GFVec<GFVOLEitH>>),
         //     (Vec<BitVec>, Vec<GFVec<GFVOLEitH>>),
@@ -35,7 +37,7 @@ impl VerifierInProtocolCheckAND {
             (&Vec<GFVec<GFVOLEitH>>, &Vec<GFVec<GFVOLEitH>>, &Vec<GFVec<GFVOLEitH>>),
             (&Vec<GFVec<GFVOLEitH>>, &Vec<GFVec<GFVOLEitH>>, &Vec<GFVec<GFVOLEitH>>)
         ),
-    )
+    ) -> Result<(), VerificationError>
     where GFVOLEitH: PartialEq + Clone + CustomAddition + CustomMultiplyingBit + Debug + Zero + Encode {
         let (
             (pa_d_bit_vec_rep, pa_voleith_mac_d_vec_rep),
@@ -71,7 +73,9 @@ impl VerifierInProtocolCheckAND {
                     &pa_voleith_mac_d_vec_rep[repetition_id],
                     &nabla_b_rep[repetition_id],
                     &pa_voleith_key_d_vec
-                );
+                ).map_err(
+                    |and_cursor| VerificationError::CheckAND { is_pa: true, block_id, repetition_id, and_cursor }
+                )?;
             }
             {
                 // println!("Verifier pa_voleith_key_e_vec at repetition_id: {}", repetition_id);
@@ -81,7 +85,9 @@ impl VerifierInProtocolCheckAND {
                     &pa_voleith_mac_e_vec_rep[repetition_id],
                     &nabla_b_rep[repetition_id],
                     &pa_voleith_key_e_vec
-                )
+                ).map_err(
+                    |and_cursor| VerificationError::CheckAND { is_pa: true, block_id, repetition_id, and_cursor }
+                )?;
             }
             {
                 let pb_voleith_key_d_vec = pb_voleith_key_x_vec_rep[repetition_id].vec_add(&pb_voleith_key_a_vec_rep[repetition_id]);
@@ -90,7 +96,9 @@ impl VerifierInProtocolCheckAND {
                     &pb_voleith_mac_d_vec_rep[repetition_id],
                     &nabla_a_rep[repetition_id],
                     &pb_voleith_key_d_vec
-                );
+                ).map_err(
+                    |and_cursor| VerificationError::CheckAND { is_pa: false, block_id, repetition_id, and_cursor }
+                )?;
             }
             {
                 let pb_voleith_key_e_vec = pb_voleith_key_y_vec_rep[repetition_id].vec_add(&pb_voleith_key_b_vec_rep[repetition_id]);
@@ -99,7 +107,9 @@ impl VerifierInProtocolCheckAND {
                     &pb_voleith_mac_e_vec_rep[repetition_id],
                     &nabla_a_rep[repetition_id],
                     &pb_voleith_key_e_vec
-                );
+                ).map_err(
+                    |and_cursor| VerificationError::CheckAND { is_pa: false, block_id, repetition_id, and_cursor }
+                )?;
             }
             {
                 let pa_voleith_key_tilde_z_vec = pa_voleith_key_z_vec_rep[repetition_id].vec_add(
@@ -118,7 +128,9 @@ impl VerifierInProtocolCheckAND {
                     &pa_voleith_mac_tilde_z_vec_rep[repetition_id],
                     &nabla_b_rep[repetition_id],
                     &pa_voleith_key_tilde_z_vec
-                );
+                ).map_err(
+                    |and_cursor| VerificationError::CheckAND { is_pa: true, block_id, repetition_id, and_cursor }
+                )?;
             }
             {
                 let pb_voleith_key_tilde_z_vec = pb_voleith_key_z_vec_rep[repetition_id].vec_add(
@@ -137,8 +149,11 @@ impl VerifierInProtocolCheckAND {
                     &pb_voleith_mac_tilde_z_vec_rep[repetition_id],
                     &nabla_a_rep[repetition_id],
                     &pb_voleith_key_tilde_z_vec
-                );
+                ).map_err(
+                    |and_cursor| VerificationError::CheckAND { is_pa: false, block_id, repetition_id, and_cursor }
+                )?;
             }
         }
+        Ok(())
     }
 }
\ No newline at end of file
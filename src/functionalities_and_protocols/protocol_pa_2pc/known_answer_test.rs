@@ -0,0 +1,382 @@
+#[cfg(test)]
+mod tests {
+    use std::fmt::Debug;
+    use std::thread;
+    use bincode::{config, encode_to_vec, Decode, Encode};
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha20Rng;
+    use crate::bristol_fashion_adaptor::bristol_fashion_adaptor::BristolFashionAdaptor;
+    use crate::functionalities_and_protocols::all_in_one_vc::{Decom, LeafExpansionVersion, VectorCommitmentMode};
+    use crate::functionalities_and_protocols::crypto_backend::{CryptoBackend, HashBackend, PrgBackend};
+    use crate::functionalities_and_protocols::channel::in_memory_channel::InMemoryChannel;
+    use crate::functionalities_and_protocols::insecure_functionality_pre::insecure_functionality_pre::InsecureFunctionalityPre;
+    use crate::functionalities_and_protocols::protocol_pa_2pc::determine_bit_trace_for_labels_in_garbling;
+    use crate::functionalities_and_protocols::protocol_pa_2pc::party_a::PartyA;
+    use crate::functionalities_and_protocols::protocol_pa_2pc::party_b::PartyB;
+    use crate::functionalities_and_protocols::protocol_pa_2pc::preprocessing_transcript::PreprocessingTranscript;
+    use crate::functionalities_and_protocols::protocol_pa_2pc::proof_transcript::ProofTranscript;
+    use crate::functionalities_and_protocols::protocol_pa_2pc::prover_in_pa_2pc::ProverInPA2PC;
+    use crate::functionalities_and_protocols::protocol_pa_2pc::verifier_in_pa_2pc::VerifierInPA2PC;
+    use crate::functionalities_and_protocols::states_and_parameters::prover_secret_state::ProverSecretState;
+    use crate::functionalities_and_protocols::states_and_parameters::public_parameter::PublicParameter;
+    use crate::value_type::gf2p128::GF2p128;
+    use crate::value_type::gf2p192::GF2p192;
+    use crate::value_type::gf2p256::GF2p256;
+    use crate::value_type::gf2p8::GF2p8;
+    use crate::value_type::seed_u8x16::SeedU8x16;
+    use crate::value_type::{ByteManipulation, CustomAddition, CustomMultiplyingBit, InsecureRandom, LeafIndexForGF, Zero};
+    use crate::vec_type::bit_vec::BitVec;
+    use crate::vec_type::BasicVecFunctions;
+
+    // Everything a proof depends on is fixed, so the blake3 digests below only change with the proof format.
+    // If a change to the hashing, garbling or encoding is intended, rerun the suite and update the digests
+    struct KnownAnswer {
+        circuit_file_name: &'static str,
+        security_level: usize,
+        seed: u64,
+        input_value_vec: &'static [u64],
+        num_pa_input_bits: usize,
+        tau: u8,
+        kappa: usize,
+        vector_commitment_mode: VectorCommitmentMode,
+        leaf_expansion_version: LeafExpansionVersion,
+        crypto_backend: CryptoBackend,
+        salted: bool,
+        context: Option<&'static str>,
+        bs: usize,
+        rm: usize,
+        preprocessing_transcript_digest: &'static str,
+        proof_transcript_digest: &'static str,
+        pa_decom_digest: &'static str,
+        pb_decom_digest: &'static str,
+    }
+
+    const KNOWN_ANSWER_VEC: [KnownAnswer; 7] = [
+        KnownAnswer {
+            circuit_file_name: "sub64.txt",
+            security_level: 128,
+            seed: 1,
+            input_value_vec: &[5, 1000],
+            num_pa_input_bits: 64,
+            tau: 8,
+            kappa: 16,
+            vector_commitment_mode: VectorCommitmentMode::PerRepetition,
+            leaf_expansion_version: LeafExpansionVersion::V1,
+            crypto_backend: CryptoBackend::new(HashBackend::Blake3, PrgBackend::Aes128),
+            salted: false,
+            context: None,
+            bs: 2,
+            rm: 63,
+            preprocessing_transcript_digest: "3cfedd8499df0483b37f863baf9f9a13fca21d41f67bb767b30336505e35fe4b",
+            proof_transcript_digest: "651fec362b1ee673e5dc9a586575d5177175cbd5ac53068c2e2f95f43cee3e6e",
+            pa_decom_digest: "e0a22568710744cb6d1a1c7c79f33efa39520064fd95f9dacbc3fffaa231c1a2",
+            pb_decom_digest: "53e4559b0e0fef18929e937810bfcf284dc3b7fa2388a59470e19853ae84d9cc",
+        },
+        KnownAnswer {
+            circuit_file_name: "sub64.txt",
+            security_level: 256,
+            seed: 2,
+            input_value_vec: &[0x0123456789abcdef, 0xfedcba9876543210],
+            num_pa_input_bits: 64,
+            tau: 8,
+            kappa: 32,
+            vector_commitment_mode: VectorCommitmentMode::PerRepetition,
+            leaf_expansion_version: LeafExpansionVersion::V1,
+            crypto_backend: CryptoBackend::new(HashBackend::Blake3, PrgBackend::Aes128),
+            salted: false,
+            context: None,
+            bs: 2,
+            rm: 63,
+            preprocessing_transcript_digest: "aaecee892531e1c944fad45b4a668292a5c83f820f2be42f72a0a0790790c841",
+            proof_transcript_digest: "1469c0696ebf4a09166de8fa5ce5a5f7eb1b32b462865a1aea303ba91b53ebbc",
+            pa_decom_digest: "988335caf13644eb014b3d29a489c9af2d367282a8d77e9940021de81977b497",
+            pb_decom_digest: "5270333348aafe444e287618ade1fd09bb598ab536cbece7a7614ccd23d9de99",
+        },
+        KnownAnswer {
+            circuit_file_name: "adder64.txt",
+            security_level: 128,
+            seed: 3,
+            input_value_vec: &[u64::MAX, 1],
+            num_pa_input_bits: 61,
+            tau: 8,
+            kappa: 16,
+            vector_commitment_mode: VectorCommitmentMode::PerRepetition,
+            leaf_expansion_version: LeafExpansionVersion::V1,
+            crypto_backend: CryptoBackend::new(HashBackend::Blake3, PrgBackend::Aes128),
+            salted: false,
+            context: None,
+            bs: 1,
+            rm: 63,
+            preprocessing_transcript_digest: "8a97ed9ab92496da23c7694351dec374a6527380d0b79091a3f957bd95b2857d",
+            proof_transcript_digest: "320fab96a038bd6d078a85ef85996a64814c8068f65301b2cc1a18b9aabe1c96",
+            pa_decom_digest: "8408a53e94b600f036177b5a6c85487b7bff0445273c6e1ca555d3c0439c54c1",
+            pb_decom_digest: "62461c7d2cede5e30fb49974fd88cc287871b8d32f2ffa8acd925c919306aecf",
+        },
+        KnownAnswer {
+            circuit_file_name: "neg64.txt",
+            security_level: 128,
+            seed: 4,
+            input_value_vec: &[42],
+            num_pa_input_bits: 32,
+            tau: 8,
+            kappa: 16,
+            vector_commitment_mode: VectorCommitmentMode::PerRepetition,
+            leaf_expansion_version: LeafExpansionVersion::V1,
+            crypto_backend: CryptoBackend::new(HashBackend::Blake3, PrgBackend::Aes128),
+            salted: false,
+            context: None,
+            bs: 2,
+            rm: 62,
+            preprocessing_transcript_digest: "a533c5ce71612dc39ab255177fa8dd7719c989b3df4dd900aee07caf411b77c2",
+            proof_transcript_digest: "9b5cdadb97c7de278903c0d473247d860506c5c8a5d00b8ad9725c6fa6530c36",
+            pa_decom_digest: "d525d43d3a896750190b7546a53fa8fe2454e8271bc2daa4b5742ba97fc1cbe0",
+            pb_decom_digest: "fb5c816e22f3ba2db93c2a99b7c8df6687782e49e8a60f47701b8650b00cceb1",
+        },
+        KnownAnswer {
+            circuit_file_name: "zero_equal.txt",
+            security_level: 256,
+            seed: 5,
+            input_value_vec: &[0],
+            num_pa_input_bits: 32,
+            tau: 8,
+            kappa: 32,
+            vector_commitment_mode: VectorCommitmentMode::PerRepetition,
+            leaf_expansion_version: LeafExpansionVersion::V1,
+            crypto_backend: CryptoBackend::new(HashBackend::Blake3, PrgBackend::Aes128),
+            salted: false,
+            context: None,
+            bs: 2,
+            rm: 63,
+            preprocessing_transcript_digest: "8d57876db5d2cd2fdae250eb2499523a98cb2d50887758f8591ac17a2cdb58b3",
+            proof_transcript_digest: "4796b1997ed929e0873f1b4e7c1dd6274163ed2b82ea1d9faba2fba5443f8578",
+            pa_decom_digest: "2ff025e318197bb55e2dcbb2afdd980c9f2a28bf1a9218bf7ba26064010be2a4",
+            pb_decom_digest: "2990a39db09f8cb08802afc39f6f5bf4c19a23b1bc9bfc3a74c974dc043b998d",
+        },
+        KnownAnswer {
+            circuit_file_name: "sub64.txt",
+            security_level: 192,
+            seed: 6,
+            input_value_vec: &[1000, 5],
+            num_pa_input_bits: 64,
+            tau: 8,
+            kappa: 24,
+            vector_commitment_mode: VectorCommitmentMode::PerRepetition,
+            leaf_expansion_version: LeafExpansionVersion::V1,
+            crypto_backend: CryptoBackend::new(HashBackend::Blake3, PrgBackend::Aes128),
+            salted: false,
+            context: None,
+            bs: 2,
+            rm: 63,
+            preprocessing_transcript_digest: "85e8357a748cbb90c2903487e9b5bd029b45b78fa5043196988d1f0cb80a3a0b",
+            proof_transcript_digest: "58249a33dcb04fd3e156a9274e46e0dcbabd9e5f97e9b2947431b538b57928c8",
+            pa_decom_digest: "fcca93c851830c4147a211e62d422baaf7d4e32d0051b00159b6a6d0d8dc3d08",
+            pb_decom_digest: "7a58b6bf0a7fa34faabb67613df736eab9ee304f4ea2c0812908bca578a4104a",
+        },
+        KnownAnswer {
+            circuit_file_name: "sub64.txt",
+            security_level: 256,
+            seed: 7,
+            input_value_vec: &[5, 1000],
+            num_pa_input_bits: 64,
+            tau: 8,
+            kappa: 32,
+            vector_commitment_mode: VectorCommitmentMode::Batched,
+            leaf_expansion_version: LeafExpansionVersion::V2,
+            crypto_backend: CryptoBackend::new(HashBackend::Shake256, PrgBackend::Aes256),
+            salted: true,
+            context: Some("known answer session 7"),
+            bs: 2,
+            rm: 63,
+            preprocessing_transcript_digest: "6980dd47b75b3aca01a4a011589a82609ae5812f9dca262d41496dc1019e6135",
+            proof_transcript_digest: "22361502ee484d2ace087e900af12f7d0321f14e3a14fd9ac660f0ab9088efe0",
+            pa_decom_digest: "e8275ec073d06b02c1838c30b8cffeebaa9a865bbe51f67b5affc215360974a9",
+            pb_decom_digest: "a5ff68873f31cbd4f90e4b4544e8c2c60ac5fef5ef1bb1212da6d1cdc3a67e02",
+        },
+    ];
+
+    struct Digests {
+        preprocessing_transcript_digest: String,
+        proof_transcript_digest: String,
+        pa_decom_digest: String,
+        pb_decom_digest: String,
+    }
+
+    impl Digests {
+        fn new<GFVOLE: Zero + Clone + Encode, GFVOLEitH: Zero + Clone + Encode>(
+            preprocessing_transcript: &PreprocessingTranscript<GFVOLE, GFVOLEitH>,
+            proof_transcript: &ProofTranscript<GFVOLE, GFVOLEitH>,
+            pa_decom: &Decom,
+            pb_decom: &Decom,
+        ) -> Self {
+            let digest = |byte_vec: &[u8]| blake3::hash(byte_vec).to_hex().to_string();
+            Self {
+                preprocessing_transcript_digest: digest(&preprocessing_transcript.to_byte_vec()),
+                proof_transcript_digest: digest(&proof_transcript.to_byte_vec()),
+                pa_decom_digest: digest(&encode_to_vec(pa_decom, config::standard()).unwrap()),
+                pb_decom_digest: digest(&encode_to_vec(pb_decom, config::standard()).unwrap()),
+            }
+        }
+
+        fn check(&self, known_answer: &KnownAnswer) {
+            let name = format!("{} at security level {}", known_answer.circuit_file_name, known_answer.security_level);
+            assert_eq!(self.preprocessing_transcript_digest, known_answer.preprocessing_transcript_digest, "preprocessing transcript of {}", name);
+            assert_eq!(self.proof_transcript_digest, known_answer.proof_transcript_digest, "proof transcript of {}", name);
+            assert_eq!(self.pa_decom_digest, known_answer.pa_decom_digest, "pa_decom of {}", name);
+            assert_eq!(self.pb_decom_digest, known_answer.pb_decom_digest, "pb_decom of {}", name);
+        }
+    }
+
+    // the public parameter seed and iv, the master seeds, the dealer and PA's labels are all drawn from
+    // the seeded rng in this order, as the binary does
+    struct FixedSetup {
+        bristol_fashion_adaptor: BristolFashionAdaptor,
+        public_parameter: PublicParameter,
+        pa_input_bit_vec: Vec<u8>,
+        pb_input_bit_vec: Vec<u8>,
+        pa_master_seed: SeedU8x16,
+        pb_master_seed: SeedU8x16,
+        rng: ChaCha20Rng,
+    }
+
+    impl FixedSetup {
+        fn new<GFVOLE: ByteManipulation, GFVOLEitH: ByteManipulation>(known_answer: &KnownAnswer) -> Self {
+            let bristol_fashion_adaptor = BristolFashionAdaptor::new(&known_answer.circuit_file_name.to_string());
+            let input_bit_vec = bristol_fashion_adaptor.determine_input_bit_vec_from_u64_vec(known_answer.input_value_vec).unwrap();
+            let num_input_bits = input_bit_vec.len();
+            let mut rng = ChaCha20Rng::seed_from_u64(known_answer.seed);
+            let public_parameter = PublicParameter::new::<GFVOLE, GFVOLEitH>(
+                &bristol_fashion_adaptor,
+                known_answer.tau,
+                known_answer.kappa,
+                known_answer.vector_commitment_mode,
+                known_answer.leaf_expansion_version,
+                known_answer.crypto_backend,
+                SeedU8x16::insecurely_random(&mut rng),
+                known_answer.salted.then(|| SeedU8x16::insecurely_random(&mut rng)),
+                (0..known_answer.num_pa_input_bits).collect(),
+                (known_answer.num_pa_input_bits..num_input_bits).collect(),
+                known_answer.bs,
+                known_answer.rm,
+                known_answer.context.map(String::from),
+            );
+            let pa_master_seed = SeedU8x16::insecurely_random(&mut rng);
+            let pb_master_seed = SeedU8x16::insecurely_random(&mut rng);
+            Self {
+                bristol_fashion_adaptor,
+                public_parameter,
+                pa_input_bit_vec: input_bit_vec[..known_answer.num_pa_input_bits].to_vec(),
+                pb_input_bit_vec: input_bit_vec[known_answer.num_pa_input_bits..].to_vec(),
+                pa_master_seed,
+                pb_master_seed,
+                rng,
+            }
+        }
+    }
+
+    fn prove_in_one_process<GFVOLE, GFVOLEitH>(known_answer: &KnownAnswer) -> Digests
+    where
+        GFVOLE: Clone + CustomAddition + CustomMultiplyingBit + InsecureRandom + Zero + Copy + PartialEq + Debug + ByteManipulation + Sync + Send + Encode + Decode<()>,
+        GFVOLEitH: Clone + Zero + CustomAddition + LeafIndexForGF + Copy + CustomMultiplyingBit + ByteManipulation + PartialEq + Debug + Sync + Send + Encode + Decode<()>
+    {
+        let FixedSetup {
+            bristol_fashion_adaptor, public_parameter, pa_input_bit_vec, pb_input_bit_vec, pa_master_seed, pb_master_seed, mut rng
+        } = FixedSetup::new::<GFVOLE, GFVOLEitH>(known_answer);
+        let mut pa_secret_state = ProverSecretState::<GFVOLE, GFVOLEitH>::new(&public_parameter, pa_master_seed, true);
+        let mut pb_secret_state = ProverSecretState::<GFVOLE, GFVOLEitH>::new(&public_parameter, pb_master_seed, false);
+        let bit_trace_vec_for_labels_in_garbling = determine_bit_trace_for_labels_in_garbling(
+            &bristol_fashion_adaptor, &public_parameter
+        );
+        let (mut pa_functionality_pre, mut pb_functionality_pre) = InsecureFunctionalityPre::new_pair(ChaCha20Rng::from_rng(&mut rng));
+        let preprocessing_transcript = ProverInPA2PC::preprocess(
+            false,
+            &mut pa_functionality_pre,
+            &mut pb_functionality_pre,
+            &mut rng,
+            &bristol_fashion_adaptor,
+            &bit_trace_vec_for_labels_in_garbling,
+            &public_parameter,
+            &mut pa_secret_state,
+            &mut pb_secret_state,
+        );
+        let (proof_transcript, pa_decom, pb_decom) = ProverInPA2PC::prove(
+            false,
+            &bristol_fashion_adaptor,
+            &public_parameter,
+            &preprocessing_transcript,
+            &mut pa_secret_state,
+            &mut pb_secret_state,
+            &pa_input_bit_vec,
+            &pb_input_bit_vec,
+        );
+
+        // the pinned proof must of course still be accepted and open to the right output
+        let verified_output = VerifierInPA2PC::verify::<GFVOLE, GFVOLEitH>(
+            false,
+            &bristol_fashion_adaptor,
+            &public_parameter,
+            &preprocessing_transcript,
+            &proof_transcript,
+            &pa_decom,
+            &pb_decom,
+        ).unwrap();
+        let full_input_bit_vec = [pa_input_bit_vec, pb_input_bit_vec].concat();
+        assert_eq!(
+            verified_output.output_bit_vec,
+            BitVec::from_vec(bristol_fashion_adaptor.compute_output_bits(&full_input_bit_vec))
+        );
+
+        Digests::new(&preprocessing_transcript, &proof_transcript, &pa_decom, &pb_decom)
+    }
+
+    fn prove_with_two_parties<GFVOLE, GFVOLEitH>(known_answer: &KnownAnswer) -> Digests
+    where
+        GFVOLE: Clone + CustomAddition + CustomMultiplyingBit + InsecureRandom + Zero + Copy + PartialEq + Debug + ByteManipulation + Sync + Send + Encode + Decode<()>,
+        GFVOLEitH: Clone + Zero + CustomAddition + LeafIndexForGF + Copy + CustomMultiplyingBit + ByteManipulation + PartialEq + Debug + Sync + Send + Encode + Decode<()>
+    {
+        let FixedSetup {
+            bristol_fashion_adaptor, public_parameter, pa_input_bit_vec, pb_input_bit_vec, pa_master_seed, pb_master_seed, mut rng
+        } = FixedSetup::new::<GFVOLE, GFVOLEitH>(known_answer);
+        let (pa_channel, pb_channel) = InMemoryChannel::new_pair();
+        let (mut pa_functionality_pre, mut pb_functionality_pre) = InsecureFunctionalityPre::new_pair(ChaCha20Rng::from_rng(&mut rng));
+        let (preprocessing_transcript, (proof_transcript, pa_decom, pb_decom)) = thread::scope(|scope| {
+            scope.spawn(|| {
+                let mut party_b = PartyB::<_, GFVOLE, GFVOLEitH>::new(
+                    &bristol_fashion_adaptor, &public_parameter, pb_master_seed, pb_channel
+                );
+                let preprocessing_transcript = party_b.preprocess(false, &mut pb_functionality_pre).unwrap();
+                party_b.prove(false, &preprocessing_transcript, &pb_input_bit_vec).unwrap();
+            });
+            let mut party_a = PartyA::<_, GFVOLE, GFVOLEitH>::new(
+                &bristol_fashion_adaptor, &public_parameter, pa_master_seed, pa_channel
+            );
+            let preprocessing_transcript = party_a.preprocess(false, &mut pa_functionality_pre, &mut rng).unwrap();
+            let proof = party_a.prove(false, &preprocessing_transcript, &pa_input_bit_vec).unwrap();
+            (preprocessing_transcript, proof)
+        });
+        Digests::new(&preprocessing_transcript, &proof_transcript, &pa_decom, &pb_decom)
+    }
+
+    fn prove_for_known_answer(known_answer: &KnownAnswer) -> Digests {
+        match known_answer.security_level {
+            128 => prove_in_one_process::<GF2p128, GF2p8>(known_answer),
+            192 => prove_in_one_process::<GF2p192, GF2p8>(known_answer),
+            256 => prove_in_one_process::<GF2p256, GF2p8>(known_answer),
+            security_level => panic!("unsupported security level {}", security_level),
+        }
+    }
+
+    #[test]
+    fn test_known_answers() {
+        for known_answer in KNOWN_ANSWER_VEC.iter() {
+            prove_for_known_answer(known_answer).check(known_answer);
+        }
+    }
+
+    // the two-party run draws the same randomness in the same order, so it must match the same digests
+    #[test]
+    fn test_known_answers_with_two_parties() {
+        prove_with_two_parties::<GF2p128, GF2p8>(&KNOWN_ANSWER_VEC[0]).check(&KNOWN_ANSWER_VEC[0]);
+        prove_with_two_parties::<GF2p256, GF2p8>(&KNOWN_ANSWER_VEC[1]).check(&KNOWN_ANSWER_VEC[1]);
+        prove_with_two_parties::<GF2p256, GF2p8>(&KNOWN_ANSWER_VEC[6]).check(&KNOWN_ANSWER_VEC[6]);
+    }
+}
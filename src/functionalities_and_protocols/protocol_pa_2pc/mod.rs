@@ -1,16 +1,22 @@
-use std::ops::{Index, IndexMut};
+use std::ops::Index;
 use crate::bristol_fashion_adaptor::bristol_fashion_adaptor::BristolFashionAdaptor;
 use crate::bristol_fashion_adaptor::GateType;
 use crate::functionalities_and_protocols::states_and_parameters::public_parameter::PublicParameter;
-use crate::value_type::Zero;
+use crate::value_type::{ByteManipulation, Zero};
 use crate::vec_type::{BasicVecFunctions, Split, ZeroVec};
 use crate::vec_type::bit_vec::BitVec;
 
 mod test;
+mod known_answer_test;
 pub mod prover_in_pa_2pc;
 pub mod verifier_in_pa_2pc;
-pub(crate) mod preprocessing_transcript;
-pub(crate) mod proof_transcript;
+pub mod preprocessing_transcript;
+pub mod proof_transcript;
+pub mod proof_bundle;
+pub mod party_message;
+pub mod party_error;
+pub mod party_a;
+pub mod party_b;
 
 fn permute<PrimitiveType, VecType>(
     public_parameter: &PublicParameter,
@@ -18,7 +24,7 @@ fn permute<PrimitiveType, VecType>(
 )
 where PrimitiveType: Clone,
       VecType: ZeroVec + BasicVecFunctions<PrimitiveType> + Clone
-      + Index<usize, Output = PrimitiveType> + IndexMut<usize, Output = PrimitiveType> {
+      + Index<usize, Output = PrimitiveType> {
     assert_eq!(permutation_rep.len(), public_parameter.kappa);
     assert_eq!(to_be_permuted_vec_rep.len(), public_parameter.kappa);
     let mut res = vec![VecType::zero_vec(public_parameter.big_l); public_parameter.kappa];
@@ -26,7 +32,7 @@ where PrimitiveType: Clone,
         assert_eq!(permutation_rep[repetition_id].len(), public_parameter.big_l);
         assert_eq!(to_be_permuted_vec_rep[repetition_id].len(), public_parameter.big_l);
         (0..public_parameter.big_l).for_each(
-            |j| res[repetition_id][permutation_rep[repetition_id][j]] = to_be_permuted_vec_rep[repetition_id][j].clone()
+            |j| res[repetition_id].set(permutation_rep[repetition_id][j], to_be_permuted_vec_rep[repetition_id][j].clone())
         )
     });
     *to_be_permuted_vec_rep = res;
@@ -53,34 +59,30 @@ fn initialize_trace<PrimitiveType, VecType>(
 )
 where PrimitiveType: Clone + Zero + Copy,
       VecType: Clone + ZeroVec + BasicVecFunctions<PrimitiveType>
-      + Index<usize, Output = PrimitiveType> + IndexMut<usize, Output = PrimitiveType> {
+      + Index<usize, Output = PrimitiveType> {
 
     // let mut res = vec![PrimitiveType::zero(); circuit_num_wires];
 
-    to_be_written_trace.as_mut_slice()[0..input_vec.len()].copy_from_slice(input_vec.as_slice());
+    for input_wire in 0..input_vec.len() {
+        to_be_written_trace.set(input_wire, input_vec[input_wire]);
+    }
     let mut and_cursor = 0usize;
     for wire in public_parameter.big_iw.iter() {
-        to_be_written_trace[*wire] = output_and_vec[and_cursor].clone();
+        to_be_written_trace.set(*wire, output_and_vec[and_cursor].clone());
         and_cursor += 1;
     }
 }
 
-fn extract_block_vec_rep<PrimitiveType, VecType>(
+fn extract_block_vec_rep<VecType>(
     public_parameter: &PublicParameter,
     block_id: usize, vec_rep: &Vec<VecType>
 ) -> Vec<VecType>
-where
-    PrimitiveType: Clone,
-    VecType: BasicVecFunctions<PrimitiveType> + Clone
-    + Index<usize, Output = PrimitiveType> + IndexMut<usize, Output = PrimitiveType>
-    + FromIterator<PrimitiveType> {
+where VecType: Split {
     assert_eq!(vec_rep.len(), public_parameter.kappa);
     (0..public_parameter.kappa).map(
         |repetition_id|
-            VecType::from_vec(
-                vec_rep[repetition_id].as_slice()[
-                    public_parameter.big_iw_size * block_id..public_parameter.big_iw_size * (block_id + 1)
-                    ].to_vec()
+            vec_rep[repetition_id].sub_vec(
+                public_parameter.big_iw_size * block_id..public_parameter.big_iw_size * (block_id + 1)
             )
     ).collect::<Vec<VecType>>()
 }
@@ -93,13 +95,25 @@ pub fn determine_bit_trace_for_labels_in_garbling(
     for gate in bristol_fashion_adaptor.get_gate_vec() {
         match gate.gate_type {
             GateType::XOR => {
-                bit_trace_vec[gate.output_wire] = bit_trace_vec[gate.left_input_wire] ^ bit_trace_vec[gate.right_input_wire];
+                bit_trace_vec.set(gate.output_wire, bit_trace_vec[gate.left_input_wire] ^ bit_trace_vec[gate.right_input_wire]);
             }
             GateType::AND => {}
             GateType::NOT => {
-                bit_trace_vec[gate.output_wire] = 1u8 ^ bit_trace_vec[gate.left_input_wire];
+                bit_trace_vec.set(gate.output_wire, 1u8 ^ bit_trace_vec[gate.left_input_wire]);
             }
         }
     }
     bit_trace_vec
-}
\ No newline at end of file
+}
+
+// PB's intermediate message of an AND gate and row k, which PB commits to in the preprocessing and opens for the
+// row that the evaluation ends up at: the bit, followed by its VOLEitH MAC of every repetition
+fn make_pb_middle_message<'a, GFVOLEitH: ByteManipulation + 'a>(
+    middle_bit: u8, middle_voleith_mac_iter: impl Iterator<Item = &'a GFVOLEitH>
+) -> Vec<u8> {
+    let mut message = vec![middle_bit];
+    for middle_voleith_mac in middle_voleith_mac_iter {
+        message.append(&mut middle_voleith_mac.to_bytes());
+    }
+    message
+}
@@ -0,0 +1,196 @@
+use std::fmt::Debug;
+use bincode::{Decode, Encode};
+use blake3::Hash;
+use rand::{CryptoRng, RngCore};
+use crate::functionalities_and_protocols::all_in_one_vc::Decom;
+use crate::bristol_fashion_adaptor::bristol_fashion_adaptor::BristolFashionAdaptor;
+use crate::functionalities_and_protocols::channel::{Channel, ChannelStatistics};
+use crate::functionalities_and_protocols::functionality_pre::PreprocessingFunctionality;
+use crate::functionalities_and_protocols::hasher::{hash_to_determine_nabla_rep, hash_to_determine_permutation_rep};
+use crate::functionalities_and_protocols::protocol_pa_2pc::determine_bit_trace_for_labels_in_garbling;
+use crate::functionalities_and_protocols::protocol_pa_2pc::party_error::PartyError;
+use crate::functionalities_and_protocols::protocol_pa_2pc::party_message::{check_check_and_cross_share_vec_lengths, check_check_and_masked_share_vec_lengths, check_decom_lengths, check_masked_input_bit_vec, CheckAndCrossShare, CheckAndMaskedShare, EvaluationMessage, PAPreprocessingMessage, PartyProof, PBPreprocessingMessage, PublishedRmShare, PublishedWireShare};
+use crate::functionalities_and_protocols::protocol_pa_2pc::preprocessing_transcript::PreprocessingTranscript;
+use crate::functionalities_and_protocols::protocol_pa_2pc::prover_in_pa_2pc::ProverInPA2PC;
+use crate::functionalities_and_protocols::protocol_svole_2pc::prover_in_protocol_svole_2pc::ProverInProtocolSVOLE2PC;
+use crate::functionalities_and_protocols::states_and_parameters::prover_secret_state::ProverSecretState;
+use crate::functionalities_and_protocols::states_and_parameters::public_parameter::PublicParameter;
+use crate::value_type::{ByteManipulation, CustomAddition, CustomMultiplyingBit, InsecureRandom, LeafIndexForGF, Zero};
+use crate::value_type::seed_u8x16::SeedU8x16;
+use crate::vec_type::bit_vec::BitVec;
+
+// PA of PiPA2PC, holding only her own secret state and talking to PB over a channel.
+// PA obtains her share of FPre from her own endpoint of the PreprocessingFunctionality, PB from his
+pub struct PartyA<'a, C, GFVOLE, GFVOLEitH> {
+    bristol_fashion_adaptor: &'a BristolFashionAdaptor,
+    public_parameter: &'a PublicParameter,
+    bit_trace_vec_for_labels_in_garbling: BitVec,
+    secret_state: ProverSecretState<GFVOLE, GFVOLEitH>,
+    channel: C,
+}
+
+impl<'a, C, GFVOLE, GFVOLEitH> PartyA<'a, C, GFVOLE, GFVOLEitH>
+where
+    C: Channel,
+    GFVOLE: Clone + CustomAddition + CustomMultiplyingBit + InsecureRandom + Zero + Copy + PartialEq + Debug + ByteManipulation + Sync + Send + Encode + Decode<()>,
+    GFVOLEitH: Clone + Zero + CustomAddition + LeafIndexForGF + Copy + CustomMultiplyingBit + ByteManipulation + Debug + Sync + Send + Encode + Decode<()>
+{
+    pub fn new(
+        bristol_fashion_adaptor: &'a BristolFashionAdaptor,
+        public_parameter: &'a PublicParameter,
+        master_seed: SeedU8x16,
+        channel: C,
+    ) -> Self {
+        Self {
+            bristol_fashion_adaptor,
+            public_parameter,
+            bit_trace_vec_for_labels_in_garbling: determine_bit_trace_for_labels_in_garbling(bristol_fashion_adaptor, public_parameter),
+            secret_state: ProverSecretState::new(public_parameter, master_seed, true),
+            channel,
+        }
+    }
+
+    pub fn channel_statistics(&self) -> ChannelStatistics {
+        self.channel.statistics()
+    }
+
+    // rng samples PA's labels, seeding it (and FPre) makes PA's messages reproducible
+    pub fn preprocess<FPre: PreprocessingFunctionality<GFVOLE>, R: RngCore + CryptoRng + ?Sized>(
+        &mut self,
+        process_printing: bool,
+        functionality_pre: &mut FPre,
+        rng: &mut R,
+    ) -> Result<PreprocessingTranscript<GFVOLE, GFVOLEitH>, PartyError> {
+        if process_printing {
+            println!("+ PA preprocessing...");
+        }
+        let pa_share = ProverInPA2PC::deal::<GFVOLE, FPre>(
+            process_printing, functionality_pre, self.bristol_fashion_adaptor, self.public_parameter, true
+        );
+        pa_share.install(&mut self.secret_state);
+        ProverInPA2PC::initialize_labels(process_printing, self.public_parameter, &mut self.secret_state, rng);
+
+        let (pa_com_hash_rep, pa_masked_bit_tuple_rep) = ProverInPA2PC::preprocess_locally(
+            process_printing, self.bristol_fashion_adaptor, self.public_parameter, &mut self.secret_state, true
+        );
+        let garbled_table = ProverInPA2PC::garble(
+            process_printing, self.bristol_fashion_adaptor, &self.bit_trace_vec_for_labels_in_garbling,
+            self.public_parameter, &self.secret_state
+        );
+        let pa_message = PAPreprocessingMessage {
+            com_hash_rep: pa_com_hash_rep.iter().map(|digest| *digest.as_bytes()).collect(),
+            masked_bit_tuple_rep: pa_masked_bit_tuple_rep,
+            garbled_table,
+        };
+        self.channel.send(&pa_message)?;
+
+        let pb_message = self.channel.recv::<PBPreprocessingMessage>()?;
+        pb_message.check_lengths(self.public_parameter)?;
+
+        Ok(PreprocessingTranscript::new(
+            pa_com_hash_rep,
+            pa_message.masked_bit_tuple_rep,
+            pb_message.com_hash_rep.into_iter().map(Hash::from_bytes).collect(),
+            pb_message.masked_bit_tuple_rep,
+            pa_message.garbled_table,
+            pb_message.commitment_vec.into_iter().map(|coms| coms.map(Hash::from_bytes)).collect(),
+        ))
+    }
+
+    // PA always sends first when both parties publish at the same time, PB answers
+    pub fn prove(
+        &mut self,
+        process_printing: bool,
+        preprocessing_transcript: &PreprocessingTranscript<GFVOLE, GFVOLEitH>,
+        pa_input_bits: &[u8],
+    ) -> Result<PartyProof<GFVOLE, GFVOLEitH>, PartyError> {
+        let public_parameter = self.public_parameter;
+        if process_printing {
+            println!("+ PA proving...");
+        }
+        let (permutation_rep, mut fiat_shamir_transcript) = hash_to_determine_permutation_rep(
+            public_parameter, preprocessing_transcript
+        );
+
+        if process_printing {
+            println!("  PA permutes and exchanges published components with PB");
+        }
+        let pa_rm_share = ProverInPA2PC::permute_and_publish_rm(public_parameter, &permutation_rep, &mut self.secret_state);
+        self.channel.send(&pa_rm_share)?;
+        let pb_rm_share = self.channel.recv::<PublishedRmShare<GFVOLEitH>>()?;
+        pb_rm_share.check_lengths(public_parameter)?;
+
+        if process_printing {
+            println!("  PA runs PiCheckAND with PB");
+        }
+        let pa_masked_share_vec = ProverInPA2PC::compute_check_and_masked_share_vec(public_parameter, &self.secret_state);
+        self.channel.send(&pa_masked_share_vec)?;
+        let pb_masked_share_vec = self.channel.recv::<Vec<CheckAndMaskedShare<GFVOLEitH>>>()?;
+        check_check_and_masked_share_vec_lengths(public_parameter, &pb_masked_share_vec)?;
+        let pa_cross_share_vec = ProverInPA2PC::compute_check_and_cross_share_vec(
+            public_parameter, &self.secret_state, &pa_masked_share_vec, &pb_masked_share_vec
+        );
+        self.channel.send(&pa_cross_share_vec)?;
+        let pb_cross_share_vec = self.channel.recv::<Vec<CheckAndCrossShare<GFVOLEitH>>>()?;
+        check_check_and_cross_share_vec_lengths(public_parameter, &pb_cross_share_vec)?;
+        let check_and_transcript_vec = ProverInPA2PC::assemble_check_and_transcript_vec(
+            pa_masked_share_vec, pa_cross_share_vec, pb_masked_share_vec, pb_cross_share_vec
+        );
+
+        if process_printing {
+            println!("  PA processes inputs");
+        }
+        let pa_input_share = ProverInPA2PC::publish_wire_share(public_parameter, &self.secret_state, &public_parameter.big_ib);
+        self.channel.send(&pa_input_share)?;
+        let pb_hat_z_input_bit_vec_with_ib = self.channel.recv::<Vec<u8>>()?;
+        check_masked_input_bit_vec("hat_z_input_bit_vec_with_ib", public_parameter.big_ib.len(), &pb_hat_z_input_bit_vec_with_ib)?;
+        let pb_input_share = self.channel.recv::<PublishedWireShare<GFVOLE, GFVOLEitH>>()?;
+        pb_input_share.check_lengths(public_parameter, public_parameter.big_ia.len())?;
+
+        if process_printing {
+            println!("  PA checks what PB just published and publishes labels");
+        }
+        let pa_hat_z_input_bit_vec_with_ia = ProverInPA2PC::check_input_share_and_mask_inputs(
+            &self.secret_state, true, &public_parameter.big_ia, &pb_input_share, pa_input_bits
+        )?;
+        let pa_input_label_message = ProverInPA2PC::publish_input_labels(
+            public_parameter, &self.secret_state, pa_hat_z_input_bit_vec_with_ia, &pb_hat_z_input_bit_vec_with_ib
+        );
+        self.channel.send(&pa_input_label_message)?;
+
+        if process_printing {
+            println!("  PA determines her outputs");
+        }
+        let pa_output_share = ProverInPA2PC::publish_wire_share(public_parameter, &self.secret_state, &public_parameter.big_io);
+        self.channel.send(&pa_output_share)?;
+
+        if process_printing {
+            println!("  PA waits for PB's evaluation");
+        }
+        let evaluation_message = self.channel.recv::<EvaluationMessage<GFVOLE, GFVOLEitH>>()?;
+        evaluation_message.check_lengths(public_parameter)?;
+
+        let proof_transcript = ProverInPA2PC::assemble_proof_transcript(
+            public_parameter,
+            pa_rm_share, pb_rm_share,
+            check_and_transcript_vec,
+            pa_input_share, pb_input_share,
+            &pa_input_label_message.hat_z_input_bit_vec_with_ia, &pb_hat_z_input_bit_vec_with_ib,
+            pa_output_share,
+            evaluation_message,
+        );
+
+        if process_printing {
+            println!("  PA computes decom after knowing nabla_b_rep");
+        }
+        let (_, nabla_b_rep) = hash_to_determine_nabla_rep(
+            public_parameter, &mut fiat_shamir_transcript, &proof_transcript
+        );
+        let pa_decom = ProverInProtocolSVOLE2PC::open(public_parameter, &mut self.secret_state, &nabla_b_rep);
+        self.channel.send(&pa_decom)?;
+        let pb_decom = self.channel.recv::<Decom>()?;
+        check_decom_lengths(public_parameter, &pb_decom)?;
+
+        Ok((proof_transcript, pa_decom, pb_decom))
+    }
+}
@@ -0,0 +1,175 @@
+use std::fmt::Debug;
+use bincode::{Decode, Encode};
+use blake3::Hash;
+use crate::functionalities_and_protocols::all_in_one_vc::Decom;
+use crate::bristol_fashion_adaptor::bristol_fashion_adaptor::BristolFashionAdaptor;
+use crate::functionalities_and_protocols::channel::{Channel, ChannelStatistics};
+use crate::functionalities_and_protocols::functionality_pre::PreprocessingFunctionality;
+use crate::functionalities_and_protocols::hasher::{hash_to_determine_nabla_rep, hash_to_determine_permutation_rep};
+use crate::functionalities_and_protocols::protocol_pa_2pc::party_error::PartyError;
+use crate::functionalities_and_protocols::protocol_pa_2pc::party_message::{check_check_and_cross_share_vec_lengths, check_check_and_masked_share_vec_lengths, check_decom_lengths, CheckAndCrossShare, CheckAndMaskedShare, PAInputLabelMessage, PAPreprocessingMessage, PartyProof, PBPreprocessingMessage, PublishedRmShare, PublishedWireShare};
+use crate::functionalities_and_protocols::protocol_pa_2pc::preprocessing_transcript::PreprocessingTranscript;
+use crate::functionalities_and_protocols::protocol_pa_2pc::prover_in_pa_2pc::ProverInPA2PC;
+use crate::functionalities_and_protocols::protocol_svole_2pc::prover_in_protocol_svole_2pc::ProverInProtocolSVOLE2PC;
+use crate::functionalities_and_protocols::states_and_parameters::prover_secret_state::ProverSecretState;
+use crate::functionalities_and_protocols::states_and_parameters::public_parameter::PublicParameter;
+use crate::value_type::{ByteManipulation, CustomAddition, CustomMultiplyingBit, InsecureRandom, LeafIndexForGF, Zero};
+use crate::value_type::seed_u8x16::SeedU8x16;
+
+// PB of PiPA2PC, holding only his own secret state and talking to PA over a channel.
+// PB obtains his share of FPre from his own endpoint of the PreprocessingFunctionality, see PartyA
+pub struct PartyB<'a, C, GFVOLE, GFVOLEitH> {
+    bristol_fashion_adaptor: &'a BristolFashionAdaptor,
+    public_parameter: &'a PublicParameter,
+    secret_state: ProverSecretState<GFVOLE, GFVOLEitH>,
+    channel: C,
+}
+
+impl<'a, C, GFVOLE, GFVOLEitH> PartyB<'a, C, GFVOLE, GFVOLEitH>
+where
+    C: Channel,
+    GFVOLE: Clone + CustomAddition + CustomMultiplyingBit + InsecureRandom + Zero + Copy + PartialEq + Debug + ByteManipulation + Sync + Send + Encode + Decode<()>,
+    GFVOLEitH: Clone + Zero + CustomAddition + LeafIndexForGF + Copy + CustomMultiplyingBit + ByteManipulation + Debug + Sync + Send + Encode + Decode<()>
+{
+    pub fn new(
+        bristol_fashion_adaptor: &'a BristolFashionAdaptor,
+        public_parameter: &'a PublicParameter,
+        master_seed: SeedU8x16,
+        channel: C,
+    ) -> Self {
+        Self {
+            bristol_fashion_adaptor,
+            public_parameter,
+            secret_state: ProverSecretState::new(public_parameter, master_seed, false),
+            channel,
+        }
+    }
+
+    pub fn channel_statistics(&self) -> ChannelStatistics {
+        self.channel.statistics()
+    }
+
+    pub fn preprocess<FPre: PreprocessingFunctionality<GFVOLE>>(
+        &mut self,
+        process_printing: bool,
+        functionality_pre: &mut FPre,
+    ) -> Result<PreprocessingTranscript<GFVOLE, GFVOLEitH>, PartyError> {
+        if process_printing {
+            println!("+ PB preprocessing...");
+        }
+        let pb_share = ProverInPA2PC::deal::<GFVOLE, FPre>(
+            process_printing, functionality_pre, self.bristol_fashion_adaptor, self.public_parameter, false
+        );
+        pb_share.install(&mut self.secret_state);
+
+        let (pb_com_hash_rep, pb_masked_bit_tuple_rep) = ProverInPA2PC::preprocess_locally(
+            process_printing, self.bristol_fashion_adaptor, self.public_parameter, &mut self.secret_state, false
+        );
+
+        let pa_message = self.channel.recv::<PAPreprocessingMessage<GFVOLE, GFVOLEitH>>()?;
+        pa_message.check_lengths(self.public_parameter)?;
+
+        let pb_middle_commitment_vec = ProverInPA2PC::commit_middle(process_printing, self.public_parameter, &mut self.secret_state);
+        let pb_message = PBPreprocessingMessage {
+            com_hash_rep: pb_com_hash_rep.iter().map(|digest| *digest.as_bytes()).collect(),
+            masked_bit_tuple_rep: pb_masked_bit_tuple_rep,
+            commitment_vec: pb_middle_commitment_vec.iter().map(|coms| coms.map(|digest| *digest.as_bytes())).collect(),
+        };
+        self.channel.send(&pb_message)?;
+
+        Ok(PreprocessingTranscript::new(
+            pa_message.com_hash_rep.into_iter().map(Hash::from_bytes).collect(),
+            pa_message.masked_bit_tuple_rep,
+            pb_com_hash_rep,
+            pb_message.masked_bit_tuple_rep,
+            pa_message.garbled_table,
+            pb_middle_commitment_vec,
+        ))
+    }
+
+    pub fn prove(
+        &mut self,
+        process_printing: bool,
+        preprocessing_transcript: &PreprocessingTranscript<GFVOLE, GFVOLEitH>,
+        pb_input_bits: &[u8],
+    ) -> Result<PartyProof<GFVOLE, GFVOLEitH>, PartyError> {
+        let public_parameter = self.public_parameter;
+        if process_printing {
+            println!("+ PB proving...");
+        }
+        let (permutation_rep, mut fiat_shamir_transcript) = hash_to_determine_permutation_rep(
+            public_parameter, preprocessing_transcript
+        );
+
+        if process_printing {
+            println!("  PB permutes and exchanges published components with PA");
+        }
+        let pb_rm_share = ProverInPA2PC::permute_and_publish_rm(public_parameter, &permutation_rep, &mut self.secret_state);
+        let pa_rm_share = self.channel.recv::<PublishedRmShare<GFVOLEitH>>()?;
+        pa_rm_share.check_lengths(public_parameter)?;
+        self.channel.send(&pb_rm_share)?;
+
+        if process_printing {
+            println!("  PB runs PiCheckAND with PA");
+        }
+        let pb_masked_share_vec = ProverInPA2PC::compute_check_and_masked_share_vec(public_parameter, &self.secret_state);
+        let pa_masked_share_vec = self.channel.recv::<Vec<CheckAndMaskedShare<GFVOLEitH>>>()?;
+        check_check_and_masked_share_vec_lengths(public_parameter, &pa_masked_share_vec)?;
+        self.channel.send(&pb_masked_share_vec)?;
+        let pb_cross_share_vec = ProverInPA2PC::compute_check_and_cross_share_vec(
+            public_parameter, &self.secret_state, &pa_masked_share_vec, &pb_masked_share_vec
+        );
+        let pa_cross_share_vec = self.channel.recv::<Vec<CheckAndCrossShare<GFVOLEitH>>>()?;
+        check_check_and_cross_share_vec_lengths(public_parameter, &pa_cross_share_vec)?;
+        self.channel.send(&pb_cross_share_vec)?;
+        let check_and_transcript_vec = ProverInPA2PC::assemble_check_and_transcript_vec(
+            pa_masked_share_vec, pa_cross_share_vec, pb_masked_share_vec, pb_cross_share_vec
+        );
+
+        if process_printing {
+            println!("  PB checks what PA just published and processes inputs");
+        }
+        let pa_input_share = self.channel.recv::<PublishedWireShare<GFVOLE, GFVOLEitH>>()?;
+        pa_input_share.check_lengths(public_parameter, public_parameter.big_ib.len())?;
+        let pb_hat_z_input_bit_vec_with_ib = ProverInPA2PC::check_input_share_and_mask_inputs(
+            &self.secret_state, false, &public_parameter.big_ib, &pa_input_share, pb_input_bits
+        )?;
+        self.channel.send(&pb_hat_z_input_bit_vec_with_ib)?;
+        let pb_input_share = ProverInPA2PC::publish_wire_share(public_parameter, &self.secret_state, &public_parameter.big_ia);
+        self.channel.send(&pb_input_share)?;
+
+        let pa_input_label_message = self.channel.recv::<PAInputLabelMessage<GFVOLE>>()?;
+        pa_input_label_message.check_lengths(public_parameter)?;
+        let pa_output_share = self.channel.recv::<PublishedWireShare<GFVOLE, GFVOLEitH>>()?;
+        pa_output_share.check_lengths(public_parameter, public_parameter.big_io_size)?;
+
+        let evaluation_message = ProverInPA2PC::evaluate(
+            process_printing, self.bristol_fashion_adaptor, public_parameter, &preprocessing_transcript.garbled_table,
+            &self.secret_state, &pb_hat_z_input_bit_vec_with_ib, &pa_input_label_message, &pa_output_share
+        )?;
+        self.channel.send(&evaluation_message)?;
+
+        let proof_transcript = ProverInPA2PC::assemble_proof_transcript(
+            public_parameter,
+            pa_rm_share, pb_rm_share,
+            check_and_transcript_vec,
+            pa_input_share, pb_input_share,
+            &pa_input_label_message.hat_z_input_bit_vec_with_ia, &pb_hat_z_input_bit_vec_with_ib,
+            pa_output_share,
+            evaluation_message,
+        );
+
+        if process_printing {
+            println!("  PB computes decom after knowing nabla_a_rep");
+        }
+        let (nabla_a_rep, _) = hash_to_determine_nabla_rep(
+            public_parameter, &mut fiat_shamir_transcript, &proof_transcript
+        );
+        let pb_decom = ProverInProtocolSVOLE2PC::open(public_parameter, &mut self.secret_state, &nabla_a_rep);
+        let pa_decom = self.channel.recv::<Decom>()?;
+        check_decom_lengths(public_parameter, &pa_decom)?;
+        self.channel.send(&pb_decom)?;
+
+        Ok((proof_transcript, pa_decom, pb_decom))
+    }
+}
@@ -0,0 +1,44 @@
+use std::fmt::{Display, Formatter};
+use crate::functionalities_and_protocols::channel::ChannelError;
+
+// Why a party aborts PiPA2PC, the checks are those each party runs on what the other one sent
+#[derive(PartialEq, Debug, Clone)]
+pub enum PartyError {
+    Channel(ChannelError),
+    // a VOLE MAC published by PA (is_pa) or PB at an input wire does not match the key of the other party
+    InputMac { is_pa: bool, input_wire: usize },
+    // a decrypted garbled row carries a VOLE MAC that does not match PB's key
+    GarbledRowMac { and_cursor: usize },
+    // a VOLE MAC of PA at an output wire does not match PB's key
+    OutputMac { output_wire: usize },
+    // a message does not have the length the public parameter requires
+    Length { field: &'static str, expected: usize, actual: usize },
+    // a message carries a byte other than 0 or 1 where a bit is expected
+    NotABit { field: &'static str, index: usize },
+    // a decommitment is not in the vector commitment mode of the public parameter
+    DecomMode,
+}
+
+impl Display for PartyError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PartyError::Channel(error) => write!(f, "{}", error),
+            PartyError::InputMac { is_pa, input_wire } => write!(
+                f, "{}'s VOLE MAC at input wire {} is invalid", if *is_pa { "PA" } else { "PB" }, input_wire
+            ),
+            PartyError::GarbledRowMac { and_cursor } => write!(f, "garbled row of AND gate {} has an invalid VOLE MAC", and_cursor),
+            PartyError::OutputMac { output_wire } => write!(f, "PA's VOLE MAC at output wire {} is invalid", output_wire),
+            PartyError::Length { field, expected, actual } => write!(f, "{} has length {}, expected {}", field, actual, expected),
+            PartyError::NotABit { field, index } => write!(f, "{} has a non-bit entry at {}", field, index),
+            PartyError::DecomMode => write!(f, "decommitment is not in the vector commitment mode of the public parameter"),
+        }
+    }
+}
+
+impl std::error::Error for PartyError {}
+
+impl From<ChannelError> for PartyError {
+    fn from(error: ChannelError) -> Self {
+        PartyError::Channel(error)
+    }
+}
@@ -0,0 +1,279 @@
+use bincode::{Decode, Encode};
+use crate::comm_types_and_constants::BLAKE3_HASH_DIGEST_NUM_BYTES;
+use crate::functionalities_and_protocols::all_in_one_vc::{Decom, VectorCommitmentMode};
+use crate::functionalities_and_protocols::protocol_pa_2pc::party_error::PartyError;
+use crate::functionalities_and_protocols::protocol_pa_2pc::proof_transcript::ProofTranscript;
+use crate::functionalities_and_protocols::states_and_parameters::prover_secret_state::ProverSecretState;
+use crate::functionalities_and_protocols::states_and_parameters::public_parameter::PublicParameter;
+use crate::value_type::garbled_row::GarbledRow;
+use crate::value_type::seed_u8x16::SeedU8x16;
+use crate::value_type::Zero;
+use crate::vec_type::{BasicVecFunctions, ZeroVec};
+use crate::vec_type::bit_vec::BitVec;
+use crate::vec_type::gf_vec::GFVec;
+
+// The messages PA and PB exchange when they run PiPA2PC as two separate parties.
+// Hashes are carried as their bytes since blake3::Hash does not implement Encode
+
+pub type MaskedBitTuple = (BitVec, BitVec, BitVec, BitVec, BitVec, BitVec);
+
+// d and e of one party in one block of PiCheckAND, each with their VOLEitH MACs
+pub type CheckAndMaskedShare<GFVOLEitH> = ((Vec<BitVec>, Vec<GFVec<GFVOLEitH>>), (Vec<BitVec>, Vec<GFVec<GFVOLEitH>>));
+
+// tilde_z of one party in one block of PiCheckAND with its VOLEitH MACs
+pub type CheckAndCrossShare<GFVOLEitH> = (Vec<BitVec>, Vec<GFVec<GFVOLEitH>>);
+
+fn check_len(field: &'static str, expected: usize, actual: usize) -> Result<(), PartyError> {
+    if expected != actual {
+        return Err(PartyError::Length { field, expected, actual });
+    }
+    Ok(())
+}
+
+// The masked inputs travel as one byte per bit, so that every byte has to be checked to be a bit
+pub fn check_masked_input_bit_vec(field: &'static str, expected: usize, bit_vec: &[u8]) -> Result<(), PartyError> {
+    check_len(field, expected, bit_vec.len())?;
+    match bit_vec.iter().position(|bit| *bit > 1) {
+        Some(index) => Err(PartyError::NotABit { field, index }),
+        None => Ok(()),
+    }
+}
+
+fn check_len_rep<VecType: BasicVecFunctions<PrimitiveType>, PrimitiveType>(
+    field: &'static str, kappa: usize, expected: usize, vec_rep: &[VecType]
+) -> Result<(), PartyError> {
+    check_len(field, kappa, vec_rep.len())?;
+    for vec in vec_rep {
+        check_len(field, expected, vec.len())?;
+    }
+    Ok(())
+}
+
+fn check_check_and_share_lengths<GFVOLEitH>(
+    public_parameter: &PublicParameter,
+    (bit_vec_rep, voleith_mac_vec_rep): &CheckAndCrossShare<GFVOLEitH>,
+) -> Result<(), PartyError> {
+    check_len_rep("check_and_bit_vec_rep", public_parameter.kappa, public_parameter.big_iw_size, bit_vec_rep)?;
+    check_len_rep("check_and_voleith_mac_vec_rep", public_parameter.kappa, public_parameter.big_iw_size, voleith_mac_vec_rep)
+}
+
+// The shares of PiCheckAND a party receives, one per block
+pub fn check_check_and_masked_share_vec_lengths<GFVOLEitH>(
+    public_parameter: &PublicParameter,
+    masked_share_vec: &[CheckAndMaskedShare<GFVOLEitH>],
+) -> Result<(), PartyError> {
+    check_len("check_and_masked_share_vec", public_parameter.bs, masked_share_vec.len())?;
+    for (d_share, e_share) in masked_share_vec {
+        check_check_and_share_lengths(public_parameter, d_share)?;
+        check_check_and_share_lengths(public_parameter, e_share)?;
+    }
+    Ok(())
+}
+
+pub fn check_check_and_cross_share_vec_lengths<GFVOLEitH>(
+    public_parameter: &PublicParameter,
+    cross_share_vec: &[CheckAndCrossShare<GFVOLEitH>],
+) -> Result<(), PartyError> {
+    check_len("check_and_cross_share_vec", public_parameter.bs, cross_share_vec.len())?;
+    for cross_share in cross_share_vec {
+        check_check_and_share_lengths(public_parameter, cross_share)?;
+    }
+    Ok(())
+}
+
+// What each party holds at the end of PiPA2PC: the proof transcript and the decommitments of PA and PB
+pub type PartyProof<GFVOLE, GFVOLEitH> = (ProofTranscript<GFVOLE, GFVOLEitH>, Decom, Decom);
+
+// What FPre hands to one party
+pub struct PreprocessingShare<GFVOLE> {
+    pub delta: GFVOLE,
+    pub r_input_bit_vec: BitVec,
+    pub vole_mac_r_input_vec: GFVec<GFVOLE>,
+    pub other_vole_key_r_input_vec: GFVec<GFVOLE>,
+    pub r_output_and_bit_vec: BitVec,
+    pub vole_mac_r_output_and_vec: GFVec<GFVOLE>,
+    pub other_vole_key_r_output_and_vec: GFVec<GFVOLE>,
+    pub r_prime_bit_vec: BitVec,
+    pub vole_mac_r_prime_vec: GFVec<GFVOLE>,
+    pub other_vole_key_r_prime_vec: GFVec<GFVOLE>,
+    pub tilde_a_bit_vec_rep: Vec<BitVec>,
+    pub tilde_b_bit_vec_rep: Vec<BitVec>,
+    pub tilde_c_bit_vec_rep: Vec<BitVec>,
+}
+
+impl<GFVOLE: Clone + Zero> PreprocessingShare<GFVOLE> {
+    // a share with all components sized as the public parameter requires, to be filled by FPre
+    pub fn zero(public_parameter: &PublicParameter, delta: GFVOLE) -> Self {
+        Self {
+            delta,
+            r_input_bit_vec: BitVec::zero_vec(public_parameter.num_input_bits),
+            vole_mac_r_input_vec: GFVec::zero_vec(public_parameter.num_input_bits),
+            other_vole_key_r_input_vec: GFVec::zero_vec(public_parameter.num_input_bits),
+            r_output_and_bit_vec: BitVec::zero_vec(public_parameter.big_iw_size),
+            vole_mac_r_output_and_vec: GFVec::zero_vec(public_parameter.big_iw_size),
+            other_vole_key_r_output_and_vec: GFVec::zero_vec(public_parameter.big_iw_size),
+            r_prime_bit_vec: BitVec::zero_vec(public_parameter.big_iw_size),
+            vole_mac_r_prime_vec: GFVec::zero_vec(public_parameter.big_iw_size),
+            other_vole_key_r_prime_vec: GFVec::zero_vec(public_parameter.big_iw_size),
+            tilde_a_bit_vec_rep: vec![BitVec::zero_vec(public_parameter.big_l); public_parameter.kappa],
+            tilde_b_bit_vec_rep: vec![BitVec::zero_vec(public_parameter.big_l); public_parameter.kappa],
+            tilde_c_bit_vec_rep: vec![BitVec::zero_vec(public_parameter.big_l); public_parameter.kappa],
+        }
+    }
+
+    pub fn install<GFVOLEitH>(self, prover_secret_state: &mut ProverSecretState<GFVOLE, GFVOLEitH>) {
+        prover_secret_state.delta = Some(self.delta);
+        prover_secret_state.r_input_bit_vec = self.r_input_bit_vec;
+        prover_secret_state.vole_mac_r_input_vec = self.vole_mac_r_input_vec;
+        prover_secret_state.other_vole_key_r_input_vec = self.other_vole_key_r_input_vec;
+        prover_secret_state.r_output_and_bit_vec = self.r_output_and_bit_vec;
+        prover_secret_state.vole_mac_r_output_and_vec = self.vole_mac_r_output_and_vec;
+        prover_secret_state.other_vole_key_r_output_and_vec = self.other_vole_key_r_output_and_vec;
+        prover_secret_state.r_prime_bit_vec = self.r_prime_bit_vec;
+        prover_secret_state.vole_mac_r_prime_vec = self.vole_mac_r_prime_vec;
+        prover_secret_state.other_vole_key_r_prime_vec = self.other_vole_key_r_prime_vec;
+        prover_secret_state.tilde_a_bit_vec_rep = self.tilde_a_bit_vec_rep;
+        prover_secret_state.tilde_b_bit_vec_rep = self.tilde_b_bit_vec_rep;
+        prover_secret_state.tilde_c_bit_vec_rep = self.tilde_c_bit_vec_rep;
+    }
+}
+
+// PA's part of the preprocessing transcript
+#[derive(Encode, Decode)]
+pub struct PAPreprocessingMessage<GFVOLE, GFVOLEitH> {
+    pub com_hash_rep: Vec<[u8; BLAKE3_HASH_DIGEST_NUM_BYTES]>,
+    pub masked_bit_tuple_rep: Vec<MaskedBitTuple>,
+    pub garbled_table: Vec<Vec<GarbledRow<GFVOLE, GFVOLEitH>>>,
+}
+
+impl<GFVOLE, GFVOLEitH> PAPreprocessingMessage<GFVOLE, GFVOLEitH> {
+    pub fn check_lengths(&self, public_parameter: &PublicParameter) -> Result<(), PartyError> {
+        check_len("com_hash_rep", public_parameter.kappa, self.com_hash_rep.len())?;
+        check_len("masked_bit_tuple_rep", public_parameter.kappa, self.masked_bit_tuple_rep.len())?;
+        check_len("garbled_table", public_parameter.big_iw_size, self.garbled_table.len())?;
+        for garbled_row_vec in &self.garbled_table {
+            check_len("garbled_table", 4, garbled_row_vec.len())?;
+            for garbled_row in garbled_row_vec {
+                check_len("garbled_row", public_parameter.kappa, garbled_row.voleith_mac_field.len())?;
+            }
+        }
+        Ok(())
+    }
+}
+
+// PB's part of the preprocessing transcript
+#[derive(Encode, Decode)]
+pub struct PBPreprocessingMessage {
+    pub com_hash_rep: Vec<[u8; BLAKE3_HASH_DIGEST_NUM_BYTES]>,
+    pub masked_bit_tuple_rep: Vec<MaskedBitTuple>,
+    pub commitment_vec: Vec<[[u8; BLAKE3_HASH_DIGEST_NUM_BYTES]; 4]>,
+}
+
+impl PBPreprocessingMessage {
+    pub fn check_lengths(&self, public_parameter: &PublicParameter) -> Result<(), PartyError> {
+        check_len("com_hash_rep", public_parameter.kappa, self.com_hash_rep.len())?;
+        check_len("masked_bit_tuple_rep", public_parameter.kappa, self.masked_bit_tuple_rep.len())?;
+        check_len("commitment_vec", public_parameter.big_iw_size, self.commitment_vec.len())
+    }
+}
+
+// The rm opened multiplication triples of one party
+#[derive(Encode, Decode)]
+pub struct PublishedRmShare<GFVOLEitH> {
+    pub rm_a_vec_rep: Vec<BitVec>,
+    pub rm_b_vec_rep: Vec<BitVec>,
+    pub rm_c_vec_rep: Vec<BitVec>,
+    pub rm_voleith_mac_a_vec_rep: Vec<GFVec<GFVOLEitH>>,
+    pub rm_voleith_mac_b_vec_rep: Vec<GFVec<GFVOLEitH>>,
+    pub rm_voleith_mac_c_vec_rep: Vec<GFVec<GFVOLEitH>>,
+}
+
+impl<GFVOLEitH> PublishedRmShare<GFVOLEitH> {
+    pub fn check_lengths(&self, public_parameter: &PublicParameter) -> Result<(), PartyError> {
+        let (kappa, rm) = (public_parameter.kappa, public_parameter.rm);
+        check_len_rep("rm_a_vec_rep", kappa, rm, &self.rm_a_vec_rep)?;
+        check_len_rep("rm_b_vec_rep", kappa, rm, &self.rm_b_vec_rep)?;
+        check_len_rep("rm_c_vec_rep", kappa, rm, &self.rm_c_vec_rep)?;
+        check_len_rep("rm_voleith_mac_a_vec_rep", kappa, rm, &self.rm_voleith_mac_a_vec_rep)?;
+        check_len_rep("rm_voleith_mac_b_vec_rep", kappa, rm, &self.rm_voleith_mac_b_vec_rep)?;
+        check_len_rep("rm_voleith_mac_c_vec_rep", kappa, rm, &self.rm_voleith_mac_c_vec_rep)
+    }
+}
+
+// The masks of one party at some wires, with the VOLE MACs checked by the other party
+// and the VOLEitH MACs that go into the proof transcript
+#[derive(Encode, Decode)]
+pub struct PublishedWireShare<GFVOLE, GFVOLEitH> {
+    pub r_bit_vec: BitVec,
+    pub vole_mac_r_vec: GFVec<GFVOLE>,
+    pub voleith_mac_r_vec_rep: Vec<GFVec<GFVOLEitH>>,
+}
+
+impl<GFVOLE, GFVOLEitH> PublishedWireShare<GFVOLE, GFVOLEitH> {
+    pub fn check_lengths(&self, public_parameter: &PublicParameter, num_wires: usize) -> Result<(), PartyError> {
+        check_len("r_bit_vec", num_wires, self.r_bit_vec.len())?;
+        check_len("vole_mac_r_vec", num_wires, self.vole_mac_r_vec.len())?;
+        check_len_rep("voleith_mac_r_vec_rep", public_parameter.kappa, num_wires, &self.voleith_mac_r_vec_rep)
+    }
+}
+
+// PA's labels of all inputs, together with the masked inputs of PA
+#[derive(Encode, Decode)]
+pub struct PAInputLabelMessage<GFVOLE> {
+    pub hat_z_input_bit_vec_with_ia: Vec<u8>,
+    pub label_with_ia: Vec<GFVOLE>,
+    pub label_with_ib: Vec<GFVOLE>,
+}
+
+impl<GFVOLE> PAInputLabelMessage<GFVOLE> {
+    pub fn check_lengths(&self, public_parameter: &PublicParameter) -> Result<(), PartyError> {
+        check_masked_input_bit_vec("hat_z_input_bit_vec_with_ia", public_parameter.big_ia.len(), &self.hat_z_input_bit_vec_with_ia)?;
+        check_len("label_with_ia", public_parameter.big_ia.len(), self.label_with_ia.len())?;
+        check_len("label_with_ib", public_parameter.big_ib.len(), self.label_with_ib.len())
+    }
+}
+
+// What PB learns by evaluating the garbled circuit
+#[derive(Encode, Decode)]
+pub struct EvaluationMessage<GFVOLE, GFVOLEitH> {
+    pub published_middle_hat_z_bit_vec: BitVec,
+    pub pb_published_middle_label_vec: GFVec<GFVOLE>,
+    pub pb_published_middle_r_bit_vec: BitVec,
+    pub pb_published_middle_voleith_mac_r_vec_rep: Vec<GFVec<GFVOLEitH>>,
+    pub pb_published_middle_randomness_vec: Vec<SeedU8x16>,
+    pub published_decrypted_garbled_row: Vec<GarbledRow<GFVOLE, GFVOLEitH>>,
+    pub pb_published_output_r_bit_vec: BitVec,
+    pub pb_published_output_voleith_mac_r_vec_rep: Vec<GFVec<GFVOLEitH>>,
+    pub published_output_bit_vec: BitVec,
+}
+
+impl<GFVOLE, GFVOLEitH> EvaluationMessage<GFVOLE, GFVOLEitH> {
+    pub fn check_lengths(&self, public_parameter: &PublicParameter) -> Result<(), PartyError> {
+        let (kappa, big_iw_size, big_io_size) = (public_parameter.kappa, public_parameter.big_iw_size, public_parameter.big_io_size);
+        check_len("published_middle_hat_z_bit_vec", big_iw_size, self.published_middle_hat_z_bit_vec.len())?;
+        check_len("pb_published_middle_label_vec", big_iw_size, self.pb_published_middle_label_vec.len())?;
+        check_len("pb_published_middle_r_bit_vec", big_iw_size, self.pb_published_middle_r_bit_vec.len())?;
+        check_len_rep("pb_published_middle_voleith_mac_r_vec_rep", kappa, big_iw_size, &self.pb_published_middle_voleith_mac_r_vec_rep)?;
+        check_len("pb_published_middle_randomness_vec", big_iw_size, self.pb_published_middle_randomness_vec.len())?;
+        check_len("published_decrypted_garbled_row", big_iw_size, self.published_decrypted_garbled_row.len())?;
+        check_len("pb_published_output_r_bit_vec", big_io_size, self.pb_published_output_r_bit_vec.len())?;
+        check_len_rep("pb_published_output_voleith_mac_r_vec_rep", kappa, big_io_size, &self.pb_published_output_voleith_mac_r_vec_rep)?;
+        check_len("published_output_bit_vec", big_io_size, self.published_output_bit_vec.len())
+    }
+}
+
+// The decommitment a party opens after nabla is known, in the vector commitment mode of the public parameter
+pub fn check_decom_lengths(
+    public_parameter: &PublicParameter,
+    decom: &Decom,
+) -> Result<(), PartyError> {
+    match (public_parameter.vector_commitment_mode, decom) {
+        (VectorCommitmentMode::PerRepetition, Decom::PerRepetition { decom_rep }) => check_len(
+            "decom_rep", public_parameter.kappa, decom_rep.len()
+        ),
+        (VectorCommitmentMode::Batched, Decom::Batched { com_at_excluded_index_rep, .. }) => check_len(
+            "com_at_excluded_index_rep", public_parameter.kappa, com_at_excluded_index_rep.len()
+        ),
+        _ => Err(PartyError::DecomMode),
+    }
+}
@@ -1,5 +1,9 @@
-use bincode::{config, encode_to_vec, Encode};
+use bincode::{config, encode_to_vec, Decode, Encode};
+use bincode::de::Decoder;
+use bincode::enc::Encoder;
+use bincode::error::{DecodeError, EncodeError};
 use blake3::Hash;
+use crate::comm_types_and_constants::BLAKE3_HASH_DIGEST_NUM_BYTES;
 use crate::value_type::garbled_row::GarbledRow;
 use crate::vec_type::bit_vec::BitVec;
 
@@ -53,4 +57,39 @@ where GFVOLE: Encode, GFVOLEitH: Encode {
         
         res
     }
-}
\ No newline at end of file
+}
+
+// blake3::Hash does not implement Encode/Decode, so the digests are stored as raw byte arrays
+impl<GFVOLE, GFVOLEitH> Encode for PreprocessingTranscript<GFVOLE, GFVOLEitH>
+where GFVOLE: Encode, GFVOLEitH: Encode {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        self.pa_com_hash_rep.iter().map(|digest| *digest.as_bytes()).collect::<Vec<_>>().encode(encoder)?;
+        self.pa_masked_bit_tuple_rep.encode(encoder)?;
+        self.pb_com_hash_rep.iter().map(|digest| *digest.as_bytes()).collect::<Vec<_>>().encode(encoder)?;
+        self.pb_masked_bit_tuple_rep.encode(encoder)?;
+        self.garbled_table.encode(encoder)?;
+        self.commitment_vec.iter().map(
+            |coms| coms.map(|digest| *digest.as_bytes())
+        ).collect::<Vec<_>>().encode(encoder)
+    }
+}
+
+impl<Context, GFVOLE, GFVOLEitH> Decode<Context> for PreprocessingTranscript<GFVOLE, GFVOLEitH>
+where GFVOLE: Decode<Context>, GFVOLEitH: Decode<Context> {
+    fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        let pa_com_hash_rep = Vec::<[u8; BLAKE3_HASH_DIGEST_NUM_BYTES]>::decode(decoder)?;
+        let pa_masked_bit_tuple_rep = Vec::decode(decoder)?;
+        let pb_com_hash_rep = Vec::<[u8; BLAKE3_HASH_DIGEST_NUM_BYTES]>::decode(decoder)?;
+        let pb_masked_bit_tuple_rep = Vec::decode(decoder)?;
+        let garbled_table = Vec::decode(decoder)?;
+        let commitment_vec = Vec::<[[u8; BLAKE3_HASH_DIGEST_NUM_BYTES]; 4]>::decode(decoder)?;
+        Ok(Self {
+            pa_com_hash_rep: pa_com_hash_rep.into_iter().map(Hash::from_bytes).collect(),
+            pa_masked_bit_tuple_rep,
+            pb_com_hash_rep: pb_com_hash_rep.into_iter().map(Hash::from_bytes).collect(),
+            pb_masked_bit_tuple_rep,
+            garbled_table,
+            commitment_vec: commitment_vec.into_iter().map(|coms| coms.map(Hash::from_bytes)).collect(),
+        })
+    }
+}
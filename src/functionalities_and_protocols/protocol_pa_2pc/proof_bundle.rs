@@ -0,0 +1,347 @@
+use std::fmt::{Display, Formatter};
+use bincode::{config, decode_from_slice, encode_to_vec, Decode, Encode};
+use crate::comm_types_and_constants::DECODING_BYTE_LIMIT;
+use crate::functionalities_and_protocols::all_in_one_vc::{Decom, VectorCommitmentMode};
+use crate::functionalities_and_protocols::protocol_pa_2pc::preprocessing_transcript::PreprocessingTranscript;
+use crate::functionalities_and_protocols::protocol_pa_2pc::proof_transcript::ProofTranscript;
+use crate::functionalities_and_protocols::states_and_parameters::public_parameter::PublicParameter;
+use crate::value_type::ByteManipulation;
+use crate::vec_type::BasicVecFunctions;
+
+pub const PROOF_BUNDLE_MAGIC: [u8; 4] = *b"PAGC";
+pub const PROOF_BUNDLE_VERSION: u16 = 1;
+
+// Everything a third party needs to verify an execution of PiPA2PC later on, given the circuit
+pub struct ProofBundle<GFVOLE, GFVOLEitH>
+where GFVOLE: Encode, GFVOLEitH: Encode {
+    pub public_parameter: PublicParameter,
+    pub preprocessing_transcript: PreprocessingTranscript<GFVOLE, GFVOLEitH>,
+    pub proof_transcript: ProofTranscript<GFVOLE, GFVOLEitH>,
+    pub pa_decom: Decom,
+    pub pb_decom: Decom,
+}
+
+// The header makes a bundle self-describing: which format version and which field sizes it was produced with
+#[derive(Encode, Decode)]
+struct ProofBundleHeader {
+    magic: [u8; 4],
+    version: u16,
+    gf_vole_num_bytes: usize,
+    gf_voleith_num_bytes: usize,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ProofBundleError {
+    // the bytes could not be decoded at all
+    Malformed(String),
+    // the bytes do not start with PROOF_BUNDLE_MAGIC
+    BadMagic,
+    // the bundle was produced by an incompatible format version
+    UnsupportedVersion(u16),
+    // the bundle was produced over different fields than the ones it is loaded with
+    FieldSizeMismatch {
+        field: &'static str,
+        expected: usize,
+        actual: usize,
+    },
+    // some vector does not have the length determined by the public parameter
+    LengthMismatch {
+        field: String,
+        expected: usize,
+        actual: usize,
+    },
+    // there are bytes left after the bundle
+    TrailingBytes(usize),
+}
+
+impl Display for ProofBundleError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ProofBundleError::Malformed(message) => write!(f, "malformed proof bundle: {}", message),
+            ProofBundleError::BadMagic => write!(f, "not a proof bundle"),
+            ProofBundleError::UnsupportedVersion(version) => write!(
+                f, "unsupported proof bundle version {} (expected {})", version, PROOF_BUNDLE_VERSION
+            ),
+            ProofBundleError::FieldSizeMismatch { field, expected, actual } => write!(
+                f, "{} has {} bytes per element, expected {}", field, actual, expected
+            ),
+            ProofBundleError::LengthMismatch { field, expected, actual } => write!(
+                f, "{} has length {}, expected {}", field, actual, expected
+            ),
+            ProofBundleError::TrailingBytes(num_bytes) => write!(f, "{} trailing bytes after proof bundle", num_bytes),
+        }
+    }
+}
+
+impl std::error::Error for ProofBundleError {}
+
+fn check_len(field: impl Into<String>, expected: usize, actual: usize) -> Result<(), ProofBundleError> {
+    if expected != actual {
+        return Err(ProofBundleError::LengthMismatch { field: field.into(), expected, actual });
+    }
+    Ok(())
+}
+
+fn check_len_rep<VecType: BasicVecFunctions<PrimitiveType>, PrimitiveType>(
+    field: &str, kappa: usize, expected: usize, vec_rep: &Vec<VecType>
+) -> Result<(), ProofBundleError> {
+    check_len(field, kappa, vec_rep.len())?;
+    for (repetition_id, vec) in vec_rep.iter().enumerate() {
+        check_len(format!("{}[{}]", field, repetition_id), expected, vec.len())?;
+    }
+    Ok(())
+}
+
+fn decode_header(byte_vec: &[u8]) -> Result<(ProofBundleHeader, usize), ProofBundleError> {
+    let (header, num_bytes): (ProofBundleHeader, usize) = decode_from_slice(byte_vec, config::standard().with_limit::<DECODING_BYTE_LIMIT>()).map_err(
+        |error| ProofBundleError::Malformed(error.to_string())
+    )?;
+    if header.magic != PROOF_BUNDLE_MAGIC {
+        return Err(ProofBundleError::BadMagic);
+    }
+    if header.version != PROOF_BUNDLE_VERSION {
+        return Err(ProofBundleError::UnsupportedVersion(header.version));
+    }
+    Ok((header, num_bytes))
+}
+
+// Decodes only the public parameter of a bundle, e.g., to learn tau and thus GFVOLEitH before loading the bundle
+pub fn peek_public_parameter(byte_vec: &[u8]) -> Result<PublicParameter, ProofBundleError> {
+    let (_, num_bytes) = decode_header(byte_vec)?;
+    let (public_parameter, _): (PublicParameter, usize) = decode_from_slice(&byte_vec[num_bytes..], config::standard().with_limit::<DECODING_BYTE_LIMIT>()).map_err(
+        |error| ProofBundleError::Malformed(error.to_string())
+    )?;
+    Ok(public_parameter)
+}
+
+impl<GFVOLE, GFVOLEitH> ProofBundle<GFVOLE, GFVOLEitH>
+where GFVOLE: Encode + Decode<()> + ByteManipulation,
+      GFVOLEitH: Encode + Decode<()> + ByteManipulation {
+    pub fn new(
+        public_parameter: PublicParameter,
+        preprocessing_transcript: PreprocessingTranscript<GFVOLE, GFVOLEitH>,
+        proof_transcript: ProofTranscript<GFVOLE, GFVOLEitH>,
+        pa_decom: Decom,
+        pb_decom: Decom,
+    ) -> Self {
+        Self {
+            public_parameter,
+            preprocessing_transcript,
+            proof_transcript,
+            pa_decom,
+            pb_decom,
+        }
+    }
+
+    pub fn to_byte_vec(&self) -> Vec<u8> {
+        let config = config::standard();
+
+        let header = ProofBundleHeader {
+            magic: PROOF_BUNDLE_MAGIC,
+            version: PROOF_BUNDLE_VERSION,
+            gf_vole_num_bytes: GFVOLE::num_bytes(),
+            gf_voleith_num_bytes: GFVOLEitH::num_bytes(),
+        };
+
+        let mut res = Vec::<u8>::new();
+
+        res.append(&mut encode_to_vec(&header, config).unwrap());
+        res.append(&mut encode_to_vec(&self.public_parameter, config).unwrap());
+        res.append(&mut encode_to_vec(&self.preprocessing_transcript, config).unwrap());
+        res.append(&mut encode_to_vec(&self.proof_transcript, config).unwrap());
+        res.append(&mut encode_to_vec(&self.pa_decom, config).unwrap());
+        res.append(&mut encode_to_vec(&self.pb_decom, config).unwrap());
+
+        res
+    }
+
+    // Decodes a bundle and checks every length against the public parameter it carries
+    pub fn from_byte_vec(byte_vec: &[u8]) -> Result<Self, ProofBundleError> {
+        let config = config::standard().with_limit::<DECODING_BYTE_LIMIT>();
+        let malformed = |error: bincode::error::DecodeError| ProofBundleError::Malformed(error.to_string());
+
+        let mut cursor = 0usize;
+        let (header, num_bytes) = decode_header(byte_vec)?;
+        cursor += num_bytes;
+        if header.gf_vole_num_bytes != GFVOLE::num_bytes() {
+            return Err(ProofBundleError::FieldSizeMismatch {
+                field: "GFVOLE", expected: GFVOLE::num_bytes(), actual: header.gf_vole_num_bytes
+            });
+        }
+        if header.gf_voleith_num_bytes != GFVOLEitH::num_bytes() {
+            return Err(ProofBundleError::FieldSizeMismatch {
+                field: "GFVOLEitH", expected: GFVOLEitH::num_bytes(), actual: header.gf_voleith_num_bytes
+            });
+        }
+
+        let (public_parameter, num_bytes) = decode_from_slice(&byte_vec[cursor..], config).map_err(malformed)?;
+        cursor += num_bytes;
+        let (preprocessing_transcript, num_bytes) = decode_from_slice(&byte_vec[cursor..], config).map_err(malformed)?;
+        cursor += num_bytes;
+        let (proof_transcript, num_bytes) = decode_from_slice(&byte_vec[cursor..], config).map_err(malformed)?;
+        cursor += num_bytes;
+        let (pa_decom, num_bytes) = decode_from_slice(&byte_vec[cursor..], config).map_err(malformed)?;
+        cursor += num_bytes;
+        let (pb_decom, num_bytes) = decode_from_slice(&byte_vec[cursor..], config).map_err(malformed)?;
+        cursor += num_bytes;
+        if cursor != byte_vec.len() {
+            return Err(ProofBundleError::TrailingBytes(byte_vec.len() - cursor));
+        }
+
+        let proof_bundle = Self::new(
+            public_parameter,
+            preprocessing_transcript,
+            proof_transcript,
+            pa_decom,
+            pb_decom,
+        );
+        proof_bundle.check_lengths()?;
+        Ok(proof_bundle)
+    }
+
+    // Checks that every vector in the bundle has the length determined by the public parameter,
+    // so that the verifier never indexes out of bounds on a malicious bundle
+    pub fn check_lengths(&self) -> Result<(), ProofBundleError> {
+        let public_parameter = &self.public_parameter;
+        let kappa = public_parameter.kappa;
+        let big_iw_size = public_parameter.big_iw_size;
+        let big_io_size = public_parameter.big_io_size;
+        let big_l = public_parameter.big_l;
+
+        // the GGM trees must be deep enough to open a leaf and shallow enough for their leaves to fit GFVOLEitH
+        if public_parameter.tau == 0 || public_parameter.tau as usize > GFVOLEitH::num_bytes() * 8 {
+            return Err(ProofBundleError::Malformed(format!("tau {} does not fit GFVOLEitH", public_parameter.tau)));
+        }
+        // the Fiat-Shamir hashes bind tau and kappa, so the depths per repetition must be the ones they determine
+        if kappa != PublicParameter::determine_kappa(GFVOLE::num_bytes() * 8, public_parameter.tau) {
+            return Err(ProofBundleError::Malformed("kappa does not match tau".to_string()));
+        }
+        check_len("public_parameter.tau_rep", kappa, public_parameter.tau_rep.len())?;
+        if public_parameter.tau_rep.contains(&0)
+            || public_parameter.tau_rep != PublicParameter::determine_tau_rep(GFVOLE::num_bytes() * 8, public_parameter.tau, kappa) {
+            return Err(ProofBundleError::Malformed("tau_rep does not match tau and kappa".to_string()));
+        }
+
+        // the derived sizes of the public parameter itself
+        check_len("public_parameter.big_ia + big_ib", public_parameter.num_input_bits, public_parameter.big_ia.len() + public_parameter.big_ib.len())?;
+        check_len("public_parameter.big_iw", big_iw_size, public_parameter.big_iw.len())?;
+        check_len("public_parameter.big_io", big_io_size, public_parameter.big_io.len())?;
+        check_len("public_parameter.big_l", public_parameter.bs * big_iw_size + public_parameter.rm, big_l)?;
+        check_len("public_parameter.big_n", public_parameter.num_input_bits + 2 * big_iw_size + 3 * big_l, public_parameter.big_n)?;
+        if public_parameter.big_ia.iter().chain(public_parameter.big_ib.iter()).any(|input_wire| *input_wire >= public_parameter.num_input_bits)
+            || public_parameter.big_iw.iter().chain(public_parameter.big_io.iter()).any(|wire| *wire >= public_parameter.num_wires) {
+            return Err(ProofBundleError::Malformed("wire index out of range".to_string()));
+        }
+
+        let preprocessing_transcript = &self.preprocessing_transcript;
+        check_len("pa_com_hash_rep", kappa, preprocessing_transcript.pa_com_hash_rep.len())?;
+        check_len("pb_com_hash_rep", kappa, preprocessing_transcript.pb_com_hash_rep.len())?;
+        for (field, masked_bit_tuple_rep) in [
+            ("pa_masked_bit_tuple_rep", &preprocessing_transcript.pa_masked_bit_tuple_rep),
+            ("pb_masked_bit_tuple_rep", &preprocessing_transcript.pb_masked_bit_tuple_rep),
+        ] {
+            check_len(field, kappa, masked_bit_tuple_rep.len())?;
+            for (repetition_id, masked_bit_tuple) in masked_bit_tuple_rep.iter().enumerate() {
+                for (entry_id, (expected, bit_vec)) in [
+                    (public_parameter.num_input_bits, &masked_bit_tuple.0),
+                    (big_iw_size, &masked_bit_tuple.1),
+                    (big_iw_size, &masked_bit_tuple.2),
+                    (big_l, &masked_bit_tuple.3),
+                    (big_l, &masked_bit_tuple.4),
+                    (big_l, &masked_bit_tuple.5),
+                ].into_iter().enumerate() {
+                    check_len(format!("{}[{}].{}", field, repetition_id, entry_id), expected, bit_vec.len())?;
+                }
+            }
+        }
+        check_len("garbled_table", big_iw_size, preprocessing_transcript.garbled_table.len())?;
+        for (and_cursor, garbled_rows) in preprocessing_transcript.garbled_table.iter().enumerate() {
+            check_len(format!("garbled_table[{}]", and_cursor), 4, garbled_rows.len())?;
+            for (k, garbled_row) in garbled_rows.iter().enumerate() {
+                check_len(format!("garbled_table[{}][{}].voleith_mac_field", and_cursor, k), kappa, garbled_row.voleith_mac_field.len())?;
+            }
+        }
+        check_len("commitment_vec", big_iw_size, preprocessing_transcript.commitment_vec.len())?;
+
+        let proof_transcript = &self.proof_transcript;
+        let rm = public_parameter.rm;
+        check_len_rep("pa_published_rm_a_vec_rep", kappa, rm, &proof_transcript.pa_published_rm_a_vec_rep)?;
+        check_len_rep("pa_published_rm_b_vec_rep", kappa, rm, &proof_transcript.pa_published_rm_b_vec_rep)?;
+        check_len_rep("pa_published_rm_c_vec_rep", kappa, rm, &proof_transcript.pa_published_rm_c_vec_rep)?;
+        check_len_rep("pa_published_rm_voleith_mac_a_vec_rep", kappa, rm, &proof_transcript.pa_published_rm_voleith_mac_a_vec_rep)?;
+        check_len_rep("pa_published_rm_voleith_mac_b_vec_rep", kappa, rm, &proof_transcript.pa_published_rm_voleith_mac_b_vec_rep)?;
+        check_len_rep("pa_published_rm_voleith_mac_c_vec_rep", kappa, rm, &proof_transcript.pa_published_rm_voleith_mac_c_vec_rep)?;
+        check_len_rep("pb_published_rm_a_vec_rep", kappa, rm, &proof_transcript.pb_published_rm_a_vec_rep)?;
+        check_len_rep("pb_published_rm_b_vec_rep", kappa, rm, &proof_transcript.pb_published_rm_b_vec_rep)?;
+        check_len_rep("pb_published_rm_c_vec_rep", kappa, rm, &proof_transcript.pb_published_rm_c_vec_rep)?;
+        check_len_rep("pb_published_rm_voleith_mac_a_vec_rep", kappa, rm, &proof_transcript.pb_published_rm_voleith_mac_a_vec_rep)?;
+        check_len_rep("pb_published_rm_voleith_mac_b_vec_rep", kappa, rm, &proof_transcript.pb_published_rm_voleith_mac_b_vec_rep)?;
+        check_len_rep("pb_published_rm_voleith_mac_c_vec_rep", kappa, rm, &proof_transcript.pb_published_rm_voleith_mac_c_vec_rep)?;
+
+        // PA publishes its masks on PB's input wires and vice versa
+        let big_ia_size = public_parameter.big_ia.len();
+        let big_ib_size = public_parameter.big_ib.len();
+        check_len("pa_published_input_r_bit_vec", big_ib_size, proof_transcript.pa_published_input_r_bit_vec.len())?;
+        check_len_rep("pa_published_input_voleith_mac_r_vec_rep", kappa, big_ib_size, &proof_transcript.pa_published_input_voleith_mac_r_vec_rep)?;
+        check_len("pb_published_input_r_bit_vec", big_ia_size, proof_transcript.pb_published_input_r_bit_vec.len())?;
+        check_len_rep("pb_published_input_voleith_mac_r_vec_rep", kappa, big_ia_size, &proof_transcript.pb_published_input_voleith_mac_r_vec_rep)?;
+
+        check_len("pa_published_output_r_bit_vec", big_io_size, proof_transcript.pa_published_output_r_bit_vec.len())?;
+        check_len("pa_published_output_vole_mac_r_vec", big_io_size, proof_transcript.pa_published_output_vole_mac_r_vec.len())?;
+        check_len_rep("pa_published_output_voleith_mac_r_vec_rep", kappa, big_io_size, &proof_transcript.pa_published_output_voleith_mac_r_vec_rep)?;
+
+        check_len("published_hat_z_input_bit_vec", public_parameter.num_input_bits, proof_transcript.published_hat_z_input_bit_vec.len())?;
+        check_len("published_middle_hat_z_bit_vec", big_iw_size, proof_transcript.published_middle_hat_z_bit_vec.len())?;
+
+        check_len("pb_published_middle_label_vec", big_iw_size, proof_transcript.pb_published_middle_label_vec.len())?;
+        check_len("pb_published_middle_r_bit_vec", big_iw_size, proof_transcript.pb_published_middle_r_bit_vec.len())?;
+        check_len_rep("pb_published_middle_voleith_mac_r_vec_rep", kappa, big_iw_size, &proof_transcript.pb_published_middle_voleith_mac_r_vec_rep)?;
+        check_len("pb_published_middle_randomness_vec", big_iw_size, proof_transcript.pb_published_middle_randomness_vec.len())?;
+        check_len("pb_published_output_r_bit_vec", big_io_size, proof_transcript.pb_published_output_r_bit_vec.len())?;
+        check_len_rep("pb_published_output_voleith_mac_r_vec_rep", kappa, big_io_size, &proof_transcript.pb_published_output_voleith_mac_r_vec_rep)?;
+
+        check_len("published_output_bit_vec", big_io_size, proof_transcript.published_output_bit_vec.len())?;
+        check_len("published_decrypted_garbled_row", big_iw_size, proof_transcript.published_decrypted_garbled_row.len())?;
+        for (and_cursor, garbled_row) in proof_transcript.published_decrypted_garbled_row.iter().enumerate() {
+            check_len(format!("published_decrypted_garbled_row[{}].voleith_mac_field", and_cursor), kappa, garbled_row.voleith_mac_field.len())?;
+        }
+
+        check_len("check_and_transcript_vec", public_parameter.bs, proof_transcript.check_and_transcript_vec.len())?;
+        for (block_id, check_and_transcript) in proof_transcript.check_and_transcript_vec.iter().enumerate() {
+            for (party, published_bit_and_voleith_mac_tuple_rep) in [
+                ("pa", &check_and_transcript.pa_published_bit_and_voleith_mac_tuple_rep),
+                ("pb", &check_and_transcript.pb_published_bit_and_voleith_mac_tuple_rep),
+            ] {
+                for (name, (bit_vec_rep, voleith_mac_vec_rep)) in [
+                    ("d", &published_bit_and_voleith_mac_tuple_rep.0),
+                    ("e", &published_bit_and_voleith_mac_tuple_rep.1),
+                    ("tilde_z", &published_bit_and_voleith_mac_tuple_rep.2),
+                ] {
+                    let field = format!("check_and_transcript_vec[{}].{}_{}", block_id, party, name);
+                    check_len_rep(&format!("{}_bit_vec_rep", field), kappa, big_iw_size, bit_vec_rep)?;
+                    check_len_rep(&format!("{}_voleith_mac_vec_rep", field), kappa, big_iw_size, voleith_mac_vec_rep)?;
+                }
+            }
+        }
+
+        for (field, decom) in [("pa_decom", &self.pa_decom), ("pb_decom", &self.pb_decom)] {
+            match (public_parameter.vector_commitment_mode, decom) {
+                (VectorCommitmentMode::PerRepetition, Decom::PerRepetition { decom_rep }) => {
+                    check_len(format!("{}.decom_rep", field), kappa, decom_rep.len())?;
+                    for (repetition_id, (_, seed_trace)) in decom_rep.iter().enumerate() {
+                        check_len(format!("{}.decom_rep[{}].1", field, repetition_id), public_parameter.tau_rep[repetition_id] as usize, seed_trace.len())?;
+                    }
+                }
+                // the length of the co-path depends on nabla and is only known to the verifier
+                (VectorCommitmentMode::Batched, Decom::Batched { com_at_excluded_index_rep, .. }) => {
+                    check_len(format!("{}.com_at_excluded_index_rep", field), kappa, com_at_excluded_index_rep.len())?;
+                }
+                _ => return Err(ProofBundleError::Malformed(
+                    format!("{} is not in the vector commitment mode of the public parameter", field)
+                )),
+            }
+        }
+
+        Ok(())
+    }
+}
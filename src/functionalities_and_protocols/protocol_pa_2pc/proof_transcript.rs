@@ -1,4 +1,4 @@
-use bincode::{config, encode_to_vec, Encode};
+use bincode::{config, encode_to_vec, Decode, Encode};
 use crate::functionalities_and_protocols::protocol_check_and::check_and_transcript::CheckAndTranscript;
 use crate::functionalities_and_protocols::states_and_parameters::public_parameter::PublicParameter;
 use crate::value_type::garbled_row::GarbledRow;
@@ -8,7 +8,7 @@ use crate::vec_type::ZeroVec;
 use crate::vec_type::bit_vec::BitVec;
 use crate::vec_type::gf_vec::GFVec;
 
-#[derive(Encode)]
+#[derive(Encode, Decode)]
 pub struct ProofTranscript<GFVOLE, GFVOLEitH>
 where GFVOLE: Encode, GFVOLEitH: Encode {
     // before nabla
@@ -1,28 +1,34 @@
 use std::fmt::Debug;
+use std::thread;
 use bincode::Encode;
 use blake3::Hash;
+use rand::{CryptoRng, RngCore};
+use crate::functionalities_and_protocols::all_in_one_vc::Decom;
 use crate::bristol_fashion_adaptor::bristol_fashion_adaptor::BristolFashionAdaptor;
 use crate::bristol_fashion_adaptor::{GateType};
+use crate::functionalities_and_protocols::commitment::{commit, Com};
 use crate::functionalities_and_protocols::hasher;
 use crate::functionalities_and_protocols::hasher::{hash_to_determine_nabla_rep, hash_to_determine_permutation_rep};
-use crate::functionalities_and_protocols::insecure_functionality_pre::insecure_functionality_pre::InsecureFunctionalityPre;
+use crate::functionalities_and_protocols::functionality_pre::PreprocessingFunctionality;
 use crate::functionalities_and_protocols::protocol_check_and::check_and_transcript::CheckAndTranscript;
 use crate::functionalities_and_protocols::protocol_check_and::prover_in_protocol_check_and::ProverInProtocolCheckAND;
-use crate::functionalities_and_protocols::protocol_pa_2pc::{extract_block_vec_rep, initialize_trace, permute, split_off_rm};
+use crate::functionalities_and_protocols::protocol_pa_2pc::{extract_block_vec_rep, initialize_trace, make_pb_middle_message, permute, split_off_rm};
 use crate::functionalities_and_protocols::protocol_svole_2pc::prover_in_protocol_svole_2pc::ProverInProtocolSVOLE2PC;
+use crate::functionalities_and_protocols::protocol_pa_2pc::party_error::PartyError;
+use crate::functionalities_and_protocols::protocol_pa_2pc::party_message::{CheckAndCrossShare, CheckAndMaskedShare, EvaluationMessage, MaskedBitTuple, PAInputLabelMessage, PreprocessingShare, PublishedRmShare, PublishedWireShare};
 use crate::functionalities_and_protocols::protocol_pa_2pc::preprocessing_transcript::PreprocessingTranscript;
 use crate::functionalities_and_protocols::protocol_pa_2pc::proof_transcript::ProofTranscript;
 use crate::functionalities_and_protocols::states_and_parameters::prover_secret_state::ProverSecretState;
 use crate::functionalities_and_protocols::states_and_parameters::public_parameter::PublicParameter;
 use crate::functionalities_and_protocols::util::parse_two_bits;
-use crate::value_type::{ByteManipulation, CustomAddition, CustomMultiplyingBit, InsecureRandom, U8ForGF, Zero};
+use crate::value_type::{ByteManipulation, CustomAddition, CustomMultiplyingBit, InsecureRandom, LeafIndexForGF, Zero};
 use crate::value_type::garbled_row::GarbledRow;
 use crate::value_type::seed_u8x16::SeedU8x16;
 use crate::vec_type::bit_vec::BitVec;
 use crate::vec_type::gf_vec::GFVec;
 use crate::vec_type::{
-    BasicVecFunctions,
     VecAddition,
+    ZeroVec,
 };
 
 pub struct ProverInPA2PC;
@@ -63,376 +69,286 @@ impl ProverInPA2PC {
             .custom_add(&voleith_mac_r_left_input.custom_multiply_bit(k1));
     }
 
-    pub fn preprocess<GFVOLE, GFVOLEitH>(
+
+    // One party obtains her share of FPre. Her random tuples are dealt while the other party obtains the keys for
+    // them and vice versa, PA's tuples coming first. The authenticated AND tuples depend on the masks at the input wires
+    // of each AND gate, so the party follows her masks through the circuit before asking for them.
+    pub fn deal<GFVOLE, FPre>(
         process_printing: bool,
+        functionality_pre: &mut FPre,
         bristol_fashion_adaptor: &BristolFashionAdaptor,
-        bit_trace_vec_for_labels_in_garbling: &BitVec,
         public_parameter: &PublicParameter,
-        pa_secret_state: &mut ProverSecretState<GFVOLE, GFVOLEitH>,
-        pb_secret_state: &mut ProverSecretState<GFVOLE, GFVOLEitH>,
-    ) -> PreprocessingTranscript<GFVOLE, GFVOLEitH>
+        is_pa: bool,
+    ) -> PreprocessingShare<GFVOLE>
     where
-        GFVOLE: Clone + CustomAddition + CustomMultiplyingBit + InsecureRandom + Zero + Copy + PartialEq + Debug + ByteManipulation + Sync + Send + Encode,
-        GFVOLEitH: Clone + Zero + CustomAddition + U8ForGF + Copy + CustomMultiplyingBit + ByteManipulation + Sync + Send + Encode
+        FPre: PreprocessingFunctionality<GFVOLE>,
+        GFVOLE: Clone + Zero
     {
+        let party = if is_pa { "PA" } else { "PB" };
         if process_printing {
-            println!("+ Preprocessing...");
-        }
-        if process_printing {
-            println!("  PA obtains delta from FPre");
+            println!("  {} obtains delta from FPre", party);
         }
-        InsecureFunctionalityPre::generate_delta(&mut pa_secret_state.delta);
+        let mut delta = None;
+        functionality_pre.generate_delta(&mut delta);
+        let mut share = PreprocessingShare::zero(public_parameter, delta.unwrap());
 
         if process_printing {
-            println!("  PB obtains delta from FPre");
+            println!("  {} obtains vole-authenticated bits and the keys for the bits of the other party", party);
+        }
+        let obtain_random_tuples = |functionality_pre: &mut FPre, share: &mut PreprocessingShare<GFVOLE>| {
+            functionality_pre.generate_random_tuples(
+                public_parameter.num_input_bits, &mut share.r_input_bit_vec, &mut share.vole_mac_r_input_vec
+            );
+            functionality_pre.generate_random_tuples(
+                public_parameter.big_iw_size, &mut share.r_output_and_bit_vec, &mut share.vole_mac_r_output_and_vec
+            );
+        };
+        let obtain_random_tuple_keys = |functionality_pre: &mut FPre, share: &mut PreprocessingShare<GFVOLE>| {
+            functionality_pre.generate_random_tuple_keys(
+                public_parameter.num_input_bits, &share.delta, &mut share.other_vole_key_r_input_vec
+            );
+            functionality_pre.generate_random_tuple_keys(
+                public_parameter.big_iw_size, &share.delta, &mut share.other_vole_key_r_output_and_vec
+            );
+        };
+        if is_pa {
+            obtain_random_tuples(functionality_pre, &mut share);
+            obtain_random_tuple_keys(functionality_pre, &mut share);
+        } else {
+            obtain_random_tuple_keys(functionality_pre, &mut share);
+            obtain_random_tuples(functionality_pre, &mut share);
         }
-        InsecureFunctionalityPre::generate_delta(&mut pb_secret_state.delta);
 
         if process_printing {
-            println!("  PA obtains vole-authenticated bits");
-        }
-        InsecureFunctionalityPre::generate_random_tuples::<GFVOLE, GFVOLEitH>(
-            public_parameter.num_input_bits,
-            &pb_secret_state.delta.as_ref().unwrap(),
-            &mut pa_secret_state.r_input_bit_vec,
-            &mut pa_secret_state.vole_mac_r_input_vec,
-            &mut pb_secret_state.other_vole_key_r_input_vec
-        );
-        assert_eq!(pa_secret_state.r_input_bit_vec.len(), public_parameter.num_input_bits);
-        assert_eq!(pa_secret_state.vole_mac_r_input_vec.len(), public_parameter.num_input_bits);
-        assert_eq!(pb_secret_state.other_vole_key_r_input_vec.len(), public_parameter.num_input_bits);
-        InsecureFunctionalityPre::generate_random_tuples::<GFVOLE, GFVOLEitH>(
-            public_parameter.big_iw_size,
-            &pb_secret_state.delta.as_ref().unwrap(),
-            &mut pa_secret_state.r_output_and_bit_vec,
-            &mut pa_secret_state.vole_mac_r_output_and_vec,
-            &mut pb_secret_state.other_vole_key_r_output_and_vec
+            println!("  {} obtains multiplication AND triples", party);
+        }
+        functionality_pre.generate_random_and_tuples(
+            public_parameter.kappa,
+            public_parameter.big_l,
+            &mut share.tilde_a_bit_vec_rep,
+            &mut share.tilde_b_bit_vec_rep,
+            &mut share.tilde_c_bit_vec_rep,
         );
-        assert_eq!(pa_secret_state.r_output_and_bit_vec.len(), public_parameter.big_iw_size);
-        assert_eq!(pa_secret_state.vole_mac_r_output_and_vec.len(), public_parameter.big_iw_size);
-        assert_eq!(pb_secret_state.other_vole_key_r_output_and_vec.len(), public_parameter.big_iw_size);
 
         if process_printing {
-            println!("  PB obtains vole-authenticated bits");
-        }
-        InsecureFunctionalityPre::generate_random_tuples::<GFVOLE, GFVOLEitH>(
-            public_parameter.num_input_bits,
-            &pa_secret_state.delta.as_ref().unwrap(),
-            &mut pb_secret_state.r_input_bit_vec,
-            &mut pb_secret_state.vole_mac_r_input_vec,
-            &mut pa_secret_state.other_vole_key_r_input_vec
+            println!("  {} obtains authenticated AND tuples following circuit's topological order", party);
+        }
+        let mut r_trace_bit_vec = BitVec::zero_vec(public_parameter.num_wires);
+        initialize_trace::<u8, BitVec>(
+            public_parameter, &share.r_input_bit_vec, &share.r_output_and_bit_vec, &mut r_trace_bit_vec
         );
-        assert_eq!(pb_secret_state.r_input_bit_vec.len(), public_parameter.num_input_bits);
-        assert_eq!(pb_secret_state.vole_mac_r_input_vec.len(), public_parameter.num_input_bits);
-        assert_eq!(pa_secret_state.other_vole_key_r_input_vec.len(), public_parameter.num_input_bits);
-
-        InsecureFunctionalityPre::generate_random_tuples::<GFVOLE, GFVOLEitH>(
-            public_parameter.big_iw_size,
-            &pa_secret_state.delta.as_ref().unwrap(),
-            &mut pb_secret_state.r_output_and_bit_vec,
-            &mut pb_secret_state.vole_mac_r_output_and_vec,
-            &mut pa_secret_state.other_vole_key_r_output_and_vec
+        let mut r_left_input_bit_vec = BitVec::new();
+        let mut r_right_input_bit_vec = BitVec::new();
+        for gate in bristol_fashion_adaptor.get_gate_vec() {
+            match gate.gate_type {
+                GateType::XOR => {
+                    r_trace_bit_vec.set(gate.output_wire, r_trace_bit_vec[gate.left_input_wire] ^ r_trace_bit_vec[gate.right_input_wire]);
+                },
+                GateType::NOT => {
+                    r_trace_bit_vec.set(gate.output_wire, r_trace_bit_vec[gate.left_input_wire]);
+                },
+                GateType::AND => {
+                    r_left_input_bit_vec.push(r_trace_bit_vec[gate.left_input_wire]);
+                    r_right_input_bit_vec.push(r_trace_bit_vec[gate.right_input_wire]);
+                }
+            }
+        }
+        functionality_pre.generate_random_authenticated_and_tuples(
+            &share.delta,
+            &r_left_input_bit_vec,
+            &r_right_input_bit_vec,
+            &mut share.r_prime_bit_vec,
+            &mut share.vole_mac_r_prime_vec,
+            &mut share.other_vole_key_r_prime_vec,
         );
-        assert_eq!(pb_secret_state.r_output_and_bit_vec.len(), public_parameter.big_iw_size);
-        assert_eq!(pb_secret_state.vole_mac_r_output_and_vec.len(), public_parameter.big_iw_size);
-        assert_eq!(pa_secret_state.other_vole_key_r_output_and_vec.len(), public_parameter.big_iw_size);
 
+        share
+    }
+
+    // Both parties obtain their shares of FPre in one process, each from her own endpoint and on her own thread,
+    // since a call on one endpoint waits for the matching call on the other
+    pub fn deal_both<GFVOLE, FPre>(
+        process_printing: bool,
+        pa_functionality_pre: &mut FPre,
+        pb_functionality_pre: &mut FPre,
+        bristol_fashion_adaptor: &BristolFashionAdaptor,
+        public_parameter: &PublicParameter,
+    ) -> (PreprocessingShare<GFVOLE>, PreprocessingShare<GFVOLE>)
+    where
+        FPre: PreprocessingFunctionality<GFVOLE> + Send,
+        GFVOLE: Clone + Zero + Send
+    {
+        thread::scope(|scope| {
+            let pb_thread = scope.spawn(
+                || Self::deal(process_printing, pb_functionality_pre, bristol_fashion_adaptor, public_parameter, false)
+            );
+            let pa_share = Self::deal(process_printing, pa_functionality_pre, bristol_fashion_adaptor, public_parameter, true);
+            (pa_share, pb_thread.join().unwrap())
+        })
+    }
+
+    // PA samples the zero-labels of the input and AND output wires from rng and propagates them to all wires,
+    // which she has to do before preprocess_locally
+    pub fn initialize_labels<GFVOLE, GFVOLEitH, R>(
+        process_printing: bool,
+        public_parameter: &PublicParameter,
+        pa_secret_state: &mut ProverSecretState<GFVOLE, GFVOLEitH>,
+        rng: &mut R,
+    )
+    where
+        GFVOLE: Clone + CustomAddition + InsecureRandom + Zero + Copy,
+        R: RngCore + CryptoRng + ?Sized
+    {
         if process_printing {
             println!("  PA initializes labels");
         }
-        let pa_label_zero_input_vec = (0..public_parameter.num_input_bits).map(
-            |_| GFVOLE::insecurely_random()
+        let label_zero_input_vec = (0..public_parameter.num_input_bits).map(
+            |_| GFVOLE::insecurely_random(rng)
         ).collect::<GFVec<GFVOLE>>();
-        let pa_label_zero_output_and_vec = (0..public_parameter.big_iw_size).map(
-            |_| GFVOLE::insecurely_random()
+        let label_zero_output_and_vec = (0..public_parameter.big_iw_size).map(
+            |_| GFVOLE::insecurely_random(rng)
         ).collect::<GFVec<GFVOLE>>();
-        assert_eq!(pa_label_zero_input_vec.len(), public_parameter.num_input_bits);
-        assert_eq!(pa_label_zero_output_and_vec.len(), public_parameter.big_iw_size);
-
-        if process_printing {
-            println!("  PA initializes traces");
-        }
-        initialize_trace::<u8, BitVec>(
-            public_parameter,
-            &pa_secret_state.r_input_bit_vec,
-            &pa_secret_state.r_output_and_bit_vec,
-            &mut pa_secret_state.r_trace_bit_vec,
-        );
-        assert_eq!(pa_secret_state.r_trace_bit_vec.len(), public_parameter.num_wires);
-        initialize_trace::<GFVOLE, GFVec<GFVOLE>>(
-            public_parameter,
-            &pa_secret_state.vole_mac_r_input_vec,
-            &pa_secret_state.vole_mac_r_output_and_vec,
-            &mut pa_secret_state.vole_mac_r_trace_vec
-        );
-        assert_eq!(pa_secret_state.vole_mac_r_trace_vec.len(), public_parameter.num_wires);
-        initialize_trace::<GFVOLE, GFVec<GFVOLE>>(
-            public_parameter,
-            &pa_secret_state.other_vole_key_r_input_vec,
-            &pa_secret_state.other_vole_key_r_output_and_vec,
-            &mut pa_secret_state.other_vole_key_r_trace_vec
-        );
-        assert_eq!(pa_secret_state.other_vole_key_r_trace_vec.len(), public_parameter.num_wires);
         initialize_trace::<GFVOLE, GFVec<GFVOLE>>(
             public_parameter,
-            &pa_label_zero_input_vec,
-            &pa_label_zero_output_and_vec,
+            &label_zero_input_vec,
+            &label_zero_output_and_vec,
             pa_secret_state.label_zero_vec.as_mut().unwrap()
         );
-        assert_eq!(pa_secret_state.label_zero_vec.as_ref().unwrap().len(), public_parameter.num_wires);
+    }
 
+    // Everything one party does on her own after installing her share from FPre (and PA initializing labels): following the circuit
+    // with VOLE MACs and keys (and labels for PA), committing via PiSVOLE2PC and following the circuit with VOLEitH MACs
+    pub fn preprocess_locally<GFVOLE, GFVOLEitH>(
+        process_printing: bool,
+        bristol_fashion_adaptor: &BristolFashionAdaptor,
+        public_parameter: &PublicParameter,
+        secret_state: &mut ProverSecretState<GFVOLE, GFVOLEitH>,
+        is_pa: bool,
+    ) -> (Vec<Hash>, Vec<MaskedBitTuple>)
+    where
+        GFVOLE: Clone + CustomAddition + CustomMultiplyingBit + InsecureRandom + Zero + Copy + Sync + Send,
+        GFVOLEitH: Clone + Zero + CustomAddition + LeafIndexForGF + Copy + CustomMultiplyingBit + Sync + Send
+    {
+        let party = if is_pa { "PA" } else { "PB" };
         if process_printing {
-            println!("  PB initializes traces");
+            println!("  {} initializes traces", party);
         }
         initialize_trace::<u8, BitVec>(
             public_parameter,
-            &pb_secret_state.r_input_bit_vec,
-            &pb_secret_state.r_output_and_bit_vec,
-            &mut pb_secret_state.r_trace_bit_vec,
+            &secret_state.r_input_bit_vec,
+            &secret_state.r_output_and_bit_vec,
+            &mut secret_state.r_trace_bit_vec,
         );
-        assert_eq!(pb_secret_state.r_trace_bit_vec.len(), public_parameter.num_wires);
         initialize_trace::<GFVOLE, GFVec<GFVOLE>>(
             public_parameter,
-            &pb_secret_state.vole_mac_r_input_vec,
-            &pb_secret_state.vole_mac_r_output_and_vec,
-            &mut pb_secret_state.vole_mac_r_trace_vec
+            &secret_state.vole_mac_r_input_vec,
+            &secret_state.vole_mac_r_output_and_vec,
+            &mut secret_state.vole_mac_r_trace_vec
         );
-        assert_eq!(pb_secret_state.vole_mac_r_trace_vec.len(), public_parameter.num_wires);
         initialize_trace::<GFVOLE, GFVec<GFVOLE>>(
             public_parameter,
-            &pb_secret_state.other_vole_key_r_input_vec,
-            &pb_secret_state.other_vole_key_r_output_and_vec,
-            &mut pb_secret_state.other_vole_key_r_trace_vec
+            &secret_state.other_vole_key_r_input_vec,
+            &secret_state.other_vole_key_r_output_and_vec,
+            &mut secret_state.other_vole_key_r_trace_vec
         );
-        assert_eq!(pb_secret_state.other_vole_key_r_trace_vec.len(), public_parameter.num_wires);
-
-        if process_printing {
-            println!("  Both parties obtain multiplication AND triples");
-        }
-        InsecureFunctionalityPre::generate_random_and_tuples(
-            public_parameter.kappa,
-            public_parameter.big_l,
-            &mut pa_secret_state.tilde_a_bit_vec_rep,
-            &mut pa_secret_state.tilde_b_bit_vec_rep,
-            &mut pa_secret_state.tilde_c_bit_vec_rep,
-            &mut pb_secret_state.tilde_a_bit_vec_rep,
-            &mut pb_secret_state.tilde_b_bit_vec_rep,
-            &mut pb_secret_state.tilde_c_bit_vec_rep,
-        );
-
-        // Prepare masked_r_and_output_bit_vec for computing with AND gates
-        // let mut pa_r_prime_bit_vec = vec![0u8; public_parameter.big_iw_size];
-        // let mut pa_vole_mac_r_prime_vec = vec![GFVOLE::zero(); public_parameter.big_iw_size];
-        // let mut pa_other_vole_key_r_prime_vec = vec![GFVOLE::zero(); public_parameter.big_iw_size];
-        // let mut pa_middle_r_and_output_bit_vec = vec![[0u8; 4]; public_parameter.big_iw_size];
-        // let mut pa_middle_vole_mac_r_and_output_vec = vec![[GFVOLE::zero(); 4]; public_parameter.big_iw_size];
-        // let mut pa_other_middle_vole_key_r_and_output_vec = vec![[GFVOLE::zero(); 4]; public_parameter.big_iw_size];
-        // let mut pb_r_prime_bit_vec = vec![0u8; public_parameter.big_iw_size];
-        // let mut pb_vole_mac_r_prime_vec = vec![GFVOLE::zero(); public_parameter.big_iw_size];
-        // let mut pb_other_vole_key_r_prime_vec = vec![GFVOLE::zero(); public_parameter.big_iw_size];
-        // let mut pb_middle_r_and_output_bit_vec = vec![[0u8; 4]; public_parameter.big_iw_size];
-        // let mut pb_middle_vole_mac_r_and_output_vec = vec![[GFVOLE::zero(); 4]; public_parameter.big_iw_size];
-        // let mut pb_other_middle_vole_key_r_and_output_vec = vec![[GFVOLE::zero(); 4]; public_parameter.big_iw_size];
 
         if process_printing {
-            println!("  Compute VOLE MACs and keys following circuit's topological order");
+            println!("  {} computes VOLE MACs and keys following circuit's topological order", party);
         }
+        // only PA adds delta to her keys of the middle values, only PB flips her middle bits
+        let delta_for_middle = if is_pa { secret_state.delta } else { None };
         let mut and_cursor = 0usize;
         for gate in bristol_fashion_adaptor.get_gate_vec() {
             match gate.gate_type {
                 GateType::XOR => {
-                    // compute for pa
-                    pa_secret_state.r_trace_bit_vec[gate.output_wire] = pa_secret_state.r_trace_bit_vec[gate.left_input_wire] ^ pa_secret_state.r_trace_bit_vec[gate.right_input_wire];
-                    pa_secret_state.vole_mac_r_trace_vec[gate.output_wire] = pa_secret_state.vole_mac_r_trace_vec[gate.left_input_wire].custom_add(&pa_secret_state.vole_mac_r_trace_vec[gate.right_input_wire]);
-                    pa_secret_state.other_vole_key_r_trace_vec[gate.output_wire] = pa_secret_state.other_vole_key_r_trace_vec[gate.left_input_wire].custom_add(&pa_secret_state.other_vole_key_r_trace_vec[gate.right_input_wire]);
-                    pa_secret_state.label_zero_vec.as_mut().unwrap()[gate.output_wire] = pa_secret_state.label_zero_vec.as_ref().unwrap()[gate.left_input_wire].custom_add(&pa_secret_state.label_zero_vec.as_ref().unwrap()[gate.right_input_wire]);
-
-                    // compute for pb
-                    pb_secret_state.r_trace_bit_vec[gate.output_wire] = pb_secret_state.r_trace_bit_vec[gate.left_input_wire] ^ pb_secret_state.r_trace_bit_vec[gate.right_input_wire];
-                    pb_secret_state.vole_mac_r_trace_vec[gate.output_wire] = pb_secret_state.vole_mac_r_trace_vec[gate.left_input_wire].custom_add(&pb_secret_state.vole_mac_r_trace_vec[gate.right_input_wire]);
-                    pb_secret_state.other_vole_key_r_trace_vec[gate.output_wire] = pb_secret_state.other_vole_key_r_trace_vec[gate.left_input_wire].custom_add(&pb_secret_state.other_vole_key_r_trace_vec[gate.right_input_wire]);
+                    secret_state.r_trace_bit_vec.set(gate.output_wire, secret_state.r_trace_bit_vec[gate.left_input_wire] ^ secret_state.r_trace_bit_vec[gate.right_input_wire]);
+                    secret_state.vole_mac_r_trace_vec[gate.output_wire] = secret_state.vole_mac_r_trace_vec[gate.left_input_wire].custom_add(&secret_state.vole_mac_r_trace_vec[gate.right_input_wire]);
+                    secret_state.other_vole_key_r_trace_vec[gate.output_wire] = secret_state.other_vole_key_r_trace_vec[gate.left_input_wire].custom_add(&secret_state.other_vole_key_r_trace_vec[gate.right_input_wire]);
+                    if let Some(label_zero_vec) = secret_state.label_zero_vec.as_mut() {
+                        label_zero_vec[gate.output_wire] = label_zero_vec[gate.left_input_wire].custom_add(&label_zero_vec[gate.right_input_wire]);
+                    }
                 },
                 GateType::NOT => {
-                    // unimplemented!();
-                    // compute for pa
-                    pa_secret_state.r_trace_bit_vec[gate.output_wire] = pa_secret_state.r_trace_bit_vec[gate.left_input_wire];
-                    pa_secret_state.vole_mac_r_trace_vec[gate.output_wire] = pa_secret_state.vole_mac_r_trace_vec[gate.left_input_wire];
-                    pa_secret_state.other_vole_key_r_trace_vec[gate.output_wire] = pa_secret_state.other_vole_key_r_trace_vec[gate.left_input_wire];
-                    pa_secret_state.label_zero_vec.as_mut().unwrap()[gate.output_wire] = pa_secret_state.label_zero_vec.as_ref().unwrap()[gate.left_input_wire];
-
-                    // compute for pb
-                    pb_secret_state.r_trace_bit_vec[gate.output_wire] = pb_secret_state.r_trace_bit_vec[gate.left_input_wire];
-                    pb_secret_state.vole_mac_r_trace_vec[gate.output_wire] = pb_secret_state.vole_mac_r_trace_vec[gate.left_input_wire];
-                    pb_secret_state.other_vole_key_r_trace_vec[gate.output_wire] = pb_secret_state.other_vole_key_r_trace_vec[gate.left_input_wire];
+                    secret_state.r_trace_bit_vec.set(gate.output_wire, secret_state.r_trace_bit_vec[gate.left_input_wire]);
+                    secret_state.vole_mac_r_trace_vec[gate.output_wire] = secret_state.vole_mac_r_trace_vec[gate.left_input_wire];
+                    secret_state.other_vole_key_r_trace_vec[gate.output_wire] = secret_state.other_vole_key_r_trace_vec[gate.left_input_wire];
+                    if let Some(label_zero_vec) = secret_state.label_zero_vec.as_mut() {
+                        label_zero_vec[gate.output_wire] = label_zero_vec[gate.left_input_wire];
+                    }
                 },
                 GateType::AND => {
-                    InsecureFunctionalityPre::generate_random_authenticated_and_tuples(
-                        pa_secret_state.delta.as_ref().unwrap(),
-                        pa_secret_state.r_trace_bit_vec[gate.left_input_wire],
-                        pa_secret_state.r_trace_bit_vec[gate.right_input_wire],
-                        &mut pa_secret_state.r_prime_bit_vec[and_cursor],
-                        &mut pa_secret_state.vole_mac_r_prime_vec[and_cursor],
-                        &mut pb_secret_state.other_vole_key_r_prime_vec[and_cursor],
-                        pb_secret_state.delta.as_ref().unwrap(),
-                        pb_secret_state.r_trace_bit_vec[gate.left_input_wire],
-                        pb_secret_state.r_trace_bit_vec[gate.right_input_wire],
-                        &mut pb_secret_state.r_prime_bit_vec[and_cursor],
-                        &mut pb_secret_state.vole_mac_r_prime_vec[and_cursor],
-                        &mut pa_secret_state.other_vole_key_r_prime_vec[and_cursor],
-                    );
-
-                    // PA computes
                     for k in 0..4 {
                         Self::compute_vole_authenticated_middle_r_and_output_bit_vec(
-                            k, &pa_secret_state.delta,
-                            &mut pa_secret_state.middle_r_and_output_bit_vec[and_cursor][k],
-                            pa_secret_state.r_prime_bit_vec[and_cursor],
-                            pa_secret_state.r_trace_bit_vec[gate.output_wire],
-                            pa_secret_state.r_trace_bit_vec[gate.left_input_wire],
-                            pa_secret_state.r_trace_bit_vec[gate.right_input_wire],
-                            &mut pa_secret_state.middle_vole_mac_r_and_output_vec[and_cursor][k],
-                            &pa_secret_state.vole_mac_r_prime_vec[and_cursor],
-                            &pa_secret_state.vole_mac_r_trace_vec[gate.output_wire],
-                            &pa_secret_state.vole_mac_r_trace_vec[gate.left_input_wire],
-                            &pa_secret_state.vole_mac_r_trace_vec[gate.right_input_wire],
-                            &mut pa_secret_state.other_middle_vole_key_r_and_output_vec[and_cursor][k],
-                            &pa_secret_state.other_vole_key_r_prime_vec[and_cursor],
-                            &pa_secret_state.other_vole_key_r_trace_vec[gate.output_wire],
-                            &pa_secret_state.other_vole_key_r_trace_vec[gate.left_input_wire],
-                            &pa_secret_state.other_vole_key_r_trace_vec[gate.right_input_wire]
+                            k, &delta_for_middle,
+                            &mut secret_state.middle_r_and_output_bit_vec[and_cursor][k],
+                            secret_state.r_prime_bit_vec[and_cursor],
+                            secret_state.r_trace_bit_vec[gate.output_wire],
+                            secret_state.r_trace_bit_vec[gate.left_input_wire],
+                            secret_state.r_trace_bit_vec[gate.right_input_wire],
+                            &mut secret_state.middle_vole_mac_r_and_output_vec[and_cursor][k],
+                            &secret_state.vole_mac_r_prime_vec[and_cursor],
+                            &secret_state.vole_mac_r_trace_vec[gate.output_wire],
+                            &secret_state.vole_mac_r_trace_vec[gate.left_input_wire],
+                            &secret_state.vole_mac_r_trace_vec[gate.right_input_wire],
+                            &mut secret_state.other_middle_vole_key_r_and_output_vec[and_cursor][k],
+                            &secret_state.other_vole_key_r_prime_vec[and_cursor],
+                            &secret_state.other_vole_key_r_trace_vec[gate.output_wire],
+                            &secret_state.other_vole_key_r_trace_vec[gate.left_input_wire],
+                            &secret_state.other_vole_key_r_trace_vec[gate.right_input_wire]
                         );
                     }
-
-                    // PB computes
-                    for k in 0..4 {
-                        Self::compute_vole_authenticated_middle_r_and_output_bit_vec(
-                            k, &None,
-                            &mut pb_secret_state.middle_r_and_output_bit_vec[and_cursor][k],
-                            pb_secret_state.r_prime_bit_vec[and_cursor],
-                            pb_secret_state.r_trace_bit_vec[gate.output_wire],
-                            pb_secret_state.r_trace_bit_vec[gate.left_input_wire],
-                            pb_secret_state.r_trace_bit_vec[gate.right_input_wire],
-                            &mut pb_secret_state.middle_vole_mac_r_and_output_vec[and_cursor][k],
-                            &pb_secret_state.vole_mac_r_prime_vec[and_cursor],
-                            &pb_secret_state.vole_mac_r_trace_vec[gate.output_wire],
-                            &pb_secret_state.vole_mac_r_trace_vec[gate.left_input_wire],
-                            &pb_secret_state.vole_mac_r_trace_vec[gate.right_input_wire],
-                            &mut pb_secret_state.other_middle_vole_key_r_and_output_vec[and_cursor][k],
-                            &pb_secret_state.other_vole_key_r_prime_vec[and_cursor],
-                            &pb_secret_state.other_vole_key_r_trace_vec[gate.output_wire],
-                            &pb_secret_state.other_vole_key_r_trace_vec[gate.left_input_wire],
-                            &pb_secret_state.other_vole_key_r_trace_vec[gate.right_input_wire]
-                        )
-                    }
-
                     and_cursor += 1;
                 }
             }
         }
 
         if process_printing {
-            println!("  PA obtains VOLEitH MACs from PiSVOLE2PC");
+            println!("  {} obtains VOLEitH MACs from PiSVOLE2PC", party);
         }
         let (
-            pa_com_hash_rep, pa_masked_bit_tuple_rep
+            com_hash_rep, masked_bit_tuple_rep
         ) = ProverInProtocolSVOLE2PC::commit_and_fix_bit_vec_and_mac_vec::<GFVOLE, GFVOLEitH>(
-            process_printing, &public_parameter, pa_secret_state,
+            process_printing, public_parameter, secret_state,
         );
 
         if process_printing {
-            println!("  PB obtains VOLEitH MACs from PiSVOLE2PC");
-        }
-        let (
-            pb_com_hash_rep, pb_masked_bit_tuple_rep
-        ) = ProverInProtocolSVOLE2PC::commit_and_fix_bit_vec_and_mac_vec::<GFVOLE, GFVOLEitH>(
-            process_printing, &public_parameter, pb_secret_state,
-        );
-
-        if process_printing {
-            println!("  PA initializes traces for computing VOLEitH MACs following circuit's topological order");
+            println!("  {} computes VOLEitH MACs following circuit's topological order", party);
         }
         (0..public_parameter.kappa).for_each(
             |repetition_id| {
                 initialize_trace::<GFVOLEitH, GFVec<GFVOLEitH>>(
-                    &public_parameter,
-                    &pa_secret_state.voleith_mac_r_input_vec_rep[repetition_id],
-                    &pa_secret_state.voleith_mac_r_output_and_vec_rep[repetition_id],
-                    &mut pa_secret_state.voleith_mac_r_trace_vec_rep[repetition_id]
-                );
-            }
-        );
-        if process_printing {
-            println!("  PB initializes traces for computing VOLEitH MACs following circuit's topological order");
-        }
-        (0..public_parameter.kappa).for_each(
-            |repetition_id| {
-                initialize_trace::<GFVOLEitH, GFVec<GFVOLEitH>>(
-                    &public_parameter,
-                    &pb_secret_state.voleith_mac_r_input_vec_rep[repetition_id],
-                    &pb_secret_state.voleith_mac_r_output_and_vec_rep[repetition_id],
-                    &mut pb_secret_state.voleith_mac_r_trace_vec_rep[repetition_id],
+                    public_parameter,
+                    &secret_state.voleith_mac_r_input_vec_rep[repetition_id],
+                    &secret_state.voleith_mac_r_output_and_vec_rep[repetition_id],
+                    &mut secret_state.voleith_mac_r_trace_vec_rep[repetition_id]
                 );
             }
         );
-
-        if process_printing {
-            println!("  Both parties compute VOLEitH MACs following circuit's topological order");
-        }
         and_cursor = 0usize;
         for gate in bristol_fashion_adaptor.get_gate_vec() {
             match gate.gate_type {
                 GateType::XOR => {
-                    // compute for pa
-                    for repetition_id in 0..public_parameter.kappa {
-                        pa_secret_state.voleith_mac_r_trace_vec_rep[repetition_id][gate.output_wire] = pa_secret_state.voleith_mac_r_trace_vec_rep[repetition_id][gate.left_input_wire].custom_add(
-                            &pa_secret_state.voleith_mac_r_trace_vec_rep[repetition_id][gate.right_input_wire]
-                        );
-                    }
-
-                    // compute for pb
                     for repetition_id in 0..public_parameter.kappa {
-                        pb_secret_state.voleith_mac_r_trace_vec_rep[repetition_id][gate.output_wire] = pb_secret_state.voleith_mac_r_trace_vec_rep[repetition_id][gate.left_input_wire].custom_add(
-                            &pb_secret_state.voleith_mac_r_trace_vec_rep[repetition_id][gate.right_input_wire]
+                        secret_state.voleith_mac_r_trace_vec_rep[repetition_id][gate.output_wire] = secret_state.voleith_mac_r_trace_vec_rep[repetition_id][gate.left_input_wire].custom_add(
+                            &secret_state.voleith_mac_r_trace_vec_rep[repetition_id][gate.right_input_wire]
                         );
                     }
                 },
                 GateType::NOT => {
-                    // compute for pa
-                    for repetition_id in 0..public_parameter.kappa {
-                        pa_secret_state.voleith_mac_r_trace_vec_rep[repetition_id][gate.output_wire] = pa_secret_state.voleith_mac_r_trace_vec_rep[repetition_id][gate.left_input_wire];
-                    }
-
-                    // compute for pb
                     for repetition_id in 0..public_parameter.kappa {
-                        pb_secret_state.voleith_mac_r_trace_vec_rep[repetition_id][gate.output_wire] = pb_secret_state.voleith_mac_r_trace_vec_rep[repetition_id][gate.left_input_wire];
+                        secret_state.voleith_mac_r_trace_vec_rep[repetition_id][gate.output_wire] = secret_state.voleith_mac_r_trace_vec_rep[repetition_id][gate.left_input_wire];
                     }
                 },
                 GateType::AND => {
-
-                    // PA computes
                     for repetition_id in 0..public_parameter.kappa {
                         for k in 0..4 {
                             Self::compute_voleith_mac_r_and_output_vec(
                                 k,
-                                &mut pa_secret_state.middle_voleith_mac_r_and_output_vec_rep[repetition_id][and_cursor][k],
-                                &pa_secret_state.voleith_mac_r_prime_vec_rep[repetition_id][and_cursor],
-                                &pa_secret_state.voleith_mac_r_trace_vec_rep[repetition_id][gate.output_wire],
-                                &pa_secret_state.voleith_mac_r_trace_vec_rep[repetition_id][gate.left_input_wire],
-                                &pa_secret_state.voleith_mac_r_trace_vec_rep[repetition_id][gate.right_input_wire],
-                            );
-                        }
-                    }
-
-                    // PB computes
-                    for repetition_id in 0..public_parameter.kappa {
-                        for k in 0..4 {
-                            Self::compute_voleith_mac_r_and_output_vec(
-                                k,
-                                &mut pb_secret_state.middle_voleith_mac_r_and_output_vec_rep[repetition_id][and_cursor][k],
-                                &pb_secret_state.voleith_mac_r_prime_vec_rep[repetition_id][and_cursor],
-                                &pb_secret_state.voleith_mac_r_trace_vec_rep[repetition_id][gate.output_wire],
-                                &pb_secret_state.voleith_mac_r_trace_vec_rep[repetition_id][gate.left_input_wire],
-                                &pb_secret_state.voleith_mac_r_trace_vec_rep[repetition_id][gate.right_input_wire],
+                                &mut secret_state.middle_voleith_mac_r_and_output_vec_rep[repetition_id][and_cursor][k],
+                                &secret_state.voleith_mac_r_prime_vec_rep[repetition_id][and_cursor],
+                                &secret_state.voleith_mac_r_trace_vec_rep[repetition_id][gate.output_wire],
+                                &secret_state.voleith_mac_r_trace_vec_rep[repetition_id][gate.left_input_wire],
+                                &secret_state.voleith_mac_r_trace_vec_rep[repetition_id][gate.right_input_wire],
                             );
                         }
                     }
@@ -441,13 +357,39 @@ impl ProverInPA2PC {
             }
         }
 
+        if process_printing {
+            println!("  {} records auxiliary components to her state", party);
+        }
+        for (and_cursor, and_gate_id) in bristol_fashion_adaptor.get_and_gate_id_vec().iter().enumerate() {
+            let gate = &bristol_fashion_adaptor.get_gate_vec()[*and_gate_id];
+            secret_state.r_prime_left_bit_vec.set(and_cursor, secret_state.r_trace_bit_vec[gate.left_input_wire]);
+            secret_state.r_prime_right_bit_vec.set(and_cursor, secret_state.r_trace_bit_vec[gate.right_input_wire]);
+
+            for repetition_id in 0..public_parameter.kappa {
+                secret_state.voleith_mac_r_prime_left_vec_rep[repetition_id][and_cursor] = secret_state.voleith_mac_r_trace_vec_rep[repetition_id][gate.left_input_wire];
+                secret_state.voleith_mac_r_prime_right_vec_rep[repetition_id][and_cursor] = secret_state.voleith_mac_r_trace_vec_rep[repetition_id][gate.right_input_wire];
+            }
+        }
+
+        (com_hash_rep, masked_bit_tuple_rep)
+    }
+
+    pub fn garble<GFVOLE, GFVOLEitH>(
+        process_printing: bool,
+        bristol_fashion_adaptor: &BristolFashionAdaptor,
+        bit_trace_vec_for_labels_in_garbling: &BitVec,
+        public_parameter: &PublicParameter,
+        pa_secret_state: &ProverSecretState<GFVOLE, GFVOLEitH>,
+    ) -> Vec<Vec<GarbledRow<GFVOLE, GFVOLEitH>>>
+    where
+        GFVOLE: Clone + CustomAddition + CustomMultiplyingBit + Zero + Copy + ByteManipulation,
+        GFVOLEitH: Clone + Zero + CustomAddition + Copy + ByteManipulation
+    {
         if process_printing {
             println!("  PA encrypts for garbling");
         }
-        and_cursor = 0usize;
         let mut garbled_table: Vec<Vec<GarbledRow<GFVOLE, GFVOLEitH>>> = vec![vec![GarbledRow::zero(); 4]; public_parameter.big_iw_size];
-        // println!("pa_middle_r: {:?}", pa_secret_state.middle_r_and_output_bit_vec);
-        for and_gate_id in bristol_fashion_adaptor.get_and_gate_id_vec() {
+        for (and_cursor, and_gate_id) in bristol_fashion_adaptor.get_and_gate_id_vec().iter().enumerate() {
             let gate = &bristol_fashion_adaptor.get_gate_vec()[*and_gate_id];
             let mut pa_label = [[GFVOLE::zero(); 2]; 2];
             pa_label[0][bit_trace_vec_for_labels_in_garbling[gate.left_input_wire] as usize] = pa_secret_state.label_zero_vec.as_ref().unwrap()[gate.left_input_wire];
@@ -457,7 +399,7 @@ impl ProverInPA2PC {
             for k in 0..4 {
                 let (k0, k1) = parse_two_bits(k);
                 garbled_table[and_cursor][k as usize] = hasher::hash_for_garbling(
-                    &public_parameter,
+                    public_parameter,
                     &pa_label[0][k0 as usize],
                     &pa_label[1][k1 as usize],
                     gate.output_wire,
@@ -466,7 +408,7 @@ impl ProverInPA2PC {
                 ).custom_add(
                     &GarbledRow::new(
                         pa_secret_state.middle_r_and_output_bit_vec[and_cursor][k as usize],
-                        pa_secret_state.middle_vole_mac_r_and_output_vec[and_cursor][k as usize].clone(),
+                        pa_secret_state.middle_vole_mac_r_and_output_vec[and_cursor][k as usize],
                         (0..public_parameter.kappa).map(
                             |repetition_id| {
                                 pa_secret_state.middle_voleith_mac_r_and_output_vec_rep[repetition_id][and_cursor][k as usize]
@@ -482,62 +424,80 @@ impl ProverInPA2PC {
                     )
                 );
             }
-            and_cursor += 1;
         }
+        garbled_table
+    }
 
+    pub fn commit_middle<GFVOLE, GFVOLEitH>(
+        process_printing: bool,
+        public_parameter: &PublicParameter,
+        pb_secret_state: &mut ProverSecretState<GFVOLE, GFVOLEitH>,
+    ) -> Vec<[Com; 4]>
+    where GFVOLEitH: Copy + ByteManipulation {
         if process_printing {
             println!("  PB commits intermediate messages");
         }
-        and_cursor = 0usize;
-        let mut pb_middle_commitment_vec = vec![[Hash::from_bytes([0u8; 32]); 4]; public_parameter.big_iw_size];
+        let mut pb_middle_commitment_vec = vec![[Com::from_bytes([0u8; 32]); 4]; public_parameter.big_iw_size];
         let (mut current_seed, _) = public_parameter.one_to_two_prg.generate_double(&pb_secret_state.seed_for_commitment_randomness);
-        for _ in 0..public_parameter.big_iw_size {
-            for k in 0..4 {
+        for (and_cursor, commitment_array) in pb_middle_commitment_vec.iter_mut().enumerate() {
+            for (k, commitment) in commitment_array.iter_mut().enumerate() {
                 let randomness;
                 (current_seed, randomness) = public_parameter.one_to_two_prg.generate_double(&current_seed);
-                pb_middle_commitment_vec[and_cursor][k] = hasher::commit_pb_secret(
-                    pb_secret_state.middle_r_and_output_bit_vec[and_cursor][k],
-                    &(0..public_parameter.kappa).map(
-                        |repetition_id| pb_secret_state.middle_voleith_mac_r_and_output_vec_rep[repetition_id][and_cursor][k]
-                    ).collect(),
+                *commitment = commit(
+                    public_parameter.crypto_backend.hash(),
+                    &make_pb_middle_message(
+                        pb_secret_state.middle_r_and_output_bit_vec[and_cursor][k],
+                        pb_secret_state.middle_voleith_mac_r_and_output_vec_rep.iter().map(
+                            |middle_voleith_mac_r_and_output_vec| &middle_voleith_mac_r_and_output_vec[and_cursor][k]
+                        )
+                    ),
                     &randomness
                 );
-                pb_secret_state.commitment_randomness_vec_rep.as_mut().unwrap()[and_cursor][k] = randomness.clone();
-            }
-            and_cursor += 1;
-        }
-
-        if process_printing {
-            println!("  PA records auxiliary components to her state");
-        }
-        and_cursor = 0usize;
-        for and_gate_id in bristol_fashion_adaptor.get_and_gate_id_vec() {
-            let gate = &bristol_fashion_adaptor.get_gate_vec()[*and_gate_id];
-            pa_secret_state.r_prime_left_bit_vec[and_cursor] = pa_secret_state.r_trace_bit_vec[gate.left_input_wire];
-            pa_secret_state.r_prime_right_bit_vec[and_cursor] = pa_secret_state.r_trace_bit_vec[gate.right_input_wire];
-
-            for repetition_id in 0..public_parameter.kappa {
-                pa_secret_state.voleith_mac_r_prime_left_vec_rep[repetition_id][and_cursor] = pa_secret_state.voleith_mac_r_trace_vec_rep[repetition_id][gate.left_input_wire].clone();
-                pa_secret_state.voleith_mac_r_prime_right_vec_rep[repetition_id][and_cursor] = pa_secret_state.voleith_mac_r_trace_vec_rep[repetition_id][gate.right_input_wire].clone();
+                pb_secret_state.commitment_randomness_vec_rep.as_mut().unwrap()[and_cursor][k] = randomness;
             }
-            and_cursor += 1;
         }
+        pb_middle_commitment_vec
+    }
 
+    // Runs the preprocessing of both parties in one process
+    #[allow(clippy::too_many_arguments)]
+    pub fn preprocess<GFVOLE, GFVOLEitH, FPre, R>(
+        process_printing: bool,
+        pa_functionality_pre: &mut FPre,
+        pb_functionality_pre: &mut FPre,
+        rng: &mut R,
+        bristol_fashion_adaptor: &BristolFashionAdaptor,
+        bit_trace_vec_for_labels_in_garbling: &BitVec,
+        public_parameter: &PublicParameter,
+        pa_secret_state: &mut ProverSecretState<GFVOLE, GFVOLEitH>,
+        pb_secret_state: &mut ProverSecretState<GFVOLE, GFVOLEitH>,
+    ) -> PreprocessingTranscript<GFVOLE, GFVOLEitH>
+    where
+        FPre: PreprocessingFunctionality<GFVOLE> + Send,
+        R: RngCore + CryptoRng + ?Sized,
+        GFVOLE: Clone + CustomAddition + CustomMultiplyingBit + InsecureRandom + Zero + Copy + PartialEq + Debug + ByteManipulation + Sync + Send + Encode,
+        GFVOLEitH: Clone + Zero + CustomAddition + LeafIndexForGF + Copy + CustomMultiplyingBit + ByteManipulation + Sync + Send + Encode
+    {
         if process_printing {
-            println!("  PB records auxiliary components to her state");
+            println!("+ Preprocessing...");
         }
-        and_cursor = 0usize;
-        for and_gate_id in bristol_fashion_adaptor.get_and_gate_id_vec() {
-            let gate = &bristol_fashion_adaptor.get_gate_vec()[*and_gate_id];
-            pb_secret_state.r_prime_left_bit_vec[and_cursor] = pb_secret_state.r_trace_bit_vec[gate.left_input_wire];
-            pb_secret_state.r_prime_right_bit_vec[and_cursor] = pb_secret_state.r_trace_bit_vec[gate.right_input_wire];
+        let (pa_share, pb_share) = Self::deal_both::<GFVOLE, FPre>(
+            process_printing, pa_functionality_pre, pb_functionality_pre, bristol_fashion_adaptor, public_parameter
+        );
+        pa_share.install(pa_secret_state);
+        pb_share.install(pb_secret_state);
 
-            for repetition_id in 0..public_parameter.kappa {
-                pb_secret_state.voleith_mac_r_prime_left_vec_rep[repetition_id][and_cursor] = pb_secret_state.voleith_mac_r_trace_vec_rep[repetition_id][gate.left_input_wire].clone();
-                pb_secret_state.voleith_mac_r_prime_right_vec_rep[repetition_id][and_cursor] = pb_secret_state.voleith_mac_r_trace_vec_rep[repetition_id][gate.right_input_wire].clone();
-            }
-            and_cursor += 1;
-        }
+        Self::initialize_labels(process_printing, public_parameter, pa_secret_state, rng);
+        let (pa_com_hash_rep, pa_masked_bit_tuple_rep) = Self::preprocess_locally(
+            process_printing, bristol_fashion_adaptor, public_parameter, pa_secret_state, true
+        );
+        let (pb_com_hash_rep, pb_masked_bit_tuple_rep) = Self::preprocess_locally(
+            process_printing, bristol_fashion_adaptor, public_parameter, pb_secret_state, false
+        );
+        let garbled_table = Self::garble(
+            process_printing, bristol_fashion_adaptor, bit_trace_vec_for_labels_in_garbling, public_parameter, pa_secret_state
+        );
+        let pb_middle_commitment_vec = Self::commit_middle(process_printing, public_parameter, pb_secret_state);
 
         PreprocessingTranscript::new(
             pa_com_hash_rep,
@@ -549,371 +509,213 @@ impl ProverInPA2PC {
         )
     }
 
-    // fn extract_single_index_rep<PrimitiveType, VecType>(
-    //     public_parameter: &PublicParameter,
-    //     index: usize, vec_rep: &Vec<VecType>
-    // ) -> Vec<PrimitiveType>
-    // where
-    //     PrimitiveType: Clone,
-    //     VecType: Index<usize, Output = PrimitiveType> {
-    //     assert_eq!(vec_rep.len(), public_parameter.kappa);
-    //     (0..public_parameter.kappa).map(
-    //         |repetition_id|
-    //             vec_rep[repetition_id][index].clone()
-    //     ).collect::<Vec<PrimitiveType>>()
-    // }
-
-    pub fn prove<GFVOLE, GFVOLEitH>(
-        process_printing: bool,
-        bristol_fashion_adaptor: &BristolFashionAdaptor,
+    pub fn permute_and_publish_rm<GFVOLE, GFVOLEitH>(
         public_parameter: &PublicParameter,
-        preprocessing_transcript: &PreprocessingTranscript<GFVOLE, GFVOLEitH>,
-        // permutation_rep: &Vec<Vec<usize>>,
-        pa_secret_state: &mut ProverSecretState<GFVOLE, GFVOLEitH>,
-        pb_secret_state: &mut ProverSecretState<GFVOLE, GFVOLEitH>,
-        pa_input_bits: &Vec<u8>,
-        pb_input_bits: &Vec<u8>,
-        // nabla_a_rep: &Vec<GFVOLEitH>, nabla_b_rep: &Vec<GFVOLEitH>,
-    ) -> (ProofTranscript<GFVOLE, GFVOLEitH>, Vec<(SeedU8x16, Vec<SeedU8x16>)>, Vec<(SeedU8x16, Vec<SeedU8x16>)>)
-    where
-        GFVOLE: Clone + Zero + CustomAddition + CustomMultiplyingBit + PartialEq + Debug + ByteManipulation + Debug + Encode,
-        GFVOLEitH: Clone + Zero + CustomAddition + ByteManipulation + Debug + U8ForGF + Encode {
-        if process_printing {
-            println!("+ Proving...");
-        }
-        
-        if process_printing {
-            println!("  Determine permutation_rep via Fiat-Shamir");
-        }
-        let (permutation_rep, auxiliary_input) = hash_to_determine_permutation_rep(
-            public_parameter, preprocessing_transcript
-        );
-
-        if process_printing {
-            println!("  PA permutes");
-        }
-        permute(public_parameter, &permutation_rep, &mut pa_secret_state.tilde_a_bit_vec_rep);
-        permute(public_parameter, &permutation_rep, &mut pa_secret_state.tilde_b_bit_vec_rep);
-        permute(public_parameter, &permutation_rep, &mut pa_secret_state.tilde_c_bit_vec_rep);
-        permute(public_parameter, &permutation_rep, &mut pa_secret_state.voleith_mac_tilde_a_vec_rep);
-        permute(public_parameter, &permutation_rep, &mut pa_secret_state.voleith_mac_tilde_b_vec_rep);
-        permute(public_parameter, &permutation_rep, &mut pa_secret_state.voleith_mac_tilde_c_vec_rep);
-
-        if process_printing {
-            println!("  PB permutes");
-        }
-        permute(public_parameter, &permutation_rep, &mut pb_secret_state.tilde_a_bit_vec_rep);
-        permute(public_parameter, &permutation_rep, &mut pb_secret_state.tilde_b_bit_vec_rep);
-        permute(public_parameter, &permutation_rep, &mut pb_secret_state.tilde_c_bit_vec_rep);
-        permute(public_parameter, &permutation_rep, &mut pb_secret_state.voleith_mac_tilde_a_vec_rep);
-        permute(public_parameter, &permutation_rep, &mut pb_secret_state.voleith_mac_tilde_b_vec_rep);
-        permute(public_parameter, &permutation_rep, &mut pb_secret_state.voleith_mac_tilde_c_vec_rep);
-
-        if process_printing {
-            println!("  PA determines published components");
+        permutation_rep: &Vec<Vec<usize>>,
+        secret_state: &mut ProverSecretState<GFVOLE, GFVOLEitH>,
+    ) -> PublishedRmShare<GFVOLEitH>
+    where GFVOLEitH: Clone + Zero {
+        permute(public_parameter, permutation_rep, &mut secret_state.tilde_a_bit_vec_rep);
+        permute(public_parameter, permutation_rep, &mut secret_state.tilde_b_bit_vec_rep);
+        permute(public_parameter, permutation_rep, &mut secret_state.tilde_c_bit_vec_rep);
+        permute(public_parameter, permutation_rep, &mut secret_state.voleith_mac_tilde_a_vec_rep);
+        permute(public_parameter, permutation_rep, &mut secret_state.voleith_mac_tilde_b_vec_rep);
+        permute(public_parameter, permutation_rep, &mut secret_state.voleith_mac_tilde_c_vec_rep);
+
+        PublishedRmShare {
+            rm_a_vec_rep: split_off_rm(public_parameter, &mut secret_state.tilde_a_bit_vec_rep),
+            rm_b_vec_rep: split_off_rm(public_parameter, &mut secret_state.tilde_b_bit_vec_rep),
+            rm_c_vec_rep: split_off_rm(public_parameter, &mut secret_state.tilde_c_bit_vec_rep),
+            rm_voleith_mac_a_vec_rep: split_off_rm(public_parameter, &mut secret_state.voleith_mac_tilde_a_vec_rep),
+            rm_voleith_mac_b_vec_rep: split_off_rm(public_parameter, &mut secret_state.voleith_mac_tilde_b_vec_rep),
+            rm_voleith_mac_c_vec_rep: split_off_rm(public_parameter, &mut secret_state.voleith_mac_tilde_c_vec_rep),
         }
-        let pa_published_rm_a_vec_rep = split_off_rm(public_parameter, &mut pa_secret_state.tilde_a_bit_vec_rep);
-        let pa_published_rm_b_vec_rep = split_off_rm(public_parameter, &mut pa_secret_state.tilde_b_bit_vec_rep);
-        let pa_published_rm_c_vec_rep = split_off_rm(public_parameter, &mut pa_secret_state.tilde_c_bit_vec_rep);
-        let pa_published_rm_voleith_mac_a_vec_rep = split_off_rm(public_parameter, &mut pa_secret_state.voleith_mac_tilde_a_vec_rep);
-        let pa_published_rm_voleith_mac_b_vec_rep = split_off_rm(public_parameter, &mut pa_secret_state.voleith_mac_tilde_b_vec_rep);
-        let pa_published_rm_voleith_mac_c_vec_rep = split_off_rm(public_parameter, &mut pa_secret_state.voleith_mac_tilde_c_vec_rep);
+    }
 
-        if process_printing {
-            println!("  PB determines published components");
-        }
-        let pb_published_rm_a_vec_rep = split_off_rm(public_parameter, &mut pb_secret_state.tilde_a_bit_vec_rep);
-        let pb_published_rm_b_vec_rep = split_off_rm(public_parameter, &mut pb_secret_state.tilde_b_bit_vec_rep);
-        let pb_published_rm_c_vec_rep = split_off_rm(public_parameter, &mut pb_secret_state.tilde_c_bit_vec_rep);
-        let pb_published_rm_voleith_mac_a_vec_rep = split_off_rm(public_parameter, &mut pb_secret_state.voleith_mac_tilde_a_vec_rep);
-        let pb_published_rm_voleith_mac_b_vec_rep = split_off_rm(public_parameter, &mut pb_secret_state.voleith_mac_tilde_b_vec_rep);
-        let pb_published_rm_voleith_mac_c_vec_rep = split_off_rm(public_parameter, &mut pb_secret_state.voleith_mac_tilde_c_vec_rep);
+    // d and e of PiCheckAND for every block
+    pub fn compute_check_and_masked_share_vec<GFVOLE, GFVOLEitH>(
+        public_parameter: &PublicParameter,
+        secret_state: &ProverSecretState<GFVOLE, GFVOLEitH>,
+    ) -> Vec<CheckAndMaskedShare<GFVOLEitH>>
+    where GFVOLEitH: Clone + Zero + CustomAddition {
+        (0..public_parameter.bs).map(
+            |block_id| ProverInProtocolCheckAND::compute_masked_bits_and_voleith_macs(
+                public_parameter,
+                &secret_state.r_prime_left_bit_vec, &secret_state.voleith_mac_r_prime_left_vec_rep,
+                &secret_state.r_prime_right_bit_vec, &secret_state.voleith_mac_r_prime_right_vec_rep,
+                &extract_block_vec_rep(public_parameter, block_id, &secret_state.tilde_a_bit_vec_rep),
+                &extract_block_vec_rep(public_parameter, block_id, &secret_state.voleith_mac_tilde_a_vec_rep),
+                &extract_block_vec_rep(public_parameter, block_id, &secret_state.tilde_b_bit_vec_rep),
+                &extract_block_vec_rep(public_parameter, block_id, &secret_state.voleith_mac_tilde_b_vec_rep),
+            )
+        ).collect()
+    }
 
-        if process_printing {
-            println!("  Both parties run PiCheckAND");
-        }
-        let check_and_transcript_vec = (0..public_parameter.bs).map(
+    // tilde_z of PiCheckAND for every block, once d and e of both parties are known
+    pub fn compute_check_and_cross_share_vec<GFVOLE, GFVOLEitH>(
+        public_parameter: &PublicParameter,
+        secret_state: &ProverSecretState<GFVOLE, GFVOLEitH>,
+        pa_masked_share_vec: &[CheckAndMaskedShare<GFVOLEitH>],
+        pb_masked_share_vec: &[CheckAndMaskedShare<GFVOLEitH>],
+    ) -> Vec<CheckAndCrossShare<GFVOLEitH>>
+    where GFVOLEitH: Clone + Zero + CustomAddition {
+        (0..public_parameter.bs).map(
             |block_id| {
-                let pa_a_bit_vec_rep = extract_block_vec_rep(&public_parameter, block_id, &pa_secret_state.tilde_a_bit_vec_rep);
-                let pa_voleith_mac_a_vec_rep = extract_block_vec_rep(&public_parameter, block_id, &pa_secret_state.voleith_mac_tilde_a_vec_rep);
-                let pa_b_bit_vec_rep = extract_block_vec_rep(&public_parameter, block_id, &pa_secret_state.tilde_b_bit_vec_rep);
-                let pa_voleith_mac_b_vec_rep = extract_block_vec_rep(&public_parameter, block_id, &pa_secret_state.voleith_mac_tilde_b_vec_rep);
-                let pa_c_bit_vec_rep = extract_block_vec_rep(&public_parameter, block_id, &pa_secret_state.tilde_c_bit_vec_rep);
-                let pa_voleith_mac_c_vec_rep = extract_block_vec_rep(&public_parameter, block_id, &pa_secret_state.voleith_mac_tilde_c_vec_rep);
-                let (
-                    (pa_d_bit_vec_rep, pa_voleith_mac_d_vec_rep),
-                    (pa_e_bit_vec_rep, pa_voleith_mac_e_vec_rep)
-                ) = ProverInProtocolCheckAND::compute_masked_bits_and_voleith_macs(
-                    public_parameter,
-                    &pa_secret_state.r_prime_left_bit_vec, &pa_secret_state.voleith_mac_r_prime_left_vec_rep,
-                    &pa_secret_state.r_prime_right_bit_vec, &pa_secret_state.voleith_mac_r_prime_right_vec_rep,
-                    &pa_a_bit_vec_rep, &pa_voleith_mac_a_vec_rep,
-                    &pa_b_bit_vec_rep, &pa_voleith_mac_b_vec_rep,
-                );
-
-                let pb_a_bit_vec_rep = extract_block_vec_rep(&public_parameter, block_id, &pb_secret_state.tilde_a_bit_vec_rep);
-                let pb_voleith_mac_a_vec_rep = extract_block_vec_rep(&public_parameter, block_id, &pb_secret_state.voleith_mac_tilde_a_vec_rep);
-                let pb_b_bit_vec_rep = extract_block_vec_rep(&public_parameter, block_id, &pb_secret_state.tilde_b_bit_vec_rep);
-                let pb_voleith_mac_b_vec_rep = extract_block_vec_rep(&public_parameter, block_id, &pb_secret_state.voleith_mac_tilde_b_vec_rep);
-                let pb_c_bit_vec_rep = extract_block_vec_rep(&public_parameter, block_id, &pb_secret_state.tilde_c_bit_vec_rep);
-                let pb_voleith_mac_c_vec_rep = extract_block_vec_rep(&public_parameter, block_id, &pb_secret_state.voleith_mac_tilde_c_vec_rep);
-                let (
-                    (pb_d_bit_vec_rep, pb_voleith_mac_d_vec_rep),
-                    (pb_e_bit_vec_rep, pb_voleith_mac_e_vec_rep)
-                ) = ProverInProtocolCheckAND::compute_masked_bits_and_voleith_macs(
-                    public_parameter,
-                    &pb_secret_state.r_prime_left_bit_vec, &pb_secret_state.voleith_mac_r_prime_left_vec_rep,
-                    &pb_secret_state.r_prime_right_bit_vec, &pb_secret_state.voleith_mac_r_prime_right_vec_rep,
-                    &pb_a_bit_vec_rep,
-                    &pb_voleith_mac_a_vec_rep,
-                    &pb_b_bit_vec_rep,
-                    &pb_voleith_mac_b_vec_rep,
-                );
-
+                let ((pa_d_bit_vec_rep, _), (pa_e_bit_vec_rep, _)) = &pa_masked_share_vec[block_id];
+                let ((pb_d_bit_vec_rep, _), (pb_e_bit_vec_rep, _)) = &pb_masked_share_vec[block_id];
                 let public_d_sum_bit_vec_rep = pa_d_bit_vec_rep.iter().zip(pb_d_bit_vec_rep.iter()).map(
                     |(pa_d_bit_vec, pb_d_bit_vec)| pa_d_bit_vec.vec_add(pb_d_bit_vec)
                 ).collect::<Vec<BitVec>>();
-
                 let public_e_sum_bit_vec_rep = pa_e_bit_vec_rep.iter().zip(pb_e_bit_vec_rep.iter()).map(
                     |(pa_e_bit_vec, pb_e_bit_vec)| pa_e_bit_vec.vec_add(pb_e_bit_vec)
                 ).collect::<Vec<BitVec>>();
-
-                let (pa_tilde_z_bit_vec_rep, pa_voleith_mac_tilde_z_vec_rep) = ProverInProtocolCheckAND::compute_masked_cross_bits_and_voleith_macs(
-                    &public_parameter,
-                    &public_d_sum_bit_vec_rep, &public_e_sum_bit_vec_rep,
-                    &pa_secret_state.r_prime_bit_vec, &pa_secret_state.voleith_mac_r_prime_vec_rep,
-                    &pa_a_bit_vec_rep, &pa_voleith_mac_a_vec_rep,
-                    &pa_b_bit_vec_rep, &pa_voleith_mac_b_vec_rep,
-                    &pa_c_bit_vec_rep, &pa_voleith_mac_c_vec_rep,
-                );
-
-                let (pb_tilde_z_bit_vec_rep, pb_voleith_mac_tilde_z_vec_rep) = ProverInProtocolCheckAND::compute_masked_cross_bits_and_voleith_macs(
-                    &public_parameter,
+                ProverInProtocolCheckAND::compute_masked_cross_bits_and_voleith_macs(
+                    public_parameter,
                     &public_d_sum_bit_vec_rep, &public_e_sum_bit_vec_rep,
-                    &pb_secret_state.r_prime_bit_vec, &pb_secret_state.voleith_mac_r_prime_vec_rep,
-                    &pb_a_bit_vec_rep, &pb_voleith_mac_a_vec_rep,
-                    &pb_b_bit_vec_rep, &pb_voleith_mac_b_vec_rep,
-                    &pb_c_bit_vec_rep, &pb_voleith_mac_c_vec_rep,
-                );
-
-                // collect all
-                CheckAndTranscript::new(
-                    (
-                        (pa_d_bit_vec_rep, pa_voleith_mac_d_vec_rep),
-                        (pa_e_bit_vec_rep, pa_voleith_mac_e_vec_rep),
-                        (pa_tilde_z_bit_vec_rep, pa_voleith_mac_tilde_z_vec_rep)
-                    ),
-                    (
-                        (pb_d_bit_vec_rep, pb_voleith_mac_d_vec_rep),
-                        (pb_e_bit_vec_rep, pb_voleith_mac_e_vec_rep),
-                        (pb_tilde_z_bit_vec_rep, pb_voleith_mac_tilde_z_vec_rep)
-                    ),
+                    &secret_state.r_prime_bit_vec, &secret_state.voleith_mac_r_prime_vec_rep,
+                    &extract_block_vec_rep(public_parameter, block_id, &secret_state.tilde_a_bit_vec_rep),
+                    &extract_block_vec_rep(public_parameter, block_id, &secret_state.voleith_mac_tilde_a_vec_rep),
+                    &extract_block_vec_rep(public_parameter, block_id, &secret_state.tilde_b_bit_vec_rep),
+                    &extract_block_vec_rep(public_parameter, block_id, &secret_state.voleith_mac_tilde_b_vec_rep),
+                    &extract_block_vec_rep(public_parameter, block_id, &secret_state.tilde_c_bit_vec_rep),
+                    &extract_block_vec_rep(public_parameter, block_id, &secret_state.voleith_mac_tilde_c_vec_rep),
                 )
             }
-        ).collect();
+        ).collect()
+    }
 
-        if process_printing {
-            println!("  PA processes inputs");
-        }
-        // let pa_published_authenticated_input_vec = public_parameter.big_ib.iter().map(
-        //     |input_wire| (
-        //         pa_secret_state.r_trace_bit_vec[*input_wire],
-        //         pa_secret_state.vole_mac_r_trace_vec[*input_wire].clone(),
-        //         Self::extract_single_index_rep(public_parameter, *input_wire, &pa_secret_state.voleith_mac_r_trace_vec_rep)
-        //     )
-        // ).collect::<Vec<(u8, GFVOLE, Vec<GFVOLEitH>)>>();
-        let pa_published_input_r_bit_vec = BitVec::from(
-            public_parameter.big_ib.iter().map(
-                |input_wire| pa_secret_state.r_trace_bit_vec[*input_wire]
-            ).collect()
-        );
-        let pa_published_input_vole_mac_r_vec = GFVec::<GFVOLE>::from(
-            public_parameter.big_ib.iter().map(
-                |input_wire| pa_secret_state.vole_mac_r_trace_vec[*input_wire].clone()
-            ).collect()
-        );
-        let pa_published_input_voleith_mac_r_vec_rep = (0..public_parameter.kappa).map(
-            |repetition_id| GFVec::<GFVOLEitH>::from_vec(public_parameter.big_ib.iter().map(
-                    |input_wire| pa_secret_state.voleith_mac_r_trace_vec_rep[repetition_id][*input_wire].clone()
-                ).collect()
+    pub fn assemble_check_and_transcript_vec<GFVOLEitH: Encode>(
+        pa_masked_share_vec: Vec<CheckAndMaskedShare<GFVOLEitH>>,
+        pa_cross_share_vec: Vec<CheckAndCrossShare<GFVOLEitH>>,
+        pb_masked_share_vec: Vec<CheckAndMaskedShare<GFVOLEitH>>,
+        pb_cross_share_vec: Vec<CheckAndCrossShare<GFVOLEitH>>,
+    ) -> Vec<CheckAndTranscript<GFVOLEitH>> {
+        pa_masked_share_vec.into_iter().zip(pa_cross_share_vec).zip(
+            pb_masked_share_vec.into_iter().zip(pb_cross_share_vec)
+        ).map(
+            |(((pa_d, pa_e), pa_tilde_z), ((pb_d, pb_e), pb_tilde_z))| CheckAndTranscript::new(
+                (pa_d, pa_e, pa_tilde_z), (pb_d, pb_e, pb_tilde_z)
             )
-        ).collect();
+        ).collect()
+    }
 
-        if process_printing {
-            println!("  PB checks what PA just published and partially computes hat_z at inputs");
+    // The masks of one party at the given wires, with their VOLE and VOLEitH MACs
+    pub fn publish_wire_share<GFVOLE, GFVOLEitH>(
+        public_parameter: &PublicParameter,
+        secret_state: &ProverSecretState<GFVOLE, GFVOLEitH>,
+        wire_vec: &[usize],
+    ) -> PublishedWireShare<GFVOLE, GFVOLEitH>
+    where GFVOLE: Clone + Zero, GFVOLEitH: Clone + Zero {
+        PublishedWireShare {
+            r_bit_vec: wire_vec.iter().map(|wire| secret_state.r_trace_bit_vec[*wire]).collect(),
+            vole_mac_r_vec: wire_vec.iter().map(|wire| secret_state.vole_mac_r_trace_vec[*wire].clone()).collect(),
+            voleith_mac_r_vec_rep: (0..public_parameter.kappa).map(
+                |repetition_id| wire_vec.iter().map(
+                    |wire| secret_state.voleith_mac_r_trace_vec_rep[repetition_id][*wire].clone()
+                ).collect()
+            ).collect(),
         }
-        let mut input_cursor = 0usize;
-        let mut pb_published_hat_z_input_vec_with_ib = vec![0u8; public_parameter.big_ib.len()];
-        public_parameter.big_ib.iter().for_each(|input_wire| {
-            let pa_input_bit = pa_published_input_r_bit_vec[input_cursor];
-            let pa_vole_mac = &pa_published_input_vole_mac_r_vec[input_cursor];
-            assert_eq!(
-                pb_secret_state.other_vole_key_r_trace_vec[*input_wire],
-                pa_vole_mac.custom_add(
-                    &pb_secret_state.delta.as_ref().unwrap().custom_multiply_bit(pa_input_bit)
-                )
-            );
-            pb_published_hat_z_input_vec_with_ib[input_cursor] = pa_input_bit ^ pb_secret_state.r_trace_bit_vec[*input_wire] ^ pb_input_bits[input_cursor];
-            input_cursor += 1;
-        });
+    }
 
-        if process_printing {
-            println!("  PA publishes labels");
-        }
-        input_cursor = 0usize;
-        let pa_published_label_with_ib = public_parameter.big_ib.iter().map(
-            |input_wire| {
-                let label = pa_secret_state.label_zero_vec.as_ref().unwrap()[*input_wire].custom_add(
-                    &pa_secret_state.delta.as_ref().unwrap().custom_multiply_bit(pb_published_hat_z_input_vec_with_ib[input_cursor])
-                );
-                input_cursor += 1;
-                label
+    // The owner of the inputs at input_wire_vec checks the masks the other party published there
+    // and computes hat_z from her own inputs
+    pub fn check_input_share_and_mask_inputs<GFVOLE, GFVOLEitH>(
+        secret_state: &ProverSecretState<GFVOLE, GFVOLEitH>,
+        is_pa: bool,
+        input_wire_vec: &[usize],
+        other_input_share: &PublishedWireShare<GFVOLE, GFVOLEitH>,
+        input_bits: &[u8],
+    ) -> Result<Vec<u8>, PartyError>
+    where GFVOLE: Clone + Zero + CustomAddition + CustomMultiplyingBit + PartialEq {
+        if input_bits.len() != input_wire_vec.len() {
+            return Err(PartyError::Length { field: "input_bits", expected: input_wire_vec.len(), actual: input_bits.len() });
+        }
+        input_wire_vec.iter().enumerate().map(
+            |(input_cursor, input_wire)| {
+                let other_input_bit = other_input_share.r_bit_vec[input_cursor];
+                if secret_state.other_vole_key_r_trace_vec[*input_wire] != other_input_share.vole_mac_r_vec[input_cursor].custom_add(
+                    &secret_state.delta.as_ref().unwrap().custom_multiply_bit(other_input_bit)
+                ) {
+                    return Err(PartyError::InputMac { is_pa: !is_pa, input_wire: *input_wire });
+                }
+                Ok(other_input_bit ^ secret_state.r_trace_bit_vec[*input_wire] ^ input_bits[input_cursor])
             }
-        ).collect::<Vec<GFVOLE>>();
+        ).collect()
+    }
 
-        if process_printing {
-            println!("  PB processes inputs");
-        }
-        // let pb_published_authenticated_input_vec = public_parameter.big_ia.iter().map(
-        //     |input_wire| (
-        //         pb_secret_state.r_trace_bit_vec[*input_wire],
-        //         pb_secret_state.vole_mac_r_trace_vec[*input_wire].clone(),
-        //         Self::extract_single_index_rep(public_parameter, *input_wire, &pb_secret_state.voleith_mac_r_trace_vec_rep)
-        //     )
-        // ).collect::<Vec<(u8, GFVOLE, Vec<GFVOLEitH>)>>();
-        let pb_published_input_r_bit_vec = BitVec::from(
-            public_parameter.big_ia.iter().map(
-                |input_wire| pb_secret_state.r_trace_bit_vec[*input_wire]
-            ).collect()
-        );
-        let pb_published_input_vole_mac_r_vec = GFVec::<GFVOLE>::from(
-            public_parameter.big_ia.iter().map(
-                |input_wire| pb_secret_state.vole_mac_r_trace_vec[*input_wire].clone()
-            ).collect()
-        );
-        let pb_published_input_voleith_mac_r_vec_rep = (0..public_parameter.kappa).map(
-            |repetition_id| GFVec::<GFVOLEitH>::from_vec(
-                public_parameter.big_ia.iter().map(
-                    |input_wire| pb_secret_state.voleith_mac_r_trace_vec_rep[repetition_id][*input_wire].clone()
-                ).collect()
+    pub fn publish_input_labels<GFVOLE, GFVOLEitH>(
+        public_parameter: &PublicParameter,
+        pa_secret_state: &ProverSecretState<GFVOLE, GFVOLEitH>,
+        pa_hat_z_input_bit_vec_with_ia: Vec<u8>,
+        pb_hat_z_input_bit_vec_with_ib: &[u8],
+    ) -> PAInputLabelMessage<GFVOLE>
+    where GFVOLE: Clone + Zero + CustomAddition + CustomMultiplyingBit {
+        let compute_label_vec = |input_wire_vec: &[usize], hat_z_bit_vec: &[u8]| input_wire_vec.iter().zip(hat_z_bit_vec).map(
+            |(input_wire, hat_z_bit)| pa_secret_state.label_zero_vec.as_ref().unwrap()[*input_wire].custom_add(
+                &pa_secret_state.delta.as_ref().unwrap().custom_multiply_bit(*hat_z_bit)
             )
-        ).collect();
-
-        if process_printing {
-            println!("  PA checks what PB just published and partially computes hat_z at inputs");
-        }
-        input_cursor = 0usize;
-        let mut pa_published_hat_z_input_vec_with_ia = vec![0u8; public_parameter.big_ia.len()];
-        public_parameter.big_ia.iter().for_each(|input_wire| {
-            let pb_input_bit = pb_published_input_r_bit_vec[input_cursor];
-            let pb_vole_mac = &pb_published_input_vole_mac_r_vec[input_cursor];
-            assert_eq!(
-                pa_secret_state.other_vole_key_r_trace_vec[*input_wire],
-                pb_vole_mac.custom_add(
-                    &pa_secret_state.delta.as_ref().unwrap().custom_multiply_bit(pb_input_bit)
-                )
-            );
-            pa_published_hat_z_input_vec_with_ia[input_cursor] = pb_input_bit ^ pa_secret_state.r_trace_bit_vec[*input_wire] ^ pa_input_bits[input_cursor];
-            input_cursor += 1;
-        });
-
-        if process_printing {
-            println!("  PA publishes labels");
-        }
-        input_cursor = 0usize;
-        let pa_published_label_with_ia = public_parameter.big_ia.iter().map(
-            |input_wire| {
-                let label = pa_secret_state.label_zero_vec.as_ref().unwrap()[*input_wire].custom_add(
-                    &pa_secret_state.delta.as_ref().unwrap().custom_multiply_bit(pa_published_hat_z_input_vec_with_ia[input_cursor])
-                );
-                input_cursor += 1;
-                label
-            }
         ).collect::<Vec<GFVOLE>>();
-
-        if process_printing {
-            println!("  Initialize proof transcript");
-        }
-        let mut proof_transcript = ProofTranscript::new(
-            public_parameter,
-            pa_published_rm_a_vec_rep,
-            pa_published_rm_b_vec_rep,
-            pa_published_rm_c_vec_rep,
-            pa_published_rm_voleith_mac_a_vec_rep,
-            pa_published_rm_voleith_mac_b_vec_rep,
-            pa_published_rm_voleith_mac_c_vec_rep,
-            pb_published_rm_a_vec_rep,
-            pb_published_rm_b_vec_rep,
-            pb_published_rm_c_vec_rep,
-            pb_published_rm_voleith_mac_a_vec_rep,
-            pb_published_rm_voleith_mac_b_vec_rep,
-            pb_published_rm_voleith_mac_c_vec_rep,
-            check_and_transcript_vec,
-            pa_published_input_r_bit_vec,
-            // pa_published_input_vole_mac_r_vec,
-            pa_published_input_voleith_mac_r_vec_rep,
-            pb_published_input_r_bit_vec,
-            // pb_published_input_vole_mac_r_vec,
-            pb_published_input_voleith_mac_r_vec_rep,
-        );
-
-        if process_printing {
-            println!("  Filling hat_z at inputs");
-        }
-        input_cursor = 0usize;
-        for input_wire in &public_parameter.big_ia {
-            proof_transcript.published_hat_z_input_bit_vec[*input_wire] = pa_published_hat_z_input_vec_with_ia[input_cursor];
-            input_cursor += 1;
-        }
-        input_cursor = 0usize;
-        for input_wire in &public_parameter.big_ib {
-            proof_transcript.published_hat_z_input_bit_vec[*input_wire] = pb_published_hat_z_input_vec_with_ib[input_cursor];
-            input_cursor += 1;
+        PAInputLabelMessage {
+            label_with_ia: compute_label_vec(&public_parameter.big_ia, &pa_hat_z_input_bit_vec_with_ia),
+            label_with_ib: compute_label_vec(&public_parameter.big_ib, pb_hat_z_input_bit_vec_with_ib),
+            hat_z_input_bit_vec_with_ia: pa_hat_z_input_bit_vec_with_ia,
         }
+    }
 
+    // PB evaluates the garbled circuit, checks the VOLE MACs of the decrypted rows and of PA's outputs,
+    // and determines the outputs
+    #[allow(clippy::too_many_arguments)]
+    pub fn evaluate<GFVOLE, GFVOLEitH>(
+        process_printing: bool,
+        bristol_fashion_adaptor: &BristolFashionAdaptor,
+        public_parameter: &PublicParameter,
+        garbled_table: &[Vec<GarbledRow<GFVOLE, GFVOLEitH>>],
+        pb_secret_state: &ProverSecretState<GFVOLE, GFVOLEitH>,
+        pb_hat_z_input_bit_vec_with_ib: &[u8],
+        pa_input_label_message: &PAInputLabelMessage<GFVOLE>,
+        pa_output_share: &PublishedWireShare<GFVOLE, GFVOLEitH>,
+    ) -> Result<EvaluationMessage<GFVOLE, GFVOLEitH>, PartyError>
+    where
+        GFVOLE: Clone + Zero + CustomAddition + CustomMultiplyingBit + PartialEq + ByteManipulation,
+        GFVOLEitH: Clone + Zero + CustomAddition + ByteManipulation {
         if process_printing {
             println!("  PB evaluates circuit following circuit's topological order");
         }
         let mut recovered_label_vec = vec![GFVOLE::zero(); public_parameter.num_wires];
         let mut recovered_hat_z_bit_vec = vec![0u8; public_parameter.num_wires];
-        input_cursor = 0usize;
-        public_parameter.big_ia.iter().for_each(|input_wire| {
-            recovered_label_vec[*input_wire] = pa_published_label_with_ia[input_cursor].clone();
-            recovered_hat_z_bit_vec[*input_wire] = pa_published_hat_z_input_vec_with_ia[input_cursor];
-            input_cursor += 1;
-        });
-        input_cursor = 0usize;
-        public_parameter.big_ib.iter().for_each(|input_wire| {
-            recovered_label_vec[*input_wire] = pa_published_label_with_ib[input_cursor].clone();
-            recovered_hat_z_bit_vec[*input_wire] = pb_published_hat_z_input_vec_with_ib[input_cursor];
-            input_cursor += 1;
-        });
-        let garbled_table = &preprocessing_transcript.garbled_table;
+        for (input_cursor, input_wire) in public_parameter.big_ia.iter().enumerate() {
+            recovered_label_vec[*input_wire] = pa_input_label_message.label_with_ia[input_cursor].clone();
+            recovered_hat_z_bit_vec[*input_wire] = pa_input_label_message.hat_z_input_bit_vec_with_ia[input_cursor];
+        }
+        for (input_cursor, input_wire) in public_parameter.big_ib.iter().enumerate() {
+            recovered_label_vec[*input_wire] = pa_input_label_message.label_with_ib[input_cursor].clone();
+            recovered_hat_z_bit_vec[*input_wire] = pb_hat_z_input_bit_vec_with_ib[input_cursor];
+        }
+
+        let mut evaluation_message = EvaluationMessage {
+            published_middle_hat_z_bit_vec: BitVec::zero_vec(public_parameter.big_iw_size),
+            pb_published_middle_label_vec: GFVec::<GFVOLE>::zero_vec(public_parameter.big_iw_size),
+            pb_published_middle_r_bit_vec: BitVec::zero_vec(public_parameter.big_iw_size),
+            pb_published_middle_voleith_mac_r_vec_rep: vec![GFVec::<GFVOLEitH>::zero_vec(public_parameter.big_iw_size); public_parameter.kappa],
+            pb_published_middle_randomness_vec: vec![SeedU8x16::zero(); public_parameter.big_iw_size],
+            published_decrypted_garbled_row: vec![GarbledRow::zero(); public_parameter.big_iw_size],
+            pb_published_output_r_bit_vec: BitVec::zero_vec(public_parameter.big_io_size),
+            pb_published_output_voleith_mac_r_vec_rep: vec![GFVec::<GFVOLEitH>::zero_vec(public_parameter.big_io_size); public_parameter.kappa],
+            published_output_bit_vec: BitVec::zero_vec(public_parameter.big_io_size),
+        };
         let mut and_cursor = 0usize;
-        // println!("------------------------------------------------------------------------------------------------------------------------------------------------------------------");
-        // (0..public_parameter.num_input_bits).for_each(
-        //     |id| {
-        //         println!("Input: ");
-        //         println!("- Recovered: {:?}", (recovered_hat_z_vec[id], recovered_label_vec[id].clone()));
-        //         println!("- PA label1: {:?}", (recovered_hat_z_vec[id], pa_secret_state.label_zero_vec.as_ref().unwrap()[id].clone().custom_add(&pa_secret_state.delta.as_ref().unwrap().custom_multiply_bit(recovered_hat_z_vec[id]))));
-        //         assert_eq!((recovered_hat_z_vec[id], recovered_label_vec[id].clone()), (recovered_hat_z_vec[id], pa_secret_state.label_zero_vec.as_ref().unwrap()[id].clone().custom_add(&pa_secret_state.delta.as_ref().unwrap().custom_multiply_bit(recovered_hat_z_vec[id]))));
-        //     }
-        // );
         for gate in bristol_fashion_adaptor.get_gate_vec() {
-            // println!("{:?}:", gate.gate_type);
             match gate.gate_type {
                 GateType::XOR => {
                     recovered_hat_z_bit_vec[gate.output_wire] = recovered_hat_z_bit_vec[gate.left_input_wire] ^ recovered_hat_z_bit_vec[gate.right_input_wire];
                     recovered_label_vec[gate.output_wire] = recovered_label_vec[gate.left_input_wire].custom_add(&recovered_label_vec[gate.right_input_wire]);
                 }
                 GateType::NOT => {
-                    // unimplemented!();
                     recovered_hat_z_bit_vec[gate.output_wire] = recovered_hat_z_bit_vec[gate.left_input_wire] ^ 1u8;
                     recovered_label_vec[gate.output_wire] = recovered_label_vec[gate.left_input_wire].clone();
                 }
                 GateType::AND => {
                     let recovered_k =  recovered_hat_z_bit_vec[gate.left_input_wire] + (recovered_hat_z_bit_vec[gate.right_input_wire] << 1);
-                    // println!("{:?}", (recovered_k, recovered_label_vec[gate.left_input_wire].clone(), recovered_label_vec[gate.right_input_wire].clone()));
                     let decrypted_gabled_row = hasher::hash_for_garbling::<GFVOLE, GFVOLEitH>(
                         public_parameter,
                         &recovered_label_vec[gate.left_input_wire],
@@ -929,84 +731,224 @@ impl ProverInPA2PC {
                     recovered_label_vec[gate.output_wire] = decrypted_gabled_row.vole_mac_remaining_field.custom_add(
                         &pb_secret_state.middle_vole_mac_r_and_output_vec[and_cursor][recovered_k as usize]
                     );
-                    // println!("and_cursor: {:?}", and_cursor);
-                    // println!("- {:?}", (
-                    //     decrypted_gabled_row.vole_mac_field.clone(),
-                    //     pb_secret_state.other_middle_vole_key_r_and_output_vec[and_cursor][recovered_k as usize].custom_add(
-                    //         &pb_secret_state.delta.as_ref().unwrap().custom_multiply_bit(decrypted_gabled_row.first_u8)
-                    //     )
-                    // ));
-                    assert_eq!(
-                        decrypted_gabled_row.vole_mac_field,
-                        pb_secret_state.other_middle_vole_key_r_and_output_vec[and_cursor][recovered_k as usize].custom_add(
-                            &pb_secret_state.delta.as_ref().unwrap().custom_multiply_bit(decrypted_gabled_row.first_u8)
-                        )
-                    );
-                    proof_transcript.published_middle_hat_z_bit_vec[and_cursor] = recovered_hat_z_bit_vec[gate.output_wire];
-                    proof_transcript.pb_published_middle_label_vec[and_cursor] = recovered_label_vec[gate.output_wire].clone();
-                    proof_transcript.pb_published_middle_r_bit_vec[and_cursor] = pb_secret_state.middle_r_and_output_bit_vec[and_cursor][recovered_k as usize];
+                    if decrypted_gabled_row.vole_mac_field != pb_secret_state.other_middle_vole_key_r_and_output_vec[and_cursor][recovered_k as usize].custom_add(
+                        &pb_secret_state.delta.as_ref().unwrap().custom_multiply_bit(decrypted_gabled_row.first_u8)
+                    ) {
+                        return Err(PartyError::GarbledRowMac { and_cursor });
+                    }
+                    evaluation_message.published_middle_hat_z_bit_vec.set(and_cursor, recovered_hat_z_bit_vec[gate.output_wire]);
+                    evaluation_message.pb_published_middle_label_vec[and_cursor] = recovered_label_vec[gate.output_wire].clone();
+                    evaluation_message.pb_published_middle_r_bit_vec.set(and_cursor, pb_secret_state.middle_r_and_output_bit_vec[and_cursor][recovered_k as usize]);
                     (0..public_parameter.kappa).for_each(|repetition_id| {
-                        proof_transcript.pb_published_middle_voleith_mac_r_vec_rep[repetition_id][and_cursor] = pb_secret_state.middle_voleith_mac_r_and_output_vec_rep[repetition_id][and_cursor][recovered_k as usize].clone();
+                        evaluation_message.pb_published_middle_voleith_mac_r_vec_rep[repetition_id][and_cursor] = pb_secret_state.middle_voleith_mac_r_and_output_vec_rep[repetition_id][and_cursor][recovered_k as usize].clone();
                     });
-                    proof_transcript.pb_published_middle_randomness_vec[and_cursor] = pb_secret_state.commitment_randomness_vec_rep.as_ref().unwrap()[and_cursor][recovered_k as usize].clone();
-                    proof_transcript.published_decrypted_garbled_row[and_cursor] = decrypted_gabled_row;
+                    evaluation_message.pb_published_middle_randomness_vec[and_cursor] = pb_secret_state.commitment_randomness_vec_rep.as_ref().unwrap()[and_cursor][recovered_k as usize];
+                    evaluation_message.published_decrypted_garbled_row[and_cursor] = decrypted_gabled_row;
                     and_cursor += 1;
                 }
             }
-            // println!("- Recovered: {:?}", (recovered_hat_z_vec[gate.output_wire], recovered_label_vec[gate.output_wire].clone()));
-            // println!("- PA label:  {:?}", (recovered_hat_z_vec[gate.output_wire], pa_secret_state.label_zero_vec.as_ref().unwrap()[gate.output_wire].clone().custom_add(&pa_secret_state.delta.as_ref().unwrap().custom_multiply_bit(recovered_hat_z_vec[gate.output_wire]))));
-            // assert_eq!((recovered_hat_z_vec[gate.output_wire], recovered_label_vec[gate.output_wire].clone()), (recovered_hat_z_vec[gate.output_wire], pa_secret_state.label_zero_vec.as_ref().unwrap()[gate.output_wire].clone().custom_add(&pa_secret_state.delta.as_ref().unwrap().custom_multiply_bit(recovered_hat_z_vec[gate.output_wire]))));
         }
 
         if process_printing {
-            println!("  PA determines her outputs");
+            println!("  PB checks PA's outputs and computes remaining things");
         }
-        let mut output_cursor = 0usize;
-        for output_wire in &public_parameter.big_io {
-            proof_transcript.pa_published_output_r_bit_vec[output_cursor] = pa_secret_state.r_trace_bit_vec[*output_wire];
-            proof_transcript.pa_published_output_vole_mac_r_vec[output_cursor] = pa_secret_state.vole_mac_r_trace_vec[*output_wire].clone();
+        for (output_cursor, output_wire) in public_parameter.big_io.iter().enumerate() {
+            if pa_output_share.vole_mac_r_vec[output_cursor] != pb_secret_state.other_vole_key_r_trace_vec[*output_wire].custom_add(
+                &pb_secret_state.delta.as_ref().unwrap().custom_multiply_bit(pa_output_share.r_bit_vec[output_cursor])
+            ) {
+                return Err(PartyError::OutputMac { output_wire: *output_wire });
+            }
+            evaluation_message.pb_published_output_r_bit_vec.set(output_cursor, pb_secret_state.r_trace_bit_vec[*output_wire]);
             for repetition_id in 0..public_parameter.kappa {
-                proof_transcript.pa_published_output_voleith_mac_r_vec_rep[repetition_id][output_cursor] = pa_secret_state.voleith_mac_r_trace_vec_rep[repetition_id][*output_wire].clone();
+                evaluation_message.pb_published_output_voleith_mac_r_vec_rep[repetition_id][output_cursor] = pb_secret_state.voleith_mac_r_trace_vec_rep[repetition_id][*output_wire].clone();
             }
-            output_cursor += 1;
+            evaluation_message.published_output_bit_vec.set(output_cursor, recovered_hat_z_bit_vec[*output_wire] ^ pa_output_share.r_bit_vec[output_cursor] ^ evaluation_message.pb_published_output_r_bit_vec[output_cursor]);
+        }
+
+        Ok(evaluation_message)
+    }
+
+    // Both parties assemble the same proof transcript from what has been published
+    #[allow(clippy::too_many_arguments)]
+    pub fn assemble_proof_transcript<GFVOLE, GFVOLEitH>(
+        public_parameter: &PublicParameter,
+        pa_rm_share: PublishedRmShare<GFVOLEitH>,
+        pb_rm_share: PublishedRmShare<GFVOLEitH>,
+        check_and_transcript_vec: Vec<CheckAndTranscript<GFVOLEitH>>,
+        pa_input_share: PublishedWireShare<GFVOLE, GFVOLEitH>,
+        pb_input_share: PublishedWireShare<GFVOLE, GFVOLEitH>,
+        pa_hat_z_input_bit_vec_with_ia: &[u8],
+        pb_hat_z_input_bit_vec_with_ib: &[u8],
+        pa_output_share: PublishedWireShare<GFVOLE, GFVOLEitH>,
+        evaluation_message: EvaluationMessage<GFVOLE, GFVOLEitH>,
+    ) -> ProofTranscript<GFVOLE, GFVOLEitH>
+    where GFVOLE: Clone + Zero + Encode, GFVOLEitH: Clone + Zero + Encode {
+        let mut proof_transcript = ProofTranscript::new(
+            public_parameter,
+            pa_rm_share.rm_a_vec_rep,
+            pa_rm_share.rm_b_vec_rep,
+            pa_rm_share.rm_c_vec_rep,
+            pa_rm_share.rm_voleith_mac_a_vec_rep,
+            pa_rm_share.rm_voleith_mac_b_vec_rep,
+            pa_rm_share.rm_voleith_mac_c_vec_rep,
+            pb_rm_share.rm_a_vec_rep,
+            pb_rm_share.rm_b_vec_rep,
+            pb_rm_share.rm_c_vec_rep,
+            pb_rm_share.rm_voleith_mac_a_vec_rep,
+            pb_rm_share.rm_voleith_mac_b_vec_rep,
+            pb_rm_share.rm_voleith_mac_c_vec_rep,
+            check_and_transcript_vec,
+            pa_input_share.r_bit_vec,
+            pa_input_share.voleith_mac_r_vec_rep,
+            pb_input_share.r_bit_vec,
+            pb_input_share.voleith_mac_r_vec_rep,
+        );
+
+        for (input_cursor, input_wire) in public_parameter.big_ia.iter().enumerate() {
+            proof_transcript.published_hat_z_input_bit_vec.set(*input_wire, pa_hat_z_input_bit_vec_with_ia[input_cursor]);
+        }
+        for (input_cursor, input_wire) in public_parameter.big_ib.iter().enumerate() {
+            proof_transcript.published_hat_z_input_bit_vec.set(*input_wire, pb_hat_z_input_bit_vec_with_ib[input_cursor]);
+        }
+
+        proof_transcript.pa_published_output_r_bit_vec = pa_output_share.r_bit_vec;
+        proof_transcript.pa_published_output_vole_mac_r_vec = pa_output_share.vole_mac_r_vec;
+        proof_transcript.pa_published_output_voleith_mac_r_vec_rep = pa_output_share.voleith_mac_r_vec_rep;
+
+        proof_transcript.published_middle_hat_z_bit_vec = evaluation_message.published_middle_hat_z_bit_vec;
+        proof_transcript.pb_published_middle_label_vec = evaluation_message.pb_published_middle_label_vec;
+        proof_transcript.pb_published_middle_r_bit_vec = evaluation_message.pb_published_middle_r_bit_vec;
+        proof_transcript.pb_published_middle_voleith_mac_r_vec_rep = evaluation_message.pb_published_middle_voleith_mac_r_vec_rep;
+        proof_transcript.pb_published_middle_randomness_vec = evaluation_message.pb_published_middle_randomness_vec;
+        proof_transcript.published_decrypted_garbled_row = evaluation_message.published_decrypted_garbled_row;
+        proof_transcript.pb_published_output_r_bit_vec = evaluation_message.pb_published_output_r_bit_vec;
+        proof_transcript.pb_published_output_voleith_mac_r_vec_rep = evaluation_message.pb_published_output_voleith_mac_r_vec_rep;
+        proof_transcript.published_output_bit_vec = evaluation_message.published_output_bit_vec;
+
+        proof_transcript
+    }
+
+    // Runs the proving phase of both parties in one process
+    pub fn prove<GFVOLE, GFVOLEitH>(
+        process_printing: bool,
+        bristol_fashion_adaptor: &BristolFashionAdaptor,
+        public_parameter: &PublicParameter,
+        preprocessing_transcript: &PreprocessingTranscript<GFVOLE, GFVOLEitH>,
+        pa_secret_state: &mut ProverSecretState<GFVOLE, GFVOLEitH>,
+        pb_secret_state: &mut ProverSecretState<GFVOLE, GFVOLEitH>,
+        pa_input_bits: &Vec<u8>,
+        pb_input_bits: &Vec<u8>,
+    ) -> (ProofTranscript<GFVOLE, GFVOLEitH>, Decom, Decom)
+    where
+        GFVOLE: Clone + Zero + CustomAddition + CustomMultiplyingBit + PartialEq + Debug + ByteManipulation + Debug + Encode,
+        GFVOLEitH: Clone + Zero + CustomAddition + ByteManipulation + Debug + LeafIndexForGF + Encode {
+        if process_printing {
+            println!("+ Proving...");
         }
 
         if process_printing {
-            println!("  PB checks PA's outputs and computes remaining things");
+            println!("  Determine permutation_rep via Fiat-Shamir");
         }
-        output_cursor = 0usize;
-        for output_wire in &public_parameter.big_io {
-            assert_eq!(
-                proof_transcript.pa_published_output_vole_mac_r_vec[output_cursor],
-                pb_secret_state.other_vole_key_r_trace_vec[*output_wire].custom_add(
-                    &pb_secret_state.delta.as_ref().unwrap().custom_multiply_bit(proof_transcript.pa_published_output_r_bit_vec[output_cursor])
-                )
-            );
-            proof_transcript.pb_published_output_r_bit_vec[output_cursor] = pb_secret_state.r_trace_bit_vec[*output_wire];
-            for repetition_id in 0..public_parameter.kappa {
-                proof_transcript.pb_published_output_voleith_mac_r_vec_rep[repetition_id][output_cursor] = pb_secret_state.voleith_mac_r_trace_vec_rep[repetition_id][*output_wire].clone();
-            }
-            proof_transcript.published_output_bit_vec[output_cursor] = recovered_hat_z_bit_vec[*output_wire] ^ proof_transcript.pa_published_output_r_bit_vec[output_cursor] ^ proof_transcript.pb_published_output_r_bit_vec[output_cursor];
-            output_cursor += 1;
+        let (permutation_rep, mut fiat_shamir_transcript) = hash_to_determine_permutation_rep(
+            public_parameter, preprocessing_transcript
+        );
+
+        if process_printing {
+            println!("  PA permutes and determines published components");
+        }
+        let pa_rm_share = Self::permute_and_publish_rm(public_parameter, &permutation_rep, pa_secret_state);
+
+        if process_printing {
+            println!("  PB permutes and determines published components");
+        }
+        let pb_rm_share = Self::permute_and_publish_rm(public_parameter, &permutation_rep, pb_secret_state);
+
+        if process_printing {
+            println!("  Both parties run PiCheckAND");
+        }
+        let pa_masked_share_vec = Self::compute_check_and_masked_share_vec(public_parameter, pa_secret_state);
+        let pb_masked_share_vec = Self::compute_check_and_masked_share_vec(public_parameter, pb_secret_state);
+        let pa_cross_share_vec = Self::compute_check_and_cross_share_vec(
+            public_parameter, pa_secret_state, &pa_masked_share_vec, &pb_masked_share_vec
+        );
+        let pb_cross_share_vec = Self::compute_check_and_cross_share_vec(
+            public_parameter, pb_secret_state, &pa_masked_share_vec, &pb_masked_share_vec
+        );
+        let check_and_transcript_vec = Self::assemble_check_and_transcript_vec(
+            pa_masked_share_vec, pa_cross_share_vec, pb_masked_share_vec, pb_cross_share_vec
+        );
+
+        if process_printing {
+            println!("  PA processes inputs");
+        }
+        let pa_input_share = Self::publish_wire_share(public_parameter, pa_secret_state, &public_parameter.big_ib);
+
+        if process_printing {
+            println!("  PB checks what PA just published and partially computes hat_z at inputs");
+        }
+        let pb_hat_z_input_bit_vec_with_ib = Self::check_input_share_and_mask_inputs(
+            pb_secret_state, false, &public_parameter.big_ib, &pa_input_share, pb_input_bits
+        ).unwrap_or_else(|error| panic!("{}", error));
+
+        if process_printing {
+            println!("  PB processes inputs");
+        }
+        let pb_input_share = Self::publish_wire_share(public_parameter, pb_secret_state, &public_parameter.big_ia);
+
+        if process_printing {
+            println!("  PA checks what PB just published and partially computes hat_z at inputs");
         }
+        let pa_hat_z_input_bit_vec_with_ia = Self::check_input_share_and_mask_inputs(
+            pa_secret_state, true, &public_parameter.big_ia, &pb_input_share, pa_input_bits
+        ).unwrap_or_else(|error| panic!("{}", error));
+
+        if process_printing {
+            println!("  PA publishes labels");
+        }
+        let pa_input_label_message = Self::publish_input_labels(
+            public_parameter, pa_secret_state, pa_hat_z_input_bit_vec_with_ia, &pb_hat_z_input_bit_vec_with_ib
+        );
+
+        if process_printing {
+            println!("  PA determines her outputs");
+        }
+        let pa_output_share = Self::publish_wire_share(public_parameter, pa_secret_state, &public_parameter.big_io);
+
+        let evaluation_message = Self::evaluate(
+            process_printing, bristol_fashion_adaptor, public_parameter, &preprocessing_transcript.garbled_table,
+            pb_secret_state, &pb_hat_z_input_bit_vec_with_ib, &pa_input_label_message, &pa_output_share
+        ).unwrap_or_else(|error| panic!("{}", error));
+
+        if process_printing {
+            println!("  Initialize proof transcript");
+        }
+        let proof_transcript = Self::assemble_proof_transcript(
+            public_parameter,
+            pa_rm_share, pb_rm_share,
+            check_and_transcript_vec,
+            pa_input_share, pb_input_share,
+            &pa_input_label_message.hat_z_input_bit_vec_with_ia, &pb_hat_z_input_bit_vec_with_ib,
+            pa_output_share,
+            evaluation_message,
+        );
 
         if process_printing {
             println!("  Determine nabla_a_rep and nabla_b_rep via Fiat-Shamir");
         }
         let (nabla_a_rep, nabla_b_rep) = hash_to_determine_nabla_rep(
-            public_parameter, &auxiliary_input, &proof_transcript
+            public_parameter, &mut fiat_shamir_transcript, &proof_transcript
         );
 
         if process_printing {
             println!("  PA computes decom after knowing nabla_b_rep");
         }
-        let pa_decom_rep = ProverInProtocolSVOLE2PC::open(public_parameter, pa_secret_state, &nabla_b_rep);
+        let pa_decom = ProverInProtocolSVOLE2PC::open(public_parameter, pa_secret_state, &nabla_b_rep);
 
         if process_printing {
             println!("  PB computes decom after knowing nabla_a_rep");
         }
-        let pb_decom_rep = ProverInProtocolSVOLE2PC::open(public_parameter, pb_secret_state, &nabla_a_rep);
+        let pb_decom = ProverInProtocolSVOLE2PC::open(public_parameter, pb_secret_state, &nabla_a_rep);
 
-        (proof_transcript, pa_decom_rep, pb_decom_rep)
+        (proof_transcript, pa_decom, pb_decom)
     }
-}
\ No newline at end of file
+}
@@ -1,29 +1,42 @@
 #[cfg(test)]
 mod tests {
-    use rand::Rng;
+    use std::fmt::Debug;
+    use std::net::TcpListener;
+    use std::str::FromStr;
+    use std::thread;
+    use bincode::{config, decode_from_slice, encode_to_vec, Decode, Encode};
+    use rand::{CryptoRng, Rng, SeedableRng};
+    use rand_chacha::ChaCha20Rng;
     use crate::bristol_fashion_adaptor::bristol_fashion_adaptor::BristolFashionAdaptor;
-    use crate::functionalities_and_protocols::protocol_pa_2pc::determine_bit_trace_for_labels_in_garbling;
+    use crate::functionalities_and_protocols::all_in_one_vc::{Decom, LeafExpansionVersion, VectorCommitmentMode};
+    use crate::functionalities_and_protocols::crypto_backend::CryptoBackend;
+    use crate::functionalities_and_protocols::channel::{Channel, ChannelError, ChannelStatistics};
+    use crate::functionalities_and_protocols::channel::in_memory_channel::InMemoryChannel;
+    use crate::functionalities_and_protocols::channel::tcp_channel::TcpChannel;
+    use crate::functionalities_and_protocols::insecure_functionality_pre::insecure_functionality_pre::InsecureFunctionalityPre;
+    use crate::functionalities_and_protocols::commitment::verify_opening;
+    use crate::functionalities_and_protocols::protocol_pa_2pc::{determine_bit_trace_for_labels_in_garbling, make_pb_middle_message};
+    use crate::functionalities_and_protocols::protocol_pa_2pc::party_a::PartyA;
+    use crate::functionalities_and_protocols::protocol_pa_2pc::party_b::PartyB;
+    use crate::functionalities_and_protocols::protocol_pa_2pc::party_error::PartyError;
+    use crate::functionalities_and_protocols::protocol_pa_2pc::party_message::{PAInputLabelMessage, PAPreprocessingMessage};
     use crate::functionalities_and_protocols::states_and_parameters::prover_secret_state::ProverSecretState;
     use crate::functionalities_and_protocols::states_and_parameters::public_parameter::PublicParameter;
+    use crate::functionalities_and_protocols::protocol_pa_2pc::proof_bundle::{ProofBundle, ProofBundleError, PROOF_BUNDLE_MAGIC, PROOF_BUNDLE_VERSION};
+    use crate::functionalities_and_protocols::protocol_pa_2pc::proof_transcript::ProofTranscript;
     use crate::functionalities_and_protocols::protocol_pa_2pc::prover_in_pa_2pc::ProverInPA2PC;
     use crate::functionalities_and_protocols::protocol_pa_2pc::verifier_in_pa_2pc::VerifierInPA2PC;
+    use crate::functionalities_and_protocols::verification_result::VerificationError;
+    use crate::value_type::gf2p128::GF2p128;
     use crate::value_type::gf2p256::GF2p256;
     use crate::value_type::gf2p8::GF2p8;
+    use crate::value_type::gf2p16::GF2p16;
     use crate::value_type::seed_u8x16::SeedU8x16;
-    use crate::value_type::{InsecureRandom, U8ForGF};
+    use crate::value_type::gf2p192::GF2p192;
+    use crate::value_type::{ByteManipulation, CustomAddition, CustomMultiplyingBit, InsecureRandom, LeafIndexForGF, Zero};
     use crate::vec_type::bit_vec::BitVec;
     use crate::vec_type::BasicVecFunctions;
     
-    fn insecurely_generate_random_permutation(len: usize) -> Vec<usize> {
-        let mut random_permutation = (0..len).collect::<Vec<usize>>();
-        let mut rng = rand::rng();
-        for i in (0..len).rev() {
-            let j = rng.random::<u32>() % (i as u32 + 1u32);
-            random_permutation.swap(i, j as usize);
-        }
-        random_permutation
-    }
-
     fn determine_full_input_bit_vec(
         public_parameter: &PublicParameter,
         pa_input_bit_vec: &Vec<u8>,
@@ -49,104 +62,137 @@ mod tests {
         full_input_bit_vec
     }
     
-    #[test]
-    fn test_pa_2pc_for_sub64() {
-        let processing_printing = true;
-        type GFVOLE = GF2p256;
-        type GFVOLEitH = GF2p8;
+    type GFVOLE = GF2p256;
+    type GFVOLEitH = GF2p8;
+
+    fn prove_for_sub64(
+        processing_printing: bool, vector_commitment_mode: VectorCommitmentMode
+    ) -> (BristolFashionAdaptor, Vec<u8>, Vec<u8>, ProofBundle<GFVOLE, GFVOLEitH>) {
         let bristol_fashion_adaptor = BristolFashionAdaptor::new(
             &"sub64.txt".to_string()
         );
-        let mut rng = rand::rng();
         // println!("Num AND gates: {:?}", bristol_fashion_adaptor.get_and_gate_output_wire_vec().len());
         let num_input_bits = bristol_fashion_adaptor.get_num_input_bits();
         let big_ia = (0..(num_input_bits >> 1) - 3).collect::<Vec<usize>>();
         let big_ib = (big_ia.len()..num_input_bits).collect::<Vec<usize>>();
+        prove_for_circuit::<GFVOLE, GFVOLEitH>(
+            processing_printing, bristol_fashion_adaptor, 8, vector_commitment_mode, big_ia, big_ib, &mut rand::rng()
+        )
+    }
+
+    // kappa = ceil(security level / tau) repetitions reach the security level of GFVOLE
+    fn prove_for_circuit<GFVOLE, GFVOLEitH>(
+        processing_printing: bool,
+        bristol_fashion_adaptor: BristolFashionAdaptor,
+        tau: u8,
+        vector_commitment_mode: VectorCommitmentMode,
+        big_ia: Vec<usize>,
+        big_ib: Vec<usize>,
+        rng: &mut impl CryptoRng,
+    ) -> (BristolFashionAdaptor, Vec<u8>, Vec<u8>, ProofBundle<GFVOLE, GFVOLEitH>)
+    where GFVOLE: Clone + CustomAddition + CustomMultiplyingBit + InsecureRandom + Zero + Copy + PartialEq + Debug + ByteManipulation + Sync + Send + Encode + Decode<()>,
+          GFVOLEitH: Clone + CustomAddition + CustomMultiplyingBit + LeafIndexForGF + Zero + Copy + PartialEq + Debug + ByteManipulation + Sync + Send + Encode + Decode<()> {
         // let big_io = (bristol_fashion_adaptor.get_num_wires() - bristol_fashion_adaptor.get_num_output_bits()..bristol_fashion_adaptor.get_num_wires()).collect::<Vec<usize>>();
-        let pa_input_bit_vec = big_ia.iter().map(
+        let pa_input_bit_vec: Vec<u8> = big_ia.iter().map(
             |_| rng.random::<u8>() & 1
         ).collect();
-        let pb_input_bit_vec = big_ib.iter().map(
+        let pb_input_bit_vec: Vec<u8> = big_ib.iter().map(
             |_| rng.random::<u8>() & 1
         ).collect();
         let bs = 1;
         let rm = bristol_fashion_adaptor.get_and_gate_output_wire_vec().len();
         let public_parameter = PublicParameter::new::<GFVOLE, GFVOLEitH>(
             &bristol_fashion_adaptor,
-            8,
-            32,
-            SeedU8x16::insecurely_random(),
+            tau,
+            PublicParameter::determine_kappa(GFVOLE::num_bytes() * 8, tau),
+            vector_commitment_mode,
+            LeafExpansionVersion::V2,
+            CryptoBackend::default(),
+            SeedU8x16::insecurely_random(rng),
+            None,
             big_ia,
             big_ib,
             bs,
             rm,
+            None,
         );
-        
+        let proof_bundle = prove_under_public_parameter(
+            processing_printing, &bristol_fashion_adaptor, public_parameter, &pa_input_bit_vec, &pb_input_bit_vec, rng
+        );
+        (bristol_fashion_adaptor, pa_input_bit_vec, pb_input_bit_vec, proof_bundle)
+    }
+
+    fn prove_under_public_parameter<GFVOLE, GFVOLEitH>(
+        processing_printing: bool,
+        bristol_fashion_adaptor: &BristolFashionAdaptor,
+        public_parameter: PublicParameter,
+        pa_input_bit_vec: &Vec<u8>,
+        pb_input_bit_vec: &Vec<u8>,
+        rng: &mut impl CryptoRng,
+    ) -> ProofBundle<GFVOLE, GFVOLEitH>
+    where GFVOLE: Clone + CustomAddition + CustomMultiplyingBit + InsecureRandom + Zero + Copy + PartialEq + Debug + ByteManipulation + Sync + Send + Encode + Decode<()>,
+          GFVOLEitH: Clone + CustomAddition + CustomMultiplyingBit + LeafIndexForGF + Zero + Copy + PartialEq + Debug + ByteManipulation + Sync + Send + Encode + Decode<()> {
         let mut pa_secret_state = ProverSecretState::<GFVOLE, GFVOLEitH>::new(
             &public_parameter, 
-            SeedU8x16::insecurely_random(),
+            SeedU8x16::insecurely_random(rng),
             true
         );
         
         let mut pb_secret_state = ProverSecretState::<GFVOLE, GFVOLEitH>::new(
             &public_parameter, 
-            SeedU8x16::insecurely_random(),
+            SeedU8x16::insecurely_random(rng),
             false
         );
 
         let bit_trace_vec_for_labels_in_garbling = determine_bit_trace_for_labels_in_garbling(
-            &bristol_fashion_adaptor,
+            bristol_fashion_adaptor,
             &public_parameter,
         );
         
+        let (mut pa_functionality_pre, mut pb_functionality_pre) = InsecureFunctionalityPre::new_pair(ChaCha20Rng::from_rng(&mut *rng));
         let preprocessing_transcript = ProverInPA2PC::preprocess(
             processing_printing,
-            &bristol_fashion_adaptor,
+            &mut pa_functionality_pre,
+            &mut pb_functionality_pre,
+            rng,
+            bristol_fashion_adaptor,
             &bit_trace_vec_for_labels_in_garbling,
             &public_parameter, 
             &mut pa_secret_state, 
             &mut pb_secret_state,
         );
 
-        let permutation_rep = (0..public_parameter.kappa).map(
-            |_| insecurely_generate_random_permutation(public_parameter.big_l)
-        ).collect::<Vec<Vec<usize>>>();
-        
-        let nabla_a_rep = (0..public_parameter.kappa).map(|_|
-            GFVOLEitH::from_u8(rng.random::<u8>())
-        ).collect::<Vec<GFVOLEitH>>();
-        let nabla_b_rep = (0..public_parameter.kappa).map(|_|
-            GFVOLEitH::from_u8(rng.random::<u8>())
-        ).collect::<Vec<GFVOLEitH>>();
-        
-        // println!("nabla_a_rep {:?}", nabla_a_rep);
-        // println!("nabla_b_rep {:?}", nabla_b_rep);
-
-        let (proof_transcript, pa_decom_rep, pb_decom_rep) = ProverInPA2PC::prove(
+        let (proof_transcript, pa_decom, pb_decom) = ProverInPA2PC::prove(
             processing_printing,
-            &bristol_fashion_adaptor,
+            bristol_fashion_adaptor,
             &public_parameter,
             &preprocessing_transcript,
-            // &permutation_rep,
             &mut pa_secret_state,
             &mut pb_secret_state,
-            &pa_input_bit_vec,
-            &pb_input_bit_vec,
-            // &nabla_a_rep,
-            // &nabla_b_rep
+            pa_input_bit_vec,
+            pb_input_bit_vec,
         );
-        
-        VerifierInPA2PC::verify::<GFVOLE, GFVOLEitH>(
+
+        ProofBundle::new(public_parameter, preprocessing_transcript, proof_transcript, pa_decom, pb_decom)
+    }
+
+    #[test]
+    fn test_pa_2pc_for_sub64() {
+        let processing_printing = true;
+        let (bristol_fashion_adaptor, pa_input_bit_vec, pb_input_bit_vec, proof_bundle) = prove_for_sub64(processing_printing, VectorCommitmentMode::PerRepetition);
+        let ProofBundle {
+            public_parameter, preprocessing_transcript, proof_transcript, pa_decom, pb_decom
+        } = proof_bundle;
+
+        let verified_output = VerifierInPA2PC::verify::<GFVOLE, GFVOLEitH>(
             processing_printing,
             &bristol_fashion_adaptor,
             &public_parameter,
-            // &permutation_rep,
-            // &nabla_a_rep, &nabla_b_rep,
             &preprocessing_transcript,
             &proof_transcript,
-            &pa_decom_rep,
-            &pb_decom_rep
-        );
+            &pa_decom,
+            &pb_decom
+        ).unwrap();
 
         println!("{:?}", proof_transcript.published_output_bit_vec);
         let full_input_bit_vec = determine_full_input_bit_vec(
@@ -157,5 +203,710 @@ mod tests {
         let expected_output_bit_vec = BitVec::from_vec(bristol_fashion_adaptor.compute_output_bits(&full_input_bit_vec));
         println!("{:?}", expected_output_bit_vec);
         assert_eq!(proof_transcript.published_output_bit_vec, expected_output_bit_vec);
+        assert_eq!(verified_output.output_bit_vec, expected_output_bit_vec);
+
+        // a tampered decommitment must be rejected at the SVOLE reconstruction of the right party and repetition
+        let mut tampered_pb_decom = pb_decom.clone();
+        let Decom::PerRepetition { decom_rep } = &mut tampered_pb_decom else { panic!("expected a decom per repetition") };
+        decom_rep[1].0[0] ^= 1;
+        assert_eq!(
+            VerifierInPA2PC::verify::<GFVOLE, GFVOLEitH>(
+                false,
+                &bristol_fashion_adaptor,
+                &public_parameter,
+                &preprocessing_transcript,
+                &proof_transcript,
+                &pa_decom,
+                &tampered_pb_decom
+            ),
+            Err(VerificationError::SVOLEReconstruction { is_pa: false, repetition_id: 1 })
+        );
+    }
+
+    // MAND, EQ and EQW are lowered by the adaptor, so that the outputs are an AND wire, a NOT of an AND wire,
+    // the constant 0 wire and an input wire of PA
+    const LOWERED_CIRCUIT: &str = "6 11\n2 2 2\n1 4\n\n\
+        4 2 0 1 2 3 4 5 MAND\n\
+        1 1 1 6 EQ\n\
+        1 1 4 7 EQW\n\
+        2 1 6 5 8 XOR\n\
+        1 1 0 9 EQ\n\
+        1 1 0 10 EQW\n";
+
+    #[test]
+    fn test_pa_2pc_for_lowered_gates() {
+        let bristol_fashion_adaptor = BristolFashionAdaptor::from_str(LOWERED_CIRCUIT).unwrap();
+        let (bristol_fashion_adaptor, pa_input_bit_vec, pb_input_bit_vec, proof_bundle) = prove_for_circuit::<GFVOLE, GFVOLEitH>(
+            false,
+            bristol_fashion_adaptor,
+            8,
+            VectorCommitmentMode::PerRepetition,
+            vec![0, 1],
+            vec![2, 3],
+            &mut rand::rng(),
+        );
+        let verified_output = VerifierInPA2PC::verify::<GFVOLE, GFVOLEitH>(
+            false,
+            &bristol_fashion_adaptor,
+            &proof_bundle.public_parameter,
+            &proof_bundle.preprocessing_transcript,
+            &proof_bundle.proof_transcript,
+            &proof_bundle.pa_decom,
+            &proof_bundle.pb_decom,
+        ).unwrap();
+        let full_input_bit_vec = determine_full_input_bit_vec(
+            &proof_bundle.public_parameter,
+            &pa_input_bit_vec,
+            &pb_input_bit_vec,
+        );
+        assert_eq!(
+            verified_output.output_bit_vec,
+            BitVec::from_vec(bristol_fashion_adaptor.compute_output_bits(&full_input_bit_vec))
+        );
+    }
+
+    #[test]
+    fn test_pa_2pc_at_security_level_192() {
+        let bristol_fashion_adaptor = BristolFashionAdaptor::new(&"sub64.txt".to_string());
+        let (bristol_fashion_adaptor, pa_input_bit_vec, pb_input_bit_vec, proof_bundle) = prove_for_circuit::<GF2p192, GFVOLEitH>(
+            false,
+            bristol_fashion_adaptor,
+            8,
+            VectorCommitmentMode::PerRepetition,
+            (0..64).collect(),
+            (64..128).collect(),
+            &mut rand::rng(),
+        );
+        assert_eq!(proof_bundle.public_parameter.kappa, 24);
+        let verified_output = VerifierInPA2PC::verify::<GF2p192, GFVOLEitH>(
+            false,
+            &bristol_fashion_adaptor,
+            &proof_bundle.public_parameter,
+            &proof_bundle.preprocessing_transcript,
+            &proof_bundle.proof_transcript,
+            &proof_bundle.pa_decom,
+            &proof_bundle.pb_decom,
+        ).unwrap();
+        let full_input_bit_vec = determine_full_input_bit_vec(
+            &proof_bundle.public_parameter,
+            &pa_input_bit_vec,
+            &pb_input_bit_vec,
+        );
+        assert_eq!(
+            verified_output.output_bit_vec,
+            BitVec::from_vec(bristol_fashion_adaptor.compute_output_bits(&full_input_bit_vec))
+        );
+
+        // a bundle at 192 bits cannot be loaded as one at another level
+        let byte_vec = proof_bundle.to_byte_vec();
+        assert!(ProofBundle::<GF2p192, GFVOLEitH>::from_byte_vec(&byte_vec).is_ok());
+        assert!(matches!(
+            ProofBundle::<GF2p256, GFVOLEitH>::from_byte_vec(&byte_vec),
+            Err(ProofBundleError::FieldSizeMismatch { field: "GFVOLE", .. })
+        ));
+    }
+
+    // GGM trees deeper than 8 need GF2p16 as GFVOLEitH, and in turn fewer repetitions. As 13 trees of depth 10
+    // exceed 128 bits, 11 of them have depth 10 and 2 have depth 9.
+    #[test]
+    fn test_pa_2pc_with_tau_10() {
+        let bristol_fashion_adaptor = BristolFashionAdaptor::new(&"sub64.txt".to_string());
+        let (bristol_fashion_adaptor, pa_input_bit_vec, pb_input_bit_vec, proof_bundle) = prove_for_circuit::<GF2p128, GF2p16>(
+            false,
+            bristol_fashion_adaptor,
+            10,
+            VectorCommitmentMode::PerRepetition,
+            (0..64).collect(),
+            (64..128).collect(),
+            &mut rand::rng(),
+        );
+        assert_eq!(proof_bundle.public_parameter.kappa, 13);
+        assert_eq!(proof_bundle.public_parameter.tau_rep, [vec![10; 11], vec![9; 2]].concat());
+        let verified_output = VerifierInPA2PC::verify::<GF2p128, GF2p16>(
+            false,
+            &bristol_fashion_adaptor,
+            &proof_bundle.public_parameter,
+            &proof_bundle.preprocessing_transcript,
+            &proof_bundle.proof_transcript,
+            &proof_bundle.pa_decom,
+            &proof_bundle.pb_decom,
+        ).unwrap();
+        let full_input_bit_vec = determine_full_input_bit_vec(
+            &proof_bundle.public_parameter,
+            &pa_input_bit_vec,
+            &pb_input_bit_vec,
+        );
+        assert_eq!(
+            verified_output.output_bit_vec,
+            BitVec::from_vec(bristol_fashion_adaptor.compute_output_bits(&full_input_bit_vec))
+        );
+
+        // every decommitment opens a path as deep as the tree of its repetition
+        let byte_vec = proof_bundle.to_byte_vec();
+        let loaded_proof_bundle = ProofBundle::<GF2p128, GF2p16>::from_byte_vec(&byte_vec).unwrap();
+        let Decom::PerRepetition { decom_rep } = &loaded_proof_bundle.pa_decom else { panic!("expected a decom per repetition") };
+        assert!(decom_rep.iter().zip(loaded_proof_bundle.public_parameter.tau_rep.iter()).all(
+            |((_, seed_trace), tau)| seed_trace.len() == *tau as usize
+        ));
+        assert!(matches!(
+            ProofBundle::<GF2p128, GF2p8>::from_byte_vec(&byte_vec),
+            Err(ProofBundleError::FieldSizeMismatch { field: "GFVOLEitH", .. })
+        ));
+
+        // shallower trees than tau and kappa determine would weaken soundness
+        let ProofBundle {
+            mut public_parameter, preprocessing_transcript, proof_transcript, pa_decom, pb_decom
+        } = loaded_proof_bundle;
+        public_parameter.tau_rep[0] = 9;
+        let tampered_proof_bundle = ProofBundle::new(
+            public_parameter, preprocessing_transcript, proof_transcript, pa_decom, pb_decom
+        );
+        assert!(matches!(
+            ProofBundle::<GF2p128, GF2p16>::from_byte_vec(&tampered_proof_bundle.to_byte_vec()),
+            Err(ProofBundleError::Malformed(_))
+        ));
+    }
+
+    #[test]
+    fn test_tau_rep_adds_up_to_the_security_level() {
+        assert_eq!(PublicParameter::determine_tau_rep(128, 8, 16), vec![8; 16]);
+        assert_eq!(PublicParameter::determine_tau_rep(128, 11, 12), [vec![11; 8], vec![10; 4]].concat());
+        assert_eq!(PublicParameter::determine_tau_rep(192, 16, 12), vec![16; 12]);
+        assert_eq!(PublicParameter::determine_tau_rep(256, 12, 22), [vec![12; 14], vec![11; 8]].concat());
+        // the trees may fall two levels short of tau, but never exceed it
+        assert_eq!(PublicParameter::determine_tau_rep(128, 14, 10), [vec![13; 8], vec![12; 2]].concat());
+        for security_level in [128, 192, 256] {
+            for tau in 1..=16u8 {
+                let tau_rep = PublicParameter::determine_tau_rep(
+                    security_level, tau, PublicParameter::determine_kappa(security_level, tau)
+                );
+                assert_eq!(tau_rep.iter().map(|tau| *tau as usize).sum::<usize>(), security_level);
+                assert!(tau_rep.iter().all(|depth| *depth <= tau && *depth + 2 >= tau));
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "kappa 32 does not match tau 8 at security level 128")]
+    fn test_public_parameter_rejects_kappa_off_the_security_level() {
+        let bristol_fashion_adaptor = BristolFashionAdaptor::new(&"sub64.txt".to_string());
+        let (big_ia, big_ib) = bristol_fashion_adaptor.determine_big_ia_and_big_ib(&[0]).unwrap();
+        PublicParameter::new::<GF2p128, GF2p8>(
+            &bristol_fashion_adaptor, 8, 32, VectorCommitmentMode::PerRepetition, LeafExpansionVersion::V2, CryptoBackend::default(),
+            SeedU8x16::insecurely_random(&mut rand::rng()), None, big_ia, big_ib, 1, 1, None,
+        );
+    }
+
+    // a prover seeded identically must produce a byte-identical proof bundle, so failing proofs can be replayed
+    #[test]
+    fn test_pa_2pc_is_reproducible_from_seed() {
+        let prove_with_seed = |seed: u64| {
+            let bristol_fashion_adaptor = BristolFashionAdaptor::from_str(LOWERED_CIRCUIT).unwrap();
+            let (_, _, _, proof_bundle) = prove_for_circuit::<GFVOLE, GFVOLEitH>(
+                false,
+                bristol_fashion_adaptor,
+                8,
+                VectorCommitmentMode::PerRepetition,
+                vec![0, 1],
+                vec![2, 3],
+                &mut ChaCha20Rng::seed_from_u64(seed),
+            );
+            proof_bundle.to_byte_vec()
+        };
+        assert_eq!(prove_with_seed(7), prove_with_seed(7));
+        assert_ne!(prove_with_seed(7), prove_with_seed(8));
+    }
+
+    #[test]
+    fn test_proof_bundle_round_trip_for_sub64() {
+        let (bristol_fashion_adaptor, _, _, proof_bundle) = prove_for_sub64(false, VectorCommitmentMode::PerRepetition);
+        let byte_vec = proof_bundle.to_byte_vec();
+
+        let loaded_proof_bundle = ProofBundle::<GFVOLE, GFVOLEitH>::from_byte_vec(&byte_vec).unwrap();
+        assert_eq!(loaded_proof_bundle.to_byte_vec(), byte_vec);
+        assert_eq!(loaded_proof_bundle.public_parameter.to_byte_vec(), proof_bundle.public_parameter.to_byte_vec());
+        assert_eq!(loaded_proof_bundle.preprocessing_transcript.to_byte_vec(), proof_bundle.preprocessing_transcript.to_byte_vec());
+        assert_eq!(loaded_proof_bundle.proof_transcript.to_byte_vec(), proof_bundle.proof_transcript.to_byte_vec());
+        let verified_output = VerifierInPA2PC::verify::<GFVOLE, GFVOLEitH>(
+            false,
+            &bristol_fashion_adaptor,
+            &loaded_proof_bundle.public_parameter,
+            &loaded_proof_bundle.preprocessing_transcript,
+            &loaded_proof_bundle.proof_transcript,
+            &loaded_proof_bundle.pa_decom,
+            &loaded_proof_bundle.pb_decom,
+        ).unwrap();
+        assert_eq!(verified_output.output_bit_vec, proof_bundle.proof_transcript.published_output_bit_vec);
+
+        // the header is checked before anything else is decoded
+        let mut wrong_version_byte_vec = byte_vec.clone();
+        wrong_version_byte_vec[PROOF_BUNDLE_MAGIC.len()] = (PROOF_BUNDLE_VERSION + 1) as u8;
+        assert_eq!(
+            ProofBundle::<GFVOLE, GFVOLEitH>::from_byte_vec(&wrong_version_byte_vec).err(),
+            Some(ProofBundleError::UnsupportedVersion(PROOF_BUNDLE_VERSION + 1))
+        );
+        assert!(matches!(
+            ProofBundle::<GF2p128, GFVOLEitH>::from_byte_vec(&byte_vec),
+            Err(ProofBundleError::FieldSizeMismatch { field: "GFVOLE", .. })
+        ));
+        assert!(matches!(
+            ProofBundle::<GFVOLE, GFVOLEitH>::from_byte_vec(&byte_vec[..byte_vec.len() - 1]),
+            Err(ProofBundleError::Malformed(_))
+        ));
+
+        // a bit vector that claims 2^40 bits is rejected before its buffer is allocated
+        let preprocessing_transcript_offset = byte_vec.len() - [
+            encode_to_vec(&proof_bundle.preprocessing_transcript, config::standard()).unwrap(),
+            encode_to_vec(&proof_bundle.proof_transcript, config::standard()).unwrap(),
+            encode_to_vec(&proof_bundle.pa_decom, config::standard()).unwrap(),
+            encode_to_vec(&proof_bundle.pb_decom, config::standard()).unwrap(),
+        ].iter().map(|encoded| encoded.len()).sum::<usize>();
+        let mut huge_bit_vec_byte_vec = byte_vec[..preprocessing_transcript_offset].to_vec();
+        // no com hash of PA, and one masked bit tuple whose first bit vector is huge
+        for len in [0usize, 1, 1 << 40] {
+            huge_bit_vec_byte_vec.append(&mut encode_to_vec(len, config::standard()).unwrap());
+        }
+        assert!(matches!(
+            ProofBundle::<GFVOLE, GFVOLEitH>::from_byte_vec(&huge_bit_vec_byte_vec),
+            Err(ProofBundleError::Malformed(error)) if error.contains("LimitExceeded")
+        ));
+
+        // a well-formed encoding with a wrong length is rejected by the loader
+        let tau = proof_bundle.public_parameter.tau_rep[2] as usize;
+        let ProofBundle {
+            public_parameter, preprocessing_transcript, proof_transcript, pa_decom, mut pb_decom
+        } = proof_bundle;
+        let Decom::PerRepetition { decom_rep } = &mut pb_decom else { panic!("expected a decom per repetition") };
+        decom_rep[2].1.pop();
+        let truncated_proof_bundle = ProofBundle::new(
+            public_parameter, preprocessing_transcript, proof_transcript, pa_decom, pb_decom
+        );
+        assert_eq!(
+            ProofBundle::<GFVOLE, GFVOLEitH>::from_byte_vec(&truncated_proof_bundle.to_byte_vec()).err(),
+            Some(ProofBundleError::LengthMismatch {
+                field: "pb_decom.decom_rep[2].1".to_string(),
+                expected: tau,
+                actual: tau - 1,
+            })
+        );
+    }
+
+    // a single tree over the leaves of all 32 repetitions reveals at most as many seeds as the separate trees
+    #[test]
+    fn test_pa_2pc_with_batched_vc_for_sub64() {
+        let (bristol_fashion_adaptor, _, _, proof_bundle) = prove_for_sub64(false, VectorCommitmentMode::Batched);
+        let byte_vec = proof_bundle.to_byte_vec();
+        let ProofBundle {
+            public_parameter, preprocessing_transcript, proof_transcript, pa_decom, pb_decom
+        } = ProofBundle::<GFVOLE, GFVOLEitH>::from_byte_vec(&byte_vec).unwrap();
+        let verified_output = VerifierInPA2PC::verify::<GFVOLE, GFVOLEitH>(
+            false,
+            &bristol_fashion_adaptor,
+            &public_parameter,
+            &preprocessing_transcript,
+            &proof_transcript,
+            &pa_decom,
+            &pb_decom,
+        ).unwrap();
+        assert_eq!(verified_output.output_bit_vec, proof_transcript.published_output_bit_vec);
+        let Decom::Batched { co_path_seed_vec, .. } = &pb_decom else { panic!("expected a batched decom") };
+        assert!(co_path_seed_vec.len() <= public_parameter.tau_rep.iter().map(|tau| *tau as usize).sum::<usize>());
+
+        let verify_with_pb_decom = |tampered_pb_decom: &Decom| VerifierInPA2PC::verify::<GFVOLE, GFVOLEitH>(
+            false,
+            &bristol_fashion_adaptor,
+            &public_parameter,
+            &preprocessing_transcript,
+            &proof_transcript,
+            &pa_decom,
+            tampered_pb_decom,
+        );
+        let mut tampered_pb_decom = pb_decom.clone();
+        let Decom::Batched { co_path_seed_vec, .. } = &mut tampered_pb_decom else { unreachable!() };
+        co_path_seed_vec.pop();
+        assert_eq!(verify_with_pb_decom(&tampered_pb_decom), Err(VerificationError::MalformedDecom { is_pa: false }));
+        let mut tampered_pb_decom = pb_decom.clone();
+        let Decom::Batched { co_path_seed_vec, .. } = &mut tampered_pb_decom else { unreachable!() };
+        co_path_seed_vec[0][0] ^= 1;
+        assert!(matches!(
+            verify_with_pb_decom(&tampered_pb_decom),
+            Err(VerificationError::SVOLEReconstruction { is_pa: false, .. })
+        ));
+
+        // a decommitment of the other mode is rejected by the loader
+        let ProofBundle {
+            public_parameter, preprocessing_transcript, proof_transcript, pa_decom, ..
+        } = proof_bundle;
+        let mismatched_proof_bundle = ProofBundle::new(
+            public_parameter, preprocessing_transcript, proof_transcript, pa_decom,
+            Decom::PerRepetition { decom_rep: Vec::new() }
+        );
+        assert!(matches!(
+            ProofBundle::<GFVOLE, GFVOLEitH>::from_byte_vec(&mismatched_proof_bundle.to_byte_vec()),
+            Err(ProofBundleError::Malformed(_))
+        ));
+    }
+
+    // the public parameter pins the circuit, and its context enters the Fiat-Shamir challenges
+    #[test]
+    fn test_pa_2pc_is_bound_to_circuit_and_context() {
+        let (bristol_fashion_adaptor, _, _, proof_bundle) = prove_for_sub64(false, VectorCommitmentMode::PerRepetition);
+        let verify_with = |bristol_fashion_adaptor: &BristolFashionAdaptor, public_parameter: &PublicParameter| {
+            VerifierInPA2PC::verify::<GFVOLE, GFVOLEitH>(
+                false,
+                bristol_fashion_adaptor,
+                public_parameter,
+                &proof_bundle.preprocessing_transcript,
+                &proof_bundle.proof_transcript,
+                &proof_bundle.pa_decom,
+                &proof_bundle.pb_decom,
+            )
+        };
+        assert!(verify_with(&bristol_fashion_adaptor, &proof_bundle.public_parameter).is_ok());
+
+        // adder64 has the same input and output groups as sub64
+        let other_bristol_fashion_adaptor = BristolFashionAdaptor::new(&"adder64.txt".to_string());
+        assert_eq!(
+            verify_with(&other_bristol_fashion_adaptor, &proof_bundle.public_parameter),
+            Err(VerificationError::CircuitDigestMismatch)
+        );
+
+        // the context is encoded last, so replacing None by a session id leaves everything else in place
+        let mut byte_vec = encode_to_vec(&proof_bundle.public_parameter, config::standard()).unwrap();
+        assert_eq!(byte_vec.pop(), Some(0));
+        byte_vec.append(&mut encode_to_vec(Some("session 2".to_string()), config::standard()).unwrap());
+        let (public_parameter_with_context, _): (PublicParameter, usize) = decode_from_slice(&byte_vec, config::standard()).unwrap();
+        assert_eq!(public_parameter_with_context.context, Some("session 2".to_string()));
+        assert_ne!(public_parameter_with_context.to_byte_vec(), proof_bundle.public_parameter.to_byte_vec());
+        assert!(verify_with(&bristol_fashion_adaptor, &public_parameter_with_context).is_err());
+    }
+
+    // the fields derived from the circuit are not in the digest, so they are checked against the circuit one by one,
+    // instead of reading the wrong output wires or sizing the verifier's vectors too short
+    #[test]
+    fn test_pa_2pc_rejects_tampered_circuit_fields() {
+        let (bristol_fashion_adaptor, _, _, proof_bundle) = prove_for_sub64(false, VectorCommitmentMode::PerRepetition);
+        let verify_with = |public_parameter: &PublicParameter| {
+            VerifierInPA2PC::verify::<GFVOLE, GFVOLEitH>(
+                false,
+                &bristol_fashion_adaptor,
+                public_parameter,
+                &proof_bundle.preprocessing_transcript,
+                &proof_bundle.proof_transcript,
+                &proof_bundle.pa_decom,
+                &proof_bundle.pb_decom,
+            )
+        };
+        let tamper = |tamper_with: &dyn Fn(&mut PublicParameter)| {
+            let byte_vec = encode_to_vec(&proof_bundle.public_parameter, config::standard()).unwrap();
+            let (mut public_parameter, _): (PublicParameter, usize) = decode_from_slice(&byte_vec, config::standard()).unwrap();
+            tamper_with(&mut public_parameter);
+            // none of the tampered fields enters the digest or byte_vec
+            assert_eq!(public_parameter.to_byte_vec(), proof_bundle.public_parameter.to_byte_vec());
+            public_parameter
+        };
+
+        let public_parameter = tamper(&|public_parameter| public_parameter.big_io.swap(0, 1));
+        assert_eq!(verify_with(&public_parameter), Err(VerificationError::CircuitDigestMismatch));
+        let public_parameter = tamper(&|public_parameter| public_parameter.num_wires -= 1);
+        assert_eq!(verify_with(&public_parameter), Err(VerificationError::CircuitDigestMismatch));
+        let public_parameter = tamper(&|public_parameter| public_parameter.num_input_bits += 1);
+        assert_eq!(verify_with(&public_parameter), Err(VerificationError::CircuitDigestMismatch));
+        let public_parameter = tamper(&|public_parameter| public_parameter.big_iw.reverse());
+        assert_eq!(verify_with(&public_parameter), Err(VerificationError::CircuitDigestMismatch));
+        let public_parameter = tamper(&|public_parameter| public_parameter.big_iw_size -= 1);
+        assert_eq!(verify_with(&public_parameter), Err(VerificationError::CircuitDigestMismatch));
+    }
+
+    // PB's commitments bind the middle bit and every VOLEitH MAC, so altering any of them breaks the opening
+    #[test]
+    fn test_pb_middle_commitments_bind_every_mac() {
+        let (bristol_fashion_adaptor, _, _, proof_bundle) = prove_for_sub64(false, VectorCommitmentMode::PerRepetition);
+        let ProofBundle {
+            public_parameter, preprocessing_transcript, proof_transcript, pa_decom, pb_decom
+        } = proof_bundle;
+        let and_cursor = 0;
+        let randomness = &proof_transcript.pb_published_middle_randomness_vec[and_cursor];
+        let middle_bit = proof_transcript.pb_published_middle_r_bit_vec[and_cursor];
+        let middle_voleith_mac_vec = proof_transcript.pb_published_middle_voleith_mac_r_vec_rep.iter().map(
+            |pb_published_middle_voleith_mac_r_vec| pb_published_middle_voleith_mac_r_vec[and_cursor]
+        ).collect::<Vec<GFVOLEitH>>();
+        let opens = |middle_bit: u8, middle_voleith_mac_vec: &[GFVOLEitH]| preprocessing_transcript.commitment_vec[and_cursor].iter().filter(
+            |com| verify_opening(public_parameter.crypto_backend.hash(), com, &make_pb_middle_message(middle_bit, middle_voleith_mac_vec.iter()), randomness)
+        ).count();
+        // exactly the commitment of the evaluated row is opened
+        assert_eq!(opens(middle_bit, &middle_voleith_mac_vec), 1);
+        assert_eq!(opens(middle_bit ^ 1, &middle_voleith_mac_vec), 0);
+        for repetition_id in 0..public_parameter.kappa {
+            let mut altered_middle_voleith_mac_vec = middle_voleith_mac_vec.clone();
+            altered_middle_voleith_mac_vec[repetition_id] = altered_middle_voleith_mac_vec[repetition_id].custom_add(
+                &GFVOLEitH::from_leaf_index(1)
+            );
+            assert_eq!(opens(middle_bit, &altered_middle_voleith_mac_vec), 0, "repetition {}", repetition_id);
+        }
+
+        // and the verifier rejects an altered MAC, here of the first and the last repetition. The proof transcript
+        // enters the Fiat-Shamir challenges, so that the altered MAC already changes nabla_a_rep and PA's decommitment
+        // no longer reconstructs, before the opening of PB's commitments is even checked
+        for repetition_id in [0, public_parameter.kappa - 1] {
+            let (mut altered_proof_transcript, _): (ProofTranscript<GFVOLE, GFVOLEitH>, usize) = decode_from_slice(
+                &encode_to_vec(&proof_transcript, config::standard()).unwrap(), config::standard()
+            ).unwrap();
+            let middle_voleith_mac = &mut altered_proof_transcript.pb_published_middle_voleith_mac_r_vec_rep[repetition_id][and_cursor];
+            *middle_voleith_mac = middle_voleith_mac.custom_add(&GFVOLEitH::from_leaf_index(1));
+            let verification_result = VerifierInPA2PC::verify::<GFVOLE, GFVOLEitH>(
+                false,
+                &bristol_fashion_adaptor,
+                &public_parameter,
+                &preprocessing_transcript,
+                &altered_proof_transcript,
+                &pa_decom,
+                &pb_decom,
+            );
+            assert!(
+                matches!(verification_result, Err(VerificationError::SVOLEReconstruction { is_pa: true, .. })),
+                "repetition {}: {:?}", repetition_id, verification_result
+            );
+        }
+    }
+
+    // in the salted mode every proof under one setup draws its own IV, which the verifier takes from the bundle
+    #[test]
+    fn test_salted_proofs_under_one_setup_use_fresh_ivs() {
+        let mut rng = ChaCha20Rng::seed_from_u64(0);
+        let bristol_fashion_adaptor = BristolFashionAdaptor::new(&"sub64.txt".to_string());
+        let (big_ia, big_ib) = bristol_fashion_adaptor.determine_big_ia_and_big_ib(&[0]).unwrap();
+        let rm = bristol_fashion_adaptor.get_and_gate_output_wire_vec().len();
+        let setup_public_parameter = PublicParameter::new::<GF2p128, GF2p8>(
+            &bristol_fashion_adaptor, 8, 16, VectorCommitmentMode::PerRepetition, LeafExpansionVersion::V2, CryptoBackend::default(),
+            SeedU8x16::insecurely_random(&mut rng), Some(SeedU8x16::insecurely_random(&mut rng)), big_ia, big_ib, 1, rm, None,
+        );
+        let setup_byte_vec = encode_to_vec(&setup_public_parameter, config::standard()).unwrap();
+        let pa_input_bit_vec = vec![1u8; setup_public_parameter.big_ia.len()];
+        let pb_input_bit_vec = vec![0u8; setup_public_parameter.big_ib.len()];
+
+        let proof_bundle_vec = (0..2).map(|_| {
+            let (mut public_parameter, _): (PublicParameter, usize) = decode_from_slice(&setup_byte_vec, config::standard()).unwrap();
+            public_parameter.refresh_iv_for_one_to_two_prg(&mut rng);
+            prove_under_public_parameter::<GF2p128, GF2p8>(
+                false, &bristol_fashion_adaptor, public_parameter, &pa_input_bit_vec, &pb_input_bit_vec, &mut rng
+            )
+        }).collect::<Vec<ProofBundle<GF2p128, GF2p8>>>();
+        let iv_vec = proof_bundle_vec.iter().map(
+            |proof_bundle| proof_bundle.public_parameter.iv_for_one_to_two_prg.unwrap()
+        ).collect::<Vec<SeedU8x16>>();
+        assert_ne!(iv_vec[0], iv_vec[1]);
+        assert!(iv_vec.iter().all(|iv| Some(*iv) != setup_public_parameter.iv_for_one_to_two_prg));
+
+        // each proof verifies under the IV of its own bundle only
+        let verify_with = |proof_bundle: &ProofBundle<GF2p128, GF2p8>, public_parameter: &PublicParameter| {
+            VerifierInPA2PC::verify::<GF2p128, GF2p8>(
+                false,
+                &bristol_fashion_adaptor,
+                public_parameter,
+                &proof_bundle.preprocessing_transcript,
+                &proof_bundle.proof_transcript,
+                &proof_bundle.pa_decom,
+                &proof_bundle.pb_decom,
+            )
+        };
+        for proof_bundle in &proof_bundle_vec {
+            assert!(verify_with(proof_bundle, &proof_bundle.public_parameter).is_ok());
+        }
+        assert!(verify_with(&proof_bundle_vec[0], &proof_bundle_vec[1].public_parameter).is_err());
+
+        // without a salted setup there is no IV to refresh
+        let (_, mut unsalted_public_parameter) = setup_sub64();
+        let unsalted_byte_vec = unsalted_public_parameter.to_byte_vec().clone();
+        unsalted_public_parameter.refresh_iv_for_one_to_two_prg(&mut rng);
+        assert_eq!(unsalted_public_parameter.iv_for_one_to_two_prg, None);
+        assert_eq!(unsalted_public_parameter.to_byte_vec(), &unsalted_byte_vec);
+    }
+
+    fn setup_sub64() -> (BristolFashionAdaptor, PublicParameter) {
+        let bristol_fashion_adaptor = BristolFashionAdaptor::new(
+            &"sub64.txt".to_string()
+        );
+        let (big_ia, big_ib) = bristol_fashion_adaptor.determine_big_ia_and_big_ib(&[0]).unwrap();
+        let rm = bristol_fashion_adaptor.get_and_gate_output_wire_vec().len();
+        let public_parameter = PublicParameter::new::<GFVOLE, GFVOLEitH>(
+            &bristol_fashion_adaptor, 8, PublicParameter::determine_kappa(GFVOLE::num_bytes() * 8, 8), VectorCommitmentMode::PerRepetition, LeafExpansionVersion::V2, CryptoBackend::default(),
+            SeedU8x16::insecurely_random(&mut rand::rng()), None, big_ia, big_ib, 1, rm, None,
+        );
+        (bristol_fashion_adaptor, public_parameter)
+    }
+
+    // PA and PB run on their own threads and must end up with the same transcripts, which verify
+    fn prove_with_two_parties<C: Channel + Send>(pa_channel: C, pb_channel: C) {
+        let (bristol_fashion_adaptor, public_parameter) = setup_sub64();
+        let pa_input_bit_vec = bristol_fashion_adaptor.determine_input_bit_vec_from_u64_vec(&[5, 0]).unwrap()[..64].to_vec();
+        let pb_input_bit_vec = bristol_fashion_adaptor.determine_input_bit_vec_from_u64_vec(&[0, 1000]).unwrap()[64..].to_vec();
+        let (mut pa_functionality_pre, mut pb_functionality_pre) = InsecureFunctionalityPre::new_pair(ChaCha20Rng::from_rng(&mut rand::rng()));
+
+        let (pa_result, pb_result) = thread::scope(|scope| {
+            let pa_thread = scope.spawn(|| {
+                let mut party_a = PartyA::<C, GFVOLE, GFVOLEitH>::new(
+                    &bristol_fashion_adaptor, &public_parameter, SeedU8x16::insecurely_random(&mut rand::rng()), pa_channel
+                );
+                let preprocessing_transcript = party_a.preprocess(false, &mut pa_functionality_pre, &mut rand::rng()).unwrap();
+                let proof = party_a.prove(false, &preprocessing_transcript, &pa_input_bit_vec).unwrap();
+                (preprocessing_transcript, proof, party_a.channel_statistics())
+            });
+            let pb_thread = scope.spawn(|| {
+                let mut party_b = PartyB::<C, GFVOLE, GFVOLEitH>::new(
+                    &bristol_fashion_adaptor, &public_parameter, SeedU8x16::insecurely_random(&mut rand::rng()), pb_channel
+                );
+                let preprocessing_transcript = party_b.preprocess(false, &mut pb_functionality_pre).unwrap();
+                let proof = party_b.prove(false, &preprocessing_transcript, &pb_input_bit_vec).unwrap();
+                (preprocessing_transcript, proof, party_b.channel_statistics())
+            });
+            (pa_thread.join().unwrap(), pb_thread.join().unwrap())
+        });
+        let (pa_preprocessing_transcript, (pa_proof_transcript, pa_decom, pb_decom), pa_statistics) = pa_result;
+        let (pb_preprocessing_transcript, (pb_proof_transcript, pb_view_of_pa_decom, pb_view_of_pb_decom), pb_statistics) = pb_result;
+
+        assert_eq!(pa_preprocessing_transcript.to_byte_vec(), pb_preprocessing_transcript.to_byte_vec());
+        assert_eq!(pa_proof_transcript.to_byte_vec(), pb_proof_transcript.to_byte_vec());
+        assert_eq!(pa_decom, pb_view_of_pa_decom);
+        assert_eq!(pb_decom, pb_view_of_pb_decom);
+
+        let verified_output = VerifierInPA2PC::verify::<GFVOLE, GFVOLEitH>(
+            false,
+            &bristol_fashion_adaptor,
+            &public_parameter,
+            &pa_preprocessing_transcript,
+            &pa_proof_transcript,
+            &pa_decom,
+            &pb_decom,
+        ).unwrap();
+        assert_eq!(
+            bristol_fashion_adaptor.split_output_bit_vec_into_u64_vec(&verified_output.output_bit_vec.iter().collect::<Vec<u8>>()).unwrap(),
+            vec![5u64.wrapping_sub(1000)]
+        );
+
+        assert!(pa_statistics.num_rounds > 1);
+        assert!(pa_statistics.num_sent_bytes > 0);
+        assert_eq!(pa_statistics.num_sent_bytes, pb_statistics.num_received_bytes);
+        assert_eq!(pa_statistics.num_received_bytes, pb_statistics.num_sent_bytes);
+    }
+
+    #[test]
+    fn test_two_parties_over_in_memory_channel() {
+        let (pa_channel, pb_channel) = InMemoryChannel::new_pair();
+        prove_with_two_parties(pa_channel, pb_channel);
+    }
+
+    #[test]
+    fn test_two_parties_over_tcp_channel() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let pb_connecting_thread = thread::spawn(move || TcpChannel::connect(address).unwrap());
+        let pa_channel = TcpChannel::accept(&listener).unwrap();
+        prove_with_two_parties(pa_channel, pb_connecting_thread.join().unwrap());
+    }
+
+    #[test]
+    fn test_party_b_rejects_malformed_messages() {
+        let (bristol_fashion_adaptor, public_parameter) = setup_sub64();
+
+        // PB obtains his share from his own endpoint of FPre, while PA's endpoint answers on another thread,
+        // and then waits for PA's preprocessing message
+        let preprocess_with_pa_message = |pa_message: Option<PAPreprocessingMessage<GFVOLE, GFVOLEitH>>| {
+            let (mut pa_channel, pb_channel) = InMemoryChannel::new_pair();
+            let (mut pa_functionality_pre, mut pb_functionality_pre) = InsecureFunctionalityPre::new_pair(ChaCha20Rng::from_rng(&mut rand::rng()));
+            thread::scope(|scope| {
+                scope.spawn(|| {
+                    ProverInPA2PC::deal::<GFVOLE, _>(false, &mut pa_functionality_pre, &bristol_fashion_adaptor, &public_parameter, true);
+                });
+                match pa_message {
+                    Some(pa_message) => pa_channel.send(&pa_message).unwrap(),
+                    None => drop(pa_channel),
+                }
+                let mut party_b = PartyB::<_, GFVOLE, GFVOLEitH>::new(
+                    &bristol_fashion_adaptor, &public_parameter, SeedU8x16::insecurely_random(&mut rand::rng()), pb_channel
+                );
+                party_b.preprocess(false, &mut pb_functionality_pre).err()
+            })
+        };
+
+        // a preprocessing message of PA with a missing commitment
+        let pa_message = PAPreprocessingMessage::<GFVOLE, GFVOLEitH> {
+            com_hash_rep: vec![[0u8; 32]; public_parameter.kappa - 1],
+            masked_bit_tuple_rep: vec![],
+            garbled_table: vec![],
+        };
+        assert_eq!(
+            preprocess_with_pa_message(Some(pa_message)),
+            Some(PartyError::Length {
+                field: "com_hash_rep",
+                expected: public_parameter.kappa,
+                actual: public_parameter.kappa - 1,
+            })
+        );
+
+        // PA hangs up before sending anything
+        assert!(matches!(preprocess_with_pa_message(None), Some(PartyError::Channel(_))));
+
+        // PA masks her first input to 2 instead of a bit, which PB rejects before evaluating the garbled circuit
+        let pa_input_bit_vec = vec![0u8; public_parameter.big_ia.len()];
+        let pb_input_bit_vec = vec![0u8; public_parameter.big_ib.len()];
+        let (pa_channel, pb_channel) = InMemoryChannel::new_pair();
+        let pb_channel = TamperingChannel {
+            channel: pb_channel,
+            num_received: 0,
+            // PA's preprocessing message, rm share, PiCheckAND shares and input share come first
+            tamper_at: 5,
+            tamper: |byte_vec: Vec<u8>| {
+                let (mut pa_input_label_message, _): (PAInputLabelMessage<GFVOLE>, usize) = decode_from_slice(
+                    &byte_vec, config::standard()
+                ).unwrap();
+                pa_input_label_message.hat_z_input_bit_vec_with_ia[0] = 2;
+                encode_to_vec(&pa_input_label_message, config::standard()).unwrap()
+            },
+        };
+        let (mut pa_functionality_pre, mut pb_functionality_pre) = InsecureFunctionalityPre::new_pair(ChaCha20Rng::from_rng(&mut rand::rng()));
+        let pb_error = thread::scope(|scope| {
+            scope.spawn(|| {
+                let mut party_a = PartyA::<_, GFVOLE, GFVOLEitH>::new(
+                    &bristol_fashion_adaptor, &public_parameter, SeedU8x16::insecurely_random(&mut rand::rng()), pa_channel
+                );
+                let preprocessing_transcript = party_a.preprocess(false, &mut pa_functionality_pre, &mut rand::rng()).unwrap();
+                // PB hangs up once he rejects the message
+                assert!(party_a.prove(false, &preprocessing_transcript, &pa_input_bit_vec).is_err());
+            });
+            let mut party_b = PartyB::<_, GFVOLE, GFVOLEitH>::new(
+                &bristol_fashion_adaptor, &public_parameter, SeedU8x16::insecurely_random(&mut rand::rng()), pb_channel
+            );
+            let preprocessing_transcript = party_b.preprocess(false, &mut pb_functionality_pre).unwrap();
+            party_b.prove(false, &preprocessing_transcript, &pb_input_bit_vec).err()
+        });
+        assert_eq!(pb_error, Some(PartyError::NotABit { field: "hat_z_input_bit_vec_with_ia", index: 0 }));
+    }
+
+    // Hands every received frame through, except the one at index tamper_at, which is altered by tamper first
+    struct TamperingChannel<C, F> {
+        channel: C,
+        num_received: usize,
+        tamper_at: usize,
+        tamper: F,
+    }
+
+    impl<C: Channel, F: FnMut(Vec<u8>) -> Vec<u8>> Channel for TamperingChannel<C, F> {
+        fn send_byte_vec(&mut self, byte_vec: Vec<u8>) -> Result<(), ChannelError> {
+            self.channel.send_byte_vec(byte_vec)
+        }
+
+        fn recv_byte_vec(&mut self) -> Result<Vec<u8>, ChannelError> {
+            let byte_vec = self.channel.recv_byte_vec()?;
+            self.num_received += 1;
+            if self.num_received - 1 == self.tamper_at {
+                return Ok((self.tamper)(byte_vec));
+            }
+            Ok(byte_vec)
+        }
+
+        fn statistics(&self) -> ChannelStatistics {
+            self.channel.statistics()
+        }
     }
-}
\ No newline at end of file
+}
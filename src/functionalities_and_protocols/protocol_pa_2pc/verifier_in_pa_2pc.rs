@@ -1,19 +1,20 @@
 use std::fmt::Debug;
 use bincode::Encode;
+use crate::functionalities_and_protocols::all_in_one_vc::Decom;
 use crate::bristol_fashion_adaptor::bristol_fashion_adaptor::BristolFashionAdaptor;
 use crate::bristol_fashion_adaptor::GateType;
-use crate::functionalities_and_protocols::hasher;
+use crate::functionalities_and_protocols::commitment::verify_opening;
 use crate::functionalities_and_protocols::hasher::{hash_to_determine_nabla_rep, hash_to_determine_permutation_rep};
 use crate::functionalities_and_protocols::protocol_check_and::verifier_in_protocol_check_and::VerifierInProtocolCheckAND;
-use crate::functionalities_and_protocols::protocol_pa_2pc::{extract_block_vec_rep, initialize_trace, permute, split_off_rm};
+use crate::functionalities_and_protocols::protocol_pa_2pc::{extract_block_vec_rep, initialize_trace, make_pb_middle_message, permute, split_off_rm};
 use crate::functionalities_and_protocols::protocol_svole_2pc::verifier_in_protocol_svole_2pc::VerifierInProtocolSVOLE2PC;
 use crate::functionalities_and_protocols::protocol_pa_2pc::preprocessing_transcript::PreprocessingTranscript;
 use crate::functionalities_and_protocols::protocol_pa_2pc::proof_transcript::ProofTranscript;
 use crate::functionalities_and_protocols::states_and_parameters::public_parameter::PublicParameter;
 use crate::functionalities_and_protocols::util::verifier::Verifier;
-use crate::value_type::{ByteManipulation, CustomAddition, CustomMultiplyingBit, U8ForGF, Zero};
-use crate::value_type::seed_u8x16::SeedU8x16;
-use crate::vec_type::{gf_vec::GFVec, BasicVecFunctions, VecAddition, ZeroVec};
+use crate::functionalities_and_protocols::verification_result::{VerificationError, VerifiedOutput};
+use crate::value_type::{ByteManipulation, CustomAddition, CustomMultiplyingBit, LeafIndexForGF, Zero};
+use crate::vec_type::{gf_vec::GFVec, VecAddition, ZeroVec};
 use crate::vec_type::bit_vec::BitVec;
 
 pub struct VerifierInPA2PC;
@@ -27,20 +28,27 @@ impl VerifierInPA2PC {
         // nabla_a_rep: &Vec<GFVOLEitH>, nabla_b_rep: &Vec<GFVOLEitH>,
         preprocessing_transcript: &PreprocessingTranscript<GFVOLE, GFVOLEitH>,
         proof_transcript: &ProofTranscript<GFVOLE, GFVOLEitH>,
-        pa_decom_rep: &Vec<(SeedU8x16, Vec<SeedU8x16>)>,
-        pb_decom_rep: &Vec<(SeedU8x16, Vec<SeedU8x16>)>,
+        pa_decom: &Decom,
+        pb_decom: &Decom,
         // pa_secret_state_to_be_removed: &ProverSecretState<GFVOLE, GFVOLEitH>,
-    )
+    ) -> Result<VerifiedOutput, VerificationError>
     where GFVOLE: Clone + Encode + Zero,
-          GFVOLEitH: Clone + CustomAddition + CustomMultiplyingBit + Zero + U8ForGF + PartialEq + Debug + Copy + ByteManipulation + Send + Sync + Encode {
+          GFVOLEitH: Clone + CustomAddition + CustomMultiplyingBit + Zero + LeafIndexForGF + PartialEq + Debug + Copy + ByteManipulation + Send + Sync + Encode {
         if process_printing {
             println!("+ Verifying ...");
         }
 
+        if process_printing {
+            println!("  Check the public parameter against the circuit");
+        }
+        if !public_parameter.matches_circuit(bristol_fashion_adaptor) {
+            return Err(VerificationError::CircuitDigestMismatch);
+        }
+
         if process_printing {
             println!("  Determine permutation_rep via Fiat-Shamir");
         }
-        let (permutation_rep, auxiliary_input) = hash_to_determine_permutation_rep(
+        let (permutation_rep, mut fiat_shamir_transcript) = hash_to_determine_permutation_rep(
             public_parameter, preprocessing_transcript
         );
 
@@ -48,7 +56,7 @@ impl VerifierInPA2PC {
             println!("  Determine nabla_a_rep and nabla_b_rep via Fiat-Shamir");
         }
         let (nabla_a_rep, nabla_b_rep) = hash_to_determine_nabla_rep(
-            public_parameter, &auxiliary_input, proof_transcript
+            public_parameter, &mut fiat_shamir_transcript, proof_transcript
         );
 
         if process_printing {
@@ -64,11 +72,12 @@ impl VerifierInPA2PC {
         let mut pa_voleith_key_tuple_rep = VerifierInProtocolSVOLE2PC::reconstruct_and_fix_voleith_key_vec(
             process_printing,
             public_parameter,
+            true,
             &preprocessing_transcript.pa_com_hash_rep,
             &preprocessing_transcript.pa_masked_bit_tuple_rep,
             &nabla_b_rep,
-            &pa_decom_rep,
-        );
+            &pa_decom,
+        )?;
         (0..public_parameter.kappa).rev().for_each(
             |repetition_id| {
                 (
@@ -93,11 +102,12 @@ impl VerifierInPA2PC {
         let mut pb_voleith_key_tuple_rep = VerifierInProtocolSVOLE2PC::reconstruct_and_fix_voleith_key_vec(
             process_printing,
             public_parameter,
+            false,
             &preprocessing_transcript.pb_com_hash_rep,
             &preprocessing_transcript.pb_masked_bit_tuple_rep,
             &nabla_a_rep,
-            &pb_decom_rep,
-        );
+            &pb_decom,
+        )?;
         (0..public_parameter.kappa).rev().for_each(
             |repetition_id| {
                 (
@@ -156,19 +166,25 @@ impl VerifierInPA2PC {
                 &proof_transcript.pa_published_rm_voleith_mac_a_vec_rep[repetition_id],
                 &nabla_b_rep[repetition_id],
                 &pa_rm_voleith_key_tilde_a_vec_rep[repetition_id],
-            );
+            ).map_err(
+                |index| VerificationError::OpenedAndTriple { is_pa: true, repetition_id, index }
+            )?;
             Verifier::verify_vole_correlations(
                 &proof_transcript.pa_published_rm_b_vec_rep[repetition_id],
                 &proof_transcript.pa_published_rm_voleith_mac_b_vec_rep[repetition_id],
                 &nabla_b_rep[repetition_id],
                 &pa_rm_voleith_key_tilde_b_vec_rep[repetition_id],
-            );
+            ).map_err(
+                |index| VerificationError::OpenedAndTriple { is_pa: true, repetition_id, index }
+            )?;
             Verifier::verify_vole_correlations(
                 &proof_transcript.pa_published_rm_c_vec_rep[repetition_id],
                 &proof_transcript.pa_published_rm_voleith_mac_c_vec_rep[repetition_id],
                 &nabla_b_rep[repetition_id],
                 &pa_rm_voleith_key_tilde_c_vec_rep[repetition_id],
-            );
+            ).map_err(
+                |index| VerificationError::OpenedAndTriple { is_pa: true, repetition_id, index }
+            )?;
 
             // Check PB's side
             Verifier::verify_vole_correlations(
@@ -176,33 +192,44 @@ impl VerifierInPA2PC {
                 &proof_transcript.pb_published_rm_voleith_mac_a_vec_rep[repetition_id],
                 &nabla_a_rep[repetition_id],
                 &pb_rm_voleith_key_tilde_a_vec_rep[repetition_id],
-            );
+            ).map_err(
+                |index| VerificationError::OpenedAndTriple { is_pa: false, repetition_id, index }
+            )?;
             Verifier::verify_vole_correlations(
                 &proof_transcript.pb_published_rm_b_vec_rep[repetition_id],
                 &proof_transcript.pb_published_rm_voleith_mac_b_vec_rep[repetition_id],
                 &nabla_a_rep[repetition_id],
                 &pb_rm_voleith_key_tilde_b_vec_rep[repetition_id],
-            );
+            ).map_err(
+                |index| VerificationError::OpenedAndTriple { is_pa: false, repetition_id, index }
+            )?;
             Verifier::verify_vole_correlations(
                 &proof_transcript.pb_published_rm_c_vec_rep[repetition_id],
                 &proof_transcript.pb_published_rm_voleith_mac_c_vec_rep[repetition_id],
                 &nabla_a_rep[repetition_id],
                 &pb_rm_voleith_key_tilde_c_vec_rep[repetition_id],
-            );
+            ).map_err(
+                |index| VerificationError::OpenedAndTriple { is_pa: false, repetition_id, index }
+            )?;
 
             // Check correct AND
-            assert_eq!(
-                proof_transcript.pa_published_rm_a_vec_rep[repetition_id].vec_add(
-                    &proof_transcript.pb_published_rm_a_vec_rep[repetition_id]
-                ).entry_wise_multiply(
-                    &proof_transcript.pa_published_rm_b_vec_rep[repetition_id].vec_add(
-                        &proof_transcript.pb_published_rm_b_vec_rep[repetition_id]
-                    )
-                ),
-                proof_transcript.pa_published_rm_c_vec_rep[repetition_id].vec_add(
-                    &proof_transcript.pb_published_rm_c_vec_rep[repetition_id]
+            let rm_ab_vec = proof_transcript.pa_published_rm_a_vec_rep[repetition_id].vec_add(
+                &proof_transcript.pb_published_rm_a_vec_rep[repetition_id]
+            ).entry_wise_multiply(
+                &proof_transcript.pa_published_rm_b_vec_rep[repetition_id].vec_add(
+                    &proof_transcript.pb_published_rm_b_vec_rep[repetition_id]
                 )
             );
+            let rm_c_vec = proof_transcript.pa_published_rm_c_vec_rep[repetition_id].vec_add(
+                &proof_transcript.pb_published_rm_c_vec_rep[repetition_id]
+            );
+            if rm_ab_vec != rm_c_vec {
+                let min_len = rm_ab_vec.len().min(rm_c_vec.len());
+                let index = (0..min_len).find(
+                    |index| rm_ab_vec[*index] != rm_c_vec[*index]
+                ).unwrap_or(min_len);
+                return Err(VerificationError::OpenedAndTripleRelation { repetition_id, index });
+            }
         }
 
         if process_printing {
@@ -246,16 +273,16 @@ impl VerifierInPA2PC {
         let mut pb_voleith_key_r_prime_right_vec_rep = vec![GFVec::zero_vec(public_parameter.big_iw_size); public_parameter.kappa];
         for repetition_id in 0..public_parameter.kappa {
             let mut and_cursor = 0usize;
-            for gate in bristol_fashion_adaptor.get_gate_vec() {
+            for (gate_id, gate) in bristol_fashion_adaptor.get_gate_vec().iter().enumerate() {
                 // println!("Verifier in PA2PC computes voleith key for gate {:?}", gate);
                 match gate.gate_type {
                     GateType::XOR => {
-                        hat_z_bit_trace_vec[gate.output_wire] = hat_z_bit_trace_vec[gate.left_input_wire] ^ hat_z_bit_trace_vec[gate.right_input_wire];
+                        hat_z_bit_trace_vec.set(gate.output_wire, hat_z_bit_trace_vec[gate.left_input_wire] ^ hat_z_bit_trace_vec[gate.right_input_wire]);
                         pa_voleith_key_r_trace_vec_rep[repetition_id][gate.output_wire] = pa_voleith_key_r_trace_vec_rep[repetition_id][gate.left_input_wire].custom_add(&pa_voleith_key_r_trace_vec_rep[repetition_id][gate.right_input_wire]);
                         pb_voleith_key_r_trace_vec_rep[repetition_id][gate.output_wire] = pb_voleith_key_r_trace_vec_rep[repetition_id][gate.left_input_wire].custom_add(&pb_voleith_key_r_trace_vec_rep[repetition_id][gate.right_input_wire]);
                     }
                     GateType::NOT => {
-                        hat_z_bit_trace_vec[gate.output_wire] = hat_z_bit_trace_vec[gate.left_input_wire] ^ 1u8;
+                        hat_z_bit_trace_vec.set(gate.output_wire, hat_z_bit_trace_vec[gate.left_input_wire] ^ 1u8);
                         pa_voleith_key_r_trace_vec_rep[repetition_id][gate.output_wire] = pa_voleith_key_r_trace_vec_rep[repetition_id][gate.left_input_wire];
                         pb_voleith_key_r_trace_vec_rep[repetition_id][gate.output_wire] = pb_voleith_key_r_trace_vec_rep[repetition_id][gate.left_input_wire];
                     }
@@ -294,23 +321,27 @@ impl VerifierInPA2PC {
                         //     ]);
                         // println!("Decrypted VOLEitH MAC:            {:?}", proof_transcript.published_decrypted_garbled_row[and_cursor].voleith_mac_field[repetition_id]);
 
-                        assert_eq!(
-                            proof_transcript.published_decrypted_garbled_row[and_cursor].voleith_mac_field[repetition_id],
-                            pa_middle_voleith_key_r_and_output_vec_rep[repetition_id][and_cursor].custom_add(
+                        if proof_transcript.published_decrypted_garbled_row[and_cursor].voleith_mac_field[repetition_id]
+                            != pa_middle_voleith_key_r_and_output_vec_rep[repetition_id][and_cursor].custom_add(
                                 &nabla_b_rep[repetition_id].custom_multiply_bit(
                                     proof_transcript.published_decrypted_garbled_row[and_cursor].first_u8
                                 )
-                            )
-                        );
+                            ) {
+                            return Err(VerificationError::GarbledRowDecryption {
+                                is_pa: true, repetition_id, and_gate_id: gate_id, and_cursor
+                            });
+                        }
 
-                        assert_eq!(
-                            proof_transcript.pb_published_middle_voleith_mac_r_vec_rep[repetition_id][and_cursor],
-                            pb_middle_voleith_key_r_and_output_vec_rep[repetition_id][and_cursor].custom_add(
+                        if proof_transcript.pb_published_middle_voleith_mac_r_vec_rep[repetition_id][and_cursor]
+                            != pb_middle_voleith_key_r_and_output_vec_rep[repetition_id][and_cursor].custom_add(
                                 &nabla_a_rep[repetition_id].custom_multiply_bit(
                                     proof_transcript.pb_published_middle_r_bit_vec[and_cursor]
                                 )
-                            )
-                        );
+                            ) {
+                            return Err(VerificationError::GarbledRowDecryption {
+                                is_pa: false, repetition_id, and_gate_id: gate_id, and_cursor
+                            });
+                        }
 
                         and_cursor += 1;
                     }
@@ -321,84 +352,77 @@ impl VerifierInPA2PC {
         if process_printing {
             println!("  Verifier verifies voleith correlations with PiCheckAND");
         }
-        (0..public_parameter.bs).for_each(
-            |block_id| {
-                // println!("block_id {:?}", block_id);
-                VerifierInProtocolCheckAND::verify(
-                    public_parameter,
-                    &proof_transcript.check_and_transcript_vec[block_id],
-                    &nabla_a_rep, &nabla_b_rep,
+        for block_id in 0..public_parameter.bs {
+            // println!("block_id {:?}", block_id);
+            VerifierInProtocolCheckAND::verify(
+                public_parameter,
+                block_id,
+                &proof_transcript.check_and_transcript_vec[block_id],
+                &nabla_a_rep, &nabla_b_rep,
+                (
                     (
-                        (
-                            &pa_voleith_key_r_prime_left_vec_rep,
-                            &pa_voleith_key_r_prime_right_vec_rep,
-                            &pa_voleith_key_r_prime_vec_rep,
-                        ),
-                        (
-                            &extract_block_vec_rep(public_parameter, block_id, &pa_voleith_key_tilde_a_vec_rep),
-                            &extract_block_vec_rep(public_parameter, block_id, &pa_voleith_key_tilde_b_vec_rep),
-                            &extract_block_vec_rep(public_parameter, block_id, &pa_voleith_key_tilde_c_vec_rep),
-                        )
+                        &pa_voleith_key_r_prime_left_vec_rep,
+                        &pa_voleith_key_r_prime_right_vec_rep,
+                        &pa_voleith_key_r_prime_vec_rep,
                     ),
                     (
-                        (
-                            &pb_voleith_key_r_prime_left_vec_rep,
-                            &pb_voleith_key_r_prime_right_vec_rep,
-                            &pb_voleith_key_r_prime_vec_rep,
-                        ),
-                        (
-                            &extract_block_vec_rep(public_parameter, block_id, &pb_voleith_key_tilde_a_vec_rep),
-                            &extract_block_vec_rep(public_parameter, block_id, &pb_voleith_key_tilde_b_vec_rep),
-                            &extract_block_vec_rep(public_parameter, block_id, &pb_voleith_key_tilde_c_vec_rep),
-                        )
-                    ),
-                )
-            }
-        );
-        (0..public_parameter.kappa).for_each(
-            |repetition_id| {
-                Verifier::verify_vole_correlations(
-                    &proof_transcript.pa_published_input_r_bit_vec,
-                    &proof_transcript.pa_published_input_voleith_mac_r_vec_rep[repetition_id],
-                    &nabla_b_rep[repetition_id],
-                    &GFVec::<GFVOLEitH>::from_vec(
-                        public_parameter.big_ib.iter().map(
-                            |input_wire| pa_voleith_key_r_trace_vec_rep[repetition_id][*input_wire]
-                        ).collect()
-                    )
-                );
-                Verifier::verify_vole_correlations(
-                    &proof_transcript.pb_published_input_r_bit_vec,
-                    &proof_transcript.pb_published_input_voleith_mac_r_vec_rep[repetition_id],
-                    &nabla_a_rep[repetition_id],
-                    &GFVec::<GFVOLEitH>::from_vec(
-                        public_parameter.big_ia.iter().map(
-                            |input_wire| pb_voleith_key_r_trace_vec_rep[repetition_id][*input_wire]
-                        ).collect()
-                    )
-                );
-                Verifier::verify_vole_correlations(
-                    &proof_transcript.pa_published_output_r_bit_vec,
-                    &proof_transcript.pa_published_output_voleith_mac_r_vec_rep[repetition_id],
-                    &nabla_b_rep[repetition_id],
-                    &GFVec::<GFVOLEitH>::from_vec(
-                        public_parameter.big_io.iter().map(
-                            |input_wire| pa_voleith_key_r_trace_vec_rep[repetition_id][*input_wire]
-                        ).collect()
+                        &extract_block_vec_rep(public_parameter, block_id, &pa_voleith_key_tilde_a_vec_rep),
+                        &extract_block_vec_rep(public_parameter, block_id, &pa_voleith_key_tilde_b_vec_rep),
+                        &extract_block_vec_rep(public_parameter, block_id, &pa_voleith_key_tilde_c_vec_rep),
                     )
-                );
-                Verifier::verify_vole_correlations(
-                    &proof_transcript.pb_published_output_r_bit_vec,
-                    &proof_transcript.pb_published_output_voleith_mac_r_vec_rep[repetition_id],
-                    &nabla_a_rep[repetition_id],
-                    &GFVec::<GFVOLEitH>::from_vec(
-                        public_parameter.big_io.iter().map(
-                            |input_wire| pb_voleith_key_r_trace_vec_rep[repetition_id][*input_wire]
-                        ).collect()
+                ),
+                (
+                    (
+                        &pb_voleith_key_r_prime_left_vec_rep,
+                        &pb_voleith_key_r_prime_right_vec_rep,
+                        &pb_voleith_key_r_prime_vec_rep,
+                    ),
+                    (
+                        &extract_block_vec_rep(public_parameter, block_id, &pb_voleith_key_tilde_a_vec_rep),
+                        &extract_block_vec_rep(public_parameter, block_id, &pb_voleith_key_tilde_b_vec_rep),
+                        &extract_block_vec_rep(public_parameter, block_id, &pb_voleith_key_tilde_c_vec_rep),
                     )
-                );
-            }
-        );
+                ),
+            )?;
+        }
+        for repetition_id in 0..public_parameter.kappa {
+            Verifier::verify_vole_correlations_at_wires(
+                &proof_transcript.pa_published_input_r_bit_vec,
+                &proof_transcript.pa_published_input_voleith_mac_r_vec_rep[repetition_id],
+                &nabla_b_rep[repetition_id],
+                &pa_voleith_key_r_trace_vec_rep[repetition_id],
+                &public_parameter.big_ib,
+            ).map_err(
+                |cursor| VerificationError::InputConsistency { is_pa: true, repetition_id, input_wire: public_parameter.big_ib.get(cursor).copied() }
+            )?;
+            Verifier::verify_vole_correlations_at_wires(
+                &proof_transcript.pb_published_input_r_bit_vec,
+                &proof_transcript.pb_published_input_voleith_mac_r_vec_rep[repetition_id],
+                &nabla_a_rep[repetition_id],
+                &pb_voleith_key_r_trace_vec_rep[repetition_id],
+                &public_parameter.big_ia,
+            ).map_err(
+                |cursor| VerificationError::InputConsistency { is_pa: false, repetition_id, input_wire: public_parameter.big_ia.get(cursor).copied() }
+            )?;
+            Verifier::verify_vole_correlations_at_wires(
+                &proof_transcript.pa_published_output_r_bit_vec,
+                &proof_transcript.pa_published_output_voleith_mac_r_vec_rep[repetition_id],
+                &nabla_b_rep[repetition_id],
+                &pa_voleith_key_r_trace_vec_rep[repetition_id],
+                &public_parameter.big_io,
+            ).map_err(
+                |cursor| VerificationError::OutputConsistency { repetition_id: Some(repetition_id), output_wire: public_parameter.big_io.get(cursor).copied() }
+            )?;
+            Verifier::verify_vole_correlations_at_wires(
+                &proof_transcript.pb_published_output_r_bit_vec,
+                &proof_transcript.pb_published_output_voleith_mac_r_vec_rep[repetition_id],
+                &nabla_a_rep[repetition_id],
+                &pb_voleith_key_r_trace_vec_rep[repetition_id],
+                &public_parameter.big_io,
+            ).map_err(
+                |cursor| VerificationError::OutputConsistency { repetition_id: Some(repetition_id), output_wire: public_parameter.big_io.get(cursor).copied() }
+            )?;
+        }
 
         if process_printing {
             println!("  Verifier verifies remaining things");
@@ -407,30 +431,30 @@ impl VerifierInPA2PC {
         for and_gate_id in bristol_fashion_adaptor.get_and_gate_id_vec() {
             let gate = &bristol_fashion_adaptor.get_gate_vec()[*and_gate_id];
             let recovered_k = hat_z_bit_trace_vec[gate.left_input_wire] + (hat_z_bit_trace_vec[gate.right_input_wire] << 1);
-            assert_eq!(
-                preprocessing_transcript.commitment_vec[and_cursor][recovered_k as usize],
-                hasher::commit_pb_secret(
+            if !verify_opening(
+                public_parameter.crypto_backend.hash(),
+                &preprocessing_transcript.commitment_vec[and_cursor][recovered_k as usize],
+                &make_pb_middle_message(
                     proof_transcript.pb_published_middle_r_bit_vec[and_cursor],
-                    &(0..public_parameter.kappa).map(
-                        |repetition_id|
-                            proof_transcript.pb_published_middle_voleith_mac_r_vec_rep[repetition_id][and_cursor]
-                    ).collect::<Vec<GFVOLEitH>>(),
-                    &proof_transcript.pb_published_middle_randomness_vec[and_cursor]
-                )
-            );
+                    proof_transcript.pb_published_middle_voleith_mac_r_vec_rep.iter().map(
+                        |pb_published_middle_voleith_mac_r_vec| &pb_published_middle_voleith_mac_r_vec[and_cursor]
+                    )
+                ),
+                &proof_transcript.pb_published_middle_randomness_vec[and_cursor]
+            ) {
+                return Err(VerificationError::CommitmentHashMismatch { and_gate_id: *and_gate_id, and_cursor });
+            }
             and_cursor += 1;
         }
-        let mut output_cursor = 0usize;
-        public_parameter.big_io.iter().for_each(
-            |output_wire| {
-                assert_eq!(
-                    proof_transcript.published_output_bit_vec[output_cursor],
-                    hat_z_bit_trace_vec[*output_wire] 
-                        ^ proof_transcript.pa_published_output_r_bit_vec[output_cursor] 
-                        ^ proof_transcript.pb_published_output_r_bit_vec[output_cursor]
-                );
-                output_cursor += 1;
+        for (output_cursor, output_wire) in public_parameter.big_io.iter().enumerate() {
+            if proof_transcript.published_output_bit_vec[output_cursor]
+                != hat_z_bit_trace_vec[*output_wire]
+                    ^ proof_transcript.pa_published_output_r_bit_vec[output_cursor]
+                    ^ proof_transcript.pb_published_output_r_bit_vec[output_cursor] {
+                return Err(VerificationError::OutputConsistency { repetition_id: None, output_wire: Some(*output_wire) });
             }
-        );
+        }
+
+        Ok(VerifiedOutput::new(proof_transcript.published_output_bit_vec.clone()))
     }
 }
\ No newline at end of file
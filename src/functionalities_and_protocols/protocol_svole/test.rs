@@ -1,6 +1,8 @@
 #[cfg(test)]
 mod tests {
     use crate::bristol_fashion_adaptor::bristol_fashion_adaptor::BristolFashionAdaptor;
+    use crate::functionalities_and_protocols::all_in_one_vc::{LeafExpansionVersion, VectorCommitmentMode};
+    use crate::functionalities_and_protocols::crypto_backend::CryptoBackend;
     use crate::functionalities_and_protocols::states_and_parameters::prover_secret_state::ProverSecretState;
     use crate::functionalities_and_protocols::states_and_parameters::public_parameter::PublicParameter;
     use crate::functionalities_and_protocols::protocol_svole::prover_in_protocol_svole::ProverInProtocolSVOLE;
@@ -24,16 +26,21 @@ mod tests {
         let public_parameter = PublicParameter::new::<GF2p256, GF2p8>(
             &bristol_fashion_adaptor,
             8,
-            20,
-            SeedU8x16::insecurely_random(),
+            PublicParameter::determine_kappa(256, 8),
+            VectorCommitmentMode::PerRepetition,
+            LeafExpansionVersion::V2,
+            CryptoBackend::default(),
+            SeedU8x16::insecurely_random(&mut rand::rng()),
+            None,
             (0..100).collect(),
             (100..200).collect(),
             10,
             4,
+            None,
         );
         let mut prover_secret_state = ProverSecretState::<GF2p256, GF2p8>::new(
             &public_parameter,
-            SeedU8x16::insecurely_random(),
+            SeedU8x16::insecurely_random(&mut rand::rng()),
             true
         );
 
@@ -46,15 +53,14 @@ mod tests {
                 &public_parameter, &prover_secret_state.seed_for_generating_ggm_tree_rep[repetition_id],
                 &mut secret_bit_vec, &mut secret_voleith_mac_vec
             );
-            let nabla = GF2p8::insecurely_random();
+            let nabla = GF2p8::insecurely_random(&mut rand::rng());
             let decom = ProverInProtocolSVOLE::open(
-                &public_parameter,
                 repetition_id,
                 &mut prover_secret_state, &nabla
             );
             let public_voleith_key_vec = VerifierInProtocolSVOLE::reconstruct(
-                &public_parameter, &prover_com_hash, &nabla, &decom
-            );
+                &public_parameter, true, repetition_id, &prover_com_hash, &nabla, &decom
+            ).unwrap();
 
             for i in 0..public_parameter.big_n {
                 let mut shifted_nabla = GF2p8::zero();
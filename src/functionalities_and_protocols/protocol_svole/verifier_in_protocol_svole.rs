@@ -1,8 +1,9 @@
 use blake3::Hash;
 use crate::functionalities_and_protocols::all_in_one_vc::verifier_in_all_in_one_vc::VerifierInAllInOneVC;
 use crate::functionalities_and_protocols::states_and_parameters::public_parameter::PublicParameter;
+use crate::functionalities_and_protocols::verification_result::VerificationError;
 use crate::value_type::seed_u8x16::SeedU8x16;
-use crate::value_type::{CustomAddition, U8ForGF, Zero};
+use crate::value_type::{CustomAddition, LeafIndexForGF, Zero};
 use crate::vec_type::gf_vec::GFVec;
 
 pub struct VerifierInProtocolSVOLE {
@@ -19,19 +20,42 @@ impl VerifierInProtocolSVOLE {
     //     GF::from_hash_digest(&hasher.finalize())
     // }
 
-    pub fn reconstruct<GFVOLEitH: Clone + CustomAddition + U8ForGF + Zero>(
+    pub fn reconstruct<GFVOLEitH: Clone + CustomAddition + LeafIndexForGF + Zero>(
         public_parameter: &PublicParameter,
+        is_pa: bool,
+        repetition_id: usize,
         prover_com_hash: &Hash,
         nabla: &GFVOLEitH,
         decom: &(SeedU8x16, Vec<SeedU8x16>)
-    ) -> GFVec<GFVOLEitH> {
+    ) -> Result<GFVec<GFVOLEitH>, VerificationError> {
         let (reconstructed_com_hash, voleith_key_vec) = VerifierInAllInOneVC::reconstruct(
-            public_parameter, nabla, decom
+            public_parameter, is_pa, repetition_id, nabla, decom
         );
-        assert_eq!(reconstructed_com_hash, *prover_com_hash);
+        if reconstructed_com_hash != *prover_com_hash {
+            return Err(VerificationError::SVOLEReconstruction { is_pa, repetition_id });
+        }
         // println!("- prover_com_hash:        {:?}", prover_com_hash);
         // println!("- reconstructed_com_hash: {:?}", reconstructed_com_hash);
-        voleith_key_vec
+        Ok(voleith_key_vec)
+    }
+
+    // the same as reconstruct, for leaves recovered from the batched tree
+    pub fn reconstruct_from_leaf_seeds<GFVOLEitH: Clone + CustomAddition + LeafIndexForGF + Zero>(
+        public_parameter: &PublicParameter,
+        is_pa: bool,
+        repetition_id: usize,
+        prover_com_hash: &Hash,
+        nabla: &GFVOLEitH,
+        com_at_excluded_index: &SeedU8x16,
+        leaf_seed_vec: &[SeedU8x16]
+    ) -> Result<GFVec<GFVOLEitH>, VerificationError> {
+        let (reconstructed_com_hash, voleith_key_vec) = VerifierInAllInOneVC::reconstruct_from_leaf_seeds(
+            public_parameter, repetition_id, nabla, com_at_excluded_index, leaf_seed_vec
+        );
+        if reconstructed_com_hash != *prover_com_hash {
+            return Err(VerificationError::SVOLEReconstruction { is_pa, repetition_id });
+        }
+        Ok(voleith_key_vec)
     }
 
 }
\ No newline at end of file
@@ -1,12 +1,14 @@
 use std::time::Instant;
-use rayon::iter::ParallelIterator;
+use rayon::iter::{IndexedParallelIterator, ParallelIterator};
 use blake3::Hash;
 use rayon::iter::IntoParallelIterator;
 use crate::comm_types_and_constants::BLAKE3_HASH_DIGEST_NUM_BYTES;
+use crate::functionalities_and_protocols::all_in_one_vc::batched_ggm_tree::BatchedGGMTree;
+use crate::functionalities_and_protocols::all_in_one_vc::{Decom, VectorCommitmentMode};
 use crate::functionalities_and_protocols::states_and_parameters::prover_secret_state::ProverSecretState;
 use crate::functionalities_and_protocols::states_and_parameters::public_parameter::PublicParameter;
 use crate::functionalities_and_protocols::protocol_svole::prover_in_protocol_svole::ProverInProtocolSVOLE;
-use crate::value_type::{CustomAddition, U8ForGF, Zero};
+use crate::value_type::{CustomAddition, LeafIndexForGF, Zero};
 use crate::value_type::seed_u8x16::SeedU8x16;
 use crate::vec_type::bit_vec::BitVec;
 use crate::vec_type::gf_vec::GFVec;
@@ -69,7 +71,7 @@ impl ProverInProtocolSVOLE2PC {
         prover_secret_state: &mut ProverSecretState<GFVOLE, GFVOLEitH>
     ) -> (Vec<Hash>, Vec<(BitVec, BitVec, BitVec, BitVec, BitVec, BitVec)>) 
     where GFVOLE: Clone + CustomAddition + Zero + Sync + Send, 
-          GFVOLEitH: Clone + Zero + CustomAddition + U8ForGF + Sync + Send {
+          GFVOLEitH: Clone + Zero + CustomAddition + LeafIndexForGF + Sync + Send {
         let mut com_hash_rep: Vec<Hash> = vec![Hash::from_bytes([0u8; BLAKE3_HASH_DIGEST_NUM_BYTES]); public_parameter.kappa];
         let mut masked_bit_tuple_rep: Vec<(BitVec, BitVec, BitVec, BitVec, BitVec, BitVec)> =
             vec![(BitVec::new(), BitVec::new(), BitVec::new(), BitVec::new(), BitVec::new(), BitVec::new()); public_parameter.kappa];
@@ -83,18 +85,39 @@ impl ProverInProtocolSVOLE2PC {
             println!("  Commit and obtain VOLEitH MACs by GGM tree");
         }
         let start_committing = Instant::now();
-        (
-            &prover_secret_state.seed_for_generating_ggm_tree_rep, 
-            &mut prover_secret_state.prover_in_all_in_one_vc_rep, 
-            &mut com_hash_rep, 
-            &mut secret_bit_vec_rep, 
-            &mut secret_voleith_mac_vec_rep
-        ).into_par_iter().for_each(
-            |(seed_for_generating_ggm_tree, prover_in_all_in_one_vc, com_hash, secret_bit_vec, secret_voleith_mac_vec)| {
-                *com_hash = prover_in_all_in_one_vc.commit(
-                    public_parameter, seed_for_generating_ggm_tree, secret_bit_vec, secret_voleith_mac_vec
+        match public_parameter.vector_commitment_mode {
+            VectorCommitmentMode::PerRepetition => (
+                &prover_secret_state.seed_for_generating_ggm_tree_rep, 
+                &mut prover_secret_state.prover_in_all_in_one_vc_rep, 
+                &mut com_hash_rep, 
+                &mut secret_bit_vec_rep, 
+                &mut secret_voleith_mac_vec_rep
+            ).into_par_iter().for_each(
+                |(seed_for_generating_ggm_tree, prover_in_all_in_one_vc, com_hash, secret_bit_vec, secret_voleith_mac_vec)| {
+                    *com_hash = prover_in_all_in_one_vc.commit(
+                        public_parameter, seed_for_generating_ggm_tree, secret_bit_vec, secret_voleith_mac_vec
+                    );
+            }),
+            VectorCommitmentMode::Batched => {
+                let batched_ggm_tree = BatchedGGMTree::new(&public_parameter.tau_rep, prover_secret_state.is_pa);
+                let tree = batched_ggm_tree.generate(
+                    &public_parameter.one_to_two_prg, &prover_secret_state.seed_for_generating_ggm_tree_rep[0]
                 );
-        });
+                (
+                    &mut prover_secret_state.prover_in_all_in_one_vc_rep, 
+                    &mut com_hash_rep, 
+                    &mut secret_bit_vec_rep, 
+                    &mut secret_voleith_mac_vec_rep
+                ).into_par_iter().enumerate().for_each(
+                    |(repetition_id, (prover_in_all_in_one_vc, com_hash, secret_bit_vec, secret_voleith_mac_vec))| {
+                        *com_hash = prover_in_all_in_one_vc.commit_to_leaves(
+                            public_parameter, &batched_ggm_tree.leaf_seed_vec(&tree, repetition_id),
+                            secret_bit_vec, secret_voleith_mac_vec
+                        );
+                });
+                prover_secret_state.batched_ggm_tree = Some(tree);
+            }
+        }
         if process_printing {
             println!("    Time elapsed: {:?}", start_committing.elapsed());
         }
@@ -115,15 +138,30 @@ impl ProverInProtocolSVOLE2PC {
         public_parameter: &PublicParameter, 
         prover_secret_state: &mut ProverSecretState<GFVOLE, GFVOLEitH>, 
         nabla_rep: &Vec<GFVOLEitH>
-    ) -> Vec<(SeedU8x16, Vec<SeedU8x16>)>
-    where GFVOLEitH: U8ForGF {
-        let mut decom_rep: Vec<(SeedU8x16, Vec<SeedU8x16>)> = Vec::new();
-        for repetition_id in 0..public_parameter.kappa {
-            let decom = ProverInProtocolSVOLE::open(
-                public_parameter, repetition_id, prover_secret_state, &nabla_rep[repetition_id]
-            );
-            decom_rep.push(decom);       
+    ) -> Decom
+    where GFVOLEitH: LeafIndexForGF {
+        match public_parameter.vector_commitment_mode {
+            VectorCommitmentMode::PerRepetition => {
+                let mut decom_rep: Vec<(SeedU8x16, Vec<SeedU8x16>)> = Vec::new();
+                for repetition_id in 0..public_parameter.kappa {
+                    let decom = ProverInProtocolSVOLE::open(
+                        repetition_id, prover_secret_state, &nabla_rep[repetition_id]
+                    );
+                    decom_rep.push(decom);       
+                }
+                Decom::PerRepetition { decom_rep }
+            }
+            VectorCommitmentMode::Batched => {
+                let excluded_index_rep = nabla_rep.iter().map(|nabla| nabla.get_leaf_index()).collect::<Vec<usize>>();
+                Decom::Batched {
+                    com_at_excluded_index_rep: excluded_index_rep.iter().zip(prover_secret_state.prover_in_all_in_one_vc_rep.iter()).map(
+                        |(excluded_index, prover_in_all_in_one_vc)| prover_in_all_in_one_vc.com_at_leaf(*excluded_index)
+                    ).collect(),
+                    co_path_seed_vec: BatchedGGMTree::new(&public_parameter.tau_rep, prover_secret_state.is_pa).open(
+                        prover_secret_state.batched_ggm_tree.as_ref().unwrap(), &excluded_index_rep
+                    ),
+                }
+            }
         }
-        decom_rep       
     }
-}
\ No newline at end of file
+}
@@ -3,6 +3,8 @@ mod tests {
     use itertools::izip;
     use rand::Rng;
     use crate::bristol_fashion_adaptor::bristol_fashion_adaptor::BristolFashionAdaptor;
+    use crate::functionalities_and_protocols::all_in_one_vc::{LeafExpansionVersion, VectorCommitmentMode};
+    use crate::functionalities_and_protocols::crypto_backend::CryptoBackend;
     use crate::functionalities_and_protocols::states_and_parameters::prover_secret_state::ProverSecretState;
     use crate::functionalities_and_protocols::states_and_parameters::public_parameter::PublicParameter;
     use crate::functionalities_and_protocols::protocol_svole_2pc::prover_in_protocol_svole_2pc::ProverInProtocolSVOLE2PC;
@@ -18,29 +20,29 @@ mod tests {
     ) {
         let mut rng = rand::rng();
         for i in 0..public_parameter.num_input_bits {
-            prover_secret_state.r_input_bit_vec[i] = rng.random::<u8>() & 1;
+            prover_secret_state.r_input_bit_vec.set(i, rng.random::<u8>() & 1);
         }
         for i in 0..public_parameter.big_iw_size {
-            prover_secret_state.r_output_and_bit_vec[i] = rng.random::<u8>() & 1;
+            prover_secret_state.r_output_and_bit_vec.set(i, rng.random::<u8>() & 1);
         }
         // prover_secret_state.r_prime_bit_vec = Some(BitVec::new());
         for i in 0..public_parameter.big_iw_size {
-            prover_secret_state.r_prime_bit_vec[i] = rng.random::<u8>() & 1;
+            prover_secret_state.r_prime_bit_vec.set(i, rng.random::<u8>() & 1);
         }
         for repetition_id in 0..public_parameter.kappa {
             for i in 0..public_parameter.big_l {
-                prover_secret_state.tilde_a_bit_vec_rep[repetition_id][i] = rng.random::<u8>() & 1;
+                prover_secret_state.tilde_a_bit_vec_rep[repetition_id].set(i, rng.random::<u8>() & 1);
             }
         }
         
         for repetition_id in 0..public_parameter.kappa {
             for i in 0..public_parameter.big_l {
-                prover_secret_state.tilde_b_bit_vec_rep[repetition_id][i] = rng.random::<u8>() & 1;
+                prover_secret_state.tilde_b_bit_vec_rep[repetition_id].set(i, rng.random::<u8>() & 1);
             }
         }
         for repetition_id in 0..public_parameter.kappa {
             for i in 0..public_parameter.big_l {
-                prover_secret_state.tilde_c_bit_vec_rep[repetition_id][i] = rng.random::<u8>() & 1;
+                prover_secret_state.tilde_c_bit_vec_rep[repetition_id].set(i, rng.random::<u8>() & 1);
             }
         }
     }
@@ -54,21 +56,26 @@ mod tests {
         let public_parameter = PublicParameter::new::<GF2p256, GF2p8>(
             &bristol_fashion_adaptor,
             8,
-            10,
-            SeedU8x16::insecurely_random(),
+            PublicParameter::determine_kappa(256, 8),
+            VectorCommitmentMode::PerRepetition,
+            LeafExpansionVersion::V2,
+            CryptoBackend::default(),
+            SeedU8x16::insecurely_random(&mut rand::rng()),
+            None,
             (0..100).collect(),
             (100..200).collect(),
             10,
-            4
+            4,
+            None,
         );
         let mut pa_secret_state = ProverSecretState::<GF2p256, GF2p8>::new(
             &public_parameter,
-            SeedU8x16::insecurely_random(),
+            SeedU8x16::insecurely_random(&mut rand::rng()),
             true
         );
         let mut pb_secret_state = ProverSecretState::<GF2p256, GF2p8>::new(
             &public_parameter,
-            SeedU8x16::insecurely_random(),
+            SeedU8x16::insecurely_random(&mut rand::rng()),
             false
         );
         sample_secret_bit_vecs(&public_parameter, &mut pa_secret_state);
@@ -92,8 +99,8 @@ mod tests {
         let mut nabla_a_rep: Vec<GF2p8> = Vec::new();
         let mut nabla_b_rep: Vec<GF2p8> = Vec::new();
         for _ in 0..public_parameter.kappa {
-            nabla_a_rep.push(GF2p8::insecurely_random());
-            nabla_b_rep.push(GF2p8::insecurely_random());
+            nabla_a_rep.push(GF2p8::insecurely_random(&mut rand::rng()));
+            nabla_b_rep.push(GF2p8::insecurely_random(&mut rand::rng()));
         }
         
         // open
@@ -102,11 +109,11 @@ mod tests {
         
         // reconstruct
         let pa_voleith_key_tuple_rep = VerifierInProtocolSVOLE2PC::reconstruct_and_fix_voleith_key_vec(
-            process_printing, &public_parameter, &pa_com_hash_rep, &pa_masked_bit_tuple_rep, &nabla_b_rep, &pa_decom_rep
-        );
+            process_printing, &public_parameter, true, &pa_com_hash_rep, &pa_masked_bit_tuple_rep, &nabla_b_rep, &pa_decom_rep
+        ).unwrap();
         let pb_voleith_key_tuple_rep = VerifierInProtocolSVOLE2PC::reconstruct_and_fix_voleith_key_vec(
-            process_printing, &public_parameter, &pb_com_hash_rep, &pb_masked_bit_tuple_rep, &nabla_a_rep, &pb_decom_rep
-        );
+            process_printing, &public_parameter, false, &pb_com_hash_rep, &pb_masked_bit_tuple_rep, &nabla_a_rep, &pb_decom_rep
+        ).unwrap();
         
         // test voleith correlations in the pa side
         for repetition_id in 0..public_parameter.kappa {
@@ -144,48 +151,48 @@ mod tests {
                 pa_secret_state.voleith_mac_r_input_vec_rep[repetition_id].iter(), 
                 voleith_key_r_input_vec.iter()
             ) {
-                println!("(bit, mac, key, nabla * bit + mac) = ({:?}, {:?}, {:?}, {:?})", bit, mac, key, nabla_b_rep[repetition_id].custom_multiply_bit(*bit).custom_add(mac));
-                assert_eq!(nabla_b_rep[repetition_id].custom_multiply_bit(*bit).custom_add(mac), *key);
+                println!("(bit, mac, key, nabla * bit + mac) = ({:?}, {:?}, {:?}, {:?})", bit, mac, key, nabla_b_rep[repetition_id].custom_multiply_bit(bit).custom_add(mac));
+                assert_eq!(nabla_b_rep[repetition_id].custom_multiply_bit(bit).custom_add(mac), *key);
             }
             for (bit, mac, key) in izip!(
                 pa_secret_state.r_output_and_bit_vec.iter(), 
                 pa_secret_state.voleith_mac_r_output_and_vec_rep[repetition_id].iter(), 
                 voleith_key_r_output_and_vec.iter()
             ) {
-                println!("(bit, mac, key, nabla * bit + mac) = ({:?}, {:?}, {:?}, {:?})", bit, mac, key, nabla_b_rep[repetition_id].custom_multiply_bit(*bit).custom_add(mac));
-                assert_eq!(nabla_b_rep[repetition_id].custom_multiply_bit(*bit).custom_add(mac), *key);
+                println!("(bit, mac, key, nabla * bit + mac) = ({:?}, {:?}, {:?}, {:?})", bit, mac, key, nabla_b_rep[repetition_id].custom_multiply_bit(bit).custom_add(mac));
+                assert_eq!(nabla_b_rep[repetition_id].custom_multiply_bit(bit).custom_add(mac), *key);
             }
             for (bit, mac, key) in izip!(
                 pa_secret_state.r_prime_bit_vec.iter(), 
                 pa_secret_state.voleith_mac_r_prime_vec_rep[repetition_id].iter(), 
                 voleith_key_r_prime_vec.iter()
             ) {
-                println!("(bit, mac, key, nabla * bit + mac) = ({:?}, {:?}, {:?}, {:?})", bit, mac, key, nabla_b_rep[repetition_id].custom_multiply_bit(*bit).custom_add(mac));
-                assert_eq!(nabla_b_rep[repetition_id].custom_multiply_bit(*bit).custom_add(mac), *key);
+                println!("(bit, mac, key, nabla * bit + mac) = ({:?}, {:?}, {:?}, {:?})", bit, mac, key, nabla_b_rep[repetition_id].custom_multiply_bit(bit).custom_add(mac));
+                assert_eq!(nabla_b_rep[repetition_id].custom_multiply_bit(bit).custom_add(mac), *key);
             }
             for (bit, mac, key) in izip!(
                 pa_secret_state.tilde_a_bit_vec_rep[repetition_id].iter(), 
                 pa_secret_state.voleith_mac_tilde_a_vec_rep[repetition_id].iter(), 
                 voleith_key_tilde_a_vec.iter()
             ) {
-                println!("(bit, mac, key, nabla * bit + mac) = ({:?}, {:?}, {:?}, {:?})", bit, mac, key, nabla_b_rep[repetition_id].custom_multiply_bit(*bit).custom_add(mac));
-                assert_eq!(nabla_b_rep[repetition_id].custom_multiply_bit(*bit).custom_add(mac), *key);
+                println!("(bit, mac, key, nabla * bit + mac) = ({:?}, {:?}, {:?}, {:?})", bit, mac, key, nabla_b_rep[repetition_id].custom_multiply_bit(bit).custom_add(mac));
+                assert_eq!(nabla_b_rep[repetition_id].custom_multiply_bit(bit).custom_add(mac), *key);
             }
             for (bit, mac, key) in izip!(
                 pa_secret_state.tilde_b_bit_vec_rep[repetition_id].iter(), 
                 pa_secret_state.voleith_mac_tilde_b_vec_rep[repetition_id].iter(), 
                 voleith_key_tilde_b_vec.iter()
             ) {
-                println!("(bit, mac, key, nabla * bit + mac) = ({:?}, {:?}, {:?}, {:?})", bit, mac, key, nabla_b_rep[repetition_id].custom_multiply_bit(*bit).custom_add(mac));
-                assert_eq!(nabla_b_rep[repetition_id].custom_multiply_bit(*bit).custom_add(mac), *key);
+                println!("(bit, mac, key, nabla * bit + mac) = ({:?}, {:?}, {:?}, {:?})", bit, mac, key, nabla_b_rep[repetition_id].custom_multiply_bit(bit).custom_add(mac));
+                assert_eq!(nabla_b_rep[repetition_id].custom_multiply_bit(bit).custom_add(mac), *key);
             }
             for (bit, mac, key) in izip!(
                 pa_secret_state.tilde_c_bit_vec_rep[repetition_id].iter(), 
                 pa_secret_state.voleith_mac_tilde_c_vec_rep[repetition_id].iter(), 
                 voleith_key_tilde_c_vec.iter()
             ) {
-                println!("(bit, mac, key, nabla * bit + mac) = ({:?}, {:?}, {:?}, {:?})", bit, mac, key, nabla_b_rep[repetition_id].custom_multiply_bit(*bit).custom_add(mac));
-                assert_eq!(nabla_b_rep[repetition_id].custom_multiply_bit(*bit).custom_add(mac), *key);
+                println!("(bit, mac, key, nabla * bit + mac) = ({:?}, {:?}, {:?}, {:?})", bit, mac, key, nabla_b_rep[repetition_id].custom_multiply_bit(bit).custom_add(mac));
+                assert_eq!(nabla_b_rep[repetition_id].custom_multiply_bit(bit).custom_add(mac), *key);
             }
         }
 
@@ -226,48 +233,48 @@ mod tests {
                 pb_secret_state.voleith_mac_r_input_vec_rep[repetition_id].iter(), 
                 voleith_key_r_input_vec.iter()
             ) {
-                println!("(bit, mac, key, nabla * bit + mac) = ({:?}, {:?}, {:?}, {:?})", bit, mac, key, nabla_a_rep[repetition_id].custom_multiply_bit(*bit).custom_add(mac));
-                assert_eq!(nabla_a_rep[repetition_id].custom_multiply_bit(*bit).custom_add(mac), *key);
+                println!("(bit, mac, key, nabla * bit + mac) = ({:?}, {:?}, {:?}, {:?})", bit, mac, key, nabla_a_rep[repetition_id].custom_multiply_bit(bit).custom_add(mac));
+                assert_eq!(nabla_a_rep[repetition_id].custom_multiply_bit(bit).custom_add(mac), *key);
             }
             for (bit, mac, key) in izip!(
                 pb_secret_state.r_output_and_bit_vec.iter(), 
                 pb_secret_state.voleith_mac_r_output_and_vec_rep[repetition_id].iter(), 
                 voleith_key_r_output_and_vec.iter()
             ) {
-                println!("(bit, mac, key, nabla * bit + mac) = ({:?}, {:?}, {:?}, {:?})", bit, mac, key, nabla_a_rep[repetition_id].custom_multiply_bit(*bit).custom_add(mac));
-                assert_eq!(nabla_a_rep[repetition_id].custom_multiply_bit(*bit).custom_add(mac), *key);
+                println!("(bit, mac, key, nabla * bit + mac) = ({:?}, {:?}, {:?}, {:?})", bit, mac, key, nabla_a_rep[repetition_id].custom_multiply_bit(bit).custom_add(mac));
+                assert_eq!(nabla_a_rep[repetition_id].custom_multiply_bit(bit).custom_add(mac), *key);
             }
             for (bit, mac, key) in izip!(
                 pb_secret_state.r_prime_bit_vec.iter(), 
                 pb_secret_state.voleith_mac_r_prime_vec_rep[repetition_id].iter(), 
                 voleith_key_r_prime_vec.iter()
             ) {
-                println!("(bit, mac, key, nabla * bit + mac) = ({:?}, {:?}, {:?}, {:?})", bit, mac, key, nabla_a_rep[repetition_id].custom_multiply_bit(*bit).custom_add(mac));
-                assert_eq!(nabla_a_rep[repetition_id].custom_multiply_bit(*bit).custom_add(mac), *key);
+                println!("(bit, mac, key, nabla * bit + mac) = ({:?}, {:?}, {:?}, {:?})", bit, mac, key, nabla_a_rep[repetition_id].custom_multiply_bit(bit).custom_add(mac));
+                assert_eq!(nabla_a_rep[repetition_id].custom_multiply_bit(bit).custom_add(mac), *key);
             }
             for (bit, mac, key) in izip!(
                 pb_secret_state.tilde_a_bit_vec_rep[repetition_id].iter(), 
                 pb_secret_state.voleith_mac_tilde_a_vec_rep[repetition_id].iter(), 
                 voleith_key_tilde_a_vec.iter()
             ) {
-                println!("(bit, mac, key, nabla * bit + mac) = ({:?}, {:?}, {:?}, {:?})", bit, mac, key, nabla_a_rep[repetition_id].custom_multiply_bit(*bit).custom_add(mac));
-                assert_eq!(nabla_a_rep[repetition_id].custom_multiply_bit(*bit).custom_add(mac), *key);
+                println!("(bit, mac, key, nabla * bit + mac) = ({:?}, {:?}, {:?}, {:?})", bit, mac, key, nabla_a_rep[repetition_id].custom_multiply_bit(bit).custom_add(mac));
+                assert_eq!(nabla_a_rep[repetition_id].custom_multiply_bit(bit).custom_add(mac), *key);
             }
             for (bit, mac, key) in izip!(
                 pb_secret_state.tilde_b_bit_vec_rep[repetition_id].iter(), 
                 pb_secret_state.voleith_mac_tilde_b_vec_rep[repetition_id].iter(), 
                 voleith_key_tilde_b_vec.iter()
             ) {
-                println!("(bit, mac, key, nabla * bit + mac) = ({:?}, {:?}, {:?}, {:?})", bit, mac, key, nabla_a_rep[repetition_id].custom_multiply_bit(*bit).custom_add(mac));
-                assert_eq!(nabla_a_rep[repetition_id].custom_multiply_bit(*bit).custom_add(mac), *key);
+                println!("(bit, mac, key, nabla * bit + mac) = ({:?}, {:?}, {:?}, {:?})", bit, mac, key, nabla_a_rep[repetition_id].custom_multiply_bit(bit).custom_add(mac));
+                assert_eq!(nabla_a_rep[repetition_id].custom_multiply_bit(bit).custom_add(mac), *key);
             }
             for (bit, mac, key) in izip!(
                 pb_secret_state.tilde_c_bit_vec_rep[repetition_id].iter(), 
                 pb_secret_state.voleith_mac_tilde_c_vec_rep[repetition_id].iter(), 
                 voleith_key_tilde_c_vec.iter()
             ) {
-                println!("(bit, mac, key, nabla * bit + mac) = ({:?}, {:?}, {:?}, {:?})", bit, mac, key, nabla_a_rep[repetition_id].custom_multiply_bit(*bit).custom_add(mac));
-                assert_eq!(nabla_a_rep[repetition_id].custom_multiply_bit(*bit).custom_add(mac), *key);
+                println!("(bit, mac, key, nabla * bit + mac) = ({:?}, {:?}, {:?}, {:?})", bit, mac, key, nabla_a_rep[repetition_id].custom_multiply_bit(bit).custom_add(mac));
+                assert_eq!(nabla_a_rep[repetition_id].custom_multiply_bit(bit).custom_add(mac), *key);
             }
         }
     }
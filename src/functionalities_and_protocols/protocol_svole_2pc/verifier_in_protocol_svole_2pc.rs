@@ -1,11 +1,13 @@
 use std::time::Instant;
 use blake3::Hash;
-use rayon::iter::ParallelIterator;
+use rayon::iter::{IndexedParallelIterator, ParallelIterator};
 use rayon::iter::IntoParallelIterator;
+use crate::functionalities_and_protocols::all_in_one_vc::verifier_in_all_in_one_vc::VerifierInAllInOneVC;
+use crate::functionalities_and_protocols::all_in_one_vc::{Decom, VectorCommitmentMode};
 use crate::functionalities_and_protocols::states_and_parameters::public_parameter::PublicParameter;
 use crate::functionalities_and_protocols::protocol_svole::verifier_in_protocol_svole::VerifierInProtocolSVOLE;
-use crate::value_type::seed_u8x16::SeedU8x16;
-use crate::value_type::{CustomAddition, CustomMultiplyingBit, U8ForGF, Zero};
+use crate::functionalities_and_protocols::verification_result::VerificationError;
+use crate::value_type::{CustomAddition, CustomMultiplyingBit, LeafIndexForGF, Zero};
 use crate::vec_type::bit_vec::BitVec;
 use crate::vec_type::gf_vec::GFVec;
 use crate::vec_type::Split;
@@ -99,29 +101,61 @@ impl VerifierInProtocolSVOLE2PC {
     pub fn reconstruct_and_fix_voleith_key_vec<GFVOLEitH>(
         process_printing: bool,
         public_parameter: &PublicParameter, 
+        is_pa: bool,
         prover_com_hash_rep: &Vec<Hash>,
         prover_masked_bit_tuple_rep: &Vec<(BitVec, BitVec, BitVec, BitVec, BitVec, BitVec)>,
         nabla_rep: &Vec<GFVOLEitH>, 
-        decom_rep: &Vec<(SeedU8x16, Vec<SeedU8x16>)>
-    ) -> Vec<(GFVec<GFVOLEitH>, GFVec<GFVOLEitH>, GFVec<GFVOLEitH>, GFVec<GFVOLEitH>, GFVec<GFVOLEitH>, GFVec<GFVOLEitH>)>
-    where GFVOLEitH: Clone + Zero + CustomAddition + U8ForGF + CustomMultiplyingBit + Send + Sync {
+        decom: &Decom
+    ) -> Result<Vec<(GFVec<GFVOLEitH>, GFVec<GFVOLEitH>, GFVec<GFVOLEitH>, GFVec<GFVOLEitH>, GFVec<GFVOLEitH>, GFVec<GFVOLEitH>)>, VerificationError>
+    where GFVOLEitH: Clone + Zero + CustomAddition + LeafIndexForGF + CustomMultiplyingBit + Send + Sync {
         let mut voleith_key_tuple_rep = vec![(GFVec::<GFVOLEitH>::new(), GFVec::<GFVOLEitH>::new(), GFVec::<GFVOLEitH>::new(), GFVec::<GFVOLEitH>::new(), GFVec::<GFVOLEitH>::new(), GFVec::<GFVOLEitH>::new()); public_parameter.kappa];
-        let mut public_voleith_key_vec_rep = vec![GFVec::<GFVOLEitH>::new(); public_parameter.kappa];
 
         if process_printing {
             println!("  Verifier reconstructs");
         }
         let start_reconstructing = Instant::now();
-        (&mut public_voleith_key_vec_rep, prover_com_hash_rep, nabla_rep, decom_rep).into_par_iter().for_each(
-            |(public_voleith_key_vec, prover_com_hash, nabla, decom)| {
-                *public_voleith_key_vec = VerifierInProtocolSVOLE::reconstruct(
-                    public_parameter,
-                    &prover_com_hash,
-                    &nabla,
-                    &decom
-                );
+        let malformed_decom = VerificationError::MalformedDecom { is_pa };
+        let mut public_voleith_key_vec_rep = match (public_parameter.vector_commitment_mode, decom) {
+            (VectorCommitmentMode::PerRepetition, Decom::PerRepetition { decom_rep }) => {
+                if decom_rep.len() != public_parameter.kappa {
+                    return Err(malformed_decom);
+                }
+                (prover_com_hash_rep, nabla_rep, decom_rep).into_par_iter().enumerate().map(
+                    |(repetition_id, (prover_com_hash, nabla, decom))| {
+                        VerifierInProtocolSVOLE::reconstruct(
+                            public_parameter,
+                            is_pa,
+                            repetition_id,
+                            prover_com_hash,
+                            nabla,
+                            decom
+                        )
+                    }
+                ).collect::<Result<Vec<GFVec<GFVOLEitH>>, VerificationError>>()?
             }
-        );
+            (VectorCommitmentMode::Batched, Decom::Batched { com_at_excluded_index_rep, co_path_seed_vec }) => {
+                if com_at_excluded_index_rep.len() != public_parameter.kappa {
+                    return Err(malformed_decom);
+                }
+                let leaf_seed_vec_rep = VerifierInAllInOneVC::reconstruct_batched_leaf_seed_vec_rep(
+                    public_parameter, is_pa, nabla_rep, co_path_seed_vec
+                ).ok_or(malformed_decom)?;
+                (prover_com_hash_rep, nabla_rep, com_at_excluded_index_rep, &leaf_seed_vec_rep).into_par_iter().enumerate().map(
+                    |(repetition_id, (prover_com_hash, nabla, com_at_excluded_index, leaf_seed_vec))| {
+                        VerifierInProtocolSVOLE::reconstruct_from_leaf_seeds(
+                            public_parameter,
+                            is_pa,
+                            repetition_id,
+                            prover_com_hash,
+                            nabla,
+                            com_at_excluded_index,
+                            leaf_seed_vec
+                        )
+                    }
+                ).collect::<Result<Vec<GFVec<GFVOLEitH>>, VerificationError>>()?
+            }
+            _ => return Err(malformed_decom),
+        };
         if process_printing {
             println!("  Time elapsed: {:?}", start_reconstructing.elapsed());
         }
@@ -138,6 +172,6 @@ impl VerifierInProtocolSVOLE2PC {
                 &prover_masked_bit_tuple_rep[repetition_id]
             );
         }
-        voleith_key_tuple_rep
+        Ok(voleith_key_tuple_rep)
     }
 }
\ No newline at end of file
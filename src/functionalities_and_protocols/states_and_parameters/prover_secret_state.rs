@@ -1,4 +1,5 @@
 use crate::functionalities_and_protocols::all_in_one_vc::prover_in_all_in_one_vc::ProverInAllInOneVC;
+use crate::functionalities_and_protocols::all_in_one_vc::VectorCommitmentMode;
 use crate::functionalities_and_protocols::states_and_parameters::public_parameter::PublicParameter;
 use crate::value_type::seed_u8x16::SeedU8x16;
 use crate::value_type::Zero;
@@ -7,8 +8,11 @@ use crate::vec_type::gf_vec::GFVec;
 use crate::vec_type::{BasicVecFunctions, ZeroVec};
 
 pub struct ProverSecretState<GFVOLE, GFVOLEitH> {
+    // tells the GGM trees of the parties apart
+    pub is_pa: bool,
     pub delta: Option<GFVOLE>,
     
+    // one seed per repetition, or a single seed for the batched tree
     pub seed_for_generating_ggm_tree_rep: Vec<SeedU8x16>,
     pub seed_for_commitment_randomness: SeedU8x16,
     pub r_input_bit_vec: BitVec,
@@ -57,6 +61,8 @@ pub struct ProverSecretState<GFVOLE, GFVOLEitH> {
 
     // random bits from PisVOLE
     pub prover_in_all_in_one_vc_rep: Vec<ProverInAllInOneVC>,
+    // kept for opening, in VectorCommitmentMode::Batched only
+    pub batched_ggm_tree: Option<Vec<SeedU8x16>>,
     // pub bar_r_bit_vec_rep: Vec<Option<BitVec>>,
     // pub bar_r_prime_bit_vec_rep: Vec<Option<BitVec>>,
     // pub bar_a_bit_vec_rep: Vec<Option<BitVec>>,
@@ -71,22 +77,27 @@ where GFVOLE: Clone + Zero + Copy, GFVOLEitH: Clone + Zero + Copy {
         master_seed: SeedU8x16,
         is_pa: bool,
     ) -> Self {
-        let mut seed_for_generating_ggm_tree_rep =  vec![SeedU8x16::zero(); public_parameter.kappa];
+        let num_ggm_trees = match public_parameter.vector_commitment_mode {
+            VectorCommitmentMode::PerRepetition => public_parameter.kappa,
+            VectorCommitmentMode::Batched => 1,
+        };
+        let mut seed_for_generating_ggm_tree_rep =  vec![SeedU8x16::zero(); num_ggm_trees];
         let prover_in_all_in_one_vc_rep = (0..public_parameter.kappa).map(
-            |_| ProverInAllInOneVC::new(&public_parameter)
+            |repetition_id| ProverInAllInOneVC::new(public_parameter, is_pa, repetition_id)
         ).collect();
         let mut current_seed = master_seed;
         // println!("kappa: {:?}", public_parameter.kappa);
-        for repetition_id in 0..public_parameter.kappa {
+        for tree_id in 0..num_ggm_trees {
             let (seed0, seed1) = public_parameter.one_to_two_prg.generate_double(&current_seed);
             // println!("(seed0, seed1): {:?}", (seed0, seed1));
-            seed_for_generating_ggm_tree_rep[repetition_id] = seed0;
+            seed_for_generating_ggm_tree_rep[tree_id] = seed0;
             // println!("seed0: {:?}", seed0);
             current_seed = seed1;
         }
         // println!("seed_for_generating_ggm_tree_rep: {:?}", seed_for_generating_ggm_tree_rep);
         // println!("current_seed: {:?}", current_seed);
         Self {
+            is_pa,
             delta: None,
             
             seed_for_generating_ggm_tree_rep,
@@ -140,6 +151,7 @@ where GFVOLE: Clone + Zero + Copy, GFVOLEitH: Clone + Zero + Copy {
                 }
             },
             prover_in_all_in_one_vc_rep,
+            batched_ggm_tree: None,
             // bar_r_bit_vec_rep: vec![None; public_parameter.kappa],
             // bar_r_prime_bit_vec_rep: vec![None; public_parameter.kappa],
             // bar_a_bit_vec_rep: vec![None; public_parameter.kappa],
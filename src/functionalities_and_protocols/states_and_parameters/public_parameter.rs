@@ -1,12 +1,26 @@
-use bincode::{config, encode_to_vec};
+use bincode::{config, encode_to_vec, Decode, Encode};
+use bincode::de::Decoder;
+use bincode::enc::Encoder;
+use bincode::error::{DecodeError, EncodeError};
 use crate::bristol_fashion_adaptor::bristol_fashion_adaptor::BristolFashionAdaptor;
 use crate::functionalities_and_protocols::all_in_one_vc::one_to_two_prg::OneToTwoPRG;
-use crate::value_type::ByteManipulation;
+use crate::functionalities_and_protocols::all_in_one_vc::{LeafExpansionVersion, VectorCommitmentMode};
+use crate::functionalities_and_protocols::crypto_backend::CryptoBackend;
+use rand::{CryptoRng, RngCore};
+use crate::value_type::{ByteManipulation, InsecureRandom};
 use crate::value_type::seed_u8x16::SeedU8x16;
 
 pub struct PublicParameter {
     pub tau: u8,
     pub kappa: usize,
+    // depth of the GGM tree of each repetition, at most tau, adding up to exactly the security level
+    pub tau_rep: Vec<u8>,
+    pub vector_commitment_mode: VectorCommitmentMode,
+    pub leaf_expansion_version: LeafExpansionVersion,
+    pub crypto_backend: CryptoBackend,
+    pub master_key_for_one_to_two_prg: SeedU8x16,
+    // public and fresh for every proof in the salted mode, see refresh_iv_for_one_to_two_prg, None otherwise
+    pub iv_for_one_to_two_prg: Option<SeedU8x16>,
     pub one_to_two_prg: OneToTwoPRG,
     // pub big_ia_size: usize,
     // pub big_ib_size: usize,
@@ -23,24 +37,50 @@ pub struct PublicParameter {
     pub big_iw: Vec<usize>,
     pub big_io: Vec<usize>,
     pub garbled_row_byte_len: usize,
+    // digest of the lowered circuit, see BristolFashionAdaptor::determine_digest
+    pub circuit_digest: [u8; 32],
+    // an optional application context, e.g., a session id, that the proof is bound to
+    pub context: Option<String>,
     byte_vec: Vec<u8>,
 }
 
 impl PublicParameter {
     pub fn new<GFVOLE: ByteManipulation, GFVOLEitH: ByteManipulation>(
         bristol_fashion_adaptor: &BristolFashionAdaptor,
-        tau: u8, kappa: usize, master_key_for_one_to_two_prg: SeedU8x16, 
+        tau: u8, kappa: usize, vector_commitment_mode: VectorCommitmentMode, leaf_expansion_version: LeafExpansionVersion,
+        crypto_backend: CryptoBackend,
+        master_key_for_one_to_two_prg: SeedU8x16, iv_for_one_to_two_prg: Option<SeedU8x16>,
         big_ia: Vec<usize>, big_ib: Vec<usize>,
         bs: usize, rm: usize, // these variables are employed for determining big_l
+        context: Option<String>,
     ) -> Self {
+        // every leaf of a GGM tree of depth tau is identified with an element of GFVOLEitH
+        assert!(tau >= 1 && tau as usize <= GFVOLEitH::num_bytes() * 8, "tau {} does not fit GFVOLEitH", tau);
+        // fewer repetitions fall short of the security level, more would make every tree shallower
+        let security_level = GFVOLE::num_bytes() * 8;
+        assert_eq!(
+            kappa, Self::determine_kappa(security_level, tau),
+            "kappa {} does not match tau {} at security level {}", kappa, tau, security_level
+        );
+        let tau_rep = Self::determine_tau_rep(security_level, tau, kappa);
         let num_and_gates = bristol_fashion_adaptor.get_and_gate_output_wire_vec().len();
         let big_l = bs * num_and_gates + rm;
         let big_io_size = bristol_fashion_adaptor.get_num_output_bits();
-        let byte_vec = Self::make_byte_vec(tau, kappa, &master_key_for_one_to_two_prg, &big_ia, &big_ib, bs, rm);
+        let circuit_digest = bristol_fashion_adaptor.determine_digest(crypto_backend.hash());
+        let byte_vec = Self::make_byte_vec(
+            tau, kappa, &tau_rep, vector_commitment_mode, leaf_expansion_version, crypto_backend, &master_key_for_one_to_two_prg, &iv_for_one_to_two_prg, &big_ia, &big_ib, bs, rm,
+            &circuit_digest, &context
+        );
         Self {
             tau,
             kappa,
-            one_to_two_prg: OneToTwoPRG::new(&master_key_for_one_to_two_prg),
+            tau_rep,
+            vector_commitment_mode,
+            leaf_expansion_version,
+            crypto_backend,
+            master_key_for_one_to_two_prg,
+            iv_for_one_to_two_prg,
+            one_to_two_prg: OneToTwoPRG::new(crypto_backend.prg_backend, &master_key_for_one_to_two_prg, iv_for_one_to_two_prg.as_ref()),
             // big_ia_size: big_ia.len(),
             // big_ib_size: big_ib.len(),
             bs,
@@ -56,14 +96,38 @@ impl PublicParameter {
             num_wires: bristol_fashion_adaptor.get_num_wires(),
             big_iw: bristol_fashion_adaptor.get_and_gate_output_wire_vec().clone(),
             garbled_row_byte_len: 1 + GFVOLE::num_bytes() + GFVOLEitH::num_bytes() * kappa + GFVOLE::num_bytes(),
+            circuit_digest,
+            context,
             byte_vec,
         }
     }
     
+    // the fewest repetitions of depth at most tau whose depths reach the security level
+    pub fn determine_kappa(security_level: usize, tau: u8) -> usize {
+        security_level.div_ceil(tau as usize)
+    }
+
+    // Splits the security level among the kappa = determine_kappa(security_level, tau) repetitions as in FAEST, so
+    // that the depths of the GGM trees differ by at most one, are at most tau and add up to exactly the security
+    // level, e.g., 128 bits at tau 11 yield 8 trees of depth 11 and 4 of depth 10 instead of 12 trees of depth 11,
+    // and at tau 14 they yield 8 trees of depth 13 and 2 of depth 12
+    pub fn determine_tau_rep(security_level: usize, tau: u8, kappa: usize) -> Vec<u8> {
+        debug_assert_eq!(kappa, Self::determine_kappa(security_level, tau));
+        let base_tau = (security_level / kappa) as u8;
+        let num_deeper_repetitions = security_level % kappa;
+        (0..kappa).map(
+            |repetition_id| if repetition_id < num_deeper_repetitions { base_tau + 1 } else { base_tau }
+        ).collect()
+    }
+
+    #[allow(clippy::too_many_arguments)]
     fn make_byte_vec(
-        tau: u8, kappa: usize, master_key_for_one_to_two_prg: &SeedU8x16,
+        tau: u8, kappa: usize, tau_rep: &Vec<u8>, vector_commitment_mode: VectorCommitmentMode, leaf_expansion_version: LeafExpansionVersion,
+        crypto_backend: CryptoBackend,
+        master_key_for_one_to_two_prg: &SeedU8x16, iv_for_one_to_two_prg: &Option<SeedU8x16>,
         big_ia: &Vec<usize>, big_ib: &Vec<usize>,
         bs: usize, rm: usize,
+        circuit_digest: &[u8; 32], context: &Option<String>,
     ) -> Vec<u8> {
         let config = config::standard();
         
@@ -71,16 +135,146 @@ impl PublicParameter {
         
         res.append(&mut tau.to_le_bytes().to_vec());
         res.append(&mut kappa.to_le_bytes().to_vec());
+        res.append(&mut encode_to_vec(tau_rep, config).unwrap());
+        res.append(&mut encode_to_vec(vector_commitment_mode, config).unwrap());
+        res.append(&mut encode_to_vec(leaf_expansion_version, config).unwrap());
+        res.append(&mut encode_to_vec(crypto_backend, config).unwrap());
         res.append(&mut master_key_for_one_to_two_prg.to_vec());
+        res.append(&mut encode_to_vec(iv_for_one_to_two_prg, config).unwrap());
         res.append(&mut encode_to_vec(&big_ia, config).unwrap());
         res.append(&mut encode_to_vec(&big_ib, config).unwrap());
         res.append(&mut bs.to_le_bytes().to_vec());
         res.append(&mut rm.to_le_bytes().to_vec());
+        res.append(&mut circuit_digest.to_vec());
+        res.append(&mut encode_to_vec(context, config).unwrap());
         
         res
     }
     
+    // Draws the IV of one proof in the salted mode, so that no two proofs under the same setup expand their GGM trees
+    // with the same keys. The prover calls it on the public parameter of the setup before proving, and the proof
+    // bundle carries the IV to the verifier. Without an IV, i.e., outside the salted mode, it does nothing.
+    pub fn refresh_iv_for_one_to_two_prg<R: RngCore + CryptoRng + ?Sized>(&mut self, rng: &mut R) {
+        if self.iv_for_one_to_two_prg.is_none() {
+            return;
+        }
+        self.iv_for_one_to_two_prg = Some(SeedU8x16::insecurely_random(rng));
+        self.one_to_two_prg = OneToTwoPRG::new(
+            self.crypto_backend.prg_backend, &self.master_key_for_one_to_two_prg, self.iv_for_one_to_two_prg.as_ref()
+        );
+        self.byte_vec = Self::make_byte_vec(
+            self.tau, self.kappa, &self.tau_rep, self.vector_commitment_mode, self.leaf_expansion_version, self.crypto_backend,
+            &self.master_key_for_one_to_two_prg, &self.iv_for_one_to_two_prg, &self.big_ia, &self.big_ib, self.bs, self.rm,
+            &self.circuit_digest, &self.context
+        );
+    }
+
+    // The digest only binds the circuit into the Fiat-Shamir challenges, while the verifier sizes its vectors and
+    // reads the output wires from the fields below, so that all of them have to be the ones derived from the circuit
+    pub fn matches_circuit(&self, bristol_fashion_adaptor: &BristolFashionAdaptor) -> bool {
+        let big_iw = bristol_fashion_adaptor.get_and_gate_output_wire_vec();
+        let num_input_bits = self.big_ia.len() + self.big_ib.len();
+        self.circuit_digest == bristol_fashion_adaptor.determine_digest(self.crypto_backend.hash())
+            && num_input_bits == bristol_fashion_adaptor.get_num_input_bits()
+            && self.num_input_bits == num_input_bits
+            && self.num_wires == bristol_fashion_adaptor.get_num_wires()
+            && &self.big_iw == big_iw
+            && self.big_iw_size == big_iw.len()
+            && &self.big_io == bristol_fashion_adaptor.get_output_wire_vec()
+            && self.big_io_size == bristol_fashion_adaptor.get_num_output_bits()
+            && self.big_l == self.bs * big_iw.len() + self.rm
+            && self.big_n == num_input_bits + 2 * big_iw.len() + 3 * self.big_l
+    }
+
     pub fn to_byte_vec(&self) -> &Vec<u8> {
         &self.byte_vec
     }
-}
\ No newline at end of file
+}
+
+// The PRG and byte_vec are not encoded, they are re-derived from the master key and the other fields
+impl Encode for PublicParameter {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        self.tau.encode(encoder)?;
+        self.kappa.encode(encoder)?;
+        self.tau_rep.encode(encoder)?;
+        self.vector_commitment_mode.encode(encoder)?;
+        self.leaf_expansion_version.encode(encoder)?;
+        self.crypto_backend.encode(encoder)?;
+        self.master_key_for_one_to_two_prg.encode(encoder)?;
+        self.iv_for_one_to_two_prg.encode(encoder)?;
+        self.bs.encode(encoder)?;
+        self.rm.encode(encoder)?;
+        self.big_iw_size.encode(encoder)?;
+        self.big_io_size.encode(encoder)?;
+        self.big_l.encode(encoder)?;
+        self.big_n.encode(encoder)?;
+        self.num_input_bits.encode(encoder)?;
+        self.num_wires.encode(encoder)?;
+        self.big_ia.encode(encoder)?;
+        self.big_ib.encode(encoder)?;
+        self.big_iw.encode(encoder)?;
+        self.big_io.encode(encoder)?;
+        self.garbled_row_byte_len.encode(encoder)?;
+        self.circuit_digest.encode(encoder)?;
+        self.context.encode(encoder)
+    }
+}
+
+impl<Context> Decode<Context> for PublicParameter {
+    fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        let tau = u8::decode(decoder)?;
+        let kappa = usize::decode(decoder)?;
+        let tau_rep = Vec::<u8>::decode(decoder)?;
+        let vector_commitment_mode = VectorCommitmentMode::decode(decoder)?;
+        let leaf_expansion_version = LeafExpansionVersion::decode(decoder)?;
+        let crypto_backend = CryptoBackend::decode(decoder)?;
+        let master_key_for_one_to_two_prg = SeedU8x16::decode(decoder)?;
+        let iv_for_one_to_two_prg = Option::<SeedU8x16>::decode(decoder)?;
+        let bs = usize::decode(decoder)?;
+        let rm = usize::decode(decoder)?;
+        let big_iw_size = usize::decode(decoder)?;
+        let big_io_size = usize::decode(decoder)?;
+        let big_l = usize::decode(decoder)?;
+        let big_n = usize::decode(decoder)?;
+        let num_input_bits = usize::decode(decoder)?;
+        let num_wires = usize::decode(decoder)?;
+        let big_ia = Vec::<usize>::decode(decoder)?;
+        let big_ib = Vec::<usize>::decode(decoder)?;
+        let big_iw = Vec::<usize>::decode(decoder)?;
+        let big_io = Vec::<usize>::decode(decoder)?;
+        let garbled_row_byte_len = usize::decode(decoder)?;
+        let circuit_digest = <[u8; 32]>::decode(decoder)?;
+        let context = Option::<String>::decode(decoder)?;
+        let byte_vec = Self::make_byte_vec(
+            tau, kappa, &tau_rep, vector_commitment_mode, leaf_expansion_version, crypto_backend, &master_key_for_one_to_two_prg, &iv_for_one_to_two_prg, &big_ia, &big_ib, bs, rm,
+            &circuit_digest, &context
+        );
+        Ok(Self {
+            tau,
+            kappa,
+            tau_rep,
+            vector_commitment_mode,
+            leaf_expansion_version,
+            crypto_backend,
+            master_key_for_one_to_two_prg,
+            iv_for_one_to_two_prg,
+            one_to_two_prg: OneToTwoPRG::new(crypto_backend.prg_backend, &master_key_for_one_to_two_prg, iv_for_one_to_two_prg.as_ref()),
+            bs,
+            rm,
+            big_iw_size,
+            big_io_size,
+            big_l,
+            big_n,
+            num_input_bits,
+            num_wires,
+            big_ia,
+            big_ib,
+            big_iw,
+            big_io,
+            garbled_row_byte_len,
+            circuit_digest,
+            context,
+            byte_vec,
+        })
+    }
+}
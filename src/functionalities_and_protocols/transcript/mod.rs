@@ -0,0 +1,67 @@
+use crate::functionalities_and_protocols::crypto_backend::{Hash, Xof, XofReader};
+use crate::functionalities_and_protocols::permutation::sample_permutation;
+use crate::value_type::LeafIndexForGF;
+
+mod test;
+
+// A Fiat-Shamir transcript. Every message is absorbed with a label, and both are prefixed with their lengths, so
+// that no two sequences of messages are absorbed alike. A challenge is squeezed by absorbing its label and reading
+// the XOF of everything absorbed so far, so that later challenges also depend on the earlier ones. The prover and
+// the verifier must absorb and squeeze the same things in the same order, which is why both drive a Transcript
+// rather than hashing the messages themselves.
+pub struct Transcript {
+    xof: Box<dyn Xof>,
+}
+
+impl Transcript {
+    // the context separates the transcripts of different protocols, it must be hardcoded and globally unique
+    pub fn new(hash: &dyn Hash, context: &str) -> Self {
+        Self {
+            xof: hash.new_xof(context),
+        }
+    }
+
+    fn absorb_with_length(&mut self, byte_vec: &[u8]) {
+        self.xof.update(&(byte_vec.len() as u64).to_le_bytes());
+        self.xof.update(byte_vec);
+    }
+
+    pub fn absorb(&mut self, label: &str, message: &[u8]) {
+        self.absorb_with_length(label.as_bytes());
+        self.absorb_with_length(message);
+    }
+
+    fn squeeze(&mut self, label: &str) -> Box<dyn XofReader> {
+        self.absorb("challenge", label.as_bytes());
+        self.xof.finalize_xof()
+    }
+
+    fn read_u64(output_reader: &mut Box<dyn XofReader>) -> u64 {
+        let mut byte_vec = [0u8; 8];
+        output_reader.read(&mut byte_vec);
+        u64::from_le_bytes(byte_vec)
+    }
+
+    // num_rep uniform permutations of 0..len, sampled from the XOF stream
+    pub fn squeeze_permutation_rep(&mut self, label: &str, num_rep: usize, len: usize) -> Vec<Vec<usize>> {
+        let mut output_reader = self.squeeze(label);
+        let mut next_u64 = || Self::read_u64(&mut output_reader);
+        (0..num_rep).map(|_| sample_permutation(&mut next_u64, len)).collect()
+    }
+
+    // for each repetition, the element of GF identified with a uniform leaf of a GGM tree of depth tau
+    pub fn squeeze_leaf_rep<GF: LeafIndexForGF>(&mut self, label: &str, tau_rep: &[u8]) -> Vec<GF> {
+        let mut output_reader = self.squeeze(label);
+        tau_rep.iter().map(
+            |tau| GF::from_leaf_index((Self::read_u64(&mut output_reader) & ((1u64 << tau) - 1)) as usize)
+        ).collect()
+    }
+}
+
+impl Clone for Transcript {
+    fn clone(&self) -> Self {
+        Self {
+            xof: self.xof.box_clone(),
+        }
+    }
+}
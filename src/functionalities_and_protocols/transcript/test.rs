@@ -0,0 +1,49 @@
+#[cfg(test)]
+mod tests {
+    use crate::functionalities_and_protocols::crypto_backend::HashBackend;
+    use crate::functionalities_and_protocols::transcript::Transcript;
+    use crate::value_type::gf2p16::GF2p16;
+    use crate::value_type::LeafIndexForGF;
+
+    const CONTEXT: &str = "pa-gc-rs transcript test";
+
+    fn squeeze_after(message_vec: &[(&str, &[u8])]) -> Vec<Vec<usize>> {
+        let mut transcript = Transcript::new(HashBackend::Blake3.hash(), CONTEXT);
+        for (label, message) in message_vec {
+            transcript.absorb(label, message);
+        }
+        transcript.squeeze_permutation_rep("permutation_rep", 1, 16)
+    }
+
+    #[test]
+    fn test_messages_are_separated() {
+        let permutation_rep = squeeze_after(&[("a", b"bc")]);
+        assert_eq!(permutation_rep, squeeze_after(&[("a", b"bc")]));
+        // moving bytes between labels and messages, or between messages, changes the challenge
+        assert_ne!(permutation_rep, squeeze_after(&[("ab", b"c")]));
+        assert_ne!(permutation_rep, squeeze_after(&[("a", b"b"), ("", b"c")]));
+        assert_ne!(permutation_rep, squeeze_after(&[("b", b"bc")]));
+
+        let mut transcript = Transcript::new(HashBackend::Blake3.hash(), CONTEXT);
+        transcript.absorb("a", b"bc");
+        assert_ne!(Transcript::new(HashBackend::Blake3.hash(), "another context").squeeze_permutation_rep("permutation_rep", 1, 16), permutation_rep);
+        assert_ne!(transcript.clone().squeeze_permutation_rep("another label", 1, 16), permutation_rep);
+    }
+
+    #[test]
+    fn test_typed_challenges() {
+        let mut transcript = Transcript::new(HashBackend::Blake3.hash(), CONTEXT);
+        let permutation_rep = transcript.squeeze_permutation_rep("permutation_rep", 3, 100);
+        for permutation in permutation_rep.iter() {
+            let mut sorted_permutation = permutation.clone();
+            sorted_permutation.sort();
+            assert_eq!(sorted_permutation, (0..100).collect::<Vec<usize>>());
+        }
+        // a challenge depends on the ones squeezed before
+        assert_ne!(permutation_rep, transcript.squeeze_permutation_rep("permutation_rep", 3, 100));
+
+        let tau_rep = [11, 11, 10, 1];
+        let leaf_rep = transcript.squeeze_leaf_rep::<GF2p16>("nabla_rep", &tau_rep);
+        assert!(leaf_rep.iter().zip(tau_rep.iter()).all(|(leaf, tau)| leaf.get_leaf_index() < 1 << tau));
+    }
+}
@@ -2,7 +2,6 @@ use std::fmt::Debug;
 use crate::value_type::{CustomAddition, CustomMultiplyingBit, Zero};
 use crate::vec_type::bit_vec::BitVec;
 use crate::vec_type::gf_vec::GFVec;
-use crate::vec_type::{BasicVecFunctions, VecAddition};
 
 pub struct Verifier;
 
@@ -12,7 +11,7 @@ impl Verifier {
         voleith_mac_vec: &GFVec<GFVOLE>,
         delta: &GFVOLE,
         voleith_key_vec: &GFVec<GFVOLE>,
-    ) {
+    ) -> Result<(), usize> {
         // println!("Verifying Voleith Correlations");
         // println!("bit_vec:           {:?}", bit_vec.iter());
         // println!("voleith_key_vec:   {:?}", voleith_key_vec.iter());
@@ -20,19 +19,47 @@ impl Verifier {
         // println!("bit * nabla + key: {:?}", voleith_key_vec.vec_add(
         //     &GFVec::<GFVOLE>::from_vec(
         //         bit_vec.iter().map(
-        //             |bit| delta.custom_multiply_bit(*bit)
+        //             |bit| delta.custom_multiply_bit(bit)
         //         ).collect::<Vec<GFVOLE>>()
         //     )
         // ).iter());
-        assert_eq!(
-            *voleith_mac_vec,
-            voleith_key_vec.vec_add(
-                &GFVec::<GFVOLE>::from_vec(
-                    bit_vec.iter().map(
-                        |bit| delta.custom_multiply_bit(*bit)
-                    ).collect::<Vec<GFVOLE>>()
-                )
-            )
-        );
+        // returns the first index at which mac = key + bit * delta does not hold
+        if bit_vec.len() != voleith_mac_vec.len() || bit_vec.len() != voleith_key_vec.len() {
+            return Err(bit_vec.len().min(voleith_mac_vec.len()).min(voleith_key_vec.len()));
+        }
+        find_violation(bit_vec, voleith_mac_vec, delta, voleith_key_vec.iter())
+    }
+
+    // the same as verify_vole_correlations for the keys voleith_key_trace_vec[wire] of the given wires, without
+    // gathering them into a GFVec first
+    pub fn verify_vole_correlations_at_wires<GFVOLE: CustomAddition + Clone + Zero + PartialEq>(
+        bit_vec: &BitVec,
+        voleith_mac_vec: &GFVec<GFVOLE>,
+        delta: &GFVOLE,
+        voleith_key_trace_vec: &GFVec<GFVOLE>,
+        wire_vec: &[usize],
+    ) -> Result<(), usize> {
+        if bit_vec.len() != voleith_mac_vec.len() || bit_vec.len() != wire_vec.len() {
+            return Err(bit_vec.len().min(voleith_mac_vec.len()).min(wire_vec.len()));
+        }
+        find_violation(bit_vec, voleith_mac_vec, delta, wire_vec.iter().map(|wire| &voleith_key_trace_vec[*wire]))
+    }
+}
+
+// compared entry by entry, without materializing key + bit * delta
+fn find_violation<'a, GFVOLE: CustomAddition + Clone + Zero + PartialEq + 'a>(
+    bit_vec: &BitVec,
+    voleith_mac_vec: &GFVec<GFVOLE>,
+    delta: &GFVOLE,
+    voleith_key_iter: impl Iterator<Item = &'a GFVOLE>,
+) -> Result<(), usize> {
+    match bit_vec.iter().zip(voleith_key_iter).zip(voleith_mac_vec.iter()).position(
+        |((bit, voleith_key), voleith_mac)| match bit {
+            0 => voleith_key != voleith_mac,
+            _ => voleith_key.custom_add(delta) != *voleith_mac,
+        }
+    ) {
+        None => Ok(()),
+        Some(index) => Err(index),
     }
 }
\ No newline at end of file
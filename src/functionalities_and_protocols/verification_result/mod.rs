@@ -0,0 +1,131 @@
+use std::fmt::{Display, Formatter};
+use crate::vec_type::bit_vec::BitVec;
+
+// What the verifier of PiPA2PC returns once every check has passed
+#[derive(Clone, Debug, PartialEq)]
+pub struct VerifiedOutput {
+    pub output_bit_vec: BitVec,
+}
+
+impl VerifiedOutput {
+    pub fn new(output_bit_vec: BitVec) -> Self {
+        Self {
+            output_bit_vec
+        }
+    }
+}
+
+// Which check of PiPA2PC rejected the proof, together with where it happened
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum VerificationError {
+    // the public parameter was set up for a different circuit than the one being verified, or one of its fields
+    // derived from the circuit, e.g., the output wires, was altered
+    CircuitDigestMismatch,
+    // the all-in-one vector commitment reconstructed from the decommitment does not match the committed hash
+    SVOLEReconstruction {
+        is_pa: bool,
+        repetition_id: usize,
+    },
+    // the decommitment does not have the shape that the vector commitment mode and the challenges call for
+    MalformedDecom {
+        is_pa: bool,
+    },
+    // the opening of PB's intermediate message does not match the commitment in the preprocessing transcript
+    CommitmentHashMismatch {
+        and_gate_id: usize,
+        and_cursor: usize,
+    },
+    // a VOLEitH correlation of the opened (rm) AND triples of one party does not hold
+    OpenedAndTriple {
+        is_pa: bool,
+        repetition_id: usize,
+        index: usize,
+    },
+    // the opened (rm) AND triples of PA and PB together are not a valid AND triple, which neither party alone is
+    // responsible for
+    OpenedAndTripleRelation {
+        repetition_id: usize,
+        index: usize,
+    },
+    // a VOLEitH correlation inside PiCheckAND does not hold
+    CheckAND {
+        is_pa: bool,
+        block_id: usize,
+        repetition_id: usize,
+        and_cursor: usize,
+    },
+    // a decrypted garbled row, or PB's published middle VOLEitH MAC, does not match the verifier's VOLEitH key
+    GarbledRowDecryption {
+        is_pa: bool,
+        repetition_id: usize,
+        and_gate_id: usize,
+        and_cursor: usize,
+    },
+    // the published masks of an input wire are not consistent with the verifier's VOLEitH keys, the wire is None if
+    // the published masks do not even have one entry per input wire
+    InputConsistency {
+        is_pa: bool,
+        repetition_id: usize,
+        input_wire: Option<usize>,
+    },
+    // the published masks of an output wire, or the published output bit itself, are not consistent, the wire is None
+    // if the published masks do not even have one entry per output wire
+    OutputConsistency {
+        repetition_id: Option<usize>,
+        output_wire: Option<usize>,
+    },
+}
+
+fn party_name(is_pa: bool) -> &'static str {
+    if is_pa { "PA" } else { "PB" }
+}
+
+fn wire_name(wire: &Option<usize>) -> String {
+    match wire {
+        Some(wire) => format!("wire {}", wire),
+        None => "a length mismatch".to_string(),
+    }
+}
+
+impl Display for VerificationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VerificationError::CircuitDigestMismatch => write!(
+                f, "the public parameter does not match the circuit"
+            ),
+            VerificationError::SVOLEReconstruction { is_pa, repetition_id } => write!(
+                f, "SVOLE reconstruction of {} failed at repetition {}", party_name(*is_pa), repetition_id
+            ),
+            VerificationError::MalformedDecom { is_pa } => write!(
+                f, "decommitment of {} is malformed", party_name(*is_pa)
+            ),
+            VerificationError::CommitmentHashMismatch { and_gate_id, and_cursor } => write!(
+                f, "commitment hash mismatch at AND gate {} (gate id {})", and_cursor, and_gate_id
+            ),
+            VerificationError::OpenedAndTriple { is_pa, repetition_id, index } => write!(
+                f, "opened AND triple check of {} failed at repetition {}, index {}", party_name(*is_pa), repetition_id, index
+            ),
+            VerificationError::OpenedAndTripleRelation { repetition_id, index } => write!(
+                f, "opened AND triples do not multiply at repetition {}, index {}", repetition_id, index
+            ),
+            VerificationError::CheckAND { is_pa, block_id, repetition_id, and_cursor } => write!(
+                f, "CheckAND of {} failed at block {}, repetition {}, AND gate {}", party_name(*is_pa), block_id, repetition_id, and_cursor
+            ),
+            VerificationError::GarbledRowDecryption { is_pa, repetition_id, and_gate_id, and_cursor } => write!(
+                f, "garbled-row decryption check of {} failed at repetition {}, AND gate {} (gate id {})",
+                party_name(*is_pa), repetition_id, and_cursor, and_gate_id
+            ),
+            VerificationError::InputConsistency { is_pa, repetition_id, input_wire } => write!(
+                f, "input consistency of {} failed at repetition {}, {}", party_name(*is_pa), repetition_id, wire_name(input_wire)
+            ),
+            VerificationError::OutputConsistency { repetition_id: Some(repetition_id), output_wire } => write!(
+                f, "output consistency failed at repetition {}, {}", repetition_id, wire_name(output_wire)
+            ),
+            VerificationError::OutputConsistency { repetition_id: None, output_wire } => write!(
+                f, "output consistency failed at {}", wire_name(output_wire)
+            ),
+        }
+    }
+}
+
+impl std::error::Error for VerificationError {}
@@ -1,22 +1,31 @@
 use std::any::type_name;
 use std::fmt::Debug;
 use std::time::Instant;
-use bincode::{config, encode_to_vec, Encode};
-use rand::Rng;
+use bincode::{config, decode_from_slice, encode_to_vec, Decode, Encode};
+use rand::{CryptoRng, Rng, RngCore, SeedableRng};
+use rand_chacha::ChaCha20Rng;
 use pa_gc_rs::bristol_fashion_adaptor::bristol_fashion_adaptor::BristolFashionAdaptor;
+use pa_gc_rs::comm_types_and_constants::DECODING_BYTE_LIMIT;
+use pa_gc_rs::functionalities_and_protocols::all_in_one_vc::{LeafExpansionVersion, VectorCommitmentMode};
+use pa_gc_rs::functionalities_and_protocols::crypto_backend::{CryptoBackend, HashBackend, PrgBackend};
+use pa_gc_rs::functionalities_and_protocols::functionality_pre::PreprocessingFunctionality;
+use pa_gc_rs::functionalities_and_protocols::insecure_functionality_pre::insecure_functionality_pre::InsecureFunctionalityPre;
 use pa_gc_rs::functionalities_and_protocols::protocol_pa_2pc::determine_bit_trace_for_labels_in_garbling;
+use pa_gc_rs::functionalities_and_protocols::protocol_pa_2pc::proof_bundle::{peek_public_parameter, ProofBundle};
 use pa_gc_rs::functionalities_and_protocols::protocol_pa_2pc::prover_in_pa_2pc::ProverInPA2PC;
 use pa_gc_rs::functionalities_and_protocols::protocol_pa_2pc::verifier_in_pa_2pc::VerifierInPA2PC;
 use pa_gc_rs::functionalities_and_protocols::states_and_parameters::prover_secret_state::ProverSecretState;
 use pa_gc_rs::functionalities_and_protocols::states_and_parameters::public_parameter::PublicParameter;
 use pa_gc_rs::value_type::gf2p256::GF2p256;
 use pa_gc_rs::value_type::gf2p8::GF2p8;
+use pa_gc_rs::value_type::gf2p16::GF2p16;
 use pa_gc_rs::value_type::seed_u8x16::SeedU8x16;
 use pa_gc_rs::value_type::{ByteManipulation, CustomAddition, CustomMultiplyingBit, InsecureRandom, Zero};
 use pa_gc_rs::value_type::gf2p128::GF2p128;
+use pa_gc_rs::value_type::gf2p192::GF2p192;
 use pa_gc_rs::vec_type::bit_vec::BitVec;
 use pa_gc_rs::vec_type::BasicVecFunctions;
-use pa_gc_rs::value_type::U8ForGF;
+use pa_gc_rs::value_type::LeafIndexForGF;
 
 // fn insecurely_generate_random_permutation(len: usize) -> Vec<usize> {
 //     let mut random_permutation = (0..len).collect::<Vec<usize>>();
@@ -53,27 +62,43 @@ fn determine_full_input_bit_vec(
     full_input_bit_vec
 }
 
-fn benchmark<GFVOLE, GFVOLEitH>(process_printing: bool, circuit_string_file_name: &str, num_threads: usize)
+fn determine_kappa<GFVOLE: ByteManipulation>(tau: u8) -> usize {
+    PublicParameter::determine_kappa(GFVOLE::num_bytes() * 8, tau)
+}
+
+fn determine_bs<GFVOLE: ByteManipulation>(bristol_fashion_adaptor: &BristolFashionAdaptor, kappa: usize) -> usize {
+    let security_level = GFVOLE::num_bytes() * 8;
+    (((security_level as f64) / (kappa as f64)) / (bristol_fashion_adaptor.get_and_gate_output_wire_vec().len() as f64).log2()).ceil() as usize
+}
+
+// PA owns the first input group and PB the others, circuits with a single input group are split in half
+fn determine_default_big_ia_and_big_ib(bristol_fashion_adaptor: &BristolFashionAdaptor) -> (Vec<usize>, Vec<usize>) {
+    if bristol_fashion_adaptor.get_input_group_size_vec().len() > 1 {
+        return bristol_fashion_adaptor.determine_big_ia_and_big_ib(&[0]).unwrap();
+    }
+    let num_input_bits = bristol_fashion_adaptor.get_num_input_bits();
+    ((0..num_input_bits >> 1).collect(), (num_input_bits >> 1..num_input_bits).collect())
+}
+
+#[allow(clippy::too_many_arguments)]
+fn benchmark<GFVOLE, GFVOLEitH, R>(
+    process_printing: bool, circuit_string_file_name: &str, num_threads: usize, tau: u8,
+    vector_commitment_mode: VectorCommitmentMode, leaf_expansion_version: LeafExpansionVersion, crypto_backend: CryptoBackend,
+    salted: bool, context: Option<&str>, rng: &mut R
+)
 where
+    R: RngCore + CryptoRng,
     GFVOLE: ByteManipulation + Copy + Zero + PartialEq + CustomAddition + CustomMultiplyingBit + InsecureRandom + Send + Sync + Debug + Encode,
-    GFVOLEitH: ByteManipulation + Clone + Zero + CustomMultiplyingBit + Copy + CustomAddition + U8ForGF + Send + Sync + Debug + PartialEq + Encode {
+    GFVOLEitH: ByteManipulation + Clone + Zero + CustomMultiplyingBit + Copy + CustomAddition + LeafIndexForGF + Send + Sync + Debug + PartialEq + Encode {
     let security_level = GFVOLE::num_bytes() * 8;
-    let tau = 8;
-    let kappa = (security_level - 1) / (tau as usize) + 1;
+    let kappa = determine_kappa::<GFVOLE>(tau);
 
-    rayon::ThreadPoolBuilder::new()
-        .num_threads(num_threads)
-        .build_global()
-        .unwrap();
     let start_total = Instant::now();
-    let bristol_fashion_adaptor = BristolFashionAdaptor::new(
-        &circuit_string_file_name.to_string()
+    let bristol_fashion_adaptor = BristolFashionAdaptor::from_path(circuit_string_file_name).unwrap_or_else(
+        |error| panic!("{}: {}", circuit_string_file_name, error)
     );
-    let mut rng = rand::rng();
     // println!("Num AND gates: {:?}", bristol_fashion_adaptor.get_and_gate_output_wire_vec().len());
-    let num_input_bits = bristol_fashion_adaptor.get_num_input_bits();
-    let big_ia = (0..num_input_bits >> 1).collect::<Vec<usize>>();
-    let big_ib = (big_ia.len()..num_input_bits).collect::<Vec<usize>>();
+    let (big_ia, big_ib) = determine_default_big_ia_and_big_ib(&bristol_fashion_adaptor);
     // let big_io = (bristol_fashion_adaptor.get_num_wires() - bristol_fashion_adaptor.get_num_output_bits()..bristol_fashion_adaptor.get_num_wires()).collect::<Vec<usize>>();
     let pa_input_bit_vec = big_ia.iter().map(
         |_| rng.random::<u8>() & 1
@@ -82,31 +107,37 @@ where
         |_| rng.random::<u8>() & 1
     ).collect();
     // let rm = bristol_fashion_adaptor.get_and_gate_output_wire_vec().len();
-    let bs = (((security_level as f64) / (kappa as f64)) / (bristol_fashion_adaptor.get_and_gate_output_wire_vec().len() as f64).log2()).ceil() as usize;
+    let bs = determine_bs::<GFVOLE>(&bristol_fashion_adaptor, kappa);
     let rm = bristol_fashion_adaptor.get_and_gate_output_wire_vec().len();
-    println!("Security level {:?}, Circuit {:?}, GFVOLE: {:?}, GFVOLEitH: {:?}, num_threads: {:?}, tau: {:?}, kappa: {:?}, bs: {:?}",
-             security_level, circuit_string_file_name, type_name::<GFVOLE>(), type_name::<GFVOLEitH>(), num_threads, tau, kappa, bs
+    println!("Security level {:?}, Circuit {:?}, GFVOLE: {:?}, GFVOLEitH: {:?}, num_threads: {:?}, tau: {:?}, kappa: {:?}, bs: {:?}, VC: {:?}, leaf expansion: {:?}, hash: {:?}, PRG: {:?}, salted: {:?}, context: {:?}",
+             security_level, circuit_string_file_name, type_name::<GFVOLE>(), type_name::<GFVOLEitH>(), num_threads, tau, kappa, bs, vector_commitment_mode, leaf_expansion_version,
+             crypto_backend.hash_backend, crypto_backend.prg_backend, salted, context
     );
     let public_parameter = PublicParameter::new::<GFVOLE, GFVOLEitH>(
         &bristol_fashion_adaptor,
         tau,
         kappa,
-        SeedU8x16::insecurely_random(),
+        vector_commitment_mode,
+        leaf_expansion_version,
+        crypto_backend,
+        SeedU8x16::insecurely_random(rng),
+        salted.then(|| SeedU8x16::insecurely_random(rng)),
         big_ia,
         big_ib,
         bs,
         rm,
+        context.map(String::from),
     );
 
     let mut pa_secret_state = ProverSecretState::<GFVOLE, GFVOLEitH>::new(
         &public_parameter,
-        SeedU8x16::insecurely_random(),
+        SeedU8x16::insecurely_random(rng),
         true
     );
 
     let mut pb_secret_state = ProverSecretState::<GFVOLE, GFVOLEitH>::new(
         &public_parameter,
-        SeedU8x16::insecurely_random(),
+        SeedU8x16::insecurely_random(rng),
         false
     );
 
@@ -116,8 +147,12 @@ where
     );
 
     let start_preprocessing = Instant::now();
+    let (mut pa_functionality_pre, mut pb_functionality_pre) = InsecureFunctionalityPre::new_pair(ChaCha20Rng::from_rng(&mut *rng));
     let preprocessing_transcript = ProverInPA2PC::preprocess(
         process_printing,
+        &mut pa_functionality_pre,
+        &mut pb_functionality_pre,
+        rng,
         &bristol_fashion_adaptor,
         &bit_trace_vec_for_labels_in_garbling,
         &public_parameter,
@@ -131,17 +166,17 @@ where
     // ).collect::<Vec<Vec<usize>>>();
     //
     // let nabla_a_rep = (0..public_parameter.kappa).map(|_|
-    //     GFVOLEitH::from_u8(rng.random::<u8>())
+    //     GFVOLEitH::from_leaf_index(rng.random_range(0..1 << public_parameter.tau))
     // ).collect::<Vec<GFVOLEitH>>();
     // let nabla_b_rep = (0..public_parameter.kappa).map(|_|
-    //     GFVOLEitH::from_u8(rng.random::<u8>())
+    //     GFVOLEitH::from_leaf_index(rng.random_range(0..1 << public_parameter.tau))
     // ).collect::<Vec<GFVOLEitH>>();
 
     // println!("nabla_a_rep {:?}", nabla_a_rep);
     // println!("nabla_b_rep {:?}", nabla_b_rep);
 
     let start_proving = Instant::now();
-    let (proof_transcript, pa_decom_rep, pb_decom_rep) = ProverInPA2PC::prove(
+    let (proof_transcript, pa_decom, pb_decom) = ProverInPA2PC::prove(
         process_printing,
         &bristol_fashion_adaptor,
         &public_parameter,
@@ -165,9 +200,9 @@ where
         // &nabla_a_rep, &nabla_b_rep,
         &preprocessing_transcript,
         &proof_transcript,
-        &pa_decom_rep,
-        &pb_decom_rep,
-    );
+        &pa_decom,
+        &pb_decom,
+    ).expect("verification failed");
     let verifying_time = start_verifying.elapsed().as_secs_f32();
     let total_time = start_total.elapsed().as_secs_f32();
 
@@ -180,8 +215,8 @@ where
     let expected_output_bit_vec = BitVec::from_vec(bristol_fashion_adaptor.compute_output_bits(&full_input_bit_vec));
     // println!("{:?}", expected_output_bit_vec);
     assert_eq!(proof_transcript.published_output_bit_vec, expected_output_bit_vec);
-    println!("+ Performance for Security level {:?}, Circuit {:?}, GFVOLE: {:?}, GFVOLEitH: {:?}, num_threads: {:?}, tau: {:?}, kappa: {:?}, bs: {:?}",
-             security_level, circuit_string_file_name, type_name::<GFVOLE>(), type_name::<GFVOLEitH>(), num_threads, tau, kappa, bs
+    println!("+ Performance for Security level {:?}, Circuit {:?}, GFVOLE: {:?}, GFVOLEitH: {:?}, num_threads: {:?}, tau: {:?}, kappa: {:?}, bs: {:?}, VC: {:?}",
+             security_level, circuit_string_file_name, type_name::<GFVOLE>(), type_name::<GFVOLEitH>(), num_threads, tau, kappa, bs, vector_commitment_mode
     );
     println!("  Running time");
     println!("    Preprocessing time: {:?}", preprocessing_time);
@@ -189,11 +224,13 @@ where
     println!("    Verifying time: {:?}", verifying_time);
     println!("    ==> Total running time: {:?}", total_time);
     println!("  Communication");
+    // zero for the insecure dealer, whose pre-processing is only simulated
+    println!("    FPre communication size: {:?} MB", ((pa_functionality_pre.num_communicated_bytes() + pb_functionality_pre.num_communicated_bytes()) as f64) / 1048576f64);
     println!("    preproccesing_transcript size: {:?} MB", (preprocessing_transcript.to_byte_vec().len() as f64) / 1048576f64);
     println!("    proof_transcript size: {:?} MB", (proof_transcript.to_byte_vec().len() as f64) / 1048576f64);
     let config = config::standard();
-    let pa_decom_bytes = encode_to_vec(&pa_decom_rep, config).unwrap();
-    let pb_decom_bytes = encode_to_vec(&pb_decom_rep, config).unwrap();
+    let pa_decom_bytes = encode_to_vec(&pa_decom, config).unwrap();
+    let pb_decom_bytes = encode_to_vec(&pb_decom, config).unwrap();
     let total_decom_byte_len = pa_decom_bytes.len() + pb_decom_bytes.len();
     println!("    (pa_decom, pb_decom) size: {:?} MB", (total_decom_byte_len as f64) / 1048576f64);
     println!("    ==> Total communication size: {:?} MB",
@@ -201,55 +238,474 @@ where
     );
 }
 
+const USAGE: &str = "\
+Usage: pa-gc-rs <COMMAND> [OPTIONS]
+
+Commands:
+  setup      Emit a public parameter file for a circuit and an input split
+  prove      Run the preprocessing and proving phases and write a proof bundle
+  verify     Verify a proof bundle and print the output bits
+  benchmark  Prove and verify with random inputs and report running time and sizes
+
+Options:
+  --circuit <PATH>          Bristol Fashion circuit file (required)
+  --security-level <BITS>   128 (GF2p128), 192 (GF2p192) or 256 (GF2p256) [default: 128]
+  --tau <TAU>               Depth of the GGM trees, from 1 to 16, GFVOLEitH is GF2p8 up to 8
+                            and GF2p16 above [default: 8]
+  --vc <MODE>               Vector commitment of setup and benchmark, per-repetition (a GGM tree
+                            per repetition) or batched (one GGM tree for all repetitions,
+                            smaller decommitments) [default: per-repetition]
+  --leaf-expansion <VERSION>
+                            Expansion of the GGM leaves into bits of setup and benchmark, v1
+                            (a chain of AES calls, one bit per byte) or v2 (AES in counter
+                            mode, every bit) [default: v2]
+  --hash <HASH>             Random oracle of setup and benchmark, blake3 or shake256 (cSHAKE256)
+                            [default: blake3]
+  --prg <PRG>               PRG of the GGM trees and leaf expansions of setup and benchmark,
+                            aes128 or aes256, both on 128-bit seeds [default: aes128]
+  --salted                  Derive the GGM tree expansion of setup and benchmark from a fresh
+                            public IV as well, against multi-target attacks across proofs
+  --context <STRING>        Application context of setup and benchmark, e.g., a session id,
+                            that the public parameter and thus every proof is bound to
+                            [default: none]
+  --threads <NUM>           Number of threads [default: 1]
+  --seed <SEED>             Seed all randomness of setup, prove and benchmark with a u64,
+                            so that they are reproducible [default: a fresh random seed]
+  --quiet                   Do not print the protocol steps
+
+Options for setup:
+  --pa-groups <IDS>         Comma-separated input groups owned by PA, PB owns the rest
+                            [default: the first group, or half of the bits for a single group]
+  --pa-input-bits <NUM>     PA owns the first NUM input bits, PB the rest, instead of --pa-groups
+  --bs <NUM>                Bucket size of PiCheckAND [default: derived from the security level]
+  --rm <NUM>                Number of AND triples opened for checking [default: number of AND gates]
+  --out <FILE>              Public parameter file to write (required)
+
+Options for prove:
+  --params <FILE>           Public parameter file from setup (required)
+  --pa-input <FILE>         PA's input bits as a string of 0s and 1s (required)
+  --pb-input <FILE>         PB's input bits as a string of 0s and 1s (required)
+  --out <FILE>              Proof bundle to write (required)
+
+Options for verify:
+  --bundle <FILE>           Proof bundle from prove (required)
+";
+
+struct CommandLineOptions {
+    circuit: String,
+    security_level: usize,
+    tau: Option<u8>,
+    vector_commitment_mode: VectorCommitmentMode,
+    leaf_expansion_version: LeafExpansionVersion,
+    crypto_backend: CryptoBackend,
+    salted: bool,
+    context: Option<String>,
+    num_threads: usize,
+    seed: Option<u64>,
+    process_printing: bool,
+    pa_groups: Option<Vec<usize>>,
+    pa_input_bits: Option<usize>,
+    bs: Option<usize>,
+    rm: Option<usize>,
+    params: Option<String>,
+    pa_input: Option<String>,
+    pb_input: Option<String>,
+    bundle: Option<String>,
+    out: Option<String>,
+}
+
+impl CommandLineOptions {
+    fn parse(args: &[String]) -> Result<Self, String> {
+        let mut circuit = None;
+        let mut options = Self {
+            circuit: String::new(),
+            security_level: 128,
+            tau: None,
+            vector_commitment_mode: VectorCommitmentMode::PerRepetition,
+            leaf_expansion_version: LeafExpansionVersion::V2,
+            crypto_backend: CryptoBackend::default(),
+            salted: false,
+            context: None,
+            num_threads: 1,
+            seed: None,
+            process_printing: true,
+            pa_groups: None,
+            pa_input_bits: None,
+            bs: None,
+            rm: None,
+            params: None,
+            pa_input: None,
+            pb_input: None,
+            bundle: None,
+            out: None,
+        };
+        let mut arg_iter = args.iter();
+        while let Some(flag) = arg_iter.next() {
+            if flag == "--quiet" {
+                options.process_printing = false;
+                continue;
+            }
+            if flag == "--salted" {
+                options.salted = true;
+                continue;
+            }
+            let value = arg_iter.next().ok_or(format!("missing value for {}", flag))?.clone();
+            let parse_number = |value: &String| value.parse::<usize>().map_err(|_| format!("invalid value for {}: {}", flag, value));
+            match flag.as_str() {
+                "--circuit" => circuit = Some(value),
+                "--security-level" => options.security_level = parse_number(&value)?,
+                "--tau" => options.tau = Some(
+                    value.parse::<u8>().map_err(|_| format!("invalid value for {}: {}", flag, value))?
+                ),
+                "--vc" => options.vector_commitment_mode = match value.as_str() {
+                    "per-repetition" => VectorCommitmentMode::PerRepetition,
+                    "batched" => VectorCommitmentMode::Batched,
+                    _ => return Err(format!("invalid value for {}: {}, expected per-repetition or batched", flag, value)),
+                },
+                "--leaf-expansion" => options.leaf_expansion_version = match value.as_str() {
+                    "v1" => LeafExpansionVersion::V1,
+                    "v2" => LeafExpansionVersion::V2,
+                    _ => return Err(format!("invalid value for {}: {}, expected v1 or v2", flag, value)),
+                },
+                "--context" => options.context = Some(value),
+                "--hash" => options.crypto_backend.hash_backend = match value.as_str() {
+                    "blake3" => HashBackend::Blake3,
+                    "shake256" => HashBackend::Shake256,
+                    _ => return Err(format!("invalid value for {}: {}, expected blake3 or shake256", flag, value)),
+                },
+                "--prg" => options.crypto_backend.prg_backend = match value.as_str() {
+                    "aes128" => PrgBackend::Aes128,
+                    "aes256" => PrgBackend::Aes256,
+                    _ => return Err(format!("invalid value for {}: {}, expected aes128 or aes256", flag, value)),
+                },
+                "--threads" => options.num_threads = parse_number(&value)?,
+                "--seed" => options.seed = Some(
+                    value.parse::<u64>().map_err(|_| format!("invalid value for {}: {}", flag, value))?
+                ),
+                "--pa-groups" => options.pa_groups = Some(
+                    value.split(',').map(|group_id| parse_number(&group_id.trim().to_string())).collect::<Result<Vec<usize>, String>>()?
+                ),
+                "--pa-input-bits" => options.pa_input_bits = Some(parse_number(&value)?),
+                "--bs" => options.bs = Some(parse_number(&value)?),
+                "--rm" => options.rm = Some(parse_number(&value)?),
+                "--params" => options.params = Some(value),
+                "--pa-input" => options.pa_input = Some(value),
+                "--pb-input" => options.pb_input = Some(value),
+                "--bundle" => options.bundle = Some(value),
+                "--out" => options.out = Some(value),
+                _ => return Err(format!("unknown option {}", flag)),
+            }
+        }
+        options.circuit = circuit.ok_or("--circuit is required")?;
+        if ![128, 192, 256].contains(&options.security_level) {
+            return Err(format!("unsupported security level {}, expected 128, 192 or 256", options.security_level));
+        }
+        if let Some(tau) = options.tau && (tau == 0 || tau > 16) {
+            return Err(format!("unsupported tau {}, expected 1 to 16", tau));
+        }
+        if options.pa_groups.is_some() && options.pa_input_bits.is_some() {
+            return Err("--pa-groups and --pa-input-bits cannot be used together".to_string());
+        }
+        if options.num_threads == 0 {
+            return Err("--threads must be positive".to_string());
+        }
+        Ok(options)
+    }
+}
+
+fn required<'a>(value: &'a Option<String>, flag: &str) -> Result<&'a String, String> {
+    value.as_ref().ok_or(format!("{} is required", flag))
+}
+
+fn read_file(file_name: &str) -> Result<Vec<u8>, String> {
+    std::fs::read(file_name).map_err(|error| format!("cannot read {}: {}", file_name, error))
+}
+
+fn write_file(file_name: &str, byte_vec: &[u8]) -> Result<(), String> {
+    std::fs::write(file_name, byte_vec).map_err(|error| format!("cannot write {}: {}", file_name, error))
+}
+
+fn read_input_bit_vec(file_name: &str, expected_len: usize) -> Result<Vec<u8>, String> {
+    let input_bit_vec = read_file(file_name)?.into_iter().filter(|c| !c.is_ascii_whitespace()).map(
+        |c| match c {
+            b'0' => Ok(0u8),
+            b'1' => Ok(1u8),
+            _ => Err(format!("{} must only contain 0s and 1s", file_name)),
+        }
+    ).collect::<Result<Vec<u8>, String>>()?;
+    if input_bit_vec.len() != expected_len {
+        return Err(format!("{} has {} input bits, expected {}", file_name, input_bit_vec.len(), expected_len));
+    }
+    Ok(input_bit_vec)
+}
+
+fn read_public_parameter(file_name: &str) -> Result<PublicParameter, String> {
+    let byte_vec = read_file(file_name)?;
+    let (public_parameter, num_bytes): (PublicParameter, usize) = decode_from_slice(&byte_vec, config::standard().with_limit::<DECODING_BYTE_LIMIT>())
+        .map_err(|error| format!("cannot decode {}: {}", file_name, error))?;
+    if num_bytes != byte_vec.len() {
+        return Err(format!("{} has trailing bytes", file_name));
+    }
+    Ok(public_parameter)
+}
+
+// prove and verify take tau from the public parameter, since it determines GFVOLEitH
+fn determine_tau(command: &str, options: &CommandLineOptions) -> Result<u8, String> {
+    match command {
+        "prove" => Ok(read_public_parameter(required(&options.params, "--params")?)?.tau),
+        "verify" => {
+            let bundle = required(&options.bundle, "--bundle")?;
+            let public_parameter = peek_public_parameter(&read_file(bundle)?)
+                .map_err(|error| format!("cannot load {}: {}", bundle, error))?;
+            Ok(public_parameter.tau)
+        }
+        _ => Ok(options.tau.unwrap_or(8)),
+    }
+}
+
+// The bucketing check is only sound for a bucket size of at least determine_bs and with at least one opened triple
+// per AND gate, which determine_bs assumes. Opening more than bs triples per AND gate buys no soundness, so such an
+// rm is rejected as well.
+fn check_bs_and_rm<GFVOLE: ByteManipulation>(bristol_fashion_adaptor: &BristolFashionAdaptor, kappa: usize, bs: usize, rm: usize) -> Result<(), String> {
+    let min_bs = determine_bs::<GFVOLE>(bristol_fashion_adaptor, kappa);
+    if bs < min_bs {
+        return Err(format!("bs {} is below the {} required for this circuit and kappa {}", bs, min_bs, kappa));
+    }
+    let num_and_gates = bristol_fashion_adaptor.get_and_gate_output_wire_vec().len();
+    if rm < num_and_gates || rm > bs * num_and_gates {
+        return Err(format!("rm {} is outside {}..={} for this circuit and bs {}", rm, num_and_gates, bs * num_and_gates, bs));
+    }
+    Ok(())
+}
+
+fn check_tau_and_kappa<GFVOLE: ByteManipulation>(
+    options: &CommandLineOptions, bristol_fashion_adaptor: &BristolFashionAdaptor, public_parameter: &PublicParameter
+) -> Result<(), String> {
+    if let Some(tau) = options.tau && tau != public_parameter.tau {
+        return Err(format!("--tau {} does not match tau {} of the public parameter", tau, public_parameter.tau));
+    }
+    if public_parameter.kappa != determine_kappa::<GFVOLE>(public_parameter.tau) {
+        return Err(format!(
+            "the public parameter was not generated for security level {}", options.security_level
+        ));
+    }
+    check_bs_and_rm::<GFVOLE>(bristol_fashion_adaptor, public_parameter.kappa, public_parameter.bs, public_parameter.rm).map_err(
+        |error| format!("the public parameter is too weak: {}", error)
+    )
+}
+
+fn load_circuit(options: &CommandLineOptions) -> Result<BristolFashionAdaptor, String> {
+    BristolFashionAdaptor::from_path(&options.circuit).map_err(
+        |error| format!("cannot load circuit {}: {}", options.circuit, error)
+    )
+}
+
+// the same --seed always yields the same rng and thus the same public parameter, proof bundle or benchmark inputs
+fn determine_rng(options: &CommandLineOptions) -> ChaCha20Rng {
+    match options.seed {
+        Some(seed) => ChaCha20Rng::seed_from_u64(seed),
+        None => ChaCha20Rng::from_rng(&mut rand::rng()),
+    }
+}
+
+fn run_setup<GFVOLE, GFVOLEitH, R>(options: &CommandLineOptions, rng: &mut R) -> Result<(), String>
+where GFVOLE: ByteManipulation, GFVOLEitH: ByteManipulation, R: RngCore + CryptoRng {
+    let out = required(&options.out, "--out")?;
+    let tau = options.tau.unwrap_or(8);
+    let kappa = determine_kappa::<GFVOLE>(tau);
+    let bristol_fashion_adaptor = load_circuit(options)?;
+    let num_input_bits = bristol_fashion_adaptor.get_num_input_bits();
+    let (big_ia, big_ib) = match (&options.pa_groups, options.pa_input_bits) {
+        (Some(pa_groups), _) => bristol_fashion_adaptor.determine_big_ia_and_big_ib(pa_groups).map_err(
+            |error| format!("--pa-groups: {}", error)
+        )?,
+        (None, Some(pa_input_bits)) => {
+            if pa_input_bits > num_input_bits {
+                return Err(format!("--pa-input-bits {} exceeds the {} input bits of the circuit", pa_input_bits, num_input_bits));
+            }
+            ((0..pa_input_bits).collect(), (pa_input_bits..num_input_bits).collect())
+        }
+        (None, None) => determine_default_big_ia_and_big_ib(&bristol_fashion_adaptor),
+    };
+    let bs = options.bs.unwrap_or(determine_bs::<GFVOLE>(&bristol_fashion_adaptor, kappa));
+    let rm = options.rm.unwrap_or(bristol_fashion_adaptor.get_and_gate_output_wire_vec().len());
+    check_bs_and_rm::<GFVOLE>(&bristol_fashion_adaptor, kappa, bs, rm).map_err(|error| format!("--bs/--rm: {}", error))?;
+    let public_parameter = PublicParameter::new::<GFVOLE, GFVOLEitH>(
+        &bristol_fashion_adaptor,
+        tau,
+        kappa,
+        options.vector_commitment_mode,
+        options.leaf_expansion_version,
+        options.crypto_backend,
+        SeedU8x16::insecurely_random(rng),
+        options.salted.then(|| SeedU8x16::insecurely_random(rng)),
+        big_ia,
+        big_ib,
+        bs,
+        rm,
+        options.context.clone(),
+    );
+    write_file(out, &encode_to_vec(&public_parameter, config::standard()).unwrap())?;
+    if options.process_printing {
+        println!("+ Public parameter written to {:?} (tau: {:?}, kappa: {:?}, bs: {:?}, rm: {:?}, VC: {:?}, leaf expansion: {:?}, hash: {:?}, PRG: {:?}, salted: {:?}, context: {:?}, PA input bits: {:?}, PB input bits: {:?})",
+                 out, tau, kappa, bs, rm, public_parameter.vector_commitment_mode, public_parameter.leaf_expansion_version,
+                 public_parameter.crypto_backend.hash_backend, public_parameter.crypto_backend.prg_backend, public_parameter.iv_for_one_to_two_prg.is_some(),
+                 public_parameter.context, public_parameter.big_ia.len(), public_parameter.big_ib.len()
+        );
+    }
+    Ok(())
+}
+
+fn run_prove<GFVOLE, GFVOLEitH, R>(options: &CommandLineOptions, rng: &mut R) -> Result<(), String>
+where
+    R: RngCore + CryptoRng,
+    GFVOLE: ByteManipulation + Copy + Zero + PartialEq + CustomAddition + CustomMultiplyingBit + InsecureRandom + Send + Sync + Debug + Encode + Decode<()>,
+    GFVOLEitH: ByteManipulation + Clone + Zero + CustomMultiplyingBit + Copy + CustomAddition + LeafIndexForGF + Send + Sync + Debug + PartialEq + Encode + Decode<()> {
+    let out = required(&options.out, "--out")?;
+    let bristol_fashion_adaptor = load_circuit(options)?;
+    let params = required(&options.params, "--params")?;
+    let mut public_parameter = read_public_parameter(params)?;
+    check_tau_and_kappa::<GFVOLE>(options, &bristol_fashion_adaptor, &public_parameter)?;
+    if !public_parameter.matches_circuit(&bristol_fashion_adaptor) {
+        return Err(format!("{} was not generated for circuit {}", params, options.circuit));
+    }
+    // a salted setup only marks the mode, every proof draws its own IV and carries it in the bundle
+    public_parameter.refresh_iv_for_one_to_two_prg(rng);
+    let pa_input_bit_vec = read_input_bit_vec(required(&options.pa_input, "--pa-input")?, public_parameter.big_ia.len())?;
+    let pb_input_bit_vec = read_input_bit_vec(required(&options.pb_input, "--pb-input")?, public_parameter.big_ib.len())?;
+
+    let mut pa_secret_state = ProverSecretState::<GFVOLE, GFVOLEitH>::new(
+        &public_parameter,
+        SeedU8x16::insecurely_random(rng),
+        true
+    );
+    let mut pb_secret_state = ProverSecretState::<GFVOLE, GFVOLEitH>::new(
+        &public_parameter,
+        SeedU8x16::insecurely_random(rng),
+        false
+    );
+    let bit_trace_vec_for_labels_in_garbling = determine_bit_trace_for_labels_in_garbling(
+        &bristol_fashion_adaptor,
+        &public_parameter,
+    );
+    let (mut pa_functionality_pre, mut pb_functionality_pre) = InsecureFunctionalityPre::new_pair(ChaCha20Rng::from_rng(&mut *rng));
+    let preprocessing_transcript = ProverInPA2PC::preprocess(
+        options.process_printing,
+        &mut pa_functionality_pre,
+        &mut pb_functionality_pre,
+        rng,
+        &bristol_fashion_adaptor,
+        &bit_trace_vec_for_labels_in_garbling,
+        &public_parameter,
+        &mut pa_secret_state,
+        &mut pb_secret_state,
+    );
+    let (proof_transcript, pa_decom, pb_decom) = ProverInPA2PC::prove(
+        options.process_printing,
+        &bristol_fashion_adaptor,
+        &public_parameter,
+        &preprocessing_transcript,
+        &mut pa_secret_state,
+        &mut pb_secret_state,
+        &pa_input_bit_vec,
+        &pb_input_bit_vec,
+    );
+
+    let proof_bundle = ProofBundle::new(
+        public_parameter, preprocessing_transcript, proof_transcript, pa_decom, pb_decom
+    );
+    let byte_vec = proof_bundle.to_byte_vec();
+    write_file(out, &byte_vec)?;
+    if options.process_printing {
+        println!("+ Proof bundle written to {:?} ({:?} MB)", out, (byte_vec.len() as f64) / 1048576f64);
+    }
+    Ok(())
+}
+
+fn run_verify<GFVOLE, GFVOLEitH>(options: &CommandLineOptions) -> Result<(), String>
+where
+    GFVOLE: ByteManipulation + Clone + Zero + Encode + Decode<()>,
+    GFVOLEitH: ByteManipulation + Clone + CustomAddition + CustomMultiplyingBit + Zero + LeafIndexForGF + PartialEq + Debug + Copy + Send + Sync + Encode + Decode<()> {
+    let bundle = required(&options.bundle, "--bundle")?;
+    let bristol_fashion_adaptor = load_circuit(options)?;
+    let proof_bundle = ProofBundle::<GFVOLE, GFVOLEitH>::from_byte_vec(&read_file(bundle)?)
+        .map_err(|error| format!("cannot load {}: {}", bundle, error))?;
+    check_tau_and_kappa::<GFVOLE>(options, &bristol_fashion_adaptor, &proof_bundle.public_parameter)?;
+    if !proof_bundle.public_parameter.matches_circuit(&bristol_fashion_adaptor) {
+        return Err(format!("{} was not generated for circuit {}", bundle, options.circuit));
+    }
+    if options.process_printing && let Some(context) = &proof_bundle.public_parameter.context {
+        println!("+ Context: {:?}", context);
+    }
+
+    let verified_output = VerifierInPA2PC::verify::<GFVOLE, GFVOLEitH>(
+        options.process_printing,
+        &bristol_fashion_adaptor,
+        &proof_bundle.public_parameter,
+        &proof_bundle.preprocessing_transcript,
+        &proof_bundle.proof_transcript,
+        &proof_bundle.pa_decom,
+        &proof_bundle.pb_decom,
+    ).map_err(|error| format!("verification failed: {}", error))?;
+    println!("{}", verified_output.output_bit_vec.iter().map(|bit| (b'0' + bit) as char).collect::<String>());
+    if options.process_printing {
+        let output_bit_vec = verified_output.output_bit_vec.iter().collect::<Vec<u8>>();
+        let output_byte_vec_vec = bristol_fashion_adaptor.split_output_bit_vec_into_byte_vec_vec(&output_bit_vec).map_err(
+            |error| format!("cannot split the output into groups: {}", error)
+        )?;
+        for (group_id, output_byte_vec) in output_byte_vec_vec.iter().enumerate() {
+            // most significant byte first
+            println!("+ Output group {}: 0x{}", group_id, output_byte_vec.iter().rev().map(|byte| format!("{:02x}", byte)).collect::<String>());
+        }
+    }
+    Ok(())
+}
+
+fn run<GFVOLE, GFVOLEitH>(command: &str, options: &CommandLineOptions) -> Result<(), String>
+where
+    GFVOLE: ByteManipulation + Copy + Zero + PartialEq + CustomAddition + CustomMultiplyingBit + InsecureRandom + Send + Sync + Debug + Encode + Decode<()>,
+    GFVOLEitH: ByteManipulation + Clone + Zero + CustomMultiplyingBit + Copy + CustomAddition + LeafIndexForGF + Send + Sync + Debug + PartialEq + Encode + Decode<()> {
+    match command {
+        "setup" => run_setup::<GFVOLE, GFVOLEitH, _>(options, &mut determine_rng(options)),
+        "prove" => run_prove::<GFVOLE, GFVOLEitH, _>(options, &mut determine_rng(options)),
+        "verify" => run_verify::<GFVOLE, GFVOLEitH>(options),
+        "benchmark" => {
+            benchmark::<GFVOLE, GFVOLEitH, _>(
+                options.process_printing, &options.circuit, options.num_threads, options.tau.unwrap_or(8),
+                options.vector_commitment_mode, options.leaf_expansion_version, options.crypto_backend, options.salted, options.context.as_deref(),
+                &mut determine_rng(options)
+            );
+            Ok(())
+        }
+        _ => Err(format!("unknown command {}\n\n{}", command, USAGE)),
+    }
+}
+
 fn main() {
-    let print_process = true;
-    let circuit_sub64 = "sub64.txt";
-    let circuit_mult64 = "mult64.txt";
-    let circuit_aes_128 = "aes_128.txt";
-    let circuit_sha256 = "sha256.txt";
-    
-    // 128-bit security
-    type GFVOLE128 = GF2p128;
-    // benchmark::<GFVOLE128, GF2p8>(print_process, circuit_sub64, 1);
-    // benchmark::<GFVOLE128, GF2p8>(print_process, circuit_sub64, 2);
-    // benchmark::<GFVOLE128, GF2p8>(print_process, circuit_sub64, 4);
-    // benchmark::<GFVOLE128, GF2p8>(print_process, circuit_sub64, 8);
-
-    // benchmark::<GFVOLE128, GF2p8>(print_process, circuit_mult64, 1);
-    // benchmark::<GFVOLE128, GF2p8>(print_process, circuit_mult64, 2);
-    // benchmark::<GFVOLE128, GF2p8>(print_process, circuit_mult64, 4);
-    // benchmark::<GFVOLE128, GF2p8>(print_process, circuit_mult64, 8);
-
-    // benchmark::<GFVOLE128, GF2p8>(print_process, circuit_aes_128, 1);
-    // benchmark::<GFVOLE128, GF2p8>(print_process, circuit_aes_128, 2);
-    // benchmark::<GFVOLE128, GF2p8>(print_process, circuit_aes_128, 4);
-    // benchmark::<GFVOLE128, GF2p8>(print_process, circuit_aes_128, 8);
-
-    // benchmark::<GFVOLE128, GF2p8>(print_process, circuit_sha256, 1);
-    // benchmark::<GFVOLE128, GF2p8>(print_process, circuit_sha256, 2);
-    // benchmark::<GFVOLE128, GF2p8>(print_process, circuit_sha256, 4);
-    // benchmark::<GFVOLE128, GF2p8>(print_process, circuit_sha256, 8);
-
-    // 256-bit security
-    type GFVOLE256 = GF2p256;
-
-    // benchmark::<GFVOLE256, GF2p8>(print_process, circuit_sub64, 1);
-    // benchmark::<GFVOLE256, GF2p8>(print_process, circuit_sub64, 2);
-    // benchmark::<GFVOLE256, GF2p8>(print_process, circuit_sub64, 4);
-    // benchmark::<GFVOLE256, GF2p8>(print_process, circuit_sub64, 8);
-
-    // benchmark::<GFVOLE256, GF2p8>(print_process, circuit_mult64, 1);
-    // benchmark::<GFVOLE256, GF2p8>(print_process, circuit_mult64, 2);
-    // benchmark::<GFVOLE256, GF2p8>(print_process, circuit_mult64, 4);
-    // benchmark::<GFVOLE256, GF2p8>(print_process, circuit_mult64, 8);
-
-    // benchmark::<GFVOLE256, GF2p8>(print_process, circuit_aes_128, 1);
-    // benchmark::<GFVOLE256, GF2p8>(print_process, circuit_aes_128, 2);
-    // benchmark::<GFVOLE256, GF2p8>(print_process, circuit_aes_128, 4);
-    // benchmark::<GFVOLE256, GF2p8>(print_process, circuit_aes_128, 8);
-
-    // benchmark::<GFVOLE256, GF2p8>(print_process, circuit_sha256, 1);
-    // benchmark::<GFVOLE256, GF2p8>(print_process, circuit_sha256, 2);
-    // benchmark::<GFVOLE256, GF2p8>(print_process, circuit_sha256, 4);
-    benchmark::<GFVOLE256, GF2p8>(print_process, circuit_sha256, 8);
-}
\ No newline at end of file
+    let args = std::env::args().collect::<Vec<String>>();
+    if args.len() < 2 || args[1] == "--help" || args[1] == "-h" {
+        print!("{}", USAGE);
+        return;
+    }
+    let result = CommandLineOptions::parse(&args[2..]).and_then(
+        |options| {
+            rayon::ThreadPoolBuilder::new()
+                .num_threads(options.num_threads)
+                .build_global()
+                .unwrap();
+            let tau = determine_tau(&args[1], &options)?;
+            match (options.security_level, tau > 8) {
+                (128, false) => run::<GF2p128, GF2p8>(&args[1], &options),
+                (128, true) => run::<GF2p128, GF2p16>(&args[1], &options),
+                (192, false) => run::<GF2p192, GF2p8>(&args[1], &options),
+                (192, true) => run::<GF2p192, GF2p16>(&args[1], &options),
+                (_, false) => run::<GF2p256, GF2p8>(&args[1], &options),
+                (_, true) => run::<GF2p256, GF2p16>(&args[1], &options),
+            }
+        }
+    );
+    if let Err(message) = result {
+        eprintln!("error: {}", message);
+        std::process::exit(1);
+    }
+}
@@ -0,0 +1,44 @@
+// Portable carry-less arithmetic on little-endian u64 words, shared by the binary fields GF2p128 and GF2p256
+
+// the 128-bit carry-less product of a and b as (low word, high word)
+pub(crate) fn carry_less_multiply_u64(a: u64, b: u64) -> (u64, u64) {
+    let mut low = 0u64;
+    let mut high = 0u64;
+    for i in 0..64 {
+        // all-ones if the i-th bit of b is set, so that the loop does not branch on secret data
+        let mask = 0u64.wrapping_sub((b >> i) & 1);
+        low ^= (a << i) & mask;
+        if i > 0 {
+            high ^= (a >> (64 - i)) & mask;
+        }
+    }
+    (low, high)
+}
+
+// schoolbook carry-less product of two N-word polynomials into 2N words
+pub(crate) fn carry_less_multiply<const N: usize>(a: &[u64; N], b: &[u64; N], product: &mut [u64]) {
+    assert_eq!(product.len(), 2 * N);
+    product.fill(0);
+    for i in 0..N {
+        for j in 0..N {
+            let (low, high) = carry_less_multiply_u64(a[i], b[j]);
+            product[i + j] ^= low;
+            product[i + j + 1] ^= high;
+        }
+    }
+}
+
+// reduces a 2N-word product modulo x^(64N) + reduction_polynomial, where reduction_polynomial has degree below 64,
+// by folding the high words down from the top, and returns the low N words
+pub(crate) fn reduce<const N: usize>(product: &mut [u64], reduction_polynomial: u64) -> [u64; N] {
+    assert_eq!(product.len(), 2 * N);
+    for i in (N..2 * N).rev() {
+        let (low, high) = carry_less_multiply_u64(product[i], reduction_polynomial);
+        product[i - N] ^= low;
+        product[i - N + 1] ^= high;
+        product[i] = 0;
+    }
+    let mut res = [0u64; N];
+    res.copy_from_slice(&product[..N]);
+    res
+}
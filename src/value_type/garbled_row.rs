@@ -1,7 +1,7 @@
-use bincode::Encode;
+use bincode::{Decode, Encode};
 use crate::value_type::{CustomAddition, Zero};
 
-#[derive(Clone, Debug, Encode)]
+#[derive(Clone, Debug, Encode, Decode)]
 pub struct GarbledRow<GFVOLE, GFVOLEitH> {
     pub first_u8: u8,
     pub vole_mac_field: GFVOLE,
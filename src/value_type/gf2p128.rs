@@ -1,8 +1,9 @@
-use bincode::Encode;
-use rand::Rng;
-use crate::value_type::{ByteManipulation, CustomAddition, CustomMultiplyingBit, InsecureRandom, Zero};
+use bincode::{Decode, Encode};
+use rand::{CryptoRng, Rng, RngCore};
+use crate::value_type::{impl_binary_field_ops, ByteManipulation, CustomAddition, CustomInversion, CustomMultiplication, CustomMultiplyingBit, InsecureRandom, One, Zero};
+use crate::value_type::carry_less::{carry_less_multiply, reduce};
 
-#[derive(Debug, Clone, PartialEq, Eq, Copy, Encode)]
+#[derive(Debug, Clone, PartialEq, Eq, Copy, Encode, Decode)]
 pub struct GF2p128 {
     val: (u64, u64)
 }
@@ -13,6 +14,51 @@ impl GF2p128 {
     // }
 }
 
+// the field is GF(2)[x] / (x^128 + x^7 + x^2 + x + 1), val.0 holds the coefficients of x^0 to x^63
+const REDUCTION_POLYNOMIAL: u64 = 0x87;
+
+impl GF2p128 {
+    fn to_words(self) -> [u64; 2] {
+        [self.val.0, self.val.1]
+    }
+
+    fn from_words(words: [u64; 2]) -> Self {
+        Self { val: (words[0], words[1]) }
+    }
+}
+
+impl One for GF2p128 {
+    fn one() -> Self {
+        Self { val: (1, 0) }
+    }
+}
+
+impl CustomMultiplication for GF2p128 {
+    fn custom_multiply(&self, rhs: &Self) -> Self {
+        let mut product = [0u64; 4];
+        carry_less_multiply(&self.to_words(), &rhs.to_words(), &mut product);
+        Self::from_words(reduce::<2>(&mut product, REDUCTION_POLYNOMIAL))
+    }
+}
+
+impl CustomInversion for GF2p128 {
+    // by Fermat, the inverse is self^(2^128 - 2) = self^2 * self^4 * ... * self^(2^127)
+    fn custom_inverse(&self) -> Option<Self> {
+        if *self == Self::zero() {
+            return None;
+        }
+        let mut power = *self;
+        let mut res = Self::one();
+        for _ in 1..128 {
+            power = power.custom_square();
+            res = res.custom_multiply(&power);
+        }
+        Some(res)
+    }
+}
+
+impl_binary_field_ops!(GF2p128);
+
 impl Zero for GF2p128 {
     fn zero() -> Self {
         Self { val: (0, 0) }
@@ -45,8 +91,7 @@ impl CustomMultiplyingBit for GF2p128 {
 }
 
 impl InsecureRandom for GF2p128 {
-    fn insecurely_random() -> Self {
-        let mut rng = rand::rng();
+    fn insecurely_random<R: RngCore + CryptoRng + ?Sized>(rng: &mut R) -> Self {
         let v0 = rng.random::<u64>();
         let v1 = rng.random::<u64>();
         Self { val: (v0, v1) }
@@ -0,0 +1,131 @@
+use bincode::{Decode, Encode};
+use blake3::Hash;
+use rand::{CryptoRng, Rng, RngCore};
+use crate::value_type::{make_lane_mask_table, ByteManipulation, CustomAddition, CustomMultiplyingBit, HashDigestToGF, InsecureRandom, LeafIndexForGF, Zero};
+
+// GFVOLEitH for GGM trees deeper than 8, i.e., tau from 9 to 16
+#[derive(Debug, Clone, PartialEq, Eq, Copy, Encode, Decode)]
+pub struct GF2p16 {
+    val: u16
+}
+
+impl LeafIndexForGF for GF2p16 {
+    fn from_leaf_index(leaf_index: usize) -> Self {
+        assert!(leaf_index < 1 << 16);
+        Self {
+            val: leaf_index as u16
+        }
+    }
+
+    fn get_leaf_index(&self) -> usize {
+        self.val as usize
+    }
+}
+
+// lane i of 16 bits of LANE_MASK_TABLE[bits] is 0xffff if bit i of bits is one
+const LANE_MASK_TABLE: [u64; 16] = make_lane_mask_table::<16>(16);
+
+// Four entries are packed into a u64, so that the masked operations act on them at once, as for GF2p8
+fn map_packed_groups<F: Fn(u64, u64) -> u64>(chunk: &mut [GF2p16], mask_word: u64, f: F) {
+    let last_group_id = chunk.len() / 4;
+    let mut entry_group_iter = chunk.chunks_exact_mut(4);
+    for (group_id, entry_group) in entry_group_iter.by_ref().enumerate() {
+        let entry_group: &mut [GF2p16; 4] = entry_group.try_into().unwrap();
+        let word = f(pack(entry_group), lane_mask(mask_word, group_id));
+        *entry_group = unpack(word);
+    }
+    let remaining_entry_group = entry_group_iter.into_remainder();
+    if remaining_entry_group.is_empty() {
+        return;
+    }
+    let mut padded_entry_group = [GF2p16 { val: 0 }; 4];
+    padded_entry_group.iter_mut().zip(remaining_entry_group.iter()).for_each(|(padded_entry, entry)| *padded_entry = *entry);
+    let word = f(pack(&padded_entry_group), lane_mask(mask_word, last_group_id));
+    remaining_entry_group.iter_mut().zip(unpack(word)).for_each(|(entry, unpacked_entry)| *entry = unpacked_entry);
+}
+
+fn pack(entry_group: &[GF2p16; 4]) -> u64 {
+    entry_group.iter().rev().fold(0, |word, entry| (word << 16) | entry.val as u64)
+}
+
+fn unpack(word: u64) -> [GF2p16; 4] {
+    [0, 1, 2, 3].map(|lane_id| GF2p16 { val: (word >> (16 * lane_id)) as u16 })
+}
+
+fn lane_mask(mask_word: u64, group_id: usize) -> u64 {
+    LANE_MASK_TABLE[((mask_word >> (4 * group_id)) & 0xf) as usize]
+}
+
+impl CustomAddition for GF2p16 {
+    fn custom_add(&self, rhs: &Self) -> Self {
+        Self {
+            val: self.val ^ rhs.val
+        }
+    }
+
+    fn add_masked_chunk(chunk: &mut [Self], mask_word: u64, value: &Self) {
+        let broadcast_value = (value.val as u64) * 0x0001_0001_0001_0001;
+        map_packed_groups(chunk, mask_word, |word, lane_mask| word ^ (broadcast_value & lane_mask));
+    }
+
+    fn multiply_bit_chunk(chunk: &mut [Self], mask_word: u64) {
+        map_packed_groups(chunk, mask_word, |word, lane_mask| word & lane_mask);
+    }
+}
+
+impl CustomMultiplyingBit for GF2p16 {
+    fn custom_multiply_bit(&self, bit: u8) -> Self {
+        if bit == 0 {
+            Self {
+                val: 0
+            }
+        } else if bit == 1 {
+            *self
+        } else {
+            panic!("{:?} is not binary!", bit);
+        }
+    }
+}
+
+impl InsecureRandom for GF2p16 {
+    fn insecurely_random<R: RngCore + CryptoRng + ?Sized>(rng: &mut R) -> Self {
+        let v = rng.random::<u16>();
+        Self { val: v }
+    }
+}
+
+impl Zero for GF2p16 {
+    fn zero() -> Self {
+        Self {
+            val: 0u16
+        }
+    }
+}
+
+impl HashDigestToGF for GF2p16 {
+    fn from_hash_digest(hash_digest: &Hash) -> Self {
+        let bytes = hash_digest.as_bytes();
+        Self {
+            val: u16::from_le_bytes([bytes[0], bytes[1]])
+        }
+    }
+}
+
+impl ByteManipulation for GF2p16 {
+
+    fn from_bytes(bytes: &[u8], cursor: &mut usize) -> Self {
+        let val = u16::from_le_bytes([bytes[*cursor], bytes[*cursor + 1]]);
+        *cursor += 2;
+        Self {
+            val
+        }
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        self.val.to_le_bytes().to_vec()
+    }
+
+    fn num_bytes() -> usize {
+        2
+    }
+}
@@ -0,0 +1,121 @@
+use bincode::{Decode, Encode};
+use rand::{CryptoRng, Rng, RngCore};
+use crate::value_type::{impl_binary_field_ops, ByteManipulation, CustomAddition, CustomInversion, CustomMultiplication, CustomMultiplyingBit, InsecureRandom, One, Zero};
+use crate::value_type::carry_less::{carry_less_multiply, reduce};
+
+#[derive(Debug, Clone, PartialEq, Eq, Copy, Encode, Decode)]
+pub struct GF2p192 {
+    val: (u64, u64, u64)
+}
+
+// the field is GF(2)[x] / (x^192 + x^7 + x^2 + x + 1), val.0 holds the coefficients of x^0 to x^63
+const REDUCTION_POLYNOMIAL: u64 = 0x87;
+
+impl GF2p192 {
+    fn to_words(self) -> [u64; 3] {
+        [self.val.0, self.val.1, self.val.2]
+    }
+
+    fn from_words(words: [u64; 3]) -> Self {
+        Self { val: (words[0], words[1], words[2]) }
+    }
+}
+
+impl One for GF2p192 {
+    fn one() -> Self {
+        Self { val: (1, 0, 0) }
+    }
+}
+
+impl CustomMultiplication for GF2p192 {
+    fn custom_multiply(&self, rhs: &Self) -> Self {
+        let mut product = [0u64; 6];
+        carry_less_multiply(&self.to_words(), &rhs.to_words(), &mut product);
+        Self::from_words(reduce::<3>(&mut product, REDUCTION_POLYNOMIAL))
+    }
+}
+
+impl CustomInversion for GF2p192 {
+    // by Fermat, the inverse is self^(2^192 - 2) = self^2 * self^4 * ... * self^(2^191)
+    fn custom_inverse(&self) -> Option<Self> {
+        if *self == Self::zero() {
+            return None;
+        }
+        let mut power = *self;
+        let mut res = Self::one();
+        for _ in 1..192 {
+            power = power.custom_square();
+            res = res.custom_multiply(&power);
+        }
+        Some(res)
+    }
+}
+
+impl_binary_field_ops!(GF2p192);
+
+impl Zero for GF2p192 {
+    fn zero() -> Self {
+        Self { val: (0, 0, 0) }
+    }
+}
+
+impl CustomAddition for GF2p192 {
+    fn custom_add(&self, rhs: &Self) -> Self {
+        Self {
+            val: (
+                self.val.0 ^ rhs.val.0,
+                self.val.1 ^ rhs.val.1,
+                self.val.2 ^ rhs.val.2,
+            )
+        }
+    }
+}
+
+impl CustomMultiplyingBit for GF2p192 {
+    fn custom_multiply_bit(&self, bit: u8) -> Self {
+        if bit == 0 {
+            Self {
+                val: (0, 0, 0)
+            }
+        } else if bit == 1 {
+            *self
+        } else {
+            panic!("{:?} is not binary!", bit);
+        }
+    }
+}
+
+impl InsecureRandom for GF2p192 {
+    fn insecurely_random<R: RngCore + CryptoRng + ?Sized>(rng: &mut R) -> Self {
+        let v0 = rng.random::<u64>();
+        let v1 = rng.random::<u64>();
+        let v2 = rng.random::<u64>();
+        Self { val: (v0, v1, v2) }
+    }
+}
+
+impl ByteManipulation for GF2p192 {
+    fn from_bytes(bytes: &[u8], cursor: &mut usize) -> Self {
+        let v0 = u64::from_bytes(bytes, cursor);
+        let v1 = u64::from_bytes(bytes, cursor);
+        let v2 = u64::from_bytes(bytes, cursor);
+        Self {
+            val: (v0, v1, v2)
+        }
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut res = vec![0; Self::num_bytes()];
+        let mut cursor = 0;
+        res[cursor..cursor+8].copy_from_slice(&(self.val.0).to_le_bytes());
+        cursor += 8;
+        res[cursor..cursor+8].copy_from_slice(&(self.val.1).to_le_bytes());
+        cursor += 8;
+        res[cursor..cursor+8].copy_from_slice(&(self.val.2).to_le_bytes());
+        res
+    }
+
+    fn num_bytes() -> usize {
+        24
+    }
+}
\ No newline at end of file
@@ -1,8 +1,9 @@
-use bincode::Encode;
-use rand::Rng;
-use crate::value_type::{ByteManipulation, CustomAddition, CustomMultiplyingBit, InsecureRandom, Zero};
+use bincode::{Decode, Encode};
+use rand::{CryptoRng, Rng, RngCore};
+use crate::value_type::{impl_binary_field_ops, ByteManipulation, CustomAddition, CustomInversion, CustomMultiplication, CustomMultiplyingBit, InsecureRandom, One, Zero};
+use crate::value_type::carry_less::{carry_less_multiply, reduce};
 
-#[derive(Debug, Clone, PartialEq, Eq, Copy, Encode)]
+#[derive(Debug, Clone, PartialEq, Eq, Copy, Encode, Decode)]
 pub struct GF2p256 {
     val: (u64, u64, u64, u64)
 }
@@ -13,6 +14,51 @@ impl GF2p256 {
     // }
 }
 
+// the field is GF(2)[x] / (x^256 + x^10 + x^5 + x^2 + 1), val.0 holds the coefficients of x^0 to x^63
+const REDUCTION_POLYNOMIAL: u64 = 0x425;
+
+impl GF2p256 {
+    fn to_words(self) -> [u64; 4] {
+        [self.val.0, self.val.1, self.val.2, self.val.3]
+    }
+
+    fn from_words(words: [u64; 4]) -> Self {
+        Self { val: (words[0], words[1], words[2], words[3]) }
+    }
+}
+
+impl One for GF2p256 {
+    fn one() -> Self {
+        Self { val: (1, 0, 0, 0) }
+    }
+}
+
+impl CustomMultiplication for GF2p256 {
+    fn custom_multiply(&self, rhs: &Self) -> Self {
+        let mut product = [0u64; 8];
+        carry_less_multiply(&self.to_words(), &rhs.to_words(), &mut product);
+        Self::from_words(reduce::<4>(&mut product, REDUCTION_POLYNOMIAL))
+    }
+}
+
+impl CustomInversion for GF2p256 {
+    // by Fermat, the inverse is self^(2^256 - 2) = self^2 * self^4 * ... * self^(2^255)
+    fn custom_inverse(&self) -> Option<Self> {
+        if *self == Self::zero() {
+            return None;
+        }
+        let mut power = *self;
+        let mut res = Self::one();
+        for _ in 1..256 {
+            power = power.custom_square();
+            res = res.custom_multiply(&power);
+        }
+        Some(res)
+    }
+}
+
+impl_binary_field_ops!(GF2p256);
+
 impl Zero for GF2p256 {
     fn zero() -> Self {
         Self { val: (0, 0, 0, 0) }
@@ -47,8 +93,7 @@ impl CustomMultiplyingBit for GF2p256 {
 }
 
 impl InsecureRandom for GF2p256 {
-    fn insecurely_random() -> Self {
-        let mut rng = rand::rng();
+    fn insecurely_random<R: RngCore + CryptoRng + ?Sized>(rng: &mut R) -> Self {
         let v0 = rng.random::<u64>();
         let v1 = rng.random::<u64>();
         let v2 = rng.random::<u64>();
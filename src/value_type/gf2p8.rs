@@ -1,31 +1,68 @@
-use bincode::Encode;
+use bincode::{Decode, Encode};
 use blake3::Hash;
-use rand::Rng;
-use crate::value_type::{ByteManipulation, CustomAddition, CustomMultiplyingBit, HashDigestToGF, InsecureRandom, U8ForGF, Zero};
+use rand::{CryptoRng, Rng, RngCore};
+use crate::value_type::{make_lane_mask_table, ByteManipulation, CustomAddition, CustomMultiplyingBit, HashDigestToGF, InsecureRandom, LeafIndexForGF, Zero};
 
-#[derive(Debug, Clone, PartialEq, Eq, Copy, Encode)]
+#[derive(Debug, Clone, PartialEq, Eq, Copy, Encode, Decode)]
 pub struct GF2p8 {
     val: u8
 }
 
-impl U8ForGF for GF2p8 {
-    fn from_u8(u8_value: u8) -> Self {
+impl LeafIndexForGF for GF2p8 {
+    fn from_leaf_index(leaf_index: usize) -> Self {
+        assert!(leaf_index < 1 << 8);
         Self {
-            val: u8_value
+            val: leaf_index as u8
         }
     }
 
-    fn get_u8(&self) -> u8 {
-        self.val
+    fn get_leaf_index(&self) -> usize {
+        self.val as usize
     }
 }
 
+// byte i of BYTE_MASK_TABLE[bits] is 0xff if bit i of bits is one
+const BYTE_MASK_TABLE: [u64; 256] = make_lane_mask_table::<256>(8);
+
+// Eight entries are packed into a u64, so that the masked operations act on them at once. The groups have a fixed
+// size for the packing to compile to a single load and store, the entries of a shorter last group are padded with zeros
+fn map_packed_groups<F: Fn(u64, u64) -> u64>(chunk: &mut [GF2p8], mask_word: u64, f: F) {
+    let last_group_id = chunk.len() / 8;
+    let mut entry_group_iter = chunk.chunks_exact_mut(8);
+    for (group_id, entry_group) in entry_group_iter.by_ref().enumerate() {
+        let entry_group: &mut [GF2p8; 8] = entry_group.try_into().unwrap();
+        let word = f(u64::from_le_bytes(entry_group.map(|entry| entry.val)), byte_mask(mask_word, group_id));
+        *entry_group = word.to_le_bytes().map(|val| GF2p8 { val });
+    }
+    let remaining_entry_group = entry_group_iter.into_remainder();
+    if remaining_entry_group.is_empty() {
+        return;
+    }
+    let mut bytes = [0u8; 8];
+    bytes.iter_mut().zip(remaining_entry_group.iter()).for_each(|(byte, entry)| *byte = entry.val);
+    let word = f(u64::from_le_bytes(bytes), byte_mask(mask_word, last_group_id));
+    remaining_entry_group.iter_mut().zip(word.to_le_bytes()).for_each(|(entry, val)| entry.val = val);
+}
+
+fn byte_mask(mask_word: u64, group_id: usize) -> u64 {
+    BYTE_MASK_TABLE[(mask_word >> (8 * group_id)) as u8 as usize]
+}
+
 impl CustomAddition for GF2p8 {
     fn custom_add(&self, rhs: &Self) -> Self {
         Self {
             val: self.val ^ rhs.val
         }
     }
+
+    fn add_masked_chunk(chunk: &mut [Self], mask_word: u64, value: &Self) {
+        let broadcast_value = u64::from_le_bytes([value.val; 8]);
+        map_packed_groups(chunk, mask_word, |word, byte_mask| word ^ (broadcast_value & byte_mask));
+    }
+
+    fn multiply_bit_chunk(chunk: &mut [Self], mask_word: u64) {
+        map_packed_groups(chunk, mask_word, |word, byte_mask| word & byte_mask);
+    }
 }
 
 impl CustomMultiplyingBit for GF2p8 {
@@ -43,8 +80,7 @@ impl CustomMultiplyingBit for GF2p8 {
 }
 
 impl InsecureRandom for GF2p8 {
-    fn insecurely_random() -> Self {
-        let mut rng = rand::rng();
+    fn insecurely_random<R: RngCore + CryptoRng + ?Sized>(rng: &mut R) -> Self {
         let v = rng.random::<u8>();
         Self { val: v }
     }
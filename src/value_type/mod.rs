@@ -1,28 +1,40 @@
 use blake3::Hash;
+use rand::{CryptoRng, RngCore};
 
 pub mod gf2p256;
 pub mod gf2p8;
+pub mod gf2p16;
 pub mod seed_u8x16;
 pub(crate) mod garbled_row;
 pub mod gf2p128;
+pub mod gf2p192;
+pub(crate) mod carry_less;
+mod test;
 
+// samples a uniformly random value from the given rng, so that seeding the rng makes the value reproducible
 pub trait InsecureRandom {
-    fn insecurely_random() -> Self;
+    fn insecurely_random<R: RngCore + CryptoRng + ?Sized>(rng: &mut R) -> Self;
 }
 
 pub trait Zero {
     fn zero() -> Self;
 }
 
+pub trait One {
+    fn one() -> Self;
+}
+
 impl Zero for u8 {
     fn zero() -> Self {
         0
     }   
 }
 
-pub trait U8ForGF {
-    fn from_u8(u8_value: u8) -> Self;
-    fn get_u8(&self) -> u8;
+// identifies a field element of GFVOLEitH with a leaf of a GGM tree, so that a tree of depth tau needs
+// the field to have at least tau bits
+pub trait LeafIndexForGF {
+    fn from_leaf_index(leaf_index: usize) -> Self;
+    fn get_leaf_index(&self) -> usize;
 }
 
 pub trait ByteManipulation {
@@ -96,7 +108,139 @@ pub trait CustomMultiplyingBit {
 
 pub trait CustomAddition {
     fn custom_add(&self, rhs: &Self) -> Self;
+
+    // adds value to chunk[i] for every bit i of mask_word that is one, chunk having at most 64 entries, which
+    // GFVec::add_assign_masked calls once per word of the mask. By default only the one bits are visited, the
+    // fields smaller than a u64 override it to pack their entries into words and XOR a word of them at once
+    fn add_masked_chunk(chunk: &mut [Self], mask_word: u64, value: &Self) where Self: Sized {
+        for_each_one_bit(mask_word, |index| chunk[index] = chunk[index].custom_add(value));
+    }
+
+    // zeroes chunk[i] for every bit i of mask_word that is zero, see add_masked_chunk
+    fn multiply_bit_chunk(chunk: &mut [Self], mask_word: u64) where Self: Sized + Zero {
+        let chunk_mask_word = 1u64.checked_shl(chunk.len() as u32).map_or(u64::MAX, |bit| bit - 1);
+        for_each_one_bit(!mask_word & chunk_mask_word, |index| chunk[index] = Self::zero());
+    }
+}
+
+fn for_each_one_bit<F: FnMut(usize)>(word: u64, mut f: F) {
+    let mut remaining_word = word;
+    while remaining_word != 0 {
+        f(remaining_word.trailing_zeros() as usize);
+        remaining_word &= remaining_word - 1;
+    }
+}
+
+// the table whose entry at bits has its i-th lane of lane_num_bits bits all ones if bit i of bits is one, which spreads
+// the mask bits of the entries packed into a u64 over their lanes
+pub(crate) const fn make_lane_mask_table<const NUM_ENTRIES: usize>(lane_num_bits: usize) -> [u64; NUM_ENTRIES] {
+    let lane = u64::MAX >> (64 - lane_num_bits);
+    let mut table = [0u64; NUM_ENTRIES];
+    let mut bits = 0;
+    while bits < NUM_ENTRIES {
+        let mut lane_id = 0;
+        while lane_id < 64 / lane_num_bits {
+            if (bits >> lane_id) & 1 == 1 {
+                table[bits] |= lane << (lane_id * lane_num_bits);
+            }
+            lane_id += 1;
+        }
+        bits += 1;
+    }
+    table
+}
+
+// multiplication in the field, as opposed to CustomMultiplyingBit that only scales by a bit
+pub trait CustomMultiplication: Sized {
+    fn custom_multiply(&self, rhs: &Self) -> Self;
+
+    fn custom_square(&self) -> Self {
+        self.custom_multiply(self)
+    }
+
+    // square-and-multiply from the most significant bit of exponent
+    fn custom_pow(&self, exponent: u64) -> Self
+    where Self: One {
+        let mut res = Self::one();
+        for i in (0..u64::BITS).rev() {
+            res = res.custom_square();
+            if (exponent >> i) & 1 == 1 {
+                res = res.custom_multiply(self);
+            }
+        }
+        res
+    }
+}
+
+// the multiplicative inverse, which zero does not have
+pub trait CustomInversion: Sized {
+    fn custom_inverse(&self) -> Option<Self>;
+}
+
+// std::ops for a binary field, where subtraction is addition and division panics on a zero divisor
+macro_rules! impl_binary_field_ops {
+    ($field:ty) => {
+        impl std::ops::Add for $field {
+            type Output = Self;
+            fn add(self, rhs: Self) -> Self {
+                self.custom_add(&rhs)
+            }
+        }
+
+        impl std::ops::AddAssign for $field {
+            fn add_assign(&mut self, rhs: Self) {
+                *self = self.custom_add(&rhs);
+            }
+        }
+
+        impl std::ops::Sub for $field {
+            type Output = Self;
+            fn sub(self, rhs: Self) -> Self {
+                self.custom_add(&rhs)
+            }
+        }
+
+        impl std::ops::SubAssign for $field {
+            fn sub_assign(&mut self, rhs: Self) {
+                *self = self.custom_add(&rhs);
+            }
+        }
+
+        impl std::ops::Neg for $field {
+            type Output = Self;
+            fn neg(self) -> Self {
+                self
+            }
+        }
+
+        impl std::ops::Mul for $field {
+            type Output = Self;
+            fn mul(self, rhs: Self) -> Self {
+                self.custom_multiply(&rhs)
+            }
+        }
+
+        impl std::ops::MulAssign for $field {
+            fn mul_assign(&mut self, rhs: Self) {
+                *self = self.custom_multiply(&rhs);
+            }
+        }
+
+        impl std::ops::Div for $field {
+            type Output = Self;
+            fn div(self, rhs: Self) -> Self {
+                self.custom_multiply(&rhs.custom_inverse().expect("division by zero"))
+            }
+        }
+
+        impl std::ops::DivAssign for $field {
+            fn div_assign(&mut self, rhs: Self) {
+                *self = *self / rhs;
+            }
+        }
+    };
 }
+pub(crate) use impl_binary_field_ops;
 
 pub trait HashDigestToGF {
     fn from_hash_digest(hash_digest: &Hash) -> Self;
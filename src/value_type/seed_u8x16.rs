@@ -1,4 +1,4 @@
-use rand::Rng;
+use rand::{CryptoRng, Rng, RngCore};
 use crate::comm_types_and_constants::SEED_BYTE_LEN;
 use crate::value_type::{InsecureRandom, Zero};
 
@@ -11,12 +11,17 @@ impl Zero for SeedU8x16 {
 }
 
 impl InsecureRandom for SeedU8x16 {
-    fn insecurely_random() -> Self {
-        let mut rng = rand::rng();
+    fn insecurely_random<R: RngCore + CryptoRng + ?Sized>(rng: &mut R) -> Self {
         let mut seed: SeedU8x16 = SeedU8x16::default();
         for i in 0..SEED_BYTE_LEN {
             seed[i] = rng.random::<u8>();
         }
         seed
     }
-}
\ No newline at end of file
+}
+
+pub fn xor_block(lhs: &SeedU8x16, rhs: &SeedU8x16) -> SeedU8x16 {
+    let mut res = *lhs;
+    res.iter_mut().zip(rhs.iter()).for_each(|(lhs, rhs)| *lhs ^= *rhs);
+    res
+}
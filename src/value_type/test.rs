@@ -0,0 +1,103 @@
+#[cfg(test)]
+mod tests {
+    use crate::value_type::gf2p128::GF2p128;
+    use crate::value_type::gf2p192::GF2p192;
+    use crate::value_type::gf2p256::GF2p256;
+    use crate::value_type::{ByteManipulation, CustomInversion, CustomMultiplication, InsecureRandom, One, Zero};
+
+    // val.0 holds the least significant coefficients, as in the little-endian byte encoding
+    fn gf2p128(value: u128) -> GF2p128 {
+        GF2p128::from_bytes(&value.to_le_bytes(), &mut 0)
+    }
+
+    fn gf2p192(high: u64, low: u128) -> GF2p192 {
+        GF2p192::from_bytes(&[low.to_le_bytes().as_slice(), high.to_le_bytes().as_slice()].concat(), &mut 0)
+    }
+
+    fn gf2p256(high: u128, low: u128) -> GF2p256 {
+        GF2p256::from_bytes(&[low.to_le_bytes(), high.to_le_bytes()].concat(), &mut 0)
+    }
+
+    // The expected values come from a bit-by-bit reference implementation of GF(2)[x] modulo the reduction
+    // polynomial, with the inverse from the extended Euclidean algorithm rather than Fermat's little theorem
+    #[test]
+    fn test_gf2p128_known_answers() {
+        let a = gf2p128(0x0123456789abcdeffedcba9876543210);
+        let b = gf2p128(0x3c2d1e0f4b5a69788796a5b4c3d2e1f0);
+        assert_eq!(a * b, gf2p128(0x6c18b9e4c0d23b16c033a041f3449320));
+        assert_eq!(a.custom_square(), gf2p128(0x55d14fc33db9278af470ee629c18862b));
+        assert_eq!(a.custom_inverse(), Some(gf2p128(0xac20a8a9f088c918e7a4a93e6b40984a)));
+        assert_eq!(a.custom_pow(0xfedcba9876543210), gf2p128(0xcb336fd183ea2bd3bcc2cddaafd8d45d));
+        // x^128 = x^7 + x^2 + x + 1
+        assert_eq!(gf2p128(2).custom_pow(128), gf2p128(0x87));
+    }
+
+    #[test]
+    fn test_gf2p192_known_answers() {
+        let a = gf2p192(0x0123456789abcdef, 0xfedcba98765432100f1e2d3c4b5a6978);
+        let b = gf2p192(0xdeadbeefcafebabe, 0x0011223344556677f0e1d2c3b4a59687);
+        assert_eq!(a * b, gf2p192(0xab37461926425a25, 0x5fe5132808239401a14fe97de6dcf289));
+        assert_eq!(a.custom_square(), gf2p192(0x15910f837df967ca, 0xe131fe269d4d825010c00fd76cbc7380));
+        assert_eq!(a.custom_inverse(), Some(gf2p192(0x174344092367a518, 0xb008c11c3fb1c4849fa6f5db8f330d91)));
+        assert_eq!(a.custom_pow(0xfedcba9876543210), gf2p192(0xc42afd7d58095f39, 0xb307811100a0db8961eaea8628197b5d));
+        // x^192 = x^7 + x^2 + x + 1
+        assert_eq!(gf2p192(0, 2).custom_pow(192), gf2p192(0, 0x87));
+    }
+
+    #[test]
+    fn test_gf2p256_known_answers() {
+        let a = gf2p256(0x0123456789abcdeffedcba9876543210, 0x0f1e2d3c4b5a69788796a5b4c3d2e1f0);
+        let b = gf2p256(0xdeadbeefcafebabe0011223344556677, 0xf0e1d2c3b4a5968778695a4b3c2d1e0f);
+        assert_eq!(a * b, gf2p256(0x0d419bcce230d7c2840bd91a54f73e89, 0x1be18d31484db64147586e9863d0f1f5));
+        assert_eq!(a.custom_square(), gf2p256(0x046081a7127696b95c38d9ff4a2ecfbf, 0xbbdf3e18adc92906e3876640f5917000));
+        assert_eq!(a.custom_inverse(), Some(gf2p256(0xf003faac60d4ead8845b17a716bb5030, 0xfff798406106773b6802c57233b97ef7)));
+        assert_eq!(a.custom_pow(0xfedcba9876543210), gf2p256(0x5895609c2bd1376f0d66369381c615e6, 0x6c22effbee065ce1cb91bb32e41969e6));
+        // x^256 = x^10 + x^5 + x^2 + 1
+        assert_eq!(gf2p256(0, 2).custom_pow(256), gf2p256(0, 0x425));
+    }
+
+    fn check_field_axioms<GF>(random: impl Fn() -> GF)
+    where GF: CustomMultiplication + CustomInversion + One + Zero + Copy + PartialEq + std::fmt::Debug
+        + std::ops::Add<Output = GF> + std::ops::Sub<Output = GF> + std::ops::Mul<Output = GF> + std::ops::Div<Output = GF> {
+        assert_eq!(GF::zero().custom_inverse(), None);
+        assert_eq!(GF::one().custom_inverse(), Some(GF::one()));
+        for _ in 0..20 {
+            let (a, b, c) = (random(), random(), random());
+            assert_eq!(a * b, b * a);
+            assert_eq!((a * b) * c, a * (b * c));
+            assert_eq!(a * (b + c), a * b + a * c);
+            assert_eq!(a * GF::one(), a);
+            assert_eq!(a * GF::zero(), GF::zero());
+            assert_eq!((a + b) - b, a);
+            assert_eq!(a + a, GF::zero());
+            assert_eq!(a.custom_square(), a * a);
+            assert_eq!(a.custom_pow(0), GF::one());
+            assert_eq!(a.custom_pow(5), a * a * a * a * a);
+            if a != GF::zero() {
+                assert_eq!(a * a.custom_inverse().unwrap(), GF::one());
+                assert_eq!((b * a) / a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn test_gf2p128_field_axioms() {
+        check_field_axioms(|| GF2p128::insecurely_random(&mut rand::rng()));
+    }
+
+    #[test]
+    fn test_gf2p192_field_axioms() {
+        check_field_axioms(|| GF2p192::insecurely_random(&mut rand::rng()));
+    }
+
+    #[test]
+    fn test_gf2p256_field_axioms() {
+        check_field_axioms(|| GF2p256::insecurely_random(&mut rand::rng()));
+    }
+
+    #[test]
+    #[should_panic(expected = "division by zero")]
+    fn test_division_by_zero() {
+        let _ = GF2p128::one() / GF2p128::zero();
+    }
+}
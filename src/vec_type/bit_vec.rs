@@ -1,46 +1,146 @@
-use std::ops::{Index, IndexMut};
-use bincode::Encode;
+use std::ops::{Index, Range};
+use bincode::de::{read::Reader, Decoder};
+use bincode::enc::{write::Writer, Encoder};
+use bincode::error::{DecodeError, EncodeError};
+use bincode::{Decode, Encode};
 use crate::vec_type::{BasicVecFunctions, Split, VecAddition, ZeroVec};
 
-#[derive(Clone, Debug, PartialEq, Encode)]
+const WORD_NUM_BITS: usize = 64;
+
+// Index returns references into this table, as a packed bit has no address of its own
+static BIT_VALUES: [u8; 2] = [0, 1];
+
+// Bits are packed into u64 words, bit i being bit i % 64 of word i / 64. The bits of the last word beyond len are
+// always zero, so that the derived PartialEq compares the bits only.
+#[derive(Clone, Debug, PartialEq)]
 pub struct BitVec {
-    val: Vec<u8>
+    len: usize,
+    word_vec: Vec<u64>,
+}
+
+fn num_words(len: usize) -> usize {
+    len.div_ceil(WORD_NUM_BITS)
+}
+
+fn check_bit(value: u8) -> u64 {
+    assert!(value <= 1, "{:?} is not binary!", value);
+    value as u64
 }
 
 impl BitVec {
-    
+
     pub fn new() -> Self {
         Self {
-            val: Vec::new()
+            len: 0,
+            word_vec: Vec::new(),
         }
     }
-    
+
     pub fn push(&mut self, value: u8) {
-        self.val.push(value);
+        if self.len.is_multiple_of(WORD_NUM_BITS) {
+            self.word_vec.push(0);
+        }
+        self.word_vec[self.len / WORD_NUM_BITS] |= check_bit(value) << (self.len % WORD_NUM_BITS);
+        self.len += 1;
     }
 
     pub fn len(&self) -> usize {
-        self.val.len()
+        self.len
+    }
+
+    pub fn get(&self, index: usize) -> u8 {
+        assert!(index < self.len, "index {} out of range for BitVec of length {}", index, self.len);
+        ((self.word_vec[index / WORD_NUM_BITS] >> (index % WORD_NUM_BITS)) & 1) as u8
     }
 
-    pub fn iter(&self) -> std::slice::Iter<u8> {
-        self.val.iter()
+    pub fn set(&mut self, index: usize, value: u8) {
+        assert!(index < self.len, "index {} out of range for BitVec of length {}", index, self.len);
+        let word = &mut self.word_vec[index / WORD_NUM_BITS];
+        *word = (*word & !(1u64 << (index % WORD_NUM_BITS))) | (check_bit(value) << (index % WORD_NUM_BITS));
     }
-    
+
+    pub fn iter(&self) -> impl Iterator<Item = u8> + '_ {
+        (0..self.len).map(|index| self.get(index))
+    }
+
     pub fn entry_wise_multiply(&self, other: &Self) -> Self {
         assert_eq!(self.len(), other.len());
         Self {
-            val: self.iter().zip(other.iter()).map(
+            len: self.len,
+            word_vec: self.word_vec.iter().zip(other.word_vec.iter()).map(
                 |(lhs, rhs)| *lhs & *rhs
             ).collect()
         }
     }
+
+    // the same as vec_add, but in place
+    pub fn add_assign(&mut self, other: &Self) {
+        assert_eq!(self.len(), other.len());
+        self.word_vec.iter_mut().zip(other.word_vec.iter()).for_each(
+            |(lhs, rhs)| *lhs ^= *rhs
+        );
+    }
+
+    // the packed words, bit i being bit i % 64 of word i / 64 and the bits beyond len being zero
+    pub fn word_vec(&self) -> &[u64] {
+        &self.word_vec
+    }
+
+    // the first len bits of the packed words, the words beyond and the bits of the last word beyond len are dropped
+    pub fn from_word_vec(len: usize, mut word_vec: Vec<u64>) -> Self {
+        assert!(word_vec.len() >= num_words(len), "{} words are too few for BitVec of length {}", word_vec.len(), len);
+        word_vec.truncate(num_words(len));
+        if !len.is_multiple_of(WORD_NUM_BITS) {
+            *word_vec.last_mut().unwrap() &= (1u64 << (len % WORD_NUM_BITS)) - 1;
+        }
+        Self {
+            len,
+            word_vec,
+        }
+    }
+
+    // the packed bytes of the bits, bit i being bit i % 8 of byte i / 8
+    pub fn to_byte_vec(&self) -> Vec<u8> {
+        let mut byte_vec = self.word_vec.iter().flat_map(|word| word.to_le_bytes()).collect::<Vec<u8>>();
+        byte_vec.truncate(self.len.div_ceil(8));
+        byte_vec
+    }
+
+    fn from_byte_vec(len: usize, byte_vec: &[u8]) -> Self {
+        assert_eq!(byte_vec.len(), len.div_ceil(8));
+        let mut word_vec = byte_vec.chunks(8).map(
+            |chunk| {
+                let mut word_bytes = [0u8; 8];
+                word_bytes[..chunk.len()].copy_from_slice(chunk);
+                u64::from_le_bytes(word_bytes)
+            }
+        ).collect::<Vec<u64>>();
+        word_vec.resize(num_words(len), 0);
+        Self {
+            len,
+            word_vec,
+        }
+    }
+
+    fn has_clear_padding(&self) -> bool {
+        match (self.word_vec.last(), self.len % WORD_NUM_BITS) {
+            (Some(last_word), num_used_bits) if num_used_bits != 0 => last_word >> num_used_bits == 0,
+            _ => true,
+        }
+    }
+}
+
+impl Default for BitVec {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl ZeroVec for BitVec {
     fn zero_vec(len: usize) -> Self {
         Self {
-            val: vec![0u8; len]
+            len,
+            word_vec: vec![0u64; num_words(len)],
         }
     }
 }
@@ -49,13 +149,7 @@ impl Index<usize> for BitVec {
     type Output = u8;
 
     fn index(&self, index: usize) -> &Self::Output {
-        &self.val[index]
-    }
-}
-
-impl IndexMut<usize> for BitVec {
-    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
-        &mut self.val[index]
+        &BIT_VALUES[self.get(index) as usize]
     }
 }
 
@@ -63,7 +157,8 @@ impl VecAddition for BitVec {
     fn vec_add(&self, other: &Self) -> Self {
         assert_eq!(self.len(), other.len());
         Self {
-            val: self.iter().zip(other.iter()).map(
+            len: self.len,
+            word_vec: self.word_vec.iter().zip(other.word_vec.iter()).map(
                 |(lhs, rhs)| *lhs ^ *rhs
             ).collect()
         }
@@ -72,8 +167,37 @@ impl VecAddition for BitVec {
 
 impl Split for BitVec {
     fn split_off(&mut self, at: usize) -> Self {
+        assert!(at <= self.len, "split index {} out of range for BitVec of length {}", at, self.len);
+        let res = self.sub_vec(at..self.len);
+        self.len = at;
+        self.word_vec.truncate(num_words(at));
+        if !at.is_multiple_of(WORD_NUM_BITS) {
+            *self.word_vec.last_mut().unwrap() &= (1u64 << (at % WORD_NUM_BITS)) - 1;
+        }
+        res
+    }
+
+    // whole words are shifted into place, rather than copying bit by bit
+    fn sub_vec(&self, range: Range<usize>) -> Self {
+        assert!(range.start <= range.end && range.end <= self.len);
+        let len = range.end - range.start;
+        let word_offset = range.start / WORD_NUM_BITS;
+        let bit_offset = range.start % WORD_NUM_BITS;
+        let mut word_vec = (0..num_words(len)).map(
+            |word_id| {
+                let low = self.word_vec[word_offset + word_id] >> bit_offset;
+                match self.word_vec.get(word_offset + word_id + 1) {
+                    Some(high) if bit_offset != 0 => low | (high << (WORD_NUM_BITS - bit_offset)),
+                    _ => low,
+                }
+            }
+        ).collect::<Vec<u64>>();
+        if !len.is_multiple_of(WORD_NUM_BITS) {
+            *word_vec.last_mut().unwrap() &= (1u64 << (len % WORD_NUM_BITS)) - 1;
+        }
         Self {
-            val: self.val.split_off(at)
+            len,
+            word_vec,
         }
     }
 }
@@ -86,28 +210,48 @@ impl Split for BitVec {
 
 impl BasicVecFunctions<u8> for BitVec {
     fn len(&self) -> usize {
-        self.val.len()   
-    }
-
-    fn as_slice(&self) -> &[u8] {
-        &self.val
+        self.len
     }
 
-    fn as_mut_slice(&mut self) -> &mut [u8] {
-        &mut self.val   
+    fn set(&mut self, index: usize, value: u8) {
+        BitVec::set(self, index, value)
     }
 
     fn from_vec(vec: Vec<u8>) -> Self {
-        Self {
-            val: vec
-        }
+        vec.into_iter().collect()
     }
 }
 
 impl FromIterator<u8> for BitVec {
     fn from_iter<T: IntoIterator<Item = u8>>(iter: T) -> Self {
-        Self {
-            val: iter.into_iter().collect()
+        let mut res = Self::new();
+        iter.into_iter().for_each(|value| res.push(value));
+        res
+    }
+}
+
+// The bits go on the wire packed, i.e., the length followed by ceil(len / 8) bytes
+impl Encode for BitVec {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        self.len.encode(encoder)?;
+        encoder.writer().write(&self.to_byte_vec())
+    }
+}
+
+impl<Context> Decode<Context> for BitVec {
+    fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        let len = usize::decode(decoder)?;
+        let num_bytes = len.div_ceil(8);
+        decoder.claim_bytes_read(num_bytes)?;
+        let mut byte_vec = vec![0u8; num_bytes];
+        decoder.reader().read(&mut byte_vec)?;
+        let res = Self::from_byte_vec(len, &byte_vec);
+        // a set padding bit would make two encodings of the same bits compare unequal
+        if !res.has_clear_padding() {
+            return Err(DecodeError::Other("BitVec has nonzero padding bits"));
         }
+        Ok(res)
     }
-}
\ No newline at end of file
+}
+
+bincode::impl_borrow_decode!(BitVec);
@@ -1,5 +1,5 @@
-use std::ops::{Index, IndexMut};
-use bincode::Encode;
+use std::ops::{Index, IndexMut, Range};
+use bincode::{Decode, Encode};
 use crate::value_type::{CustomAddition, Zero};
 use crate::vec_type::{
     BasicVecFunctions, 
@@ -10,7 +10,7 @@ use crate::vec_type::{
 };
 use crate::vec_type::bit_vec::BitVec;
 
-#[derive(Clone, Debug, PartialEq, Eq, Encode)]
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode)]
 pub struct GFVec<GF> {
     val: Vec<GF>
 }
@@ -30,24 +30,39 @@ impl<GF: Clone + Zero> GFVec<GF> {
         self.val.len()
     }
 
-    pub fn entry_wise_multiply_bit_vec(&self, other: &BitVec) -> Self {
-        assert_eq!(self.len(), other.len());
-        Self {
-            val: self.val.iter().zip(other.iter()).map(
-                |(lhs, rhs)| if *rhs == 1u8 {
-                    lhs.clone()
-                } else {
-                    GF::zero()
-                }
-            ).collect()
-        }
-    }
-    
     pub fn iter(&self) -> std::slice::Iter<GF> {
         self.val.iter()
     }
 }
 
+impl<GF: Clone + Zero + CustomAddition> GFVec<GF> {
+    // the same as vec_add, but in place
+    pub fn add_assign(&mut self, other: &Self) {
+        assert_eq!(self.len(), other.len());
+        self.val.iter_mut().zip(other.val.iter()).for_each(
+            |(lhs, rhs)| *lhs = lhs.custom_add(rhs)
+        );
+    }
+
+    // adds value to the entries at which mask has a one bit, i.e., self += mask * value, a word of the mask at a time
+    pub fn add_assign_masked(&mut self, mask: &BitVec, value: &GF) {
+        assert_eq!(self.len(), mask.len());
+        self.val.chunks_mut(u64::BITS as usize).zip(mask.word_vec()).for_each(
+            |(chunk, mask_word)| GF::add_masked_chunk(chunk, *mask_word, value)
+        );
+    }
+
+    // zeroes the entries at which other has a zero bit, a word of other at a time
+    pub fn entry_wise_multiply_bit_vec(&self, other: &BitVec) -> Self {
+        assert_eq!(self.len(), other.len());
+        let mut res = self.clone();
+        res.val.chunks_mut(u64::BITS as usize).zip(other.word_vec()).for_each(
+            |(chunk, mask_word)| GF::multiply_bit_chunk(chunk, *mask_word)
+        );
+        res
+    }
+}
+
 impl<GF: Clone + Zero> Index<usize> for GFVec<GF> {
     type Output = GF;
 
@@ -81,12 +96,18 @@ impl<GF: Clone + Zero + CustomAddition> VecAddition for GFVec<GF> {
     }
 }
 
-impl<GF> Split for GFVec<GF> {
+impl<GF: Clone> Split for GFVec<GF> {
     fn split_off(&mut self, at: usize) -> Self {
         Self {
             val: self.val.split_off(at)
         }
     }
+
+    fn sub_vec(&self, range: Range<usize>) -> Self {
+        Self {
+            val: self.val[range].to_vec()
+        }
+    }
 }
 
 // impl<GF> VecAppending for GFVec<GF> {
@@ -108,13 +129,10 @@ impl<GF> BasicVecFunctions<GF> for GFVec<GF> {
         self.val.len()
     }
 
-    fn as_slice(&self) -> &[GF] {
-        &self.val
+    fn set(&mut self, index: usize, value: GF) {
+        self.val[index] = value;
     }
 
-    fn as_mut_slice(&mut self) -> &mut [GF] {
-        &mut self.val
-    }
 
     fn from_vec(vec: Vec<GF>) -> Self {
         Self {
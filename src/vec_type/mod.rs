@@ -1,5 +1,8 @@
+use std::ops::Range;
+
 pub mod gf_vec;
 pub mod bit_vec;
+mod test;
 
 pub trait ZeroVec {
     fn zero_vec(len: usize) -> Self;
@@ -11,16 +14,18 @@ pub trait VecAddition {
 
 pub trait Split {
     fn split_off(&mut self, at: usize) -> Self;
+    fn sub_vec(&self, range: Range<usize>) -> Self;
 }
 
 // pub trait VecAppending {
 //     fn append(&mut self, other: &mut Self);
 // }
 
+// Entries are read through Index and written through set, since a packed BitVec cannot hand out a mutable
+// reference to a single bit
 pub trait BasicVecFunctions<OutputType> {
     fn len(&self) -> usize;
-    fn as_slice(&self) -> &[OutputType];
-    fn as_mut_slice(&mut self) -> &mut [OutputType];
-    
+    fn set(&mut self, index: usize, value: OutputType);
+
     fn from_vec(vec: Vec<OutputType>) -> Self;
 }
\ No newline at end of file
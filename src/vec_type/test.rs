@@ -0,0 +1,98 @@
+#[cfg(test)]
+mod tests {
+    use std::fmt::Debug;
+    use bincode::{config, decode_from_slice, encode_to_vec};
+    use rand::{Rng, SeedableRng};
+    use rand_chacha::ChaCha20Rng;
+    use crate::value_type::gf2p8::GF2p8;
+    use crate::value_type::gf2p16::GF2p16;
+    use crate::value_type::gf2p128::GF2p128;
+    use crate::value_type::gf2p192::GF2p192;
+    use crate::value_type::gf2p256::GF2p256;
+    use crate::value_type::{CustomAddition, InsecureRandom, Zero};
+    use crate::vec_type::bit_vec::BitVec;
+    use crate::vec_type::gf_vec::GFVec;
+    use crate::vec_type::{BasicVecFunctions, Split, VecAddition};
+
+    fn random_bits(len: usize, rng: &mut ChaCha20Rng) -> Vec<u8> {
+        (0..len).map(|_| rng.random_range(0..2u8)).collect()
+    }
+
+    #[test]
+    fn test_bit_vec_matches_unpacked_bits() {
+        let mut rng = ChaCha20Rng::seed_from_u64(0);
+        // lengths around the word boundaries
+        for len in [0, 1, 63, 64, 65, 127, 128, 129, 200] {
+            let a = random_bits(len, &mut rng);
+            let b = random_bits(len, &mut rng);
+            let a_bit_vec = BitVec::from_vec(a.clone());
+            let b_bit_vec = BitVec::from_vec(b.clone());
+            assert_eq!(a_bit_vec.iter().collect::<Vec<u8>>(), a);
+
+            let sum = a.iter().zip(b.iter()).map(|(x, y)| x ^ y).collect::<Vec<u8>>();
+            assert_eq!(a_bit_vec.vec_add(&b_bit_vec), BitVec::from_vec(sum));
+            let product = a.iter().zip(b.iter()).map(|(x, y)| x & y).collect::<Vec<u8>>();
+            assert_eq!(a_bit_vec.entry_wise_multiply(&b_bit_vec), BitVec::from_vec(product));
+
+            for at in [0, len / 3, len / 2, len] {
+                assert_eq!(a_bit_vec.sub_vec(at..len), BitVec::from_vec(a[at..].to_vec()));
+                let mut head = a_bit_vec.clone();
+                let tail = head.split_off(at);
+                assert_eq!(head, BitVec::from_vec(a[..at].to_vec()));
+                assert_eq!(tail, BitVec::from_vec(a[at..].to_vec()));
+                assert_eq!(BitVec::from_word_vec(at, a_bit_vec.word_vec().to_vec()), head);
+            }
+        }
+    }
+
+    #[test]
+    fn test_bit_vec_encoding() {
+        let mut rng = ChaCha20Rng::seed_from_u64(1);
+        let bit_vec = BitVec::from_vec(random_bits(100, &mut rng));
+        let byte_vec = encode_to_vec(&bit_vec, config::standard()).unwrap();
+        // one byte for the varint length and 13 packed bytes
+        assert_eq!(byte_vec.len(), 1 + 13);
+        let (decoded, _): (BitVec, usize) = decode_from_slice(&byte_vec, config::standard()).unwrap();
+        assert_eq!(decoded, bit_vec);
+
+        // a set padding bit must not decode
+        let mut tampered_byte_vec = byte_vec.clone();
+        *tampered_byte_vec.last_mut().unwrap() |= 0x80;
+        assert!(decode_from_slice::<BitVec, _>(&tampered_byte_vec, config::standard()).is_err());
+    }
+
+    fn check_masked_operations<GF: Clone + Copy + Zero + CustomAddition + InsecureRandom + PartialEq + Debug>(rng: &mut ChaCha20Rng) {
+        // lengths that end within a word of packed entries, at and beyond a word of the mask
+        for len in [0, 1, 3, 5, 9, 64, 65, 200] {
+            let mask = BitVec::from_vec(random_bits(len, rng));
+            let value = GF::insecurely_random(rng);
+            let gf_vec = (0..len).map(|_| GF::insecurely_random(rng)).collect::<GFVec<GF>>();
+
+            let mut accumulated_gf_vec = gf_vec.clone();
+            accumulated_gf_vec.add_assign_masked(&mask, &value);
+            let expected_gf_vec = gf_vec.iter().zip(mask.iter()).map(
+                |(entry, bit)| if bit == 1 { entry.custom_add(&value) } else { *entry }
+            ).collect::<GFVec<GF>>();
+            assert_eq!(accumulated_gf_vec, expected_gf_vec);
+
+            let mut summed_gf_vec = gf_vec.clone();
+            summed_gf_vec.add_assign(&expected_gf_vec);
+            assert_eq!(summed_gf_vec, gf_vec.vec_add(&expected_gf_vec));
+
+            let multiplied_gf_vec = gf_vec.entry_wise_multiply_bit_vec(&mask);
+            assert!(multiplied_gf_vec.iter().zip(gf_vec.iter()).zip(mask.iter()).all(
+                |((product, entry), bit)| *product == if bit == 1 { *entry } else { GF::zero() }
+            ));
+        }
+    }
+
+    #[test]
+    fn test_gf_vec_masked_operations_match_entry_wise_ones() {
+        let mut rng = ChaCha20Rng::seed_from_u64(2);
+        check_masked_operations::<GF2p8>(&mut rng);
+        check_masked_operations::<GF2p16>(&mut rng);
+        check_masked_operations::<GF2p128>(&mut rng);
+        check_masked_operations::<GF2p192>(&mut rng);
+        check_masked_operations::<GF2p256>(&mut rng);
+    }
+}